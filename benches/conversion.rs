@@ -0,0 +1,44 @@
+//! Benchmarks for turning numbers of days and seconds into calendar dates,
+//! which every formatting call has to do first.
+//!
+//! These don’t need a nightly compiler or any extra crates: run them with
+//! `cargo bench`, and each prints how long one conversion took on average.
+
+extern crate datetime;
+extern crate locale;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use datetime::{DatePiece, LocalDate, LocalDateTime};
+use datetime::format::DateFormat;
+
+
+/// The number of conversions that each benchmark times.
+const ITERATIONS: i64 = 10_000_000;
+
+/// Runs the given function once for every number in a range that spans
+/// several thousand years, and prints the average time taken.
+fn bench<T, F: Fn(i64) -> T>(name: &str, step: i64, f: F) {
+    let start = Instant::now();
+    for i in 0 .. ITERATIONS {
+        black_box(f(black_box((i - ITERATIONS / 2) * step)));
+    }
+
+    let elapsed = start.elapsed();
+    println!("{:<24} {:>8.2} ns/iter", name, elapsed.as_nanos() as f64 / ITERATIONS as f64);
+}
+
+fn main() {
+    bench("julian_day_to_date", 1, |day| LocalDate::from_julian_day(2_440_588 + day));
+    bench("timestamp_to_datetime", 86_400 / 7, LocalDateTime::at);
+    bench("datetime_month_and_day", 86_400 / 7, |seconds| {
+        let datetime = LocalDateTime::at(seconds);
+        (datetime.month(), datetime.day())
+    });
+    bench("format_iso_date", 1, |day| LocalDate::from_julian_day(2_440_588 + day).to_string());
+
+    let format = DateFormat::parse("{:Y}-{02>:D} {0>2:h}:{0>2:m}:{0>2:s}").unwrap();
+    let english = locale::Time::english();
+    bench("format_pattern", 86_400 / 7, |seconds| format.format(&LocalDateTime::at(seconds), &english));
+}
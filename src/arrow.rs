@@ -0,0 +1,199 @@
+//! Conversions to and from Apache Arrow’s temporal types.
+//!
+//! Arrow stores dates and times as plain integers, so these conversions
+//! work on the raw values found in Arrow arrays, without depending on any
+//! particular Arrow implementation:
+//!
+//! - `Date32` is a number of days since the Unix epoch;
+//! - `Date64` is a number of milliseconds since the Unix epoch, which
+//!   should be a whole number of days;
+//! - `Timestamp` is a number of seconds, milliseconds, microseconds, or
+//!   nanoseconds since the Unix epoch, depending on its `TimeUnit`. A
+//!   timestamp *without* a time zone is a local date-time, and a timestamp
+//!   *with* one is an exact instant in UTC, displayed in that time zone.
+//!
+//! Values that are too large to fit in Arrow’s integers produce `None`.
+
+use std::convert::TryFrom;
+
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime};
+use cal::offset::{Offset, OffsetDateTime};
+use duration::Duration;
+use instant::Instant;
+
+
+/// The number of milliseconds in a day.
+const MILLISECONDS_IN_DAY: i64 = 86400 * 1000;
+
+
+/// Returns the date stored as an Arrow `Date32` value.
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::arrow;
+///
+/// assert_eq!(arrow::date32(17000), LocalDate::ymd(2016, Month::July, 18).unwrap());
+/// ```
+pub fn date32(days: i32) -> LocalDate {
+    LocalDateTime::at(days as i64 * 86400).date()
+}
+
+/// Returns the Arrow `Date32` value for the given date.
+pub fn to_date32(date: LocalDate) -> Option<i32> {
+    i32::try_from(unix_days(date)).ok()
+}
+
+/// Returns the date stored as an Arrow `Date64` value. Any part of a day
+/// left over is ignored.
+pub fn date64(milliseconds: i64) -> LocalDate {
+    LocalDateTime::at(milliseconds.div_euclid(MILLISECONDS_IN_DAY) * 86400).date()
+}
+
+/// Returns the Arrow `Date64` value for the given date.
+pub fn to_date64(date: LocalDate) -> Option<i64> {
+    unix_days(date).checked_mul(MILLISECONDS_IN_DAY)
+}
+
+fn unix_days(date: LocalDate) -> i64 {
+    LocalDateTime::new(date, LocalTime::midnight()).to_instant().seconds() / 86400
+}
+
+
+/// The unit of an Arrow `Timestamp`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum TimeUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+impl TimeUnit {
+
+    /// Returns the number of nanoseconds in one of this unit.
+    fn nanoseconds(&self) -> i128 {
+        match *self {
+            TimeUnit::Second       => 1_000_000_000,
+            TimeUnit::Millisecond  => 1_000_000,
+            TimeUnit::Microsecond  => 1_000,
+            TimeUnit::Nanosecond   => 1,
+        }
+    }
+
+    /// Returns the local date-time stored as a `Timestamp` without a time
+    /// zone.
+    ///
+    /// ```rust
+    /// use datetime::LocalDateTime;
+    /// use datetime::arrow::TimeUnit;
+    ///
+    /// let datetime = TimeUnit::Microsecond.to_datetime(1_500_000);
+    /// assert_eq!(datetime, LocalDateTime::at_ms(1, 500));
+    /// ```
+    pub fn to_datetime(&self, value: i64) -> LocalDateTime {
+        LocalDateTime::from_unix_nanos(value as i128 * self.nanoseconds())
+    }
+
+    /// Returns the `Timestamp` value without a time zone for the given
+    /// local date-time, rounding down to this unit.
+    pub fn from_datetime(&self, datetime: LocalDateTime) -> Option<i64> {
+        i64::try_from(datetime.to_unix_nanos().div_euclid(self.nanoseconds())).ok()
+    }
+
+    /// Returns the instant stored as a `Timestamp` with a time zone.
+    pub fn to_instant(&self, value: i64) -> Instant {
+        Instant::from_nanos(value as i128 * self.nanoseconds())
+    }
+
+    /// Returns the `Timestamp` value with a time zone for the given
+    /// instant.
+    pub fn from_instant(&self, instant: Instant) -> Option<i64> {
+        i64::try_from(instant.to_nanos().div_euclid(self.nanoseconds())).ok()
+    }
+
+    /// Returns the date-time stored as a `Timestamp` with a time zone,
+    /// displayed at the given offset from UTC.
+    pub fn to_offset_datetime(&self, value: i64, offset: Offset) -> OffsetDateTime {
+        let utc = self.to_datetime(value);
+        offset.transform_date(utc + Duration::of(offset.seconds() as i64))
+    }
+
+    /// Returns the `Timestamp` value with a time zone for the given
+    /// date-time, which gets stored in UTC.
+    pub fn from_offset_datetime(&self, datetime: OffsetDateTime) -> Option<i64> {
+        let utc = datetime.local() - Duration::of(datetime.offset().seconds() as i64);
+        self.from_datetime(utc)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::Month;
+
+    #[test]
+    fn date32_round_trip() {
+        let date = LocalDate::ymd(1969, Month::December, 31).unwrap();
+        assert_eq!(to_date32(date), Some(-1));
+        assert_eq!(date32(-1), date);
+    }
+
+    #[test]
+    fn date32_out_of_range() {
+        let date = LocalDate::ymd(10_000_000, Month::January, 1).unwrap();
+        assert_eq!(to_date32(date), None);
+    }
+
+    #[test]
+    fn date64_round_trip() {
+        let date = LocalDate::ymd(2016, Month::February, 29).unwrap();
+        assert_eq!(to_date64(date), Some(1456704000000));
+        assert_eq!(date64(1456704000000), date);
+    }
+
+    #[test]
+    fn date64_ignores_time() {
+        assert_eq!(date64(-1), LocalDate::ymd(1969, Month::December, 31).unwrap());
+    }
+
+    #[test]
+    fn timestamp_units() {
+        let datetime = LocalDateTime::at_ns(1_234_567_890, 123_456_789);
+        assert_eq!(TimeUnit::Second.from_datetime(datetime), Some(1_234_567_890));
+        assert_eq!(TimeUnit::Millisecond.from_datetime(datetime), Some(1_234_567_890_123));
+        assert_eq!(TimeUnit::Microsecond.from_datetime(datetime), Some(1_234_567_890_123_456));
+        assert_eq!(TimeUnit::Nanosecond.from_datetime(datetime), Some(1_234_567_890_123_456_789));
+        assert_eq!(TimeUnit::Nanosecond.to_datetime(1_234_567_890_123_456_789), datetime);
+    }
+
+    #[test]
+    fn timestamp_before_epoch() {
+        let datetime = LocalDateTime::at_ns(-1, 999_999_000);
+        assert_eq!(TimeUnit::Microsecond.from_datetime(datetime), Some(-1));
+        assert_eq!(TimeUnit::Microsecond.to_datetime(-1), datetime);
+    }
+
+    #[test]
+    fn nanoseconds_out_of_range() {
+        let datetime = LocalDateTime::new(LocalDate::ymd(2300, Month::January, 1).unwrap(), LocalTime::midnight());
+        assert_eq!(TimeUnit::Nanosecond.from_datetime(datetime), None);
+        assert!(TimeUnit::Microsecond.from_datetime(datetime).is_some());
+    }
+
+    #[test]
+    fn instants() {
+        let instant = Instant::at_ms(1_600_000_000, 250);
+        assert_eq!(TimeUnit::Microsecond.from_instant(instant), Some(1_600_000_000_250_000));
+        assert_eq!(TimeUnit::Microsecond.to_instant(1_600_000_000_250_000), instant);
+        assert_eq!(TimeUnit::Nanosecond.to_instant(1_600_000_000_250_999_999), Instant::at_ns(1_600_000_000, 250_999_999));
+    }
+
+    #[test]
+    fn with_offset() {
+        let offset = Offset::of_hours_and_minutes(5, 30).unwrap();
+        let datetime = TimeUnit::Second.to_offset_datetime(0, offset);
+        assert_eq!(datetime.to_string(), "1970-01-01T05:30:00+05:30");
+        assert_eq!(TimeUnit::Second.from_offset_datetime(datetime), Some(0));
+    }
+}
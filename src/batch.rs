@@ -0,0 +1,182 @@
+//! Converting and formatting many values at once.
+//!
+//! Analytics code often has to turn millions of timestamps into date-times,
+//! or write millions of date-times out as text. Doing them one at a time
+//! works, but each value then pays the costs that could have been shared:
+//! looking up which of a time zone’s offsets is in effect, or allocating a
+//! new string. The functions here work on whole slices instead.
+//!
+//! ```rust
+//! use datetime::LocalDateTime;
+//! use datetime::batch::{convert_epochs, format_iso};
+//!
+//! let seconds = [ 0, 86400, 1_000_000_000 ];
+//! let mut datetimes = [ LocalDateTime::default(); 3 ];
+//! convert_epochs(&seconds, &mut datetimes);
+//!
+//! let mut buffer = String::new();
+//! let mut ends = Vec::new();
+//! format_iso(&datetimes, &mut buffer, &mut ends);
+//! assert_eq!(&buffer[ends[0] .. ends[1]], "1970-01-02T00:00:00");
+//! ```
+
+use std::fmt::Write;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use cal::datetime::LocalDateTime;
+use cal::zone::{TimeZone, FixedTimespanSet};
+
+
+/// Converts each Unix timestamp in `seconds` into a date-time in UTC,
+/// writing it into the same position in `output`.
+///
+/// ### Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn convert_epochs(seconds: &[i64], output: &mut [LocalDateTime]) {
+    assert_eq!(seconds.len(), output.len(), "slices have different lengths");
+
+    for (datetime, &seconds) in output.iter_mut().zip(seconds) {
+        *datetime = LocalDateTime::at(seconds);
+    }
+}
+
+/// Converts each Unix timestamp in `seconds` into a date-time as shown on
+/// a wall clock in the given time zone, writing it into the same position
+/// in `output`, the same as `TimeZone::to_zoned` would.
+///
+/// The time zone’s offsets are only searched when a timestamp falls outside
+/// the timespan of the one before it, so timestamps that are sorted, or
+/// close together, are converted nearly as quickly as they would be in UTC.
+///
+/// ### Panics
+///
+/// Panics if the two slices have different lengths, or if adding a time
+/// zone’s offset takes a timestamp outside the range of date-times that
+/// can be represented.
+pub fn convert_epochs_in(zone: &TimeZone, seconds: &[i64], output: &mut [LocalDateTime]) {
+    assert_eq!(seconds.len(), output.len(), "slices have different lengths");
+
+    let mut cursor = TimespanCursor::new(&zone.fixed_timespans);
+    for (datetime, &seconds) in output.iter_mut().zip(seconds) {
+        let offset = cursor.offset_at(seconds);
+        *datetime = LocalDateTime::at(seconds.checked_add(offset).expect("date-time out of range"));
+    }
+}
+
+/// Formats each date-time in the ISO 8601 format, such as
+/// `2015-06-26T14:30:00`, appending them all to `buffer` without any
+/// separators. After each one, the length of the buffer is pushed onto
+/// `ends`, so the `n`th value is the text between the `n - 1`th end (or the
+/// buffer’s original length) and the `n`th one.
+///
+/// This is the same layout as the offsets of an Arrow string array, and
+/// only ever needs to grow the buffer a few times, rather than allocating
+/// a string for each value.
+pub fn format_iso(datetimes: &[LocalDateTime], buffer: &mut String, ends: &mut Vec<usize>) {
+    buffer.reserve(datetimes.len() * "0000-00-00T00:00:00.000".len());
+    ends.reserve(datetimes.len());
+
+    for datetime in datetimes {
+        write!(buffer, "{}", datetime).expect("writing to a string failed");
+        ends.push(buffer.len());
+    }
+}
+
+
+/// Remembers which timespan of a time zone the last timestamp fell in, so
+/// that the next one only has to be checked against its boundaries.
+struct TimespanCursor<'z, 'a: 'z> {
+    timespans: &'z FixedTimespanSet<'a>,
+
+    /// The position of the current timespan, where 0 is the first one, and
+    /// every other is one past its transition in `rest`.
+    position: usize,
+}
+
+impl<'z, 'a> TimespanCursor<'z, 'a> {
+    fn new(timespans: &'z FixedTimespanSet<'a>) -> TimespanCursor<'z, 'a> {
+        TimespanCursor { timespans: timespans, position: 0 }
+    }
+
+    /// Returns the offset in effect at the given time. A transition’s own
+    /// instant still belongs to the timespan before it.
+    fn offset_at(&mut self, time: i64) -> i64 {
+        let rest = self.timespans.rest;
+        let after_start = self.position == 0 || rest[self.position - 1].0 < time;
+        let before_end = rest.get(self.position).map_or(true, |t| time <= t.0);
+
+        if !(after_start && before_end) {
+            self.position = rest.partition_point(|t| t.0 < time);
+        }
+
+        match self.position {
+            0        => self.timespans.first.offset,
+            position => rest[position - 1].1.offset,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    use cal::zone::FixedTimespan;
+
+    const ZONE: TimeZone<'static> = TimeZone {
+        name: "Test/Zone",
+        fixed_timespans: FixedTimespanSet {
+            first: FixedTimespan { offset: 0, is_dst: false, name: "ZONE_A" },
+            rest: &[
+                (1000, FixedTimespan { offset: 3600, is_dst: true, name: "ZONE_B" }),
+                (2000, FixedTimespan { offset: 0, is_dst: false, name: "ZONE_A" }),
+                (3000, FixedTimespan { offset: -1800, is_dst: false, name: "ZONE_C" }),
+            ],
+        },
+    };
+
+    #[test]
+    fn epochs() {
+        let seconds = [ -1, 0, 1_234_567_890, i64::max_value() ];
+        let mut output = [ LocalDateTime::default(); 4 ];
+        convert_epochs(&seconds, &mut output);
+
+        for (&seconds, &datetime) in seconds.iter().zip(&output) {
+            assert_eq!(datetime, LocalDateTime::at(seconds));
+        }
+    }
+
+    #[test]
+    fn epochs_in_zone_match_one_at_a_time() {
+        let mut seconds: Vec<i64> = (-5 .. 400).map(|n| n * 10).collect();
+        seconds.extend(vec![ 3500, 0, 1000, 1001, 2000, 2001, 2999, 3000, 3001, 500 ]);
+
+        let mut output = vec![ LocalDateTime::default(); seconds.len() ];
+        convert_epochs_in(&ZONE, &seconds, &mut output);
+
+        for (&seconds, &datetime) in seconds.iter().zip(&output) {
+            assert_eq!(datetime, ZONE.to_zoned(LocalDateTime::at(seconds)), "at {}", seconds);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_lengths() {
+        let mut output = [ LocalDateTime::default(); 1 ];
+        convert_epochs(&[ 1, 2 ], &mut output);
+    }
+
+    #[test]
+    fn formatting() {
+        let datetimes = [ LocalDateTime::at(0), LocalDateTime::at_ms(1_000_000_000, 250) ];
+        let mut buffer = String::from("existing");
+        let mut ends = Vec::new();
+        format_iso(&datetimes, &mut buffer, &mut ends);
+
+        assert_eq!(ends, vec![ 27, 50 ]);
+        assert_eq!(&buffer[8 .. ends[0]], "1970-01-01T00:00:00");
+        assert_eq!(&buffer[ends[0] .. ends[1]], "2001-09-09T01:46:40.250");
+    }
+}
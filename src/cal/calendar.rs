@@ -0,0 +1,630 @@
+//! Calendar systems, which give days their years, months, and day
+//! numbers.
+//!
+//! Everything else in this library uses the proleptic Gregorian calendar,
+//! but a `LocalDate` is really just a day, and other calendars can name
+//! that same day differently. A `CalendarSystem` converts between the two,
+//! and a `CalendarDate` is a date along with the calendar it’s given in:
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month};
+//! use datetime::calendar::{CalendarDate, Gregorian};
+//!
+//! let date = CalendarDate::new(Gregorian, 2024, 2, 29).unwrap();
+//! assert_eq!(date.to_local_date(), LocalDate::ymd(2024, Month::February, 29).unwrap());
+//! assert_eq!(date.days_in_month(), 29);
+//! assert_eq!(date.to_string(), "2024-02-29");
+//! ```
+//!
+//! Besides the `Gregorian` calendar, there’s the `Julian` one, and
+//! `Changeover`, which switches from one to the other on a given day, for
+//! dates from places and times that were still using the Julian calendar.
+//!
+//! Dates in other calendars can be formatted with `DateFormat::format_in`,
+//! which takes the year, month, and day fields from the calendar.
+
+use std::cmp::{self, Ordering};
+use std::fmt;
+use std::ops::Range;
+
+use cal::DatePiece;
+use cal::datetime::{LocalDate, Month, Weekday, Error};
+
+
+/// A way of dividing days up into years and months.
+///
+/// Months are numbered from 1, and years can have any number of months,
+/// each with any number of days, as long as every day has exactly one
+/// year, month, and day number, and they count up in order.
+pub trait CalendarSystem {
+
+    /// Returns the name of this calendar, such as “Gregorian”.
+    fn name(&self) -> &str;
+
+    /// Returns the year, month, and day that the given date has in this
+    /// calendar.
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8);
+
+    /// Returns the date with the given year, month, and day in this
+    /// calendar, returning an error if there’s no such day, or if it’s
+    /// outside the range of dates that can be represented.
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error>;
+
+    /// Returns the number of months in the given year.
+    fn months_in_year(&self, _year: i64) -> i8 {
+        12
+    }
+
+    /// Returns the number of days in the given month of the given year.
+    /// The month must be one that the year has.
+    fn days_in_month(&self, year: i64, month: i8) -> i8;
+
+    /// Returns the first day of the given month of the given year, which
+    /// is the 1st unless the calendar skips some days.
+    fn start_of_month(&self, year: i64, month: i8) -> Result<LocalDate, Error> {
+        self.from_ymd(year, month, 1)
+    }
+
+    /// Returns the number of days in the given year.
+    fn days_in_year(&self, year: i64) -> i16 {
+        (1 ..= self.months_in_year(year)).map(|month| self.days_in_month(year, month) as i16).sum()
+    }
+
+    /// Returns the name of the given month in the given year, if this
+    /// calendar has names of its own for its months. Calendars that use
+    /// the same months as the Gregorian calendar return `None`, so the
+    /// names from the locale get used instead.
+    fn month_name(&self, _year: i64, _month: i8) -> Option<&str> {
+        None
+    }
+
+    /// Returns the era that the given date is in, along with the date’s
+    /// year in that era, if this calendar numbers its years within eras as
+    /// well as from its epoch.
+    fn era_year(&self, _date: LocalDate) -> Option<EraYear> {
+        None
+    }
+}
+
+/// The era that a date is in, and its year in that era, in a calendar that
+/// numbers years within eras.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct EraYear<'a> {
+
+    /// The name of the era written in the Latin alphabet, such as “Reiwa”.
+    pub name: &'a str,
+
+    /// The name of the era in the script of the calendar it’s from, such
+    /// as “令和”.
+    pub native_name: &'a str,
+
+    /// The year in the era, starting from 1.
+    pub year: i64,
+}
+
+/// Returns an error if the given month or day doesn’t exist in the given
+/// year of the calendar, for use by implementations of `from_ymd`.
+pub(crate) fn check_ymd<C: CalendarSystem + ?Sized>(calendar: &C, year: i64, month: i8, day: i8) -> Result<(), Error> {
+    if month < 1 || month > calendar.months_in_year(year) {
+        return Err(Error::MonthOutOfRange { given: month });
+    }
+
+    let max = calendar.days_in_month(year, month);
+    if day < 1 || day > max {
+        return Err(Error::DayOutOfRange { given: day, max: max });
+    }
+
+    Ok(())
+}
+
+
+/// The proleptic Gregorian calendar, which is what `LocalDate` uses.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub struct Gregorian;
+
+impl CalendarSystem for Gregorian {
+    fn name(&self) -> &str {
+        "Gregorian"
+    }
+
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        (date.year(), date.month().to_one(), date.day())
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        LocalDate::ymd(year, try!(Month::from_one(month)), day)
+    }
+
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        Month::from_one(month).expect("month out of range").days_in(year)
+    }
+}
+
+
+/// The proleptic Julian calendar, which has a leap year every four years
+/// without exception. It was used across Europe until the Gregorian
+/// calendar replaced it, and is still used by some Orthodox churches.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::calendar::{CalendarSystem, Julian};
+///
+/// // The day the Gregorian calendar was first used was the 5th of October
+/// // in the Julian one.
+/// let date = LocalDate::ymd(1582, Month::October, 15).unwrap();
+/// assert_eq!(Julian.to_ymd(date), (1582, 10, 5));
+/// assert_eq!(Julian.from_ymd(1582, 10, 5), Ok(date));
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub struct Julian;
+
+impl CalendarSystem for Julian {
+    fn name(&self) -> &str {
+        "Julian"
+    }
+
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        // Richards’s algorithm, without the Gregorian correction.
+        let e = 4 * (date.julian_day() + 1401) + 3;
+        let h = 5 * (e.rem_euclid(1461) / 4) + 2;
+        let day = h % 153 / 5 + 1;
+        let month = (h / 153 + 2) % 12 + 1;
+        let year = e.div_euclid(1461) - 4716 + (14 - month) / 12;
+        (year, month as i8, day as i8)
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        try!(check_year(year));
+        try!(check_ymd(self, year, month, day));
+        date_from_julian_day(julian_day(year, month, false) + day as i64 - 1, year)
+    }
+
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        match month {
+            2                => if year.rem_euclid(4) == 0 { 29 } else { 28 },
+            4 | 6 | 9 | 11   => 30,
+            1 ..= 12         => 31,
+            _                => panic!("month out of range"),
+        }
+    }
+}
+
+
+/// The calendar used in a place that switched from the Julian calendar to
+/// the Gregorian one: the Julian calendar up to some day, and the Gregorian
+/// calendar from the next day on. The day numbers in between are skipped,
+/// so the month of the changeover is shorter than usual.
+///
+/// Years are taken to start on the 1st of January throughout, even though
+/// many places used to start them on another day, such as the 25th of
+/// March.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::calendar::{CalendarDate, CalendarSystem, Changeover};
+///
+/// let date = CalendarDate::new(Changeover::GREAT_BRITAIN, 1752, 9, 2).unwrap();
+/// assert_eq!(date.to_local_date(), LocalDate::ymd(1752, Month::September, 13).unwrap());
+/// assert_eq!(date.days_in_month(), 19);
+///
+/// // The 3rd to the 13th of September, 1752, never happened in Britain.
+/// assert!(Changeover::GREAT_BRITAIN.from_ymd(1752, 9, 3).is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Changeover {
+    first_gregorian_day: LocalDate,
+}
+
+impl Changeover {
+
+    /// The changeover decreed by Pope Gregory XIII, and followed by Spain,
+    /// Portugal, and most of Italy, where the 4th of October, 1582, was
+    /// followed by the 15th.
+    pub const ROME: Changeover = Changeover::new(match LocalDate::ymd(1582, Month::October, 15) {
+        Ok(date) => date,
+        Err(_)   => panic!("changeover out of range"),
+    });
+
+    /// The changeover in Great Britain and its colonies, where the 2nd of
+    /// September, 1752, was followed by the 14th.
+    pub const GREAT_BRITAIN: Changeover = Changeover::new(match LocalDate::ymd(1752, Month::September, 14) {
+        Ok(date) => date,
+        Err(_)   => panic!("changeover out of range"),
+    });
+
+    /// Creates a calendar that switches to the Gregorian calendar on the
+    /// given day, and uses the Julian calendar before it.
+    ///
+    /// The Julian calendar has to be behind the Gregorian one on that day,
+    /// as it is from the year 300 onwards, for every day to have just one
+    /// year, month, and day.
+    pub const fn new(first_gregorian_day: LocalDate) -> Changeover {
+        Changeover { first_gregorian_day: first_gregorian_day }
+    }
+
+    /// Returns the first day of the Gregorian calendar.
+    pub fn first_gregorian_day(&self) -> LocalDate {
+        self.first_gregorian_day
+    }
+
+    /// Returns the last day of the Julian calendar.
+    pub fn last_julian_day(&self) -> LocalDate {
+        LocalDate::from_julian_day(self.first_gregorian_day.julian_day() - 1)
+    }
+
+    /// Returns the Julian days in the given month by the Julian calendar
+    /// and by the Gregorian calendar, leaving out the days of each that
+    /// aren’t used.
+    fn month_days(&self, year: i64, month: i8) -> (Range<i64>, Range<i64>) {
+        let changeover = self.first_gregorian_day.julian_day();
+
+        let julian = julian_day(year, month, false);
+        let julian_end = julian + Julian.days_in_month(year, month) as i64;
+        let gregorian = julian_day(year, month, true);
+        let gregorian_end = gregorian + Gregorian.days_in_month(year, month) as i64;
+
+        (julian .. cmp::min(julian_end, changeover), cmp::max(gregorian, changeover) .. gregorian_end)
+    }
+}
+
+impl CalendarSystem for Changeover {
+    fn name(&self) -> &str {
+        "Julian and Gregorian"
+    }
+
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        if date < self.first_gregorian_day { Julian.to_ymd(date) } else { Gregorian.to_ymd(date) }
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        try!(check_year(year));
+        if month < 1 || month > 12 {
+            return Err(Error::MonthOutOfRange { given: month });
+        }
+
+        let (julian, gregorian) = self.month_days(year, month);
+        let as_julian = julian.start + day as i64 - 1;
+        let as_gregorian = julian_day(year, month, true) + day as i64 - 1;
+
+        if day >= 1 && julian.contains(&as_julian) {
+            date_from_julian_day(as_julian, year)
+        }
+        else if day >= 1 && gregorian.contains(&as_gregorian) {
+            date_from_julian_day(as_gregorian, year)
+        }
+        else {
+            let max = if gregorian.start < gregorian.end { Gregorian.days_in_month(year, month) } else { cmp::max(julian.end - julian.start, 0) as i8 };
+            Err(Error::DayOutOfRange { given: day, max: max })
+        }
+    }
+
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        let (julian, gregorian) = self.month_days(year, month);
+        (cmp::max(julian.end - julian.start, 0) + cmp::max(gregorian.end - gregorian.start, 0)) as i8
+    }
+
+    fn start_of_month(&self, year: i64, month: i8) -> Result<LocalDate, Error> {
+        try!(check_year(year));
+        if month < 1 || month > 12 {
+            return Err(Error::MonthOutOfRange { given: month });
+        }
+
+        let (julian, gregorian) = self.month_days(year, month);
+        if julian.start < julian.end {
+            date_from_julian_day(julian.start, year)
+        }
+        else if gregorian.start < gregorian.end {
+            date_from_julian_day(gregorian.start, year)
+        }
+        else {
+            Err(Error::MonthOutOfRange { given: month })
+        }
+    }
+}
+
+/// Returns the Julian day of the 1st of the given month, in the Gregorian
+/// calendar if `gregorian` is true, or in the Julian one otherwise. The
+/// result might be outside the range of dates that can be represented.
+fn julian_day(year: i64, month: i8, gregorian: bool) -> i64 {
+    let a = (14 - month as i64) / 12;
+    let y = year + 4800 - a;
+    let m = month as i64 + 12 * a - 3;
+    let days = (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) + 1;
+
+    if gregorian {
+        days - y.div_euclid(100) + y.div_euclid(400) - 32045
+    }
+    else {
+        days - 32083
+    }
+}
+
+/// Returns an error if the year is so far away that working out the Julian
+/// days in it could overflow.
+fn check_year(year: i64) -> Result<(), Error> {
+    if year < LocalDate::MIN.year() - 1 || year > LocalDate::MAX.year() + 1 {
+        Err(Error::YearOutOfRange { given: year })
+    }
+    else {
+        Ok(())
+    }
+}
+
+/// Returns the date with the given Julian day, or an error mentioning the
+/// given year if it’s outside the range of dates that can be represented.
+pub(crate) fn date_from_julian_day(day: i64, year: i64) -> Result<LocalDate, Error> {
+    LocalDate::checked_from_julian_day(day).ok_or(Error::YearOutOfRange { given: year })
+}
+
+
+/// A date, given as a year, month, and day in a particular calendar.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct CalendarDate<C> {
+    calendar: C,
+    date: LocalDate,
+    year: i64,
+    month: i8,
+    day: i8,
+}
+
+impl<C: CalendarSystem> CalendarDate<C> {
+
+    /// Creates a date from a year, month, and day in the given calendar,
+    /// returning an error if there’s no such day.
+    pub fn new(calendar: C, year: i64, month: i8, day: i8) -> Result<CalendarDate<C>, Error> {
+        let date = try!(calendar.from_ymd(year, month, day));
+        Ok(CalendarDate { calendar: calendar, date: date, year: year, month: month, day: day })
+    }
+
+    /// Returns the given date as it is written in the given calendar.
+    pub fn from_date(calendar: C, date: LocalDate) -> CalendarDate<C> {
+        let (year, month, day) = calendar.to_ymd(date);
+        CalendarDate { calendar: calendar, date: date, year: year, month: month, day: day }
+    }
+
+    /// Returns the calendar this date is given in.
+    pub fn calendar(&self) -> &C {
+        &self.calendar
+    }
+
+    /// Returns the year in this date’s calendar.
+    pub fn year(&self) -> i64 {
+        self.year
+    }
+
+    /// Returns the number of the month in this date’s calendar, starting
+    /// from 1.
+    pub fn month(&self) -> i8 {
+        self.month
+    }
+
+    /// Returns the day of the month in this date’s calendar, starting
+    /// from 1.
+    pub fn day(&self) -> i8 {
+        self.day
+    }
+
+    /// Returns the day of the year in this date’s calendar, starting from
+    /// 1.
+    pub fn yearday(&self) -> i16 {
+        let before: i16 = (1 .. self.month).map(|month| self.calendar.days_in_month(self.year, month) as i16).sum();
+        before + (self.date.julian_day() - self.start_of_month().julian_day()) as i16 + 1
+    }
+
+    /// Returns the day of the week, which is the same in every calendar.
+    pub fn weekday(&self) -> Weekday {
+        self.date.weekday()
+    }
+
+    /// Returns the number of days in this date’s month.
+    pub fn days_in_month(&self) -> i8 {
+        self.calendar.days_in_month(self.year, self.month)
+    }
+
+    /// Returns the name of this date’s month, if its calendar has names of
+    /// its own for its months.
+    pub fn month_name(&self) -> Option<&str> {
+        self.calendar.month_name(self.year, self.month)
+    }
+
+    fn start_of_month(&self) -> LocalDate {
+        self.calendar.start_of_month(self.year, self.month).expect("month out of range")
+    }
+
+    /// Returns the era this date is in and its year in that era, if its
+    /// calendar numbers years within eras.
+    pub fn era_year(&self) -> Option<EraYear> {
+        self.calendar.era_year(self.date)
+    }
+
+    /// Returns this date in the Gregorian calendar.
+    pub fn to_local_date(&self) -> LocalDate {
+        self.date
+    }
+
+    /// Returns this date as it is written in another calendar.
+    pub fn in_calendar<D: CalendarSystem>(&self, calendar: D) -> CalendarDate<D> {
+        CalendarDate::from_date(calendar, self.date)
+    }
+}
+
+impl<C: CalendarSystem + Clone> CalendarDate<C> {
+
+    /// Returns an iterator over every day in this date’s month, from the
+    /// first to the last.
+    pub fn month_days(&self) -> CalendarDays<C> {
+        let first = self.start_of_month().julian_day();
+        CalendarDays {
+            calendar: self.calendar.clone(),
+            days: first .. first + self.days_in_month() as i64,
+        }
+    }
+}
+
+impl<C: PartialEq> PartialOrd for CalendarDate<C> {
+
+    /// Dates in the same calendar are ordered chronologically. Dates in
+    /// different calendars can’t be compared.
+    fn partial_cmp(&self, other: &CalendarDate<C>) -> Option<Ordering> {
+        if self.calendar == other.calendar { self.date.partial_cmp(&other.date) } else { None }
+    }
+}
+
+impl<C> fmt::Display for CalendarDate<C> {
+
+    /// Formats this date with its numbers in the same order as in ISO
+    /// 8601, such as `5784-07-01`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.year < 0 {
+            write!(f, "{:05}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+        else {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+}
+
+
+/// An iterator over consecutive days in a calendar, returned by
+/// `CalendarDate::month_days`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CalendarDays<C> {
+    calendar: C,
+    days: Range<i64>,
+}
+
+impl<C: CalendarSystem + Clone> Iterator for CalendarDays<C> {
+    type Item = CalendarDate<C>;
+
+    fn next(&mut self) -> Option<CalendarDate<C>> {
+        self.days.next().map(|day| CalendarDate::from_date(self.calendar.clone(), LocalDate::from_julian_day(day)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.days.size_hint()
+    }
+}
+
+impl<C: CalendarSystem + Clone> DoubleEndedIterator for CalendarDays<C> {
+    fn next_back(&mut self) -> Option<CalendarDate<C>> {
+        self.days.next_back().map(|day| CalendarDate::from_date(self.calendar.clone(), LocalDate::from_julian_day(day)))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    #[test]
+    fn gregorian_round_trip() {
+        for day in LocalDate::ymd(1999, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2002, Month::January, 1).unwrap().julian_day() {
+            let date = LocalDate::from_julian_day(day);
+            let (year, month, day) = Gregorian.to_ymd(date);
+            assert_eq!(Gregorian.from_ymd(year, month, day), Ok(date));
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(Gregorian.days_in_year(2023), 365);
+        assert_eq!(Gregorian.days_in_year(2024), 366);
+        assert_eq!(Gregorian.days_in_month(2024, 2), 29);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(CalendarDate::new(Gregorian, 2023, 13, 1), Err(Error::MonthOutOfRange { given: 13 }));
+        assert_eq!(CalendarDate::new(Gregorian, 2023, 2, 29), Err(Error::DayOutOfRange { given: 29, max: 28 }));
+        assert_eq!(check_ymd(&Gregorian, 2023, 0, 1), Err(Error::MonthOutOfRange { given: 0 }));
+    }
+
+    #[test]
+    fn fields() {
+        let date = CalendarDate::from_date(Gregorian, LocalDate::ymd(2024, Month::March, 1).unwrap());
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 3, 1));
+        assert_eq!(date.yearday(), 61);
+        assert_eq!(date.weekday(), Weekday::Friday);
+        assert_eq!(date.month_name(), None);
+        assert_eq!(date.to_string(), "2024-03-01");
+    }
+
+    #[test]
+    fn month_days() {
+        let date = CalendarDate::new(Gregorian, 2024, 2, 10).unwrap();
+        let days: Vec<_> = date.month_days().collect();
+        assert_eq!(days.len(), 29);
+        assert_eq!(days[0], CalendarDate::new(Gregorian, 2024, 2, 1).unwrap());
+        assert_eq!(date.month_days().rev().next(), CalendarDate::new(Gregorian, 2024, 2, 29).ok());
+        assert!(days[0] < days[1]);
+    }
+
+    #[test]
+    fn julian_dates() {
+        assert_eq!(Julian.to_ymd(LocalDate::ymd(1582, Month::October, 15).unwrap()), (1582, 10, 5));
+        assert_eq!(Julian.to_ymd(LocalDate::ymd(1918, Month::February, 13).unwrap()), (1918, 1, 31));
+        assert_eq!(Julian.from_ymd(1900, 2, 29), LocalDate::ymd(1900, Month::March, 13));
+        assert_eq!(Julian.from_ymd(250, 3, 1), LocalDate::ymd(250, Month::March, 1));
+        assert_eq!(Julian.from_ymd(-44, 1, 1), LocalDate::ymd(-45, Month::December, 30));
+        assert_eq!(Julian.from_ymd(2023, 2, 29), Err(Error::DayOutOfRange { given: 29, max: 28 }));
+        assert_eq!(Julian.from_ymd(i64::max_value(), 1, 1), Err(Error::YearOutOfRange { given: i64::max_value() }));
+    }
+
+    #[test]
+    fn julian_round_trip() {
+        for day in LocalDate::ymd(-1001, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2101, Month::January, 1).unwrap().julian_day() {
+            let date = LocalDate::from_julian_day(day);
+            let (year, month, day) = Julian.to_ymd(date);
+            assert_eq!(Julian.from_ymd(year, month, day), Ok(date));
+        }
+    }
+
+    #[test]
+    fn rome() {
+        let calendar = Changeover::ROME;
+        assert_eq!(calendar.last_julian_day(), LocalDate::ymd(1582, Month::October, 14).unwrap());
+        assert_eq!(calendar.from_ymd(1582, 10, 4), LocalDate::ymd(1582, Month::October, 14));
+        assert_eq!(calendar.from_ymd(1582, 10, 15), LocalDate::ymd(1582, Month::October, 15));
+        assert_eq!(calendar.from_ymd(1582, 10, 10), Err(Error::DayOutOfRange { given: 10, max: 31 }));
+        assert_eq!(calendar.from_ymd(1500, 2, 29), LocalDate::ymd(1500, Month::March, 10));
+
+        assert_eq!(calendar.days_in_month(1582, 10), 21);
+        assert_eq!(calendar.days_in_month(1582, 11), 30);
+        assert_eq!(calendar.days_in_year(1582), 355);
+
+        let date = CalendarDate::new(calendar, 1582, 10, 15).unwrap();
+        assert_eq!(date.yearday(), 278);
+        let days: Vec<_> = date.month_days().map(|date| date.day()).collect();
+        assert_eq!(days, [ 1, 2, 3, 4, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31 ]);
+    }
+
+    #[test]
+    fn great_britain() {
+        let calendar = Changeover::GREAT_BRITAIN;
+        assert_eq!(calendar.days_in_month(1700, 2), 29);
+        assert_eq!(calendar.days_in_month(1752, 9), 19);
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(1752, Month::September, 13).unwrap()), (1752, 9, 2));
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(1752, Month::September, 14).unwrap()), (1752, 9, 14));
+
+        let date = CalendarDate::from_date(Gregorian, LocalDate::ymd(1750, Month::January, 1).unwrap()).in_calendar(calendar);
+        assert_eq!((date.year(), date.month(), date.day()), (1749, 12, 21));
+    }
+
+    #[test]
+    fn changeover_round_trip() {
+        // Russia switched in 1918, after the 31st of January.
+        let calendar = Changeover::new(LocalDate::ymd(1918, Month::February, 14).unwrap());
+        assert_eq!(calendar.start_of_month(1918, 2), LocalDate::ymd(1918, Month::February, 14));
+        assert_eq!(calendar.days_in_month(1918, 2), 15);
+
+        for day in LocalDate::ymd(1917, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(1920, Month::January, 1).unwrap().julian_day() {
+            let date = LocalDate::from_julian_day(day);
+            let (year, month, day) = calendar.to_ymd(date);
+            assert_eq!(calendar.from_ymd(year, month, day), Ok(date));
+        }
+    }
+}
@@ -1,15 +1,30 @@
 //! Dates, times, datetimes, months, and weekdays.
 
 use std::cmp::{Ordering, PartialOrd};
+use std::convert::TryFrom;
 use std::error::Error as ErrorTrait;
 use std::fmt;
-use std::ops::{Add, Sub};
+use std::hash::{Hash, Hasher};
+use std::iter::Copied;
+use std::iter::FromIterator;
+use std::ops::{Add, Sub, BitAnd, BitOr, Not};
+use std::slice;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "std")]
+use locale;
 
 use cal::{DatePiece, TimePiece};
+#[cfg(feature = "std")]
+use cal::zone::TimeZone;
 use duration::Duration;
 use instant::Instant;
-use system::sys_time;
-use util::RangeExt;
+#[cfg(feature = "std")]
+use system::{sys_time, split_system_time, join_system_time};
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+use system::{ClockSource, sys_clock_time};
+use util::{RangeExt, join_bytes, split_bytes, round};
 
 use self::Month::*;
 use self::Weekday::*;
@@ -24,6 +39,20 @@ const DAYS_IN_100Y: i64 = 365 * 100 + 24;
 /// Number of days guaranteed to be in four hundred years.
 const DAYS_IN_400Y: i64 = 365 * 400 + 97;
 
+/// The Julian Day Number of the 1st of January, 1970.
+const JULIAN_DAY_OF_UNIX_EPOCH: i64 = 2440588;
+
+/// The first and last days that can be represented, as numbers of days
+/// since the 1st of January, 1970. These are the days that contain the
+/// smallest and largest Unix timestamps that fit in an `i64`.
+const MIN_DAY: i64 = -106_751_991_167_301;
+const MAX_DAY: i64 = 106_751_991_167_300;
+
+/// The years that `MIN_DAY` and `MAX_DAY` fall in, which are checked first
+/// so that the number of days in a year can’t overflow.
+const MIN_YEAR: i64 = -292_277_022_657;
+const MAX_YEAR: i64 = 292_277_026_596;
+
 /// Number of seconds in a day. As everywhere in this library, leap seconds
 /// are simply ignored.
 const SECONDS_IN_DAY: i64 = 86400;
@@ -59,26 +88,27 @@ const EPOCH_DIFFERENCE: i64 = (30 * 365      // 30 years between 2000 and 1970..
                                + 31 + 29);   // plus all the days in January and February in 2000.
 
 
-/// This rather strange triangle is an array of the number of days elapsed
-/// at the end of each month, starting at the beginning of March (the first
-/// month after the EPOCH above), going backwards, ignoring February.
-const TIME_TRIANGLE: &'static [i64; 11] =
-    &[31 + 30 + 31 + 30 + 31 + 31 + 30 + 31 + 30 + 31 + 31,  // January
-      31 + 30 + 31 + 30 + 31 + 31 + 30 + 31 + 30 + 31,  // December
-      31 + 30 + 31 + 30 + 31 + 31 + 30 + 31 + 30,  // November
-      31 + 30 + 31 + 30 + 31 + 31 + 30 + 31,  // October
-      31 + 30 + 31 + 30 + 31 + 31 + 30,  // September
-      31 + 30 + 31 + 30 + 31 + 31,  // August
-      31 + 30 + 31 + 30 + 31,  // July
-      31 + 30 + 31 + 30,  // June
-      31 + 30 + 31,  // May
-      31 + 30,  // April
-      31]; // March
-
+/// The number of days in a year before the start of each month, with a
+/// thirteenth entry for the end of December: the first row is for common
+/// years, and the second for leap years.
+///
+/// Looking a month up in here is quicker than adding up the lengths of
+/// the months before it, and going the other way, the month a day of the
+/// year falls in is either the one at `day / 32` or the one after it.
+const CUMULATIVE_DAYS: [[i16; 13]; 2] = [
+    [ 0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334, 365 ],
+    [ 0, 31, 60, 91, 121, 152, 182, 213, 244, 274, 305, 335, 366 ],
+];
 
 
 /// A **local date** is a day-long span on the timeline, *without a time
 /// zone*.
+///
+/// Dates can be anywhere from the 27th of January in the year
+/// -292,277,022,657 to the 4th of December in the year 292,277,026,596,
+/// which is the range of days that a 64-bit Unix timestamp can reach, and
+/// are available as `LocalDate::MIN` and `LocalDate::MAX`. Trying to
+/// create one outside that range returns an error.
 #[derive(Eq, Clone, Copy)]
 pub struct LocalDate {
     ymd:     YMD,
@@ -88,25 +118,61 @@ pub struct LocalDate {
 
 /// A **local time** is a time on the timeline that recurs once a day,
 /// *without a time zone*.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+///
+/// The second field can be 60, to represent a leap second inserted at the
+/// end of an hour, such as the `23:59:60` that sometimes ends a day in UTC.
+/// Only `hms_leap` and `hms_ns_leap` create one, along with parsing, as a
+/// 60th second passed to the other constructors is more likely to be a
+/// mistake. When converted to a number of seconds, a leap second counts as
+/// the second before it, as this library’s instants ignore leap seconds.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct LocalTime {
     hour:   i8,
     minute: i8,
     second: i8,
-    millisecond: i16,
+    nanosecond: i32,
 }
 
 /// A **local date-time** is an exact instant on the timeline, *without a
 /// time zone*.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+///
+/// Date-times can be anywhere from `-292277022657-01-27T08:29:52` to
+/// `+292277026596-12-04T15:30:07`, the range of a 64-bit Unix timestamp,
+/// available as `LocalDateTime::MIN` and `LocalDateTime::MAX`. The
+/// arithmetic operators panic if their result would be outside that range;
+/// use `checked_add` and `checked_sub` to get `None` instead.
+///
+/// Rather than holding a `LocalDate` and a `LocalTime`, a date-time is
+/// stored as two numbers, so it takes up 16 bytes and can be compared
+/// without looking at its fields one by one. The calendar fields are
+/// worked out from them when they’re asked for.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct LocalDateTime {
-    date: LocalDate,
-    time: LocalTime,
+
+    /// The number of days since the 1st of January, 1970.
+    days: i64,
+
+    /// The time of day, packed by `LocalTime::pack`.
+    time: u64,
 }
 
 
 impl LocalDate {
 
+    /// The earliest date that can be represented, the 27th of January in
+    /// the year -292,277,022,657.
+    pub const MIN: LocalDate = match LocalDate::ymd(MIN_YEAR, January, 27) {
+        Ok(date) => date,
+        Err(_)   => panic!("first date out of range"),
+    };
+
+    /// The latest date that can be represented, the 4th of December in the
+    /// year 292,277,026,596.
+    pub const MAX: LocalDate = match LocalDate::ymd(MAX_YEAR, December, 4) {
+        Ok(date) => date,
+        Err(_)   => panic!("last date out of range"),
+    };
+
     /// Creates a new local date instance from the given year, month, and day
     /// fields.
     ///
@@ -128,10 +194,24 @@ impl LocalDate {
     ///
     /// assert!(LocalDate::ymd(2100, Month::February, 29).is_err());
     /// ```
-    pub fn ymd(year: i64, month: Month, day: i8) -> Result<LocalDate, Error> {
-        YMD { year: year, month: month, day: day }
-            .to_days_since_epoch()
-            .map(|days| LocalDate::from_days_since_epoch(days - EPOCH_DIFFERENCE))
+    ///
+    /// This is a `const fn`, so dates can be defined as constants. Matching
+    /// on the result, and panicking if it’s an error, makes an invalid date
+    /// fail to compile:
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month};
+    ///
+    /// const LAUNCH: LocalDate = match LocalDate::ymd(2015, Month::June, 26) {
+    ///     Ok(date) => date,
+    ///     Err(_)   => panic!("invalid launch date"),
+    /// };
+    /// ```
+    pub const fn ymd(year: i64, month: Month, day: i8) -> Result<LocalDate, Error> {
+        match (YMD { year: year, month: month, day: day }).to_days_since_epoch() {
+            Ok(days) => Ok(LocalDate::from_days_since_epoch(days - EPOCH_DIFFERENCE)),
+            Err(e)   => Err(e),
+        }
     }
 
     /// Creates a new local date instance from the given year and day-of-year
@@ -160,7 +240,7 @@ impl LocalDate {
             Ok(LocalDate::from_days_since_epoch(days + yearday - 1 - EPOCH_DIFFERENCE))
         }
         else {
-            Err(Error::OutOfRange)
+            Err(Error::YeardayOutOfRange { given: yearday })
         }
     }
 
@@ -205,18 +285,15 @@ impl LocalDate {
     /// ```
     pub fn ywd(year: i64, week: i64, weekday: Weekday) -> Result<LocalDate, Error> {
         let jan_4 = YMD { year: year, month: January, day: 4 };
-        let correction = days_to_weekday(jan_4.to_days_since_epoch().unwrap() - EPOCH_DIFFERENCE).days_from_monday_as_one() as i64 + 3;
+        let correction = days_to_weekday(try!(jan_4.to_days_since_epoch()) - EPOCH_DIFFERENCE).number_from_monday() as i64 + 3;
 
-        let yearday = 7 * week + weekday.days_from_monday_as_one() as i64 - correction;
+        let yearday = 7 * week + weekday.number_from_monday() as i64 - correction;
 
         if yearday <= 0 {
-            let (_, is_leap_year) = YMD { year: year - 1, month: January, day: 1 }.leap_year_calculations();
-            let days_in_year = if is_leap_year { 366 } else { 365 };
-            LocalDate::yd(year - 1, days_in_year + yearday)
+            LocalDate::yd(year - 1, days_in_year(year - 1) as i64 + yearday)
         }
         else {
-            let (_, is_leap_year) = YMD { year: year, month: January, day: 1 }.leap_year_calculations();
-            let days_in_year = if is_leap_year { 366 } else { 365 };
+            let days_in_year = days_in_year(year) as i64;
 
             if yearday >= days_in_year {
                 LocalDate::yd(year + 1, yearday - days_in_year)
@@ -227,6 +304,77 @@ impl LocalDate {
         }
     }
 
+    /// Computes a LocalDate from its Julian Day Number: the number of days
+    /// since the 1st of January, 4713 BC, in the proleptic Julian calendar,
+    /// as used by astronomers.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month};
+    ///
+    /// let date = LocalDate::from_julian_day(2457448);
+    /// assert_eq!(date, LocalDate::ymd(2016, Month::February, 29).unwrap());
+    /// ```
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented. Use `checked_from_julian_day` to handle that case
+    /// instead.
+    pub fn from_julian_day(julian_day: i64) -> LocalDate {
+        LocalDate::checked_from_julian_day(julian_day).expect("Julian day out of range")
+    }
+
+    /// Computes a LocalDate from its Julian Day Number, returning `None` if
+    /// the day is outside the range of dates that can be represented.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month};
+    ///
+    /// assert_eq!(LocalDate::checked_from_julian_day(2457448), LocalDate::ymd(2016, Month::February, 29).ok());
+    /// assert_eq!(LocalDate::checked_from_julian_day(i64::max_value()), None);
+    /// ```
+    pub fn checked_from_julian_day(julian_day: i64) -> Option<LocalDate> {
+        julian_day.checked_sub(JULIAN_DAY_OF_UNIX_EPOCH)
+                  .filter(|days| days.is_within(MIN_DAY .. MAX_DAY + 1))
+                  .map(|days| LocalDate::from_days_since_epoch(days - EPOCH_DIFFERENCE))
+    }
+
+    /// Returns the Julian Day Number of this date. Julian days begin at
+    /// noon, so this is the number of the day that begins at noon on this
+    /// date.
+    pub fn julian_day(&self) -> i64 {
+        self.ymd.to_days_since_epoch().unwrap() + JULIAN_DAY_OF_UNIX_EPOCH
+    }
+
+    /// Combines this date with a time to make a date-time.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+    ///
+    /// let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+    /// assert_eq!(date.at(LocalTime::NOON), LocalDateTime::new(date, LocalTime::NOON));
+    /// ```
+    ///
+    /// ### Panics
+    ///
+    /// Panics in the same cases as `LocalDateTime::new`.
+    pub const fn at(self, time: LocalTime) -> LocalDateTime {
+        LocalDateTime::new(self, time)
+    }
+
+    /// Returns the current date in UTC, according to the system clock.
+    /// Use `LocalDateTime::now_in` to get the date somewhere else.
+    #[cfg(feature = "std")]
+    pub fn today() -> LocalDate {
+        LocalDateTime::now().date()
+    }
+
     /// Computes a LocalDate - year, month, day, weekday, and yearday -
     /// given the number of days that have passed since the EPOCH.
     ///
@@ -254,89 +402,19 @@ impl LocalDate {
     /// assert_eq!(date.month(), Month::September);
     /// assert_eq!(date.day(), 24);  // not the 25th!
     /// ```
-    fn from_days_since_epoch(days: i64) -> LocalDate {
-
-        // The Gregorian calendar works in 400-year cycles, which repeat
-        // themselves ever after.
-        //
-        // This calculation works by finding the number of 400-year,
-        // 100-year, and 4-year cycles, then constantly subtracting the
-        // number of leftover days.
-        let (num_400y_cycles, mut remainder) = split_cycles(days, DAYS_IN_400Y);
-
-        // Calculate the numbers of 100-year cycles, 4-year cycles, and
-        // leftover years, continually reducing the number of days left to
-        // think about.
-        let num_100y_cycles = remainder / DAYS_IN_100Y;
-        remainder -= num_100y_cycles * DAYS_IN_100Y;  // remainder is now days left in this 100-year cycle
-
-        let num_4y_cycles = remainder / DAYS_IN_4Y;
-        remainder -= num_4y_cycles * DAYS_IN_4Y;  // remainder is now days left in this 4-year cycle
-
-        let mut years = remainder / 365;
-        remainder -= years * 365;  // remainder is now days left in this year
-
-        // Leap year calculation goes thusly:
-        //
-        // 1. If the year is a multiple of 400, it’s a leap year.
-        // 2. Else, if the year is a multiple of 100, it’s *not* a leap year.
-        // 3. Else, if the year is a multiple of 4, it’s a leap year again!
-        //
-        // We already have the values for the numbers of multiples at this
-        // point, and it’s safe to re-use them.
-        let days_this_year =
-            if years == 0 && !(num_4y_cycles == 0 && num_100y_cycles != 0) { 366 }
-                                                                      else { 365 };
-
-        // Find out which number day of the year it is.
-        // The 306 here refers to the number of days in a year excluding
-        // January and February (which are excluded because of the EPOCH)
-        let mut day_of_year = remainder + days_this_year - 306;
-        if day_of_year >= days_this_year {
-            day_of_year -= days_this_year;  // wrap around for January and February
-        }
-
-        // Turn all those cycles into an actual number of years.
-        years +=   4 * num_4y_cycles
-               + 100 * num_100y_cycles
-               + 400 * num_400y_cycles;
-
-        // Work out the month and number of days into the month by scanning
-        // the time triangle, finding the month that has the correct number
-        // of days elapsed at the end of it.
-        // (it’s “11 - index” below because the triangle goes backwards)
-        let result = TIME_TRIANGLE.iter()
-                                  .enumerate()
-                                  .find(|&(_, days)| *days <= remainder);
-
-        let (mut month, month_days) = match result {
-            Some((index, days)) => (11 - index, remainder - *days),
-            None => (0, remainder),  // No month found? Then it’s February.
-        };
-
-        // Need to add 2 to the month in order to compensate for the EPOCH
-        // being in March.
-        month += 2;
-
-        if month >= 12 {
-            years += 1;   // wrap around for January and February
-            month -= 12;  // (yes, again)
-        }
-
-        // The check immediately above means we can `unwrap` this, as the
-        // month number is guaranteed to be in the range (0..12).
-        let month_variant = Month::from_zero(month as i8).unwrap();
+    const fn from_days_since_epoch(days: i64) -> LocalDate {
+        let (year, yearday, leap_year) = year_and_yearday(days);
+        let (month, day) = month_and_day(yearday, leap_year);
 
         // Finally, adjust the day numbers for human reasons: the first day
-        // of the month is the 1st, rather than the 0th, and the year needs
-        // to be adjusted relative to the EPOCH.
+        // of the month is the 1st, rather than the 0th.
         LocalDate {
-            yearday: (day_of_year + 1) as i16,
+            yearday: yearday + 1,
             weekday: days_to_weekday(days),
             ymd: YMD {
-                year:  years + 2000,
-                month: month_variant,
-                day:   (month_days + 1) as i8,
+                year:  year,
+                month: month,
+                day:   day + 1,
             },
         }
     }
@@ -364,12 +442,42 @@ impl LocalDate {
     // technically *need* to be unsafe, but I’ll stick with it for now.
 }
 
+impl Default for LocalDate {
+
+    /// Returns the date of the Unix epoch, the 1st of January, 1970.
+    fn default() -> LocalDate {
+        LocalDate::from_days_since_epoch(-EPOCH_DIFFERENCE)
+    }
+}
+
 impl fmt::Debug for LocalDate {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(f, "{:04}-{:02}-{:02}", self.year(), self.month().months_from_january(), self.day())
     }
 }
 
+impl fmt::Display for LocalDate {
+
+    /// Formats this date in the ISO 8601 format, such as `2015-06-26`.
+    /// Years before 1 BCE are negative, following the proleptic rules in
+    /// ISO 8601, so the Ides of March in 44 BCE is `-0043-03-15`. Years
+    /// after 9999 have a plus sign in front of them, such as
+    /// `+12345-03-15`, so they can be told apart from the basic format.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.year() < 0 {
+            // The width includes the minus sign, so there are still four
+            // digits after it.
+            write!(f, "{:05}-{:02}-{:02}", self.year(), self.month() as i8, self.day())
+        }
+        else if self.year() > 9999 {
+            write!(f, "+{}-{:02}-{:02}", self.year(), self.month() as i8, self.day())
+        }
+        else {
+            write!(f, "{:04}-{:02}-{:02}", self.year(), self.month() as i8, self.day())
+        }
+    }
+}
+
 impl DatePiece for LocalDate {
     fn year(&self) -> i64 { self.ymd.year }
     fn month(&self) -> Month { self.ymd.month }
@@ -384,6 +492,15 @@ impl PartialEq for LocalDate {
     }
 }
 
+impl Hash for LocalDate {
+
+    /// Hashes only the year, month, and day, as the other fields are
+    /// derived from them, matching the `PartialEq` implementation.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ymd.hash(state)
+    }
+}
+
 impl PartialOrd for LocalDate {
     fn partial_cmp(&self, other: &LocalDate) -> Option<Ordering> {
         self.ymd.partial_cmp(&other.ymd)
@@ -398,56 +515,87 @@ impl Ord for LocalDate {
 
 impl LocalTime {
 
+    /// The first moment of the day, `00:00:00`.
+    pub const MIDNIGHT: LocalTime = LocalTime { hour: 0, minute: 0, second: 0, nanosecond: 0 };
+
+    /// The middle of the day, `12:00:00`.
+    pub const NOON: LocalTime = LocalTime { hour: 12, minute: 0, second: 0, nanosecond: 0 };
+
+    /// The last nanosecond of a day without a leap second,
+    /// `23:59:59.999999999`.
+    pub const MAX: LocalTime = LocalTime { hour: 23, minute: 59, second: 59, nanosecond: 999_999_999 };
+
     /// Computes the number of hours, minutes, and seconds, based on the
     /// number of seconds that have elapsed since midnight.
     pub fn from_seconds_since_midnight(seconds: i64) -> LocalTime {
-        LocalTime::from_seconds_and_milliseconds_since_midnight(seconds, 0)
+        LocalTime::from_seconds_and_nanoseconds_since_midnight(seconds, 0)
     }
 
     /// Computes the number of hours, minutes, and seconds, based on the
     /// number of seconds that have elapsed since midnight.
     pub fn from_seconds_and_milliseconds_since_midnight(seconds: i64, millisecond_of_second: i16) -> LocalTime {
+        LocalTime::from_seconds_and_nanoseconds_since_midnight(seconds, millisecond_of_second as i32 * 1_000_000)
+    }
+
+    /// Computes the number of hours, minutes, and seconds, based on the
+    /// number of seconds that have elapsed since midnight, along with the
+    /// number of nanoseconds so far this second. Nanoseconds that are
+    /// negative, or that add up to a second or more, carry over into the
+    /// seconds.
+    pub fn from_seconds_and_nanoseconds_since_midnight(seconds: i64, nanosecond_of_second: i32) -> LocalTime {
+        let (carry, nanoseconds) = split_cycles(nanosecond_of_second as i64, 1_000_000_000);
+        let seconds = seconds + carry;
+
         LocalTime {
             hour:   (seconds / 60 / 60) as i8,
             minute: (seconds / 60 % 60) as i8,
             second: (seconds % 60) as i8,
-            millisecond: millisecond_of_second,
+            nanosecond: nanoseconds as i32,
         }
     }
 
     /// Returns the time at midnight, with all fields initialised to 0.
     pub fn midnight() -> LocalTime {
-        LocalTime { hour: 0, minute: 0, second: 0, millisecond: 0 }
+        LocalTime::MIDNIGHT
+    }
+
+    /// Returns the current time of day in UTC, with as much precision as
+    /// the system clock provides.
+    #[cfg(feature = "std")]
+    pub fn now() -> LocalTime {
+        LocalDateTime::now().time()
     }
 
     /// Creates a new timestamp instance with the given hour and minute
-    /// fields. The second and millisecond fields are set to 0.
+    /// fields. The second and nanosecond fields are set to 0.
     ///
     /// The values are checked for validity before instantiation, and
     /// passing in values out of range will return an `Err`.
-    pub fn hm(hour: i8, minute: i8) -> Result<LocalTime, Error> {
-        if (hour.is_within(0..24) && minute.is_within(0..60))
-        || (hour == 24 && minute == 00) {
-            Ok(LocalTime { hour: hour, minute: minute, second: 0, millisecond: 0 })
-        }
-        else {
-            Err(Error::OutOfRange)
+    pub const fn hm(hour: i8, minute: i8) -> Result<LocalTime, Error> {
+        if hour != 24 || minute != 00 {
+            if let Err(e) = check_hms(hour, minute, 0) {
+                return Err(e);
+            }
         }
+
+        Ok(LocalTime { hour: hour, minute: minute, second: 0, nanosecond: 0 })
     }
 
     /// Creates a new timestamp instance with the given hour, minute, and
-    /// second fields. The millisecond field is set to 0.
+    /// second fields. The nanosecond field is set to 0.
     ///
     /// The values are checked for validity before instantiation, and
     /// passing in values out of range will return an `Err`.
-    pub fn hms(hour: i8, minute: i8, second: i8) -> Result<LocalTime, Error> {
-        if (hour.is_within(0..24) && minute.is_within(0..60) && second.is_within(0..60))
-        || (hour == 24 && minute == 00 && second == 00) {
-            Ok(LocalTime { hour: hour, minute: minute, second: second, millisecond: 0 })
-        }
-        else {
-            Err(Error::OutOfRange)
+    ///
+    /// This is a `const fn`, so times can be defined as constants.
+    pub const fn hms(hour: i8, minute: i8, second: i8) -> Result<LocalTime, Error> {
+        if hour != 24 || minute != 00 || second != 00 {
+            if let Err(e) = check_hms(hour, minute, second) {
+                return Err(e);
+            }
         }
+
+        Ok(LocalTime { hour: hour, minute: minute, second: second, nanosecond: 0 })
     }
 
     /// Creates a new timestamp instance with the given hour, minute,
@@ -456,22 +604,130 @@ impl LocalTime {
     /// The values are checked for validity before instantiation, and
     /// passing in values out of range will return an `Err`.
     pub fn hms_ms(hour: i8, minute: i8, second: i8, millisecond: i16) -> Result<LocalTime, Error> {
-        if hour.is_within(0..24)   && minute.is_within(0..60)
-        && second.is_within(0..60) && millisecond.is_within(0..1000)
-        {
-            Ok(LocalTime { hour: hour, minute: minute, second: second, millisecond: millisecond })
+        if millisecond.is_within(0..1000) {
+            LocalTime::hms_ns(hour, minute, second, millisecond as i32 * 1_000_000)
         }
         else {
-            Err(Error::OutOfRange)
+            Err(Error::MillisecondOutOfRange { given: millisecond })
+        }
+    }
+
+    /// Creates a new timestamp instance with the given hour, minute,
+    /// second, and nanosecond fields.
+    ///
+    /// The values are checked for validity before instantiation, and
+    /// passing in values out of range will return an `Err`.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalTime, TimePiece};
+    ///
+    /// let time = LocalTime::hms_ns(12, 34, 56, 789_012_345).unwrap();
+    /// assert_eq!(time.millisecond(), 789);
+    /// assert_eq!(time.microsecond(), 789_012);
+    /// assert_eq!(time.nanosecond(), 789_012_345);
+    ///
+    /// assert!(LocalTime::hms_ns(12, 34, 56, 1_000_000_000).is_err());
+    /// ```
+    pub const fn hms_ns(hour: i8, minute: i8, second: i8, nanosecond: i32) -> Result<LocalTime, Error> {
+        if let Err(e) = check_hms(hour, minute, second) {
+            return Err(e);
+        }
+
+        if nanosecond >= 0 && nanosecond < 1_000_000_000 {
+            Ok(LocalTime { hour: hour, minute: minute, second: second, nanosecond: nanosecond })
+        }
+        else {
+            Err(Error::NanosecondOutOfRange { given: nanosecond })
+        }
+    }
+
+    /// Creates a new timestamp instance with the given hour, minute, and
+    /// second fields, like `hms`, except that the second can be 60 at the
+    /// end of the last minute of an hour, for a leap second.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalTime, TimePiece};
+    ///
+    /// let time = LocalTime::hms_leap(23, 59, 60).unwrap();
+    /// assert!(time.is_leap_second());
+    ///
+    /// assert!(LocalTime::hms(23, 59, 60).is_err());
+    /// assert!(LocalTime::hms_leap(23, 58, 60).is_err());
+    /// ```
+    pub const fn hms_leap(hour: i8, minute: i8, second: i8) -> Result<LocalTime, Error> {
+        LocalTime::hms_ns_leap(hour, minute, second, 0)
+    }
+
+    /// Creates a new timestamp instance with the given hour, minute,
+    /// second, and nanosecond fields, like `hms_ns`, except that the second
+    /// can be 60 at the end of the last minute of an hour, for a leap
+    /// second.
+    pub const fn hms_ns_leap(hour: i8, minute: i8, second: i8, nanosecond: i32) -> Result<LocalTime, Error> {
+        if second != 60 || minute != 59 {
+            return LocalTime::hms_ns(hour, minute, second, nanosecond);
+        }
+
+        match LocalTime::hms_ns(hour, minute, 59, nanosecond) {
+            Ok(time)  => Ok(LocalTime { second: 60, .. time }),
+            Err(e)    => Err(e),
         }
     }
 
     /// Calculate the number of seconds since midnight this time is at,
-    /// ignoring milliseconds.
-    pub fn to_seconds(&self) -> i64 {
+    /// ignoring fractions of a second. A leap second is counted as the
+    /// 59th second of its minute.
+    pub const fn to_seconds(&self) -> i64 {
         self.hour as i64 * 3600
             + self.minute as i64 * 60
-            + self.second as i64
+            + if self.second > 59 { 59 } else { self.second as i64 }
+    }
+
+    /// Packs this time into a single number that sorts the same way: the
+    /// number of nanoseconds since midnight, but counting 61 seconds in
+    /// every minute, so that a leap second gets a number of its own.
+    const fn pack(&self) -> u64 {
+        let minutes = self.hour as u64 * 60 + self.minute as u64;
+        (minutes * 61 + self.second as u64) * 1_000_000_000 + self.nanosecond as u64
+    }
+
+    /// Unpacks a time that was packed by `pack`.
+    const fn unpack(packed: u64) -> LocalTime {
+        let seconds = packed / 1_000_000_000;
+        let minutes = seconds / 61;
+
+        LocalTime {
+            hour:   (minutes / 60) as i8,
+            minute: (minutes % 60) as i8,
+            second: (seconds % 61) as i8,
+            nanosecond: (packed % 1_000_000_000) as i32,
+        }
+    }
+
+    /// Returns whether this time falls during a leap second: the 60th
+    /// second of a minute.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::LocalTime;
+    ///
+    /// assert!(LocalTime::hms_leap(23, 59, 60).unwrap().is_leap_second());
+    /// assert!(!LocalTime::hms(23, 59, 59).unwrap().is_leap_second());
+    /// ```
+    pub fn is_leap_second(&self) -> bool {
+        self.second == 60
+    }
+}
+
+impl Default for LocalTime {
+
+    /// Returns midnight, the time of the Unix epoch.
+    fn default() -> LocalTime {
+        LocalTime::midnight()
     }
 }
 
@@ -481,20 +737,61 @@ impl fmt::Debug for LocalTime {
     }
 }
 
+impl fmt::Display for LocalTime {
+
+    /// Formats this time in the ISO 8601 format, such as `14:30:00` or
+    /// `14:30:00.250`. The fraction of a second is only included if there
+    /// is one, and is written with three, six, or nine digits, whichever
+    /// is the fewest that keep all of it.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        try!(write!(f, "{:02}:{:02}:{:02}", self.hour(), self.minute(), self.second()));
+
+        if self.nanosecond % 1_000_000 == 0 {
+            if self.nanosecond != 0 {
+                try!(write!(f, ".{:03}", self.nanosecond / 1_000_000));
+            }
+        }
+        else if self.nanosecond % 1_000 == 0 {
+            try!(write!(f, ".{:06}", self.nanosecond / 1_000));
+        }
+        else {
+            try!(write!(f, ".{:09}", self.nanosecond));
+        }
+
+        Ok(())
+    }
+}
+
 impl TimePiece for LocalTime {
     fn hour(&self) -> i8 { self.hour }
     fn minute(&self) -> i8 { self.minute }
     fn second(&self) -> i8 { self.second }
-    fn millisecond(&self) -> i16 { self.millisecond }
+    fn nanosecond(&self) -> i32 { self.nanosecond }
 }
 
 
 impl LocalDateTime {
 
+    /// The earliest date-time that can be represented,
+    /// `-292277022657-01-27T08:29:52`, which is the smallest Unix timestamp
+    /// that fits in an `i64`.
+    pub const MIN: LocalDateTime = LocalDateTime::new(
+        LocalDate::MIN,
+        LocalTime { hour: 8, minute: 29, second: 52, nanosecond: 0 },
+    );
+
+    /// The latest date-time that can be represented, the last nanosecond of
+    /// `+292277026596-12-04T15:30:07`, which is the largest Unix timestamp
+    /// that fits in an `i64`.
+    pub const MAX: LocalDateTime = LocalDateTime::new(
+        LocalDate::MAX,
+        LocalTime { hour: 15, minute: 30, second: 7, nanosecond: 999_999_999 },
+    );
+
     /// Computes a complete date-time based on the values in the given
     /// Instant parameter.
     pub fn from_instant(instant: Instant) -> LocalDateTime {
-        LocalDateTime::at_ms(instant.seconds(), instant.milliseconds())
+        LocalDateTime::at_ns(instant.seconds(), instant.nanoseconds())
     }
 
     /// Computes a complete date-time based on the number of seconds that
@@ -505,90 +802,690 @@ impl LocalDateTime {
     }
 
     /// Computes a complete date-time based on the number of seconds that
-    /// have elapsed since **midnight, 1st January, 1970**,
+    /// have elapsed since **midnight, 1st January, 1970**, along with the
+    /// number of milliseconds so far this second. Milliseconds that are
+    /// negative, or that add up to a second or more, carry over into the
+    /// seconds.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if carrying the milliseconds over makes the number of
+    /// seconds overflow. Use `try_at_ms` to handle that case instead.
     pub fn at_ms(seconds_since_1970_epoch: i64, millisecond_of_second: i16) -> LocalDateTime {
-        let seconds = seconds_since_1970_epoch - EPOCH_DIFFERENCE * SECONDS_IN_DAY;
+        LocalDateTime::try_at_ms(seconds_since_1970_epoch, millisecond_of_second).expect("datetime out of range")
+    }
+
+    /// Computes a complete date-time in the same way as `at_ms`, returning
+    /// an error if carrying the milliseconds over makes the number of
+    /// seconds overflow.
+    pub fn try_at_ms(seconds_since_1970_epoch: i64, millisecond_of_second: i16) -> Result<LocalDateTime, Error> {
+        LocalDateTime::try_at(seconds_since_1970_epoch, millisecond_of_second as i64 * 1_000_000)
+    }
+
+    /// Computes a complete date-time based on the number of seconds that
+    /// have elapsed since **midnight, 1st January, 1970**, along with the
+    /// number of nanoseconds so far this second. Nanoseconds that are
+    /// negative, or that add up to a second or more, carry over into the
+    /// seconds.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if carrying the nanoseconds over makes the number of
+    /// seconds overflow. Use `try_at_ns` to handle that case instead.
+    pub fn at_ns(seconds_since_1970_epoch: i64, nanosecond_of_second: i32) -> LocalDateTime {
+        LocalDateTime::try_at_ns(seconds_since_1970_epoch, nanosecond_of_second).expect("datetime out of range")
+    }
+
+    /// Computes a complete date-time in the same way as `at_ns`, returning
+    /// an error if carrying the nanoseconds over makes the number of
+    /// seconds overflow.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDateTime, DateTimeError};
+    ///
+    /// assert_eq!(LocalDateTime::try_at_ns(0, -1), Ok(LocalDateTime::at_ns(-1, 999_999_999)));
+    /// assert_eq!(LocalDateTime::try_at_ns(i64::max_value(), 1_999_999_999), Err(DateTimeError::OutOfRange));
+    /// ```
+    pub fn try_at_ns(seconds_since_1970_epoch: i64, nanosecond_of_second: i32) -> Result<LocalDateTime, Error> {
+        LocalDateTime::try_at(seconds_since_1970_epoch, nanosecond_of_second as i64)
+    }
 
-        // Just split the input value into days and seconds, and let
-        // LocalDate and LocalTime do all the hard work.
+    /// Computes a complete date-time from a number of seconds and a number
+    /// of nanoseconds that may be more than a second, or negative.
+    fn try_at(seconds_since_1970_epoch: i64, nanoseconds: i64) -> Result<LocalDateTime, Error> {
+        let (carry, nanoseconds) = split_cycles(nanoseconds, 1_000_000_000);
+        let seconds = try!(seconds_since_1970_epoch.checked_add(carry).ok_or(Error::OutOfRange));
+
+        // Just split the input value into days and seconds, which is all
+        // that gets stored. LocalTime does the work of packing the time.
         let (days, secs) = split_cycles(seconds, SECONDS_IN_DAY);
 
-        LocalDateTime {
-            date: LocalDate::from_days_since_epoch(days),
-            time: LocalTime::from_seconds_and_milliseconds_since_midnight(secs, millisecond_of_second),
+        Ok(LocalDateTime {
+            days: days,
+            time: LocalTime::from_seconds_and_nanoseconds_since_midnight(secs, nanoseconds as i32).pack(),
+        })
+    }
+
+    /// Computes a complete date-time based on the number of milliseconds
+    /// that have elapsed since **midnight, 1st January, 1970**, the format
+    /// used by JavaScript’s `Date.now()`.
+    pub fn from_unix_millis(milliseconds_since_1970_epoch: i64) -> LocalDateTime {
+        LocalDateTime::from_instant(Instant::from_unix_millis(milliseconds_since_1970_epoch))
+    }
+
+    /// Computes a complete date-time based on the number of nanoseconds
+    /// that have elapsed since **midnight, 1st January, 1970**, keeping the
+    /// full nanosecond precision.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of seconds does not fit in an `i64`. Use
+    /// `try_from_unix_nanos` to handle that case instead.
+    pub fn from_unix_nanos(nanoseconds_since_1970_epoch: i128) -> LocalDateTime {
+        LocalDateTime::try_from_unix_nanos(nanoseconds_since_1970_epoch).expect("nanosecond timestamp out of range")
+    }
+
+    /// Computes a complete date-time based on the number of nanoseconds
+    /// that have elapsed since **midnight, 1st January, 1970**, returning
+    /// an error if the number of seconds does not fit in an `i64`.
+    pub fn try_from_unix_nanos(nanoseconds_since_1970_epoch: i128) -> Result<LocalDateTime, Error> {
+        let seconds = try!(i64::try_from(nanoseconds_since_1970_epoch.div_euclid(1_000_000_000)).map_err(|_| Error::OutOfRange));
+        let nanoseconds = nanoseconds_since_1970_epoch.rem_euclid(1_000_000_000);
+        LocalDateTime::try_at_ns(seconds, nanoseconds as i32)
+    }
+
+    /// Computes a complete date-time from a fractional Julian Date, the
+    /// number of days since noon on the 1st of January, 4713 BC, to the
+    /// nearest millisecond.
+    ///
+    /// Returns an error if the Julian Date is not a finite number, or is
+    /// too far away to be represented.
+    pub fn from_julian_date(julian_date: f64) -> Result<LocalDateTime, Error> {
+        let days = julian_date - JULIAN_DAY_OF_UNIX_EPOCH as f64 + 0.5;
+        let milliseconds = round(days * (SECONDS_IN_DAY * 1000) as f64);
+
+        if !milliseconds.is_finite() || milliseconds.abs() >= i64::max_value() as f64 {
+            return Err(Error::OutOfRange);
         }
+
+        Ok(LocalDateTime::from_unix_millis(milliseconds as i64))
     }
 
     /// Creates a new local date time from a local date and a local time.
-    pub fn new(date: LocalDate, time: LocalTime) -> LocalDateTime {
-        LocalDateTime {
-            date: date,
-            time: time,
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the date is the first or last one that can be
+    /// represented, and the time is outside the part of it that a Unix
+    /// timestamp can reach. Use `try_new` to handle that case instead.
+    pub const fn new(date: LocalDate, time: LocalTime) -> LocalDateTime {
+        match LocalDateTime::try_new(date, time) {
+            Ok(datetime) => datetime,
+            Err(_)       => panic!("date-time out of range"),
         }
     }
 
-    /// Returns the date portion of this date-time stamp.
-    pub fn date(&self) -> LocalDate {
-        self.date
+    /// Creates a new local date time from a local date and a local time,
+    /// returning an error if the date is the first or last one that can
+    /// be represented, and the time is outside the part of it that a Unix
+    /// timestamp can reach.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, LocalTime, LocalDateTime};
+    ///
+    /// assert!(LocalDateTime::try_new(LocalDate::MAX, LocalTime::MIDNIGHT).is_ok());
+    /// assert!(LocalDateTime::try_new(LocalDate::MAX, LocalTime::MAX).is_err());
+    /// ```
+    pub const fn try_new(date: LocalDate, time: LocalTime) -> Result<LocalDateTime, Error> {
+        let datetime = LocalDateTime::from_parts(date, time);
+        if datetime.is_in_range() {
+            Ok(datetime)
+        }
+        else {
+            Err(Error::OutOfRange)
+        }
     }
 
-    /// Returns the time portion of this date-time stamp.
-    pub fn time(&self) -> LocalTime {
-        self.time
+    /// Returns the month and the zero-based day of the month, without
+    /// working out the rest of the date.
+    const fn month_and_day(&self) -> (Month, i8) {
+        let (_, yearday, leap_year) = year_and_yearday(self.days - EPOCH_DIFFERENCE);
+        month_and_day(yearday, leap_year)
     }
 
-    /// Creates a new date-time stamp set to the current time.
-    pub fn now() -> LocalDateTime {
-        let (s, ms) = unsafe { sys_time() };
-        LocalDateTime::at_ms(s, ms)
+    /// Stores a date and a time as a date-time, without checking that the
+    /// result is in range.
+    const fn from_parts(date: LocalDate, time: LocalTime) -> LocalDateTime {
+        let days = match date.ymd.to_days_since_epoch() {
+            Ok(days) => days,
+            Err(_)   => panic!("date out of range"),
+        };
+
+        LocalDateTime { days: days, time: time.pack() }
     }
 
-    pub fn to_instant(&self) -> Instant {
-        let seconds = self.date.ymd.to_days_since_epoch().unwrap() * SECONDS_IN_DAY + self.time.to_seconds();
-        Instant::at_ms(seconds, self.time.millisecond)
+    /// Returns a builder that creates a date-time from its fields, each of
+    /// which can be set by name. Any field that isn’t set defaults to its
+    /// value at the Unix epoch, `1970-01-01T00:00:00`.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDateTime, LocalDate, LocalTime, Month};
+    ///
+    /// let datetime = LocalDateTime::builder().year(2024).month(Month::July).day(7).hour(9).build().unwrap();
+    /// assert_eq!(datetime, LocalDateTime::new(LocalDate::ymd(2024, Month::July, 7).unwrap(),
+    ///                                         LocalTime::hms(9, 0, 0).unwrap()));
+    ///
+    /// assert!(LocalDateTime::builder().month(Month::February).day(30).build().is_err());
+    /// ```
+    pub fn builder() -> LocalDateTimeBuilder {
+        LocalDateTimeBuilder {
+            year: 1970, month: January, day: 1,
+            hour: 0, minute: 0, second: 0, nanosecond: 0,
+        }
     }
 
-    pub fn add_seconds(&self, seconds: i64) -> LocalDateTime {
-        Self::from_instant(self.to_instant() + Duration::of(seconds))
+    /// Returns whether the number of seconds since the Unix epoch fits in
+    /// an `i64`, which is true for every date-time of every date except
+    /// the first and last ones.
+    const fn is_in_range(&self) -> bool {
+        let seconds = self.seconds();
+        seconds >= i64::min_value() as i128 && seconds <= i64::max_value() as i128
     }
-}
 
-impl fmt::Debug for LocalDateTime {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{:?}T{:?}", self.date, self.time)
+    /// Returns the date portion of this date-time stamp.
+    pub const fn date(&self) -> LocalDate {
+        LocalDate::from_days_since_epoch(self.days - EPOCH_DIFFERENCE)
     }
-}
 
-impl DatePiece for LocalDateTime {
-    fn year(&self) -> i64 { self.date.ymd.year }
-    fn month(&self) -> Month { self.date.ymd.month }
-    fn day(&self) -> i8 { self.date.ymd.day }
-    fn yearday(&self) -> i16 { self.date.yearday }
-    fn weekday(&self) -> Weekday { self.date.weekday }
-}
+    /// Returns the time portion of this date-time stamp.
+    pub const fn time(&self) -> LocalTime {
+        LocalTime::unpack(self.time)
+    }
 
-impl TimePiece for LocalDateTime {
-    fn hour(&self) -> i8 { self.time.hour }
-    fn minute(&self) -> i8 { self.time.minute }
-    fn second(&self) -> i8 { self.time.second }
-    fn millisecond(&self) -> i16 { self.time.millisecond }
-}
+    /// Returns this date-time with its date replaced by the given one,
+    /// keeping the same time.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+    ///
+    /// let datetime = LocalDate::ymd(2015, Month::June, 26).unwrap().at(LocalTime::NOON);
+    /// let moved = datetime.with_date(LocalDate::ymd(2016, Month::July, 1).unwrap());
+    /// assert_eq!(moved.to_string(), "2016-07-01T12:00:00");
+    /// ```
+    ///
+    /// ### Panics
+    ///
+    /// Panics in the same cases as `LocalDateTime::new`.
+    pub const fn with_date(&self, date: LocalDate) -> LocalDateTime {
+        LocalDateTime::new(date, self.time())
+    }
 
-impl Add<Duration> for LocalDateTime {
-    type Output = LocalDateTime;
+    /// Returns this date-time with its time replaced by the given one,
+    /// keeping the same date.
+    ///
+    /// ### Panics
+    ///
+    /// Panics in the same cases as `LocalDateTime::new`.
+    pub const fn with_time(&self, time: LocalTime) -> LocalDateTime {
+        LocalDateTime::new(self.date(), time)
+    }
 
-    fn add(self, duration: Duration) -> LocalDateTime {
-        LocalDateTime::from_instant(self.to_instant() + duration)
+    /// Creates a new date-time stamp set to the current time, with as much
+    /// precision as the system clock provides.
+    #[cfg(feature = "std")]
+    pub fn now() -> LocalDateTime {
+        let (s, ns) = unsafe { sys_time() };
+        LocalDateTime::at_ns(s, ns)
     }
-}
 
-impl Sub<Duration> for LocalDateTime {
-    type Output = LocalDateTime;
+    /// Creates a new date-time stamp set to the current time in UTC,
+    /// according to the given clock, returning `None` if it isn’t one of
+    /// the realtime clocks, or the kernel doesn’t support it.
+    #[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+    pub fn now_from(source: ClockSource) -> Option<LocalDateTime> {
+        if !source.is_realtime() {
+            return None;
+        }
 
-    fn sub(self, duration: Duration) -> LocalDateTime {
-        LocalDateTime::from_instant(self.to_instant() - duration)
+        let (s, ns) = sys_clock_time(source)?;
+        Some(LocalDateTime::at_ns(s, ns))
     }
-}
+
+    /// Returns the current date and time as shown on a wall clock in the
+    /// given time zone.
+    #[cfg(feature = "std")]
+    pub fn now_in(zone: &TimeZone) -> LocalDateTime {
+        zone.to_zoned(LocalDateTime::now())
+    }
+
+    /// Returns the instant this date-time is at, treating it as though it
+    /// were in UTC.
+    pub fn to_instant(&self) -> Instant {
+        Instant::at_ns(self.seconds() as i64, self.nanosecond())
+    }
+
+    /// Returns the Unix timestamp of this date-time as an `i128`, as the
+    /// midnight at the start of the first day would overflow an `i64`.
+    const fn seconds(&self) -> i128 {
+        self.days as i128 * SECONDS_IN_DAY as i128 + self.time().to_seconds() as i128
+    }
+
+    /// Returns the number of milliseconds that have elapsed since
+    /// **midnight, 1st January, 1970**, treating this date-time as though it
+    /// were in UTC. Any nanoseconds beyond the millisecond are discarded.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of milliseconds does not fit in an `i64`. Use
+    /// `checked_to_unix_millis` to handle that case instead.
+    pub fn to_unix_millis(&self) -> i64 {
+        self.to_instant().to_unix_millis()
+    }
+
+    /// Returns the number of milliseconds that have elapsed since
+    /// **midnight, 1st January, 1970**, treating this date-time as though it
+    /// were in UTC, or `None` if it does not fit in an `i64`.
+    pub fn checked_to_unix_millis(&self) -> Option<i64> {
+        self.to_instant().checked_to_unix_millis()
+    }
+
+    /// Returns the number of nanoseconds that have elapsed since
+    /// **midnight, 1st January, 1970**, treating this date-time as though it
+    /// were in UTC.
+    pub fn to_unix_nanos(&self) -> i128 {
+        let seconds = self.to_instant().seconds();
+        seconds as i128 * 1_000_000_000 + self.nanosecond() as i128
+    }
+
+    /// Returns this date-time in a fixed-width binary encoding, treating it
+    /// as though it were in UTC: the number of seconds since the Unix epoch
+    /// as a big-endian `i64`, followed by the number of nanoseconds so far
+    /// that second as a big-endian `u32`. `Instant` uses the same encoding.
+    pub fn to_bytes(&self) -> [u8; 12] {
+        join_bytes(self.to_instant().seconds(), self.nanosecond() as u32)
+    }
+
+    /// Decodes a date-time from the binary encoding produced by
+    /// `to_bytes`, returning `None` if the number of nanoseconds is too
+    /// large.
+    ///
+    /// ```rust
+    /// use datetime::LocalDateTime;
+    ///
+    /// let datetime = LocalDateTime::at_ns(1_234_567_890, 123_456_789);
+    /// assert_eq!(LocalDateTime::from_bytes(datetime.to_bytes()), Some(datetime));
+    /// ```
+    pub fn from_bytes(bytes: [u8; 12]) -> Option<LocalDateTime> {
+        let (seconds, nanoseconds) = split_bytes(bytes)?;
+        Some(LocalDateTime::at_ns(seconds, nanoseconds as i32))
+    }
+
+    /// Returns the fractional Julian Date of this date-time, treating it as
+    /// though it were in UTC.
+    ///
+    /// ```rust
+    /// use datetime::LocalDateTime;
+    ///
+    /// assert_eq!(LocalDateTime::at(0).julian_date(), 2440587.5);
+    /// ```
+    pub fn julian_date(&self) -> f64 {
+        let seconds = self.to_instant().seconds() as f64 + self.nanosecond() as f64 / 1_000_000_000.0;
+        JULIAN_DAY_OF_UNIX_EPOCH as f64 - 0.5 + seconds / SECONDS_IN_DAY as f64
+    }
+
+    pub fn add_seconds(&self, seconds: i64) -> LocalDateTime {
+        *self + Duration::of(seconds)
+    }
+
+    /// Adds a duration to this date-time, returning `None` if the result
+    /// would be outside the range of date-times that can be represented.
+    pub fn checked_add(self, duration: Duration) -> Option<LocalDateTime> {
+        Some(LocalDateTime::from_instant(self.to_instant().checked_add(duration)?))
+    }
+
+    /// Subtracts a duration from this date-time, returning `None` if the
+    /// result would be outside the range of date-times that can be
+    /// represented.
+    pub fn checked_sub(self, duration: Duration) -> Option<LocalDateTime> {
+        Some(LocalDateTime::from_instant(self.to_instant().checked_sub(duration)?))
+    }
+}
+
+impl Default for LocalDateTime {
+
+    /// Returns the Unix epoch, `1970-01-01T00:00:00`.
+    fn default() -> LocalDateTime {
+        LocalDateTime::new(LocalDate::default(), LocalTime::default())
+    }
+}
+
+impl fmt::Debug for LocalDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:?}T{:?}", self.date(), self.time())
+    }
+}
+
+impl fmt::Display for LocalDateTime {
+
+    /// Formats this date-time in the ISO 8601 format, such as
+    /// `2015-06-26T14:30:00`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}T{}", self.date(), self.time())
+    }
+}
+
+impl DatePiece for LocalDateTime {
+    fn year(&self) -> i64 { year_and_yearday(self.days - EPOCH_DIFFERENCE).0 }
+    fn month(&self) -> Month { self.month_and_day().0 }
+    fn day(&self) -> i8 { self.month_and_day().1 + 1 }
+    fn yearday(&self) -> i16 { year_and_yearday(self.days - EPOCH_DIFFERENCE).1 + 1 }
+    fn weekday(&self) -> Weekday { days_to_weekday(self.days - EPOCH_DIFFERENCE) }
+}
+
+impl TimePiece for LocalDateTime {
+    fn hour(&self) -> i8 { self.time().hour }
+    fn minute(&self) -> i8 { self.time().minute }
+    fn second(&self) -> i8 { self.time().second }
+    fn nanosecond(&self) -> i32 { (self.time % 1_000_000_000) as i32 }
+}
+
+impl Add<Duration> for LocalDateTime {
+    type Output = LocalDateTime;
+
+    fn add(self, duration: Duration) -> LocalDateTime {
+        self.checked_add(duration).expect("date-time out of range")
+    }
+}
+
+impl Sub<Duration> for LocalDateTime {
+    type Output = LocalDateTime;
+
+    fn sub(self, duration: Duration) -> LocalDateTime {
+        self.checked_sub(duration).expect("date-time out of range")
+    }
+}
+
+impl Sub<LocalDateTime> for LocalDateTime {
+    type Output = Duration;
+
+    /// Returns the length of time between two date-times, treating both of
+    /// them as though they were in UTC.
+    fn sub(self, earlier: LocalDateTime) -> Duration {
+        self.to_instant() - earlier.to_instant()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SystemTime> for LocalDateTime {
+
+    /// Converts a standard library `SystemTime` into a date-time in UTC,
+    /// keeping its full nanosecond precision.
+    fn from(time: SystemTime) -> LocalDateTime {
+        let (seconds, nanoseconds) = split_system_time(time);
+        LocalDateTime::at_ns(seconds, nanoseconds)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<LocalDateTime> for SystemTime {
+
+    /// Converts a date-time, treated as though it were in UTC, into a
+    /// standard library `SystemTime`.
+    ///
+    /// This panics if the date-time is too far in the past or future to be
+    /// represented by the platform’s `SystemTime` type.
+    fn from(datetime: LocalDateTime) -> SystemTime {
+        join_system_time(datetime.to_instant().seconds(), datetime.nanosecond())
+    }
+}
+
+
+/// A builder for a local date-time, returned by `LocalDateTime::builder`.
+///
+/// The fields aren’t checked until `build` is called, so they can be set
+/// in any order.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LocalDateTimeBuilder {
+    year:       i64,
+    month:      Month,
+    day:        i8,
+    hour:       i8,
+    minute:     i8,
+    second:     i8,
+    nanosecond: i32,
+}
+
+impl LocalDateTimeBuilder {
+
+    /// Sets the year.
+    pub fn year(mut self, year: i64) -> LocalDateTimeBuilder {
+        self.year = year;
+        self
+    }
+
+    /// Sets the month.
+    pub fn month(mut self, month: Month) -> LocalDateTimeBuilder {
+        self.month = month;
+        self
+    }
+
+    /// Sets the day of the month, starting from 1.
+    pub fn day(mut self, day: i8) -> LocalDateTimeBuilder {
+        self.day = day;
+        self
+    }
+
+    /// Sets the hour, from 0 to 23.
+    pub fn hour(mut self, hour: i8) -> LocalDateTimeBuilder {
+        self.hour = hour;
+        self
+    }
+
+    /// Sets the minute, from 0 to 59.
+    pub fn minute(mut self, minute: i8) -> LocalDateTimeBuilder {
+        self.minute = minute;
+        self
+    }
+
+    /// Sets the second, from 0 to 59, or 60 for a leap second.
+    pub fn second(mut self, second: i8) -> LocalDateTimeBuilder {
+        self.second = second;
+        self
+    }
+
+    /// Sets the fraction of a second to the given number of milliseconds.
+    /// This replaces any nanoseconds that have been set.
+    pub fn millisecond(mut self, millisecond: i16) -> LocalDateTimeBuilder {
+        self.nanosecond = millisecond as i32 * 1_000_000;
+        self
+    }
+
+    /// Sets the fraction of a second to the given number of nanoseconds.
+    /// This replaces any milliseconds that have been set.
+    pub fn nanosecond(mut self, nanosecond: i32) -> LocalDateTimeBuilder {
+        self.nanosecond = nanosecond;
+        self
+    }
+
+    /// Checks the fields, and returns the date-time they make up, or an
+    /// error naming the first field that’s out of range.
+    pub fn build(&self) -> Result<LocalDateTime, Error> {
+        let date = try!(LocalDate::ymd(self.year, self.month, self.day));
+        let time = try!(LocalTime::hms_ns(self.hour, self.minute, self.second, self.nanosecond));
+        let datetime = LocalDateTime::from_parts(date, time);
+
+        if datetime.is_in_range() {
+            Ok(datetime)
+        }
+        else {
+            Err(Error::OutOfRange)
+        }
+    }
+}
+
+
+/// The fields of a date-time, broken down into plain numbers.
+///
+/// This has the same layout as a C struct, so it can be passed across an
+/// FFI boundary, and its fields are all public, so it can be filled in
+/// from any serialization format without going through a string. None of
+/// the fields are checked until it’s converted into a date or date-time
+/// with `TryFrom`.
+///
+/// ### Examples
+///
+/// ```rust
+/// use std::convert::TryFrom;
+/// use datetime::{Fields, LocalDateTime};
+///
+/// let fields = Fields { year: 2015, month: 6, day: 26, hour: 14, minute: 30, second: 0, nanosecond: 0 };
+/// let datetime = LocalDateTime::try_from(fields).unwrap();
+/// assert_eq!(datetime.to_string(), "2015-06-26T14:30:00");
+/// assert_eq!(Fields::from(datetime), fields);
+/// ```
+#[repr(C)]
+#[derive(PartialEq, Eq, Hash, Debug, Default, Clone, Copy)]
+pub struct Fields {
+
+    /// The year, such as 2015.
+    pub year: i64,
+
+    /// The month, from 1 for January to 12 for December.
+    pub month: i8,
+
+    /// The day of the month, from 1 to 31.
+    pub day: i8,
+
+    /// The hour, from 0 to 23.
+    pub hour: i8,
+
+    /// The minute, from 0 to 59.
+    pub minute: i8,
+
+    /// The second, from 0 to 60, with 60 only used for leap seconds.
+    pub second: i8,
+
+    /// The nanosecond of the second, from 0 to 999,999,999.
+    pub nanosecond: i32,
+}
+
+impl From<LocalDateTime> for Fields {
+    fn from(datetime: LocalDateTime) -> Fields {
+        Fields {
+            year:       datetime.year(),
+            month:      datetime.month().to_one(),
+            day:        datetime.day(),
+            hour:       datetime.hour(),
+            minute:     datetime.minute(),
+            second:     datetime.second(),
+            nanosecond: datetime.nanosecond(),
+        }
+    }
+}
+
+impl From<LocalDate> for Fields {
+
+    /// Breaks down a date into fields, with the time fields set to
+    /// midnight.
+    fn from(date: LocalDate) -> Fields {
+        Fields::from(LocalDateTime::from_parts(date, LocalTime::MIDNIGHT))
+    }
+}
+
+impl TryFrom<Fields> for LocalDateTime {
+    type Error = Error;
+
+    /// Converts the fields into a date-time. The second can be 60 at the
+    /// end of an hour, as with `LocalTime::hms_ns_leap`, so that a leap
+    /// second survives being taken apart and put back together.
+    fn try_from(fields: Fields) -> Result<LocalDateTime, Error> {
+        let date = try!(LocalDate::try_from(fields));
+        let time = try!(LocalTime::hms_ns_leap(fields.hour, fields.minute, fields.second, fields.nanosecond));
+        LocalDateTime::try_new(date, time)
+    }
+}
+
+impl TryFrom<Fields> for LocalDate {
+    type Error = Error;
+
+    /// Converts the date fields into a date. The time fields are ignored.
+    fn try_from(fields: Fields) -> Result<LocalDate, Error> {
+        let month = try!(Month::from_one(fields.month));
+        LocalDate::ymd(fields.year, month, fields.day)
+    }
+}
+
+
+/// Returns whether the given year is a leap year in the proleptic
+/// Gregorian calendar: one that’s divisible by four, except for centuries,
+/// except for centuries that are divisible by four hundred.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::is_leap_year;
+///
+/// assert!(is_leap_year(2016));
+/// assert!(is_leap_year(2000));
+/// assert!(!is_leap_year(1900));
+/// ```
+pub const fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in the given month of the given year.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{days_in_month, Month};
+///
+/// assert_eq!(days_in_month(2015, Month::February), 28);
+/// assert_eq!(days_in_month(2016, Month::February), 29);
+/// assert_eq!(days_in_month(2016, Month::April), 30);
+/// ```
+pub const fn days_in_month(year: i64, month: Month) -> i8 {
+    month.days_in_month(is_leap_year(year))
+}
+
+/// Returns the number of days in the given year: 366 in a leap year, and
+/// 365 otherwise.
+pub const fn days_in_year(year: i64) -> i16 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+/// Returns the number of weeks in the given ISO year, which is 53 when the
+/// year starts on a Thursday, or when it’s a leap year that starts on a
+/// Wednesday, and 52 otherwise.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{weeks_in_iso_year, LocalDate, Weekday};
+///
+/// assert_eq!(weeks_in_iso_year(2015), 53);
+/// assert_eq!(weeks_in_iso_year(2016), 52);
+/// assert!(LocalDate::ywd(2015, 53, Weekday::Monday).is_ok());
+/// ```
+pub fn weeks_in_iso_year(year: i64) -> i8 {
+
+    // The weekday of the 31st of December in the given year, with Sunday
+    // as 0. This is done in i128 so that no year can make it overflow.
+    fn december_31st(year: i128) -> i128 {
+        (year + year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400)).rem_euclid(7)
+    }
+
+    let year = year as i128;
+    if december_31st(year) == 4 || december_31st(year - 1) == 3 { 53 } else { 52 }
+}
 
 
 /// A **YMD** is an implementation detail of LocalDate. It provides
@@ -600,7 +1497,7 @@ impl Sub<Duration> for LocalDateTime {
 /// create an instance of the 74th of March, for example, but you’re
 /// free to create such an instance of YMD. For this reason, it is not
 /// exposed to implementors of this library.
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Debug, Copy)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone, Debug, Copy)]
 pub struct YMD {
     pub year:    i64,
     pub month:   Month,
@@ -616,12 +1513,17 @@ impl YMD {
     /// This method returns a Result instead of exposing is_valid to
     /// the user, because the leap year calculations are used in both
     /// functions, so it makes more sense to only do them once.
-    pub fn to_days_since_epoch(&self) -> Result<i64, Error> {
+    pub const fn to_days_since_epoch(&self) -> Result<i64, Error> {
+        if self.year < MIN_YEAR || self.year > MAX_YEAR {
+            return Err(Error::YearOutOfRange { given: self.year });
+        }
+
         let years = self.year - 2000;
         let (leap_days_elapsed, is_leap_year) = self.leap_year_calculations();
 
         if !self.is_valid(is_leap_year) {
-            return Err(Error::OutOfRange);
+            let max = self.month.days_in_month(is_leap_year);
+            return Err(Error::DayOutOfRange { given: self.day, max: max });
         }
 
         // Work out the number of days from the start of 1970 to now,
@@ -638,16 +1540,18 @@ impl YMD {
             + leap_days_elapsed
 
             // Plus the number of days in all the months leading up to
-            // the current month...
-            + self.month.days_before_start() as i64
-
-            // Plus an extra leap day for *this* year...
-            + if is_leap_year && self.month >= March { 1 } else { 0 }
+            // the current month, including an extra leap day for *this*
+            // year...
+            + CUMULATIVE_DAYS[is_leap_year as usize][self.month as usize - 1] as i64
 
             // Plus the number of days in the month so far! (Days are
             // 1-indexed, so we make them 0-indexed here)
             + (self.day - 1) as i64;
 
+        if days < MIN_DAY || days > MAX_DAY {
+            return Err(Error::OutOfRange);
+        }
+
         Ok(days)
     }
 
@@ -657,7 +1561,7 @@ impl YMD {
     /// Whether the current year is a leap year should already have been
     /// calculated at this point, so the value is passed in rather than
     /// calculating it afresh.
-    pub fn is_valid(&self, is_leap_year: bool) -> bool {
+    pub const fn is_valid(&self, is_leap_year: bool) -> bool {
         self.day >= 1 && self.day <= self.month.days_in_month(is_leap_year)
     }
 
@@ -666,7 +1570,7 @@ impl YMD {
     ///
     /// 1. The number of leap years that have elapsed prior to this date;
     /// 2. Whether the current year is a leap year or not.
-    pub fn leap_year_calculations(&self) -> (i64, bool) {
+    pub const fn leap_year_calculations(&self) -> (i64, bool) {
         let year = self.year - 2000;
 
         // This calculation is the reverse of LocalDate::from_days_since_epoch.
@@ -687,14 +1591,81 @@ impl YMD {
     }
 }
 
+/// Computes the year, the zero-based day of the year, and whether it’s a
+/// leap year, given the number of days that have passed since the EPOCH.
+const fn year_and_yearday(days: i64) -> (i64, i16, bool) {
+
+    // This uses Howard Hinnant’s `civil_from_days` algorithm, which avoids
+    // the loops and branches of working through the cycles one at a time.
+    // As the EPOCH is the start of a 400-year cycle, the number of whole
+    // cycles and the day within the current one come out of a single
+    // division.
+    let (num_400y_cycles, day_of_cycle) = split_cycles(days, DAYS_IN_400Y);
+
+    // Everything within the cycle is positive, and the unsigned divisions
+    // that follow are cheaper than signed ones.
+    let day_of_cycle = day_of_cycle as u64;
+
+    // The year within the cycle, counting from March. Every 4-year,
+    // 100-year, and 400-year cycle ends with a leap day, so these divisions
+    // take each of those days back out before dividing by the length of a
+    // common year.
+    let year_of_cycle = (day_of_cycle
+                         - day_of_cycle / (DAYS_IN_4Y as u64 - 1)
+                         + day_of_cycle / DAYS_IN_100Y as u64
+                         - day_of_cycle / (DAYS_IN_400Y as u64 - 1)) / 365;
+
+    // The day within that March-based year, from 0 to 365.
+    let day_of_march_year = day_of_cycle - (365 * year_of_cycle + year_of_cycle / 4 - year_of_cycle / 100);
+
+    // January and February are the last 31 + 28 (or 29) days of the
+    // March-based year, and belong to the calendar year after the one it
+    // started in. As the cycle starts on a multiple of 400, the year of the
+    // cycle is a leap year under the same rules as the calendar year.
+    let in_january_or_february = day_of_march_year >= 306;
+    let year_of_cycle = if in_january_or_february { year_of_cycle + 1 } else { year_of_cycle };
+    let leap_year = year_of_cycle % 4 == 0 && (year_of_cycle % 100 != 0 || year_of_cycle % 400 == 0);
+
+    let yearday =
+        if in_january_or_february { day_of_march_year - 306 }
+                             else { day_of_march_year + 31 + 28 + leap_year as u64 };
+
+    (2000 + 400 * num_400y_cycles + year_of_cycle as i64, yearday as i16, leap_year)
+}
+
+/// Computes the month and the zero-based day of the month, given the
+/// zero-based day of the year and whether it’s a leap year.
+const fn month_and_day(yearday: i16, leap_year: bool) -> (Month, i8) {
+    let cumulative_days = &CUMULATIVE_DAYS[leap_year as usize];
+
+    // Every month is shorter than 32 days, so dividing by 32 either gets
+    // the right month, or the one before it.
+    let mut month = (yearday / 32) as usize;
+    if yearday >= cumulative_days[month + 1] {
+        month += 1;
+    }
+
+    // The check above means this can’t fail, as the month number is
+    // guaranteed to be in the range (0..12).
+    let month_variant = match Month::from_zero(month as i8) {
+        Ok(month) => month,
+        Err(_)    => panic!("month out of range"),
+    };
+
+    (month_variant, (yearday - cumulative_days[month]) as i8)
+}
+
 /// Computes the weekday, given the number of days that have passed
 /// since the EPOCH.
-fn days_to_weekday(days: i64) -> Weekday {
+const fn days_to_weekday(days: i64) -> Weekday {
     // March 1st, 2000 was a Wednesday, so add 3 to the number of days.
     let weekday = (days + 3) % 7;
 
-    // We can unwrap since we’ve already done the bounds checking.
-    Weekday::from_zero(if weekday < 0 { weekday + 7 } else { weekday } as i8).unwrap()
+    // This can’t fail since we’ve already done the bounds checking.
+    match Weekday::from_zero(if weekday < 0 { weekday + 7 } else { weekday } as i8) {
+        Ok(weekday) => weekday,
+        Err(_)      => panic!("weekday out of range"),
+    }
 }
 
 /// Split a number of years into a number of year-cycles, and the number
@@ -705,7 +1676,7 @@ fn days_to_weekday(days: i64) -> Weekday {
 /// remainder, with the difference that a negative value gets ‘wrapped
 /// around’ to be a positive value, owing to the way the modulo operator
 /// works for negative values.
-fn split_cycles(number_of_periods: i64, cycle_length: i64) -> (i64, i64) {
+const fn split_cycles(number_of_periods: i64, cycle_length: i64) -> (i64, i64) {
     let mut cycles    = number_of_periods / cycle_length;
     let mut remainder = number_of_periods % cycle_length;
 
@@ -718,20 +1689,110 @@ fn split_cycles(number_of_periods: i64, cycle_length: i64) -> (i64, i64) {
 }
 
 
+/// Checks that the given hour, minute, and second make up a time of day,
+/// returning an error naming the first field that doesn’t.
+const fn check_hms(hour: i8, minute: i8, second: i8) -> Result<(), Error> {
+    if hour < 0 || hour >= 24 {
+        Err(Error::HourOutOfRange { given: hour })
+    }
+    else if minute < 0 || minute >= 60 {
+        Err(Error::MinuteOutOfRange { given: minute })
+    }
+    else if second < 0 || second >= 60 {
+        Err(Error::SecondOutOfRange { given: second })
+    }
+    else {
+        Ok(())
+    }
+}
+
+
+/// An error that occurs when the fields given to construct a date or time
+/// don’t make sense. Each variant names the field that was wrong, along
+/// with the value it was given.
+#[non_exhaustive]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum Error {
+
+    /// The year is outside the range of dates this library can represent.
+    YearOutOfRange { given: i64 },
+
+    /// The month number doesn’t correspond to any month.
+    MonthOutOfRange { given: i8 },
+
+    /// The day of the month is zero or negative, or past the end of a
+    /// month that has `max` days.
+    DayOutOfRange { given: i8, max: i8 },
+
+    /// The day of the year is outside the range 1 to 366.
+    YeardayOutOfRange { given: i64 },
+
+    /// The ISO week number is zero or negative, or past the end of an ISO
+    /// year that has `max` weeks.
+    WeekOutOfRange { given: i8, max: i8 },
+
+    /// The weekday number doesn’t correspond to any day of the week.
+    WeekdayOutOfRange { given: i8 },
+
+    /// The hour is outside the range 0 to 23. (24 is only allowed when
+    /// the rest of the time is zero.)
+    HourOutOfRange { given: i8 },
+
+    /// The minute is outside the range 0 to 59.
+    MinuteOutOfRange { given: i8 },
+
+    /// The second is outside the range 0 to 59, or is a leap second where
+    /// one isn’t allowed.
+    SecondOutOfRange { given: i8 },
+
+    /// The millisecond is outside the range 0 to 999.
+    MillisecondOutOfRange { given: i16 },
+
+    /// The nanosecond is outside the range 0 to 999,999,999.
+    NanosecondOutOfRange { given: i32 },
+
+    /// The value as a whole, rather than any one of its fields, is too
+    /// large or too small to be represented.
     OutOfRange,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}", self.description())
+        use self::Error::*;
+
+        match *self {
+            YearOutOfRange { given }        => write!(f, "{} ({})", self.description(), given),
+            MonthOutOfRange { given }       => write!(f, "{} ({})", self.description(), given),
+            DayOutOfRange { given, max }    => write!(f, "{} ({}, but the month has {} days)", self.description(), given, max),
+            YeardayOutOfRange { given }     => write!(f, "{} ({})", self.description(), given),
+            WeekOutOfRange { given, max }   => write!(f, "{} ({}, but the year has {} weeks)", self.description(), given, max),
+            WeekdayOutOfRange { given }     => write!(f, "{} ({})", self.description(), given),
+            HourOutOfRange { given }        => write!(f, "{} ({})", self.description(), given),
+            MinuteOutOfRange { given }      => write!(f, "{} ({})", self.description(), given),
+            SecondOutOfRange { given }      => write!(f, "{} ({})", self.description(), given),
+            MillisecondOutOfRange { given } => write!(f, "{} ({})", self.description(), given),
+            NanosecondOutOfRange { given }  => write!(f, "{} ({})", self.description(), given),
+            OutOfRange                      => write!(f, "{}", self.description()),
+        }
     }
 }
 
 impl ErrorTrait for Error {
     fn description(&self) -> &str {
-        "datetime field out of range"
+        match *self {
+            Error::YearOutOfRange { .. }        => "year out of range",
+            Error::MonthOutOfRange { .. }       => "month out of range",
+            Error::DayOutOfRange { .. }         => "day out of range",
+            Error::YeardayOutOfRange { .. }     => "day of year out of range",
+            Error::WeekOutOfRange { .. }        => "week out of range",
+            Error::WeekdayOutOfRange { .. }     => "weekday out of range",
+            Error::HourOutOfRange { .. }        => "hour out of range",
+            Error::MinuteOutOfRange { .. }      => "minute out of range",
+            Error::SecondOutOfRange { .. }      => "second out of range",
+            Error::MillisecondOutOfRange { .. } => "millisecond out of range",
+            Error::NanosecondOutOfRange { .. }  => "nanosecond out of range",
+            Error::OutOfRange                   => "datetime out of range",
+        }
     }
 }
 
@@ -742,7 +1803,7 @@ impl ErrorTrait for Error {
 /// off-by-one errors: is month 2 February (1-indexed) or March (0-indexed)?
 /// In this case, it’s 1-indexed, to have January become 1 when you use
 /// `as i32` in code.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 pub enum Month {
     January =  1, February =  2, March     =  3,
     April   =  4, May      =  5, June      =  6,
@@ -752,9 +1813,65 @@ pub enum Month {
 
 impl Month {
 
+    /// Every month of the year, in order, starting with January.
+    pub const ALL: [Month; 12] = [
+        January,  February,  March,
+        April,    May,       June,
+        July,     August,    September,
+        October,  November,  December,
+    ];
+
+    /// Returns an iterator over every month of the year, in order, starting
+    /// with January.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    ///
+    /// let months: Vec<Month> = Month::all().rev().take(2).collect();
+    /// assert_eq!(months, vec![ Month::December, Month::November ]);
+    /// ```
+    pub fn all() -> Copied<slice::Iter<'static, Month>> {
+        Month::ALL.iter().copied()
+    }
+
+    /// Returns the month after this one, wrapping around from December to
+    /// January.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// assert_eq!(Month::June.next(), Month::July);
+    /// assert_eq!(Month::December.next(), Month::January);
+    /// ```
+    pub const fn next(&self) -> Month {
+        Month::ALL[*self as usize % 12]
+    }
+
+    /// Returns the month before this one, wrapping around from January to
+    /// December.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// assert_eq!(Month::June.previous(), Month::May);
+    /// assert_eq!(Month::January.previous(), Month::December);
+    /// ```
+    pub const fn previous(&self) -> Month {
+        Month::ALL[(*self as usize + 10) % 12]
+    }
+
+    /// Returns the number of days in this month in the given year.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// assert_eq!(Month::February.days_in(2015), 28);
+    /// assert_eq!(Month::February.days_in(2016), 29);
+    /// ```
+    pub const fn days_in(&self, year: i64) -> i8 {
+        self.days_in_month(is_leap_year(year))
+    }
+
     /// Returns the number of days in this month, depending on whether it’s
     /// a leap year or not.
-    pub fn days_in_month(&self, leap_year: bool) -> i8 {
+    pub const fn days_in_month(&self, leap_year: bool) -> i8 {
         match *self {
             January   => 31, February  => if leap_year { 29 } else { 28 },
             March     => 31, April     => 30,
@@ -765,17 +1882,6 @@ impl Month {
         }
     }
 
-    /// Returns the number of days that have elapsed in a year *before* this
-    /// month begins, with no leap year check.
-    fn days_before_start(&self) -> i16 {
-        match *self {
-            January =>   0, February =>  31, March     =>  59,
-            April   =>  90, May      => 120, June      => 151,
-            July    => 181, August   => 212, September => 243,
-            October => 273, November => 304, December  => 334,
-        }
-    }
-
     pub fn months_from_january(&self) -> usize {
         match *self {
             January =>   0, February =>   1, March     =>  2,
@@ -793,13 +1899,13 @@ impl Month {
     /// assert_eq!(Month::from_one(5), Ok(Month::May));
     /// assert!(Month::from_one(0).is_err());
     /// ```
-    pub fn from_one(month: i8) -> Result<Month, Error> {
+    pub const fn from_one(month: i8) -> Result<Month, Error> {
         Ok(match month {
              1 => January,   2 => February,   3 => March,
              4 => April,     5 => May,        6 => June,
              7 => July,      8 => August,     9 => September,
             10 => October,  11 => November,  12 => December,
-             _ => return Err(Error::OutOfRange),
+             _ => return Err(Error::MonthOutOfRange { given: month }),
         })
     }
 
@@ -811,44 +1917,186 @@ impl Month {
     /// assert_eq!(Month::from_zero(5), Ok(Month::June));
     /// assert!(Month::from_zero(12).is_err());
     /// ```
-    pub fn from_zero(month: i8) -> Result<Month, Error> {
+    pub const fn from_zero(month: i8) -> Result<Month, Error> {
         Ok(match month {
             0 => January,   1 => February,   2 => March,
             3 => April,     4 => May,        5 => June,
             6 => July,      7 => August,     8 => September,
             9 => October,  10 => November,  11 => December,
-            _ => return Err(Error::OutOfRange),
+            _ => return Err(Error::MonthOutOfRange { given: month }),
         })
     }
-}
 
+    /// Returns the month based on an unsigned number, such as one read
+    /// from a database or a wire format, with January as **Month 1**.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// assert_eq!(Month::from_one_indexed(12), Ok(Month::December));
+    /// assert!(Month::from_one_indexed(13).is_err());
+    /// ```
+    pub fn from_one_indexed(month: u8) -> Result<Month, Error> {
+        Month::try_from(month)
+    }
 
-/// A named day of the week.
-///
-/// Sunday is Day 0. This seems to be a North American thing? It’s pretty
-/// much an arbitrary choice, and as you can’t use the from_zero method,
-/// it won’t affect you at all. If you want to change it, the only thing
-/// that should be affected is LocalDate::days_to_weekday.
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
-pub enum Weekday {
-    Sunday, Monday, Tuesday, Wednesday, Thursday, Friday, Saturday,
-}
+    /// Returns the number of this month, with January as **Month 1**. This
+    /// is the reverse of `from_one`.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// assert_eq!(Month::May.to_one(), 5);
+    /// ```
+    pub const fn to_one(&self) -> i8 {
+        *self as i8
+    }
 
-// I’m not going to give weekdays an Ord instance because there’s no
-// real standard as to whether Sunday should come before Monday, or the
-// other way around. Luckily, they don’t need one, as the field is
+    /// Returns the number of this month, with January as **Month 0**. This
+    /// is the reverse of `from_zero`.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// assert_eq!(Month::June.to_zero(), 5);
+    /// ```
+    pub const fn to_zero(&self) -> i8 {
+        *self as i8 - 1
+    }
+
+    /// Returns the full name of this month in the given locale.
+    ///
+    /// ```rust
+    /// extern crate locale;
+    /// extern crate datetime;
+    /// use datetime::Month;
+    ///
+    /// # fn main() {
+    /// let english = locale::Time::english();
+    /// assert_eq!(Month::September.name(&english), "September");
+    /// assert_eq!(Month::September.short_name(&english), "Sep");
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn name(&self, locale: &locale::Time) -> String {
+        locale.long_month_name(self.months_from_january())
+    }
+
+    /// Returns the abbreviated name of this month in the given locale.
+    #[cfg(feature = "std")]
+    pub fn short_name(&self, locale: &locale::Time) -> String {
+        locale.short_month_name(self.months_from_january())
+    }
+}
+
+/// Months convert to and from numbers with January as **Month 1**, the way
+/// they’re usually written down and stored.
+impl TryFrom<i8> for Month {
+    type Error = Error;
+
+    fn try_from(month: i8) -> Result<Month, Error> {
+        Month::from_one(month)
+    }
+}
+
+impl TryFrom<u8> for Month {
+    type Error = Error;
+
+    fn try_from(month: u8) -> Result<Month, Error> {
+        let month = try!(i8::try_from(month).map_err(|_| Error::OutOfRange));
+        Month::from_one(month)
+    }
+}
+
+impl From<Month> for i8 {
+    fn from(month: Month) -> i8 {
+        month.to_one()
+    }
+}
+
+impl From<Month> for u8 {
+    fn from(month: Month) -> u8 {
+        month.to_one() as u8
+    }
+}
+
+
+/// A named day of the week.
+///
+/// Sunday is Day 0. This seems to be a North American thing? It’s pretty
+/// much an arbitrary choice, and as you can’t use the from_zero method,
+/// it won’t affect you at all. If you want to change it, the only thing
+/// that should be affected is LocalDate::days_to_weekday.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Weekday {
+    Sunday, Monday, Tuesday, Wednesday, Thursday, Friday, Saturday,
+}
+
+// I’m not going to give weekdays an Ord instance because there’s no
+// real standard as to whether Sunday should come before Monday, or the
+// other way around. Luckily, they don’t need one, as the field is
 // ignored when comparing LocalDates.
 
 impl Weekday {
-    fn days_from_monday_as_one(&self) -> i8 {
-        match *self {
-            Sunday => 7,   Monday => 1,
-            Tuesday => 2,  Wednesday => 3,
-            Thursday => 4, Friday => 5,
-            Saturday => 6,
+
+    /// Returns the weekday after this one, wrapping around from Saturday
+    /// to Sunday.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::Friday.succ(), Weekday::Saturday);
+    /// assert_eq!(Weekday::Saturday.succ(), Weekday::Sunday);
+    /// ```
+    pub const fn succ(&self) -> Weekday {
+        match Weekday::from_zero((*self as i8 + 1) % 7) {
+            Ok(weekday) => weekday,
+            Err(_)      => panic!("weekday out of range"),
+        }
+    }
+
+    /// Returns the weekday before this one, wrapping around from Sunday
+    /// to Saturday.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::Monday.pred(), Weekday::Sunday);
+    /// assert_eq!(Weekday::Sunday.pred(), Weekday::Saturday);
+    /// ```
+    pub const fn pred(&self) -> Weekday {
+        match Weekday::from_zero((*self as i8 + 6) % 7) {
+            Ok(weekday) => weekday,
+            Err(_)      => panic!("weekday out of range"),
         }
     }
 
+    /// Returns the number of this weekday counting from Monday as 1, up to
+    /// Sunday as 7. This is the same as `to_one`.
+    pub const fn number_from_monday(&self) -> i8 {
+        self.to_one()
+    }
+
+    /// Returns the number of this weekday counting from Sunday as 1, up to
+    /// Saturday as 7.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::Sunday.number_from_sunday(), 1);
+    /// assert_eq!(Weekday::Saturday.number_from_sunday(), 7);
+    /// ```
+    pub const fn number_from_sunday(&self) -> i8 {
+        *self as i8 + 1
+    }
+
+    /// Returns the number of days from this weekday until the next time
+    /// it’s the given one, from 0 (if they’re the same day) to 6.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::Friday.days_until(Weekday::Monday), 3);
+    /// assert_eq!(Weekday::Monday.days_until(Weekday::Friday), 4);
+    /// assert_eq!(Weekday::Monday.days_until(Weekday::Monday), 0);
+    /// ```
+    pub const fn days_until(&self, other: Weekday) -> i8 {
+        (other as i8 - *self as i8 + 7) % 7
+    }
+
     /// Return the weekday based on a number, with Sunday as Day 0, Monday as
     /// Day 1, and so on.
     ///
@@ -857,371 +2105,2036 @@ impl Weekday {
     /// assert_eq!(Weekday::from_zero(4), Ok(Weekday::Thursday));
     /// assert!(Weekday::from_zero(7).is_err());
     /// ```
-    pub fn from_zero(weekday: i8) -> Result<Weekday, Error> {
+    pub const fn from_zero(weekday: i8) -> Result<Weekday, Error> {
         Ok(match weekday {
             0 => Sunday,     1 => Monday,    2 => Tuesday,
             3 => Wednesday,  4 => Thursday,  5 => Friday,
-            6 => Saturday,   _ => return Err(Error::OutOfRange),
+            6 => Saturday,   _ => return Err(Error::WeekdayOutOfRange { given: weekday }),
         })
     }
 
-    pub fn from_one(weekday: i8) -> Result<Weekday, Error> {
+    /// Return the weekday based on a number, with Monday as Day 1, and
+    /// Sunday as Day 7, as in ISO 8601.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::from_one(7), Ok(Weekday::Sunday));
+    /// assert!(Weekday::from_one(0).is_err());
+    /// ```
+    pub const fn from_one(weekday: i8) -> Result<Weekday, Error> {
         Ok(match weekday {
             7 => Sunday,     1 => Monday,    2 => Tuesday,
             3 => Wednesday,  4 => Thursday,  5 => Friday,
-            6 => Saturday,   _ => return Err(Error::OutOfRange),
+            6 => Saturday,   _ => return Err(Error::WeekdayOutOfRange { given: weekday }),
         })
     }
+
+    /// Returns the number of this weekday, with Sunday as Day 0. This is
+    /// the reverse of `from_zero`.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::Sunday.to_zero(), 0);
+    /// assert_eq!(Weekday::Thursday.to_zero(), 4);
+    /// ```
+    pub const fn to_zero(&self) -> i8 {
+        *self as i8
+    }
+
+    /// Returns the number of this weekday, with Monday as Day 1 and Sunday
+    /// as Day 7. This is the reverse of `from_one`.
+    ///
+    /// ```rust
+    /// use datetime::Weekday;
+    /// assert_eq!(Weekday::Monday.to_one(), 1);
+    /// assert_eq!(Weekday::Sunday.to_one(), 7);
+    /// ```
+    pub const fn to_one(&self) -> i8 {
+        match *self {
+            Sunday => 7,
+            other  => other as i8,
+        }
+    }
+
+    /// Returns the full name of this weekday in the given locale.
+    ///
+    /// Version 0.1 of the `locale` crate looks up full day names in its
+    /// list of abbreviated ones, so this gives the same result as
+    /// `short_name` until that gets fixed.
+    #[cfg(feature = "std")]
+    pub fn name(&self, locale: &locale::Time) -> String {
+        locale.long_day_name(self.to_zero() as usize)
+    }
+
+    /// Returns the abbreviated name of this weekday in the given locale.
+    ///
+    /// ```rust
+    /// extern crate locale;
+    /// extern crate datetime;
+    /// use datetime::Weekday;
+    ///
+    /// # fn main() {
+    /// let english = locale::Time::english();
+    /// assert_eq!(Weekday::Thursday.short_name(&english), "Thu");
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn short_name(&self, locale: &locale::Time) -> String {
+        locale.short_day_name(self.to_zero() as usize)
+    }
 }
 
-#[cfg(test)]
-mod test {
-    pub use super::{LocalDateTime, LocalDate, LocalTime, Month, Weekday};
-    pub use cal::iter::Year;
-    pub use cal::DatePiece;
-    pub use std::str::FromStr;
-    use super::YMD;
+/// Weekdays convert to and from numbers with Monday as **Day 1** and Sunday
+/// as **Day 7**, the ISO 8601 numbering, which is also used by many
+/// databases.
+impl TryFrom<i8> for Weekday {
+    type Error = Error;
 
-    mod seconds_to_datetimes {
-        pub use super::*;
-        use super::super::YMD;
+    fn try_from(weekday: i8) -> Result<Weekday, Error> {
+        Weekday::from_one(weekday)
+    }
+}
 
-        #[test]
-        fn before_time() {
-            let date = LocalDateTime::at(-1_000_000_000_i64);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 1938, month: Month::April, day: 24, },
-                    weekday: Weekday::Sunday, yearday: 114,
-                },
-                time: LocalTime {
-                    hour: 22, minute: 13, second: 20, millisecond: 0,
-                },
-            };
+impl TryFrom<u8> for Weekday {
+    type Error = Error;
 
-            assert_eq!(date, res)
-        }
+    fn try_from(weekday: u8) -> Result<Weekday, Error> {
+        let weekday = try!(i8::try_from(weekday).map_err(|_| Error::OutOfRange));
+        Weekday::from_one(weekday)
+    }
+}
 
-        #[test]
-        fn start_of_magic() {
-            let date = LocalDateTime::at(0_i64);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 1970, month: Month::January, day: 1, },
-                    weekday: Weekday::Thursday, yearday: 1,
-                },
-                time: LocalTime::midnight(),
-            };
+impl From<Weekday> for i8 {
+    fn from(weekday: Weekday) -> i8 {
+        weekday.to_one()
+    }
+}
 
-            assert_eq!(date, res)
-        }
+impl From<Weekday> for u8 {
+    fn from(weekday: Weekday) -> u8 {
+        weekday.to_one() as u8
+    }
+}
 
-        #[test]
-        fn billennium() {
-            let date = LocalDateTime::at(1_000_000_000_i64);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 2001, month: Month::September, day: 9, },
-                    weekday: Weekday::Sunday, yearday: 252,
-                },
-                time: LocalTime {
-                    hour: 1, minute: 46, second: 40, millisecond: 0,
-                },
-            };
 
-            assert_eq!(date, res)
-        }
+/// A set of weekdays, stored as one bit for each day.
+///
+/// Sets can be combined with the `|`, `&`, and `-` operators, and `!`
+/// gives every day that isn’t in the set. Iterating over a set gives its
+/// days in order from Sunday.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{Weekday, WeekdaySet};
+///
+/// let gym = WeekdaySet::of(&[ Weekday::Monday, Weekday::Thursday ]);
+/// let swimming = WeekdaySet::of(&[ Weekday::Thursday, Weekday::Saturday ]);
+///
+/// assert_eq!(gym | swimming, WeekdaySet::of(&[ Weekday::Monday, Weekday::Thursday, Weekday::Saturday ]));
+/// assert_eq!(gym & swimming, WeekdaySet::single(Weekday::Thursday));
+/// assert_eq!((!(gym | swimming)).len(), 4);
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Default, Copy, Clone)]
+pub struct WeekdaySet {
+
+    /// One bit for each weekday, with Sunday in the lowest bit.
+    bits: u8,
+}
 
-        #[test]
-        fn numbers() {
-            let date = LocalDateTime::at(1_234_567_890_i64);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 2009, month: Month::February, day: 13, },
-                    weekday: Weekday::Friday, yearday: 44,
-                },
-                time: LocalTime {
-                    hour: 23, minute: 31, second: 30, millisecond: 0,
-                },
-            };
+impl WeekdaySet {
 
-            assert_eq!(date, res)
-        }
+    /// The set with no days in it.
+    pub const EMPTY: WeekdaySet = WeekdaySet { bits: 0 };
 
-        #[test]
-        fn year_2038_problem() {
-            let date = LocalDateTime::at(0x7FFF_FFFF_i64);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 2038, month: Month::January, day: 19, },
-                    weekday: Weekday::Tuesday, yearday: 19,
-                },
-                time: LocalTime {
-                    hour: 3, minute: 14, second: 7, millisecond: 0,
-                },
-            };
+    /// The set with all seven days in it.
+    pub const ALL: WeekdaySet = WeekdaySet { bits: 0b111_1111 };
 
-            assert_eq!(date, res)
+    /// The five days from Monday to Friday.
+    pub const MONDAY_TO_FRIDAY: WeekdaySet = WeekdaySet::of(&[ Monday, Tuesday, Wednesday, Thursday, Friday ]);
+
+    /// Returns the set of the given days, ignoring any duplicates.
+    pub const fn of(weekdays: &[Weekday]) -> WeekdaySet {
+        let mut set = WeekdaySet::EMPTY;
+        let mut i = 0;
+        while i < weekdays.len() {
+            set = set.with(weekdays[i]);
+            i += 1;
         }
+        set
+    }
 
-        #[test]
-        fn the_end_of_time() {
-            let date = LocalDateTime::at(0x7FFF_FFFF_FFFF_FFFF_i64);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 292_277_026_596, month: Month::December, day: 4, },
-                    weekday: Weekday::Sunday, yearday: 339,
-                },
-                time: LocalTime {
-                    hour: 15, minute: 30, second: 7, millisecond: 0,
-                },
-            };
+    /// Returns the set containing only the given day.
+    pub const fn single(weekday: Weekday) -> WeekdaySet {
+        WeekdaySet { bits: 1 << weekday as u8 }
+    }
+
+    /// Returns whether the given day is in this set.
+    pub const fn contains(&self, weekday: Weekday) -> bool {
+        self.bits & WeekdaySet::single(weekday).bits != 0
+    }
+
+    /// Returns this set with the given day added.
+    pub const fn with(&self, weekday: Weekday) -> WeekdaySet {
+        self.union(WeekdaySet::single(weekday))
+    }
+
+    /// Returns this set with the given day removed.
+    pub const fn without(&self, weekday: Weekday) -> WeekdaySet {
+        self.difference(WeekdaySet::single(weekday))
+    }
+
+    /// Adds the given day to this set, returning whether it was missing.
+    pub fn insert(&mut self, weekday: Weekday) -> bool {
+        let missing = !self.contains(weekday);
+        *self = self.with(weekday);
+        missing
+    }
+
+    /// Removes the given day from this set, returning whether it was
+    /// there.
+    pub fn remove(&mut self, weekday: Weekday) -> bool {
+        let present = self.contains(weekday);
+        *self = self.without(weekday);
+        present
+    }
+
+    /// Returns the days that are in either set.
+    pub const fn union(&self, other: WeekdaySet) -> WeekdaySet {
+        WeekdaySet { bits: self.bits | other.bits }
+    }
+
+    /// Returns the days that are in both sets.
+    pub const fn intersection(&self, other: WeekdaySet) -> WeekdaySet {
+        WeekdaySet { bits: self.bits & other.bits }
+    }
+
+    /// Returns the days that are in this set but not the other one.
+    pub const fn difference(&self, other: WeekdaySet) -> WeekdaySet {
+        WeekdaySet { bits: self.bits & !other.bits }
+    }
+
+    /// Returns the days that aren’t in this set.
+    pub const fn complement(&self) -> WeekdaySet {
+        WeekdaySet::ALL.difference(*self)
+    }
+
+    /// Returns whether every day in this set is also in the other one.
+    pub const fn is_subset(&self, other: WeekdaySet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Returns the number of days in this set.
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns whether this set has no days in it.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns an iterator over the days in this set, starting from
+    /// Sunday.
+    pub fn iter(&self) -> impl Iterator<Item=Weekday> {
+        let set = *self;
+        (0 .. 7).map(|n| Weekday::from_zero(n).unwrap())
+                .filter(move |weekday| set.contains(*weekday))
+    }
+
+    /// Returns the first day in this set that comes on or after the given
+    /// one, wrapping around the end of the week, or `None` if the set is
+    /// empty.
+    ///
+    /// ```rust
+    /// use datetime::{Weekday, WeekdaySet};
+    ///
+    /// let set = WeekdaySet::of(&[ Weekday::Monday, Weekday::Wednesday ]);
+    /// assert_eq!(set.next_from(Weekday::Tuesday), Some(Weekday::Wednesday));
+    /// assert_eq!(set.next_from(Weekday::Thursday), Some(Weekday::Monday));
+    /// ```
+    pub fn next_from(&self, weekday: Weekday) -> Option<Weekday> {
+        self.iter().min_by_key(|day| weekday.days_until(*day))
+    }
+}
+
+impl fmt::Debug for WeekdaySet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<Weekday> for WeekdaySet {
+    fn from(weekday: Weekday) -> WeekdaySet {
+        WeekdaySet::single(weekday)
+    }
+}
+
+impl FromIterator<Weekday> for WeekdaySet {
+    fn from_iter<I: IntoIterator<Item=Weekday>>(weekdays: I) -> WeekdaySet {
+        weekdays.into_iter().fold(WeekdaySet::EMPTY, |set, weekday| set.with(weekday))
+    }
+}
+
+impl BitOr for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn bitor(self, other: WeekdaySet) -> WeekdaySet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn bitand(self, other: WeekdaySet) -> WeekdaySet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn sub(self, other: WeekdaySet) -> WeekdaySet {
+        self.difference(other)
+    }
+}
+
+impl Not for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn not(self) -> WeekdaySet {
+        self.complement()
+    }
+}
+
+
+/// The days of the week that count as the weekend.
+///
+/// Most of the world takes Saturday and Sunday off, which is what
+/// `DatePiece::is_weekend` uses, but several countries use Friday and
+/// Saturday instead, and some only have one day. Any set of days can be
+/// used with `DatePiece::is_weekend_in`.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct Weekend {
+    days: WeekdaySet,
+}
+
+impl Weekend {
+
+    /// Saturday and Sunday.
+    pub const SATURDAY_SUNDAY: Weekend = Weekend::of(&[ Saturday, Sunday ]);
+
+    /// Friday and Saturday.
+    pub const FRIDAY_SATURDAY: Weekend = Weekend::of(&[ Friday, Saturday ]);
+
+    /// Returns a weekend made up of the given days.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{Weekday, Weekend};
+    ///
+    /// let weekend = Weekend::of(&[ Weekday::Friday ]);
+    /// assert!(weekend.contains(Weekday::Friday));
+    /// assert!(!weekend.contains(Weekday::Saturday));
+    /// ```
+    pub const fn of(weekdays: &[Weekday]) -> Weekend {
+        Weekend { days: WeekdaySet::of(weekdays) }
+    }
+
+    /// Returns whether the given weekday is part of this weekend.
+    pub const fn contains(&self, weekday: Weekday) -> bool {
+        self.days.contains(weekday)
+    }
+
+    /// Returns the set of days in this weekend.
+    pub const fn days(&self) -> WeekdaySet {
+        self.days
+    }
+}
+
+impl From<WeekdaySet> for Weekend {
+    fn from(days: WeekdaySet) -> Weekend {
+        Weekend { days: days }
+    }
+}
+
+impl Default for Weekend {
+
+    /// Returns the weekend of Saturday and Sunday.
+    fn default() -> Weekend {
+        Weekend::SATURDAY_SUNDAY
+    }
+}
+
+
+/// A way of numbering the weeks of a year: the day that each week starts
+/// on, and how many of the days in the week containing the 1st of January
+/// have to be in the new year for it to count as week 1 of that year,
+/// rather than as the last week of the year before.
+///
+/// ISO 8601 weeks start on Monday, and week 1 is the one with at least
+/// four days of the new year in it, which is the one with the first
+/// Thursday. In the United States, weeks usually start on Sunday, and week
+/// 1 is whichever one has the 1st of January in it.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month, WeekRule, DatePiece};
+///
+/// let date = LocalDate::ymd(2022, Month::January, 1).unwrap();
+/// assert_eq!((date.week_year_in(WeekRule::ISO), date.week_in(WeekRule::ISO)), (2021, 52));
+/// assert_eq!((date.week_year_in(WeekRule::US), date.week_in(WeekRule::US)), (2022, 1));
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct WeekRule {
+
+    /// The day of the week that each week starts on.
+    pub first_day: Weekday,
+
+    /// The number of days, from 1 to 7, that the first week of a year has
+    /// to have in that year. Numbers outside that range are treated as the
+    /// nearest one inside it.
+    pub minimal_days_in_first_week: i8,
+}
+
+impl WeekRule {
+
+    /// ISO 8601 weeks, which start on Monday, with week 1 being the one
+    /// with the year’s first Thursday in it.
+    pub const ISO: WeekRule = WeekRule { first_day: Monday, minimal_days_in_first_week: 4 };
+
+    /// Weeks as usually numbered in the United States, which start on
+    /// Sunday, with week 1 being the one with the 1st of January in it.
+    pub const US: WeekRule = WeekRule { first_day: Sunday, minimal_days_in_first_week: 1 };
+
+    /// Returns the number of the week that the given date is in, counting
+    /// only weeks that are numbered in the date’s own year, so any days
+    /// before week 1 are in week 0. This is how `strftime` counts weeks
+    /// for `%U` and `%W`, which use weeks starting on Sunday and Monday
+    /// that need all seven days.
+    pub fn week_of_year<D: DatePiece + ?Sized>(&self, date: &D) -> i8 {
+        let start = self.first_week_start(new_year_weekday(date.year()));
+        let yearday = date.yearday() as i64;
+
+        if yearday < start { 0 } else { ((yearday - start) / 7 + 1) as i8 }
+    }
+
+    /// Returns the year that the given date’s week is numbered in, which
+    /// isn’t always the year that the date is in, along with the week’s
+    /// number in that year, from 1 to 53.
+    pub fn week_date<D: DatePiece + ?Sized>(&self, date: &D) -> (i64, i8) {
+        let year = date.year();
+        let week = self.week_of_year(date);
+
+        if week == 0 {
+            (year - 1, self.weeks_in_year(year - 1))
+        }
+        else if week > self.weeks_in_year(year) {
+            (year + 1, 1)
+        }
+        else {
+            (year, week)
+        }
+    }
+
+    /// Returns the number of weeks numbered in the given year, which is
+    /// either 52 or 53.
+    pub fn weeks_in_year(&self, year: i64) -> i8 {
+        let start = self.first_week_start(new_year_weekday(year));
+        let next_start = self.first_week_start(new_year_weekday(year.wrapping_add(1))) + days_in_year(year) as i64;
+        ((next_start - start) / 7) as i8
+    }
+
+    /// Returns the first day of the given week of the given year,
+    /// returning an error if the year doesn’t have that many weeks, or if
+    /// the day is outside the range of dates that can be represented.
+    pub fn start_of_week(&self, year: i64, week: i8) -> Result<LocalDate, Error> {
+        let max = self.weeks_in_year(year);
+        if week < 1 || week > max {
+            return Err(Error::WeekOutOfRange { given: week, max: max });
+        }
+
+        let yearday = self.first_week_start(new_year_weekday(year)) + 7 * (week as i64 - 1);
+        if yearday < 1 {
+            LocalDate::yd(year - 1, days_in_year(year - 1) as i64 + yearday)
+        }
+        else if yearday > days_in_year(year) as i64 {
+            LocalDate::yd(year + 1, yearday - days_in_year(year) as i64)
+        }
+        else {
+            LocalDate::yd(year, yearday)
+        }
+    }
+
+    /// Returns the day of the year that week 1 starts on in a year that
+    /// starts on the given weekday, which is zero or negative if it starts
+    /// in December.
+    fn first_week_start(&self, new_year: Weekday) -> i64 {
+        let minimum = self.minimal_days_in_first_week.max(1).min(7) as i64;
+        let days_before = self.first_day.days_until(new_year) as i64;
+
+        if 7 - days_before >= minimum { 1 - days_before } else { 8 - days_before }
+    }
+}
+
+impl Default for WeekRule {
+
+    /// Returns the ISO 8601 week rule.
+    fn default() -> WeekRule {
+        WeekRule::ISO
+    }
+}
+
+/// Returns the weekday of the 1st of January in the given year. This is
+/// done in i128 so that no year can make it overflow.
+fn new_year_weekday(year: i64) -> Weekday {
+    let year = year as i128 - 1;
+    let weekday = (year + year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400) + 1).rem_euclid(7);
+    Weekday::from_zero(weekday as i8).unwrap()
+}
+
+
+/// One of the two eras that the Gregorian and Julian calendars number their
+/// years in, either side of the year 1.
+///
+/// This library numbers years astronomically, so the year before 1 is 0,
+/// and the year before that is -1. In the eras, there’s no year 0: the
+/// year 0 is 1 BC, and -1 is 2 BC.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month, DatePiece, Era};
+///
+/// let date = LocalDate::ymd(-43, Month::March, 15).unwrap();
+/// assert_eq!((date.year_of_era(), date.era()), (44, Era::BeforeCommonEra));
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum Era {
+
+    /// The years before the year 1, written as BC or BCE.
+    BeforeCommonEra,
+
+    /// The years from the year 1 onwards, written as AD or CE.
+    CommonEra,
+}
+
+impl Era {
+
+    /// Returns the era that the given year is in.
+    pub fn of_year(year: i64) -> Era {
+        if year > 0 { Era::CommonEra } else { Era::BeforeCommonEra }
+    }
+
+    /// Returns the number of the given year in its era, which is always
+    /// positive.
+    pub fn year_of_era(year: i64) -> i64 {
+        if year > 0 { year } else { 1 - year }
+    }
+
+    /// Returns the name of this era out of the given set of names.
+    pub fn name<'a>(self, names: &EraNames<'a>) -> &'a str {
+        match self {
+            Era::BeforeCommonEra  => names.before_common_era,
+            Era::CommonEra        => names.common_era,
+        }
+    }
+}
+
+/// The names of the two eras, in some language.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct EraNames<'a> {
+
+    /// The name of the years before the year 1, such as “BC”.
+    pub before_common_era: &'a str,
+
+    /// The name of the years from the year 1 onwards, such as “AD”.
+    pub common_era: &'a str,
+}
+
+impl EraNames<'static> {
+
+    /// The English names “BC” and “AD”.
+    pub const BC_AD: EraNames<'static> = EraNames { before_common_era: "BC", common_era: "AD" };
+
+    /// The English names “BCE” and “CE”, which don’t refer to Christ.
+    pub const BCE_CE: EraNames<'static> = EraNames { before_common_era: "BCE", common_era: "CE" };
+
+    /// Returns the usual abbreviations for the eras in the language with
+    /// the given ISO 639-1 code, such as `"fr"` or `"de"`, if they’re
+    /// known. Any region after the language code, as in `"en_GB"` or
+    /// `"pt-BR"`, is ignored.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::EraNames;
+    ///
+    /// assert_eq!(EraNames::for_language("de_DE").unwrap().before_common_era, "v. Chr.");
+    /// assert_eq!(EraNames::for_language("tlh"), None);
+    /// ```
+    pub fn for_language(code: &str) -> Option<EraNames<'static>> {
+        let language = code.split(|c| c == '_' || c == '-').next().unwrap_or(code);
+
+        let (before, after) = match language {
+            "en"  => ("BC", "AD"),
+            "de"  => ("v. Chr.", "n. Chr."),
+            "es"  => ("a. C.", "d. C."),
+            "fr"  => ("av. J.-C.", "ap. J.-C."),
+            "it"  => ("a.C.", "d.C."),
+            "ja"  => ("紀元前", "西暦"),
+            "nl"  => ("v.Chr.", "n.Chr."),
+            "pt"  => ("a.C.", "d.C."),
+            "ru"  => ("до н. э.", "н. э."),
+            "zh"  => ("公元前", "公元"),
+            _     => return None,
+        };
+
+        Some(EraNames { before_common_era: before, common_era: after })
+    }
+}
+
+impl Default for EraNames<'static> {
+
+    /// Returns the English names “BC” and “AD”.
+    fn default() -> EraNames<'static> {
+        EraNames::BC_AD
+    }
+}
+
+
+/// The names of the months in a language whose month names change form
+/// depending on how they’re used.
+///
+/// In many Slavic and Baltic languages, the name of a month on its own, as
+/// in a calendar’s heading, is in the nominative case, but the name in a
+/// date, after the day of the month, is in the genitive case: in Russian,
+/// March is “март”, but the 7th of March is “7 марта”.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct MonthNames<'a> {
+
+    /// The full names of the months on their own, starting from January.
+    pub standalone: [&'a str; 12],
+
+    /// The full names of the months as they’re used in a date.
+    pub genitive: [&'a str; 12],
+
+    /// The abbreviated names of the months on their own.
+    pub short_standalone: [&'a str; 12],
+
+    /// The abbreviated names of the months as they’re used in a date.
+    pub short_genitive: [&'a str; 12],
+}
+
+impl MonthNames<'static> {
+
+    /// The month names in Czech.
+    pub const CZECH: MonthNames<'static> = MonthNames {
+        standalone: [ "leden", "únor", "březen", "duben", "květen", "červen", "červenec", "srpen", "září", "říjen", "listopad", "prosinec" ],
+        genitive:   [ "ledna", "února", "března", "dubna", "května", "června", "července", "srpna", "září", "října", "listopadu", "prosince" ],
+        short_standalone: [ "led", "úno", "bře", "dub", "kvě", "čvn", "čvc", "srp", "zář", "říj", "lis", "pro" ],
+        short_genitive:   [ "led", "úno", "bře", "dub", "kvě", "čvn", "čvc", "srp", "zář", "říj", "lis", "pro" ],
+    };
+
+    /// The month names in Lithuanian.
+    pub const LITHUANIAN: MonthNames<'static> = MonthNames {
+        standalone: [ "sausis", "vasaris", "kovas", "balandis", "gegužė", "birželis", "liepa", "rugpjūtis", "rugsėjis", "spalis", "lapkritis", "gruodis" ],
+        genitive:   [ "sausio", "vasario", "kovo", "balandžio", "gegužės", "birželio", "liepos", "rugpjūčio", "rugsėjo", "spalio", "lapkričio", "gruodžio" ],
+        short_standalone: [ "saus.", "vas.", "kov.", "bal.", "geg.", "birž.", "liep.", "rugp.", "rugs.", "spal.", "lapkr.", "gruod." ],
+        short_genitive:   [ "saus.", "vas.", "kov.", "bal.", "geg.", "birž.", "liep.", "rugp.", "rugs.", "spal.", "lapkr.", "gruod." ],
+    };
+
+    /// The month names in Polish.
+    pub const POLISH: MonthNames<'static> = MonthNames {
+        standalone: [ "styczeń", "luty", "marzec", "kwiecień", "maj", "czerwiec", "lipiec", "sierpień", "wrzesień", "październik", "listopad", "grudzień" ],
+        genitive:   [ "stycznia", "lutego", "marca", "kwietnia", "maja", "czerwca", "lipca", "sierpnia", "września", "października", "listopada", "grudnia" ],
+        short_standalone: [ "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru" ],
+        short_genitive:   [ "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru" ],
+    };
+
+    /// The month names in Russian.
+    pub const RUSSIAN: MonthNames<'static> = MonthNames {
+        standalone: [ "январь", "февраль", "март", "апрель", "май", "июнь", "июль", "август", "сентябрь", "октябрь", "ноябрь", "декабрь" ],
+        genitive:   [ "января", "февраля", "марта", "апреля", "мая", "июня", "июля", "августа", "сентября", "октября", "ноября", "декабря" ],
+        short_standalone: [ "янв.", "февр.", "март", "апр.", "май", "июнь", "июль", "авг.", "сент.", "окт.", "нояб.", "дек." ],
+        short_genitive:   [ "янв.", "февр.", "мар.", "апр.", "мая", "июн.", "июл.", "авг.", "сент.", "окт.", "нояб.", "дек." ],
+    };
+
+    /// The month names in Ukrainian.
+    pub const UKRAINIAN: MonthNames<'static> = MonthNames {
+        standalone: [ "січень", "лютий", "березень", "квітень", "травень", "червень", "липень", "серпень", "вересень", "жовтень", "листопад", "грудень" ],
+        genitive:   [ "січня", "лютого", "березня", "квітня", "травня", "червня", "липня", "серпня", "вересня", "жовтня", "листопада", "грудня" ],
+        short_standalone: [ "січ.", "лют.", "бер.", "квіт.", "трав.", "черв.", "лип.", "серп.", "вер.", "жовт.", "лист.", "груд." ],
+        short_genitive:   [ "січ.", "лют.", "бер.", "квіт.", "трав.", "черв.", "лип.", "серп.", "вер.", "жовт.", "лист.", "груд." ],
+    };
+
+    /// Returns the month names in the language with the given ISO 639-1
+    /// code, if its month names have more than one form and they’re known.
+    /// Any region after the language code is ignored.
+    pub fn for_language(code: &str) -> Option<MonthNames<'static>> {
+        match code.split(|c| c == '_' || c == '-').next().unwrap_or(code) {
+            "cs"  => Some(MonthNames::CZECH),
+            "lt"  => Some(MonthNames::LITHUANIAN),
+            "pl"  => Some(MonthNames::POLISH),
+            "ru"  => Some(MonthNames::RUSSIAN),
+            "uk"  => Some(MonthNames::UKRAINIAN),
+            _     => None,
+        }
+    }
+}
+
+impl<'a> MonthNames<'a> {
+
+    /// Returns the name of the given month, in its full or abbreviated
+    /// form, either on its own or as it’s used in a date.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{Month, MonthNames};
+    ///
+    /// assert_eq!(MonthNames::POLISH.name(Month::May, true, false), "maj");
+    /// assert_eq!(MonthNames::POLISH.name(Month::May, true, true), "maja");
+    /// ```
+    pub fn name(&self, month: Month, long: bool, genitive: bool) -> &'a str {
+        let names = match (long, genitive) {
+            (true,  false)  => &self.standalone,
+            (true,  true)   => &self.genitive,
+            (false, false)  => &self.short_standalone,
+            (false, true)   => &self.short_genitive,
+        };
+
+        names[month.months_from_january()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    pub use super::{LocalDateTime, LocalDate, LocalTime, Month, Weekday};
+    pub use cal::iter::Year;
+    pub use cal::DatePiece;
+    pub use std::str::FromStr;
+    use super::{YMD, DAYS_IN_400Y, EPOCH_DIFFERENCE, MIN_DAY, MAX_DAY};
+
+    mod seconds_to_datetimes {
+        pub use super::*;
+        use super::super::YMD;
+
+        #[test]
+        fn before_time() {
+            let date = LocalDateTime::at(-1_000_000_000_i64);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 1938, month: Month::April, day: 24, },
+                    weekday: Weekday::Sunday, yearday: 114,
+                },
+                LocalTime {
+                    hour: 22, minute: 13, second: 20, nanosecond: 0,
+                },
+            );
+
+            assert_eq!(date, res)
+        }
+
+        #[test]
+        fn start_of_magic() {
+            let date = LocalDateTime::at(0_i64);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 1970, month: Month::January, day: 1, },
+                    weekday: Weekday::Thursday, yearday: 1,
+                },
+                LocalTime::midnight(),
+            );
+
+            assert_eq!(date, res)
+        }
+
+        #[test]
+        fn billennium() {
+            let date = LocalDateTime::at(1_000_000_000_i64);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 2001, month: Month::September, day: 9, },
+                    weekday: Weekday::Sunday, yearday: 252,
+                },
+                LocalTime {
+                    hour: 1, minute: 46, second: 40, nanosecond: 0,
+                },
+            );
+
+            assert_eq!(date, res)
+        }
+
+        #[test]
+        fn numbers() {
+            let date = LocalDateTime::at(1_234_567_890_i64);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 2009, month: Month::February, day: 13, },
+                    weekday: Weekday::Friday, yearday: 44,
+                },
+                LocalTime {
+                    hour: 23, minute: 31, second: 30, nanosecond: 0,
+                },
+            );
+
+            assert_eq!(date, res)
+        }
+
+        #[test]
+        fn year_2038_problem() {
+            let date = LocalDateTime::at(0x7FFF_FFFF_i64);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 2038, month: Month::January, day: 19, },
+                    weekday: Weekday::Tuesday, yearday: 19,
+                },
+                LocalTime {
+                    hour: 3, minute: 14, second: 7, nanosecond: 0,
+                },
+            );
+
+            assert_eq!(date, res)
+        }
+
+        #[test]
+        fn the_end_of_time() {
+            let date = LocalDateTime::at(0x7FFF_FFFF_FFFF_FFFF_i64);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 292_277_026_596, month: Month::December, day: 4, },
+                    weekday: Weekday::Sunday, yearday: 339,
+                },
+                LocalTime {
+                    hour: 15, minute: 30, second: 7, nanosecond: 0,
+                },
+            );
+
+            assert_eq!(date, res)
+        }
+
+        #[test]
+        fn just_another_date() {
+            let date = LocalDateTime::at(146096 * 86400);
+            let res = LocalDateTime::new(
+                LocalDate {
+                    ymd: YMD { year: 2369, month: Month::December, day: 31, },
+                    weekday: Weekday::Wednesday, yearday: 365,
+                },
+                LocalTime::midnight(),
+            );
+
+            assert_eq!(date, res)
+        }
+    }
+
+    mod leap_days {
+        use super::*;
+
+        #[test]
+        fn from_seconds() {
+            let date = LocalDateTime::at(1456704000).date();
+            assert_eq!(date, LocalDate::ymd(2016, Month::February, 29).unwrap());
+            assert_eq!(date.yearday(), 60);
+        }
+
+        #[test]
+        fn end_of_400_year_cycle() {
+            let date = LocalDateTime::at(13574563200).date();
+            assert_eq!(date, LocalDate::ymd(2400, Month::February, 29).unwrap());
+            assert_eq!(date.weekday(), Weekday::Tuesday);
+        }
+
+        #[test]
+        fn every_day_round_trips() {
+            for days in -150_000 .. 150_000 {
+                let date = LocalDateTime::at(days * 86400).date();
+                let ymd = LocalDate::ymd(date.year(), date.month(), date.day()).unwrap();
+                assert_eq!(date.yearday(), ymd.yearday());
+                assert_eq!(ymd.ymd.to_days_since_epoch(), Ok(days));
+            }
+        }
+    }
+
+    mod julian {
+        use super::*;
+        use super::super::Error;
+
+        #[test]
+        fn julian_day() {
+            let date = LocalDate::ymd(2000, Month::January, 1).unwrap();
+            assert_eq!(date.julian_day(), 2451545);
+            assert_eq!(LocalDate::from_julian_day(2451545), date);
+        }
+
+        #[test]
+        fn before_unix_epoch() {
+            let date = LocalDate::ymd(1858, Month::November, 17).unwrap();
+            assert_eq!(date.julian_day(), 2400001);
+            assert_eq!(LocalDate::from_julian_day(2400001), date);
+        }
+
+        #[test]
+        fn julian_day_out_of_range() {
+            assert_eq!(LocalDate::checked_from_julian_day(LocalDate::MAX.julian_day()), Some(LocalDate::MAX));
+            assert_eq!(LocalDate::checked_from_julian_day(LocalDate::MAX.julian_day() + 1), None);
+            assert_eq!(LocalDate::checked_from_julian_day(i64::min_value()), None);
+        }
+
+        #[test]
+        fn julian_date_at_noon() {
+            let datetime = LocalDateTime::new(LocalDate::ymd(2000, Month::January, 1).unwrap(), LocalTime::hm(12, 0).unwrap());
+            assert_eq!(datetime.julian_date(), 2451545.0);
+            assert_eq!(LocalDateTime::from_julian_date(2451545.0), Ok(datetime));
+        }
+
+        #[test]
+        fn julian_date_round_trip() {
+            let datetime = LocalDateTime::at_ms(1_234_567_890, 250);
+            assert_eq!(LocalDateTime::from_julian_date(datetime.julian_date()), Ok(datetime));
+        }
+
+        #[test]
+        fn julian_date_out_of_range() {
+            use std::f64;
+            assert_eq!(LocalDateTime::from_julian_date(f64::NAN), Err(Error::OutOfRange));
+            assert_eq!(LocalDateTime::from_julian_date(1e300), Err(Error::OutOfRange));
+        }
+    }
+
+    mod ymd_to_datetimes {
+        use super::*;
+
+        #[test]
+        fn the_distant_past() {
+            let date = LocalDate::ymd(7, Month::April, 1).unwrap();
+            assert_eq!(7, date.year());
+            assert_eq!(Month::April, date.month());
+            assert_eq!(1, date.day());
+        }
+
+        #[test]
+        fn the_distant_present() {
+            let date = LocalDate::ymd(2015, Month::January, 16).unwrap();
+            assert_eq!(2015, date.year());
+            assert_eq!(Month::January, date.month());
+            assert_eq!(16, date.day());
+        }
+
+        #[test]
+        fn the_distant_future() {
+            let date = LocalDate::ymd(1048576, Month::October, 13).unwrap();
+            assert_eq!(1048576, date.year());
+            assert_eq!(Month::October, date.month());
+            assert_eq!(13, date.day());
+        }
+    }
+
+    #[test]
+    fn start_of_year_day() {
+        let date = LocalDate::ymd(2015, Month::January, 1).unwrap();
+        assert_eq!(date.yearday(), 1);
+    }
+
+    #[test]
+    fn end_of_year_day() {
+        let date = LocalDate::ymd(2015, Month::December, 31).unwrap();
+        assert_eq!(date.yearday(), 365);
+    }
+
+    #[test]
+    fn end_of_leap_year_day() {
+        let date = LocalDate::ymd(2016, Month::December, 31).unwrap();
+        assert_eq!(date.yearday(), 366);
+    }
+
+    #[test]
+    fn day_start_of_year() {
+        let date = LocalDate::yd(2015, 1).unwrap();
+        assert_eq!(2015, date.year());
+        assert_eq!(Month::January, date.month());
+        assert_eq!(1, date.day());
+    }
+
+
+    #[test]
+    fn leap_year_1600() {
+        let date = YMD { year: 1600, month: Month::January, day: 1 };
+        assert!(date.leap_year_calculations().1 == true)
+    }
+
+    #[test]
+    fn leap_year_1900() {
+        let date = YMD { year: 1900, month: Month::January, day: 1 };
+        assert!(date.leap_year_calculations().1 == false)
+    }
+
+    #[test]
+    fn some_leap_years() {
+        for year in [2004,2008,2012,2016].iter() {
+            assert!(LocalDate::ymd(*year, Month::February, 29).is_ok());
+            assert!(LocalDate::ymd(*year + 1, Month::February, 29).is_err());
+        }
+        assert!(LocalDate::ymd(1600,Month::February,29).is_ok());
+        assert!(LocalDate::ymd(1601,Month::February,29).is_err());
+        assert!(LocalDate::ymd(1602,Month::February,29).is_err());
+    }
+
+
+    #[test]
+    fn parse_iso_ymd() {
+        let date_option = LocalDate::from_str("2015-06-26");
+        assert!(date_option.is_ok());
+        let date = date_option.unwrap();
+        assert!(date.year() == 2015);
+        assert!(date.month() == Month::June);
+        assert!(date.day() == 26);
+    }
+
+    #[test]
+    fn new() {
+        for year in 1..3000 {
+            assert!(LocalDate::ymd(year, Month::from_one( 1).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 2).unwrap(), 30).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 3).unwrap(), 32).is_err());
+            assert!(LocalDate::ymd(year, Month::from_one( 4).unwrap(), 31).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 5).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 6).unwrap(), 31).is_err());
+            assert!(LocalDate::ymd(year, Month::from_one( 7).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 8).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 9).unwrap(), 31).is_err());
+            assert!(LocalDate::ymd(year, Month::from_one(10).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one(11).unwrap(), 31).is_err()); assert!(LocalDate::ymd(year, Month::from_one(12).unwrap(), 32).is_err());
+        }
+    }
+
+    #[test]
+    fn to_from_days_since_epoch() {
+        let epoch_difference: i64 = 30 * 365 + 7 + 31 + 29;  // see EPOCH_DIFFERENCE
+        for date in  vec![
+            LocalDate::ymd(1970, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(  01, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1971, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1973, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1977, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1989, Month::from_one(11).unwrap(), 10).unwrap(),
+            LocalDate::ymd(1990, Month::from_one( 7).unwrap(),  8).unwrap(),
+            LocalDate::ymd(2014, Month::from_one( 7).unwrap(), 13).unwrap(),
+            LocalDate::ymd(2001, Month::from_one( 2).unwrap(), 03).unwrap()
+        ]{
+            assert_eq!( date,
+                LocalDate::from_days_since_epoch(
+                    date.ymd.to_days_since_epoch().unwrap() - epoch_difference));
+        }
+    }
+
+    #[test]
+    fn days_since_epoch_every_day() {
+        let mut previous = LocalDate::from_days_since_epoch(-DAYS_IN_400Y - 1);
+        for days in -DAYS_IN_400Y .. DAYS_IN_400Y * 2 {
+            let date = LocalDate::from_days_since_epoch(days);
+            assert_eq!(date.ymd.to_days_since_epoch(), Ok(days + EPOCH_DIFFERENCE));
+            assert_eq!(LocalDate::yd(date.year(), date.yearday() as i64), Ok(date));
+            assert_eq!(date.weekday(), previous.weekday().succ());
+            previous = date;
+        }
+    }
+
+    #[test]
+    fn days_since_epoch_extremes() {
+        assert_eq!(LocalDate::from_days_since_epoch(MIN_DAY - EPOCH_DIFFERENCE), LocalDate::MIN);
+        assert_eq!(LocalDate::from_days_since_epoch(MAX_DAY - EPOCH_DIFFERENCE), LocalDate::MAX);
+    }
+
+    #[test]
+    fn from_yearday() {
+        for date in vec![
+            //LocalDate::ymd(1970, 01 , 01).unwrap(),
+            LocalDate::ymd(1971, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1973, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1977, Month::from_one(01).unwrap(), 01).unwrap(),
+            LocalDate::ymd(1989, Month::from_one(11).unwrap(), 10).unwrap(),
+            LocalDate::ymd(1990, Month::from_one( 7).unwrap(),  8).unwrap(),
+            LocalDate::ymd(2014, Month::from_one( 7).unwrap(), 13).unwrap(),
+            LocalDate::ymd(2001, Month::from_one( 2).unwrap(), 03).unwrap(),
+        ]{
+            let new_date = LocalDate::yd(date.year(), date.yearday() as i64).unwrap();
+            assert_eq!(new_date, date);
+            assert!(LocalDate::yd(2002, 1).is_ok());
+
+            assert_eq!(new_date.yearday(), date.yearday());
+        }
+    }
+
+    #[test]
+    fn yearday() {
+        for year in 1..2058 {
+            assert_eq!( LocalDate::ymd(year, Month::from_one(01).unwrap(), 31).unwrap().yearday() + 1,
+                        LocalDate::ymd(year, Month::from_one(02).unwrap(), 01).unwrap().yearday());
+            assert_eq!( LocalDate::ymd(year, Month::from_one(03).unwrap(), 31).unwrap().yearday() + 1,
+                        LocalDate::ymd(year, Month::from_one(04).unwrap(), 01).unwrap().yearday());
+            assert_eq!( LocalDate::ymd(year, Month::from_one(04).unwrap(), 30).unwrap().yearday() + 1,
+                        LocalDate::ymd(year, Month::from_one(05).unwrap(), 01).unwrap().yearday());
+            assert!(    LocalDate::ymd(year, Month::from_one(12).unwrap(), 31).unwrap().yearday() > 0);
+        }
+        assert_eq!( LocalDate::ymd(1600, Month::from_one(02).unwrap(), 29).unwrap().yearday() + 1, // leap year
+                    LocalDate::ymd(1600, Month::from_one(03).unwrap(), 01).unwrap().yearday());
+        assert_eq!( LocalDate::ymd(1601, Month::from_one(02).unwrap(), 28).unwrap().yearday() + 1, // no leap year
+                    LocalDate::ymd(1601, Month::from_one(03).unwrap(), 01).unwrap().yearday());
+
+    }
+
+    #[test]
+    fn parse_month() {
+        assert_eq!( LocalDate::from_str("2015-01-26").unwrap().month(), Month::January);
+        assert_eq!( LocalDate::from_str("1970-01-26").unwrap().month(), Month::January);
+        assert_eq!( LocalDate::from_str("1969-01-26").unwrap().month(), Month::January);
+    }
+
+    #[test]
+    fn leap_year_2000() {
+        let date = YMD { year: 2000, month: Month::January, day: 1 };
+        assert!(date.leap_year_calculations().1 == true)
+    }
+
+    mod datetimes_to_seconds {
+        pub use super::*;
+
+        #[test]
+        fn test_1970() {
+            let date = LocalDateTime::at(0);
+            let res = date.to_instant().seconds();
+
+            assert_eq!(res, 0)
+        }
+
+        #[test]
+        fn test_1971() {
+            let date = LocalDateTime::at(86400);
+            let res = date.to_instant().seconds();
+
+            assert_eq!(res, 86400)
+        }
+
+        #[test]
+        fn test_1972() {
+            let date = LocalDateTime::at(86400 * 365 * 2);
+            let res = date.to_instant().seconds();
+
+            assert_eq!(0, 86400 * 365 * 2 - res)
+        }
+
+        #[test]
+        fn test_1973() {
+            let date = LocalDateTime::at(86400 * (365 * 3 + 1));
+            let res = date.to_instant().seconds();
+
+            assert_eq!(0, 86400 * (365 * 3 + 1) - res)
+        }
+
+        #[test]
+        fn unix_millis() {
+            let date = LocalDateTime::from_unix_millis(-86_399_999);
+            assert_eq!(date, LocalDateTime::at_ms(-86_400, 1));
+            assert_eq!(date.to_unix_millis(), -86_399_999);
+        }
+
+        #[test]
+        fn unix_nanos() {
+            let date = LocalDateTime::from_unix_nanos(-1);
+            assert_eq!(date, LocalDateTime::at_ns(-1, 999_999_999));
+            assert_eq!(date.to_unix_nanos(), -1);
+        }
+
+        #[test]
+        fn unix_nanos_round_trip() {
+            let nanos = 1_234_567_890_987_654_321;
+            assert_eq!(LocalDateTime::from_unix_nanos(nanos).to_unix_nanos(), nanos);
+        }
+
+        #[test]
+        fn unix_millis_discards_nanoseconds() {
+            let date = LocalDateTime::at_ns(1, 250_999_999);
+            assert_eq!(date.to_unix_millis(), 1_250);
+        }
+
+        #[test]
+        fn some_date() {
+            let date = LocalDateTime::at(1234567890);
+            let res = date.to_instant().seconds();
+
+            assert_eq!(1234567890, res)
+        }
+
+        #[test]
+        fn far_far_future() {
+            let date = LocalDateTime::at(54321234567890);
+            let res = date.to_instant().seconds();
+
+            assert_eq!(54321234567890, res)
+        }
+
+        #[test]
+        fn the_distant_past() {
+            let date = LocalDateTime::at(-54321234567890);
+            let res = date.to_instant().seconds();
+
+            assert_eq!(-54321234567890, res)
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn date() {
+            assert_eq!(LocalDate::ymd(1969, Month::July, 20).unwrap().to_string(), "1969-07-20")
+        }
+
+        #[test]
+        fn time() {
+            assert_eq!(LocalTime::hms(9, 5, 0).unwrap().to_string(), "09:05:00")
+        }
+
+        #[test]
+        fn time_with_milliseconds() {
+            assert_eq!(LocalTime::hms_ms(9, 5, 0, 25).unwrap().to_string(), "09:05:00.025")
+        }
+
+        #[test]
+        fn datetime() {
+            let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            let time = LocalTime::hms(14, 30, 15).unwrap();
+            assert_eq!(LocalDateTime::new(date, time).to_string(), "2015-06-26T14:30:15")
+        }
+
+        #[test]
+        fn round_trip() {
+            let date = LocalDate::ymd(2015, Month::December, 31).unwrap();
+            let time = LocalTime::hms_ms(23, 59, 59, 999).unwrap();
+            let datetime = LocalDateTime::new(date, time);
+            assert_eq!(LocalDateTime::from_str(&datetime.to_string()), Ok(datetime))
+        }
+    }
+
+    mod range {
+        use super::*;
+        use super::super::{Error, MIN_YEAR, MAX_YEAR};
+        use duration::Duration;
+        use instant::Instant;
+
+        #[test]
+        fn first_and_last_dates() {
+            assert!(LocalDate::ymd(MIN_YEAR, Month::January, 27).is_ok());
+            assert!(LocalDate::ymd(MAX_YEAR, Month::December, 4).is_ok());
+            assert_eq!(LocalDate::ymd(MIN_YEAR, Month::January, 26), Err(Error::OutOfRange));
+            assert_eq!(LocalDate::ymd(MAX_YEAR, Month::December, 5), Err(Error::OutOfRange));
+        }
+
+        #[test]
+        fn constants() {
+            assert_eq!(LocalDate::MIN, LocalDate::ymd(MIN_YEAR, Month::January, 27).unwrap());
+            assert_eq!(LocalDate::MAX, LocalDate::ymd(MAX_YEAR, Month::December, 4).unwrap());
+            assert_eq!(LocalDateTime::MIN, LocalDateTime::at(i64::min_value()));
+            assert_eq!(LocalDateTime::MAX, LocalDateTime::at_ns(i64::max_value(), 999_999_999));
+            assert_eq!(LocalDateTime::MIN.to_instant(), Instant::MIN);
+            assert_eq!(LocalDateTime::MAX.to_instant(), Instant::MAX);
+            assert_eq!(LocalDateTime::MIN.date(), LocalDate::MIN);
+            assert_eq!(LocalDateTime::MAX.date(), LocalDate::MAX);
+        }
+
+        #[test]
+        fn time_constants() {
+            assert_eq!(LocalTime::MIDNIGHT, LocalTime::hms(0, 0, 0).unwrap());
+            assert_eq!(LocalTime::NOON, LocalTime::hms(12, 0, 0).unwrap());
+            assert_eq!(LocalTime::MAX, LocalTime::hms_ns(23, 59, 59, 999_999_999).unwrap());
+            assert_eq!(LocalTime::MAX.to_seconds(), 86399);
+            assert!(LocalTime::MIDNIGHT < LocalTime::NOON && LocalTime::NOON < LocalTime::MAX);
+        }
+
+        #[test]
+        fn nothing_beyond_the_constants() {
+            assert_eq!(LocalDateTime::MIN.checked_sub(Duration::of_ms(0, 1)), None);
+            assert_eq!(LocalDateTime::MAX.checked_add(Duration::of_ms(0, 1)), None);
+            assert_eq!(Instant::MIN.checked_sub(Duration::of_ms(0, 1)), None);
+            assert_eq!(Instant::MAX.checked_add(Duration::of_ms(0, 1)), None);
+        }
+
+        #[test]
+        fn constructors_out_of_range() {
+            assert_eq!(LocalDateTime::try_at_ns(i64::max_value(), 999_999_999), Ok(LocalDateTime::MAX));
+            assert_eq!(LocalDateTime::try_at_ns(i64::max_value(), 1_999_999_999), Err(Error::OutOfRange));
+            assert_eq!(LocalDateTime::try_at_ns(i64::min_value(), -1), Err(Error::OutOfRange));
+            assert_eq!(LocalDateTime::try_at_ms(i64::max_value(), 1000), Err(Error::OutOfRange));
+            assert_eq!(LocalDateTime::try_at_ms(0, 5000), Ok(LocalDateTime::at(5)));
+            assert_eq!(LocalDateTime::try_from_unix_nanos(Instant::MAX.to_nanos()), Ok(LocalDateTime::MAX));
+            assert_eq!(LocalDateTime::try_from_unix_nanos(Instant::MAX.to_nanos() + 1), Err(Error::OutOfRange));
+        }
+
+        #[test]
+        #[should_panic]
+        fn at_ns_overflow() {
+            let _ = LocalDateTime::at_ns(i64::max_value(), 1_999_999_999);
+        }
+
+        #[test]
+        fn first_and_last_datetimes() {
+            assert_eq!(LocalDateTime::at(i64::min_value()).to_string(), "-292277022657-01-27T08:29:52");
+            assert_eq!(LocalDateTime::at(i64::max_value()).to_string(), "+292277026596-12-04T15:30:07");
+            assert_eq!(LocalDateTime::at(i64::min_value()).to_instant(), Instant::at(i64::min_value()));
+        }
+
+        #[test]
+        fn years_out_of_range() {
+            assert_eq!(LocalDate::ymd(i64::max_value(), Month::January, 1), Err(Error::YearOutOfRange { given: i64::max_value() }));
+            assert_eq!(LocalDate::yd(i64::min_value(), 1), Err(Error::YearOutOfRange { given: i64::min_value() }));
+            assert_eq!(LocalDate::ywd(i64::max_value(), 1, Weekday::Monday), Err(Error::YearOutOfRange { given: i64::max_value() }));
+        }
+
+        #[test]
+        #[should_panic]
+        fn time_after_the_end() {
+            let date = LocalDate::ymd(MAX_YEAR, Month::December, 4).unwrap();
+            let _ = LocalDateTime::new(date, LocalTime::hms(15, 30, 8).unwrap());
+        }
+
+        #[test]
+        fn try_new() {
+            let date = LocalDate::ymd(MAX_YEAR, Month::December, 4).unwrap();
+            assert_eq!(LocalDateTime::try_new(date, LocalTime::hms(15, 30, 7).unwrap()), Ok(LocalDateTime::at(i64::max_value())));
+            assert_eq!(LocalDateTime::try_new(date, LocalTime::hms(15, 30, 8).unwrap()), Err(Error::OutOfRange));
+        }
+
+        #[test]
+        fn unix_millis_out_of_range() {
+            assert_eq!(LocalDateTime::at(i64::max_value()).checked_to_unix_millis(), None);
+            assert_eq!(LocalDateTime::at_ms(-1, 500).checked_to_unix_millis(), Some(-500));
+        }
+
+        #[test]
+        fn checked_arithmetic() {
+            let last = LocalDateTime::at_ns(i64::max_value(), 500);
+            assert_eq!(last.checked_add(Duration::of(1)), None);
+            assert_eq!(last.checked_sub(Duration::of(1)), Some(LocalDateTime::at_ns(i64::max_value() - 1, 500)));
+            assert_eq!(LocalDateTime::at(i64::min_value()).checked_sub(Duration::of_ms(0, 1)), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn addition_panics() {
+            let _ = LocalDateTime::at(i64::max_value()) + Duration::of(1);
+        }
+    }
+
+    mod constants {
+        use super::*;
+        use super::super::Error;
+        use duration::Duration;
+        use cal::offset::{Offset, Error as OffsetError};
+
+        const LAUNCH_DATE: Result<LocalDate, Error> = LocalDate::ymd(2015, Month::June, 26);
+        const LAUNCH_TIME: Result<LocalTime, Error> = LocalTime::hms(14, 30, 0);
+        const INVALID_DATE: Result<LocalDate, Error> = LocalDate::ymd(2015, Month::February, 29);
+        const INVALID_TIME: Result<LocalTime, Error> = LocalTime::hms(14, 60, 0);
+        const GRACE_PERIOD: Duration = Duration::of(3600);
+        const PARIS: Result<Offset, OffsetError> = Offset::of_seconds(3600);
+
+        #[test]
+        fn dates() {
+            assert_eq!(LAUNCH_DATE, LocalDate::ymd(2015, Month::June, 26));
+            assert_eq!(LAUNCH_DATE.unwrap().weekday(), Weekday::Friday);
+            assert_eq!(LAUNCH_DATE.unwrap().yearday(), 177);
+            assert_eq!(INVALID_DATE, Err(Error::DayOutOfRange { given: 29, max: 28 }));
+        }
+
+        #[test]
+        fn times() {
+            assert_eq!(LAUNCH_TIME, LocalTime::hms(14, 30, 0));
+            assert_eq!(INVALID_TIME, Err(Error::MinuteOutOfRange { given: 60 }));
+        }
+
+        #[test]
+        fn durations_and_offsets() {
+            assert_eq!(GRACE_PERIOD, Duration::of(3600));
+            assert_eq!(PARIS, Offset::of_hours_and_minutes(1, 0));
+        }
+    }
+
+    mod hashing {
+        use super::*;
+        use std::collections::HashSet;
+
+        #[test]
+        fn dates_made_differently() {
+            let set: HashSet<LocalDate> = vec![
+                LocalDate::ymd(2015, Month::September, 13).unwrap(),
+                LocalDate::yd(2015, 0x100).unwrap(),
+                LocalDate::ywd(2015, 37, Weekday::Sunday).unwrap(),
+                LocalDate::ymd(2015, Month::September, 14).unwrap(),
+            ].into_iter().collect();
+            assert_eq!(set.len(), 2);
+        }
+
+        #[test]
+        fn deduplicate_datetimes() {
+            let set: HashSet<LocalDateTime> = vec![
+                LocalDateTime::at_ns(1_000_000_000, 5),
+                LocalDateTime::at_ns(1_000_000_000, 5),
+                LocalDateTime::at_ns(1_000_000_000, 6),
+            ].into_iter().collect();
+            assert_eq!(set.len(), 2);
+        }
+    }
+
+    mod representation {
+        use super::*;
+        use cal::TimePiece;
+        use std::mem::size_of;
+
+        #[test]
+        fn size() {
+            assert_eq!(size_of::<LocalDateTime>(), 16);
+        }
+
+        #[test]
+        fn times_round_trip() {
+            for &time in &[ LocalTime::MIDNIGHT, LocalTime::MAX, LocalTime::hms_leap(23, 59, 60).unwrap(),
+                            LocalTime::hms(24, 0, 0).unwrap(), LocalTime::hms_ns(12, 34, 56, 789).unwrap() ] {
+                assert_eq!(LocalTime::unpack(time.pack()), time);
+                assert_eq!(LocalDate::default().at(time).time(), time);
+            }
+        }
+
+        #[test]
+        fn leap_seconds_sort_in_place() {
+            let date = LocalDate::ymd(2016, Month::December, 31).unwrap();
+            let before = date.at(LocalTime::hms_ns(12, 59, 59, 999_999_999).unwrap());
+            let leap = date.at(LocalTime::hms_leap(12, 59, 60).unwrap());
+            let after = date.at(LocalTime::hms(13, 0, 0).unwrap());
+            assert!(before < leap && leap < after);
+        }
+
+        #[test]
+        fn midnight_at_the_end_of_the_day() {
+            let date = LocalDate::ymd(2016, Month::December, 31).unwrap();
+            let end_of_day = date.at(LocalTime::hms(24, 0, 0).unwrap());
+            assert!(end_of_day > date.at(LocalTime::MAX));
+            assert!(end_of_day < LocalDate::ymd(2017, Month::January, 1).unwrap().at(LocalTime::MIDNIGHT));
+            assert_eq!(end_of_day.date(), date);
+        }
+
+        #[test]
+        fn fields_at_the_extremes() {
+            assert_eq!(LocalDateTime::MIN.date(), LocalDate::MIN);
+            assert_eq!(LocalDateTime::MAX.date(), LocalDate::MAX);
+            assert_eq!(LocalDateTime::MAX.nanosecond(), 999_999_999);
+            assert_eq!(LocalDateTime::MIN.weekday(), LocalDate::MIN.weekday());
+        }
+    }
+
+    mod tables {
+        use super::*;
+        use super::super::CUMULATIVE_DAYS;
+
+        #[test]
+        fn cumulative_days_match_month_lengths() {
+            for &leap_year in &[false, true] {
+                for month in Month::all() {
+                    let index = month.months_from_january();
+                    let table = CUMULATIVE_DAYS[leap_year as usize];
+                    assert_eq!(table[index + 1] - table[index], month.days_in_month(leap_year) as i16);
+                }
+            }
+        }
+
+        #[test]
+        fn datetime_fields_match_dates() {
+            for days in -800 .. 800 {
+                let datetime = LocalDateTime::at(days * 86400 + 3600);
+                let date = datetime.date();
+                assert_eq!((datetime.year(), datetime.month(), datetime.day(), datetime.yearday(), datetime.weekday()),
+                           (date.year(), date.month(), date.day(), date.yearday(), date.weekday()));
+            }
+        }
+    }
+
+    mod calendar {
+        use super::*;
+        use super::super::{is_leap_year, days_in_year, weeks_in_iso_year, WeekRule};
+
+        #[test]
+        fn leap_years_agree_with_dates() {
+            for year in -1000 .. 3000 {
+                assert_eq!(is_leap_year(year), LocalDate::ymd(year, Month::February, 29).is_ok());
+                assert_eq!(days_in_year(year) as i64, LocalDate::yd(year + 1, 1).unwrap().julian_day() - LocalDate::yd(year, 1).unwrap().julian_day());
+            }
+        }
+
+        #[test]
+        fn weeks_agree_with_week_dates() {
+            // A week belongs to the year that its Thursday is in.
+            for year in -1000 .. 3000 {
+                let weeks = weeks_in_iso_year(year) as i64;
+                assert_eq!(LocalDate::ywd(year, weeks, Weekday::Thursday).unwrap().year(), year);
+                assert_eq!(LocalDate::ywd(year, weeks + 1, Weekday::Thursday).unwrap().year(), year + 1);
+            }
+        }
+
+        #[test]
+        fn eras() {
+            use super::super::{Era, EraNames};
+
+            let era = |year| { let date = LocalDate::ymd(year, Month::January, 1).unwrap(); (date.year_of_era(), date.era()) };
+            assert_eq!(era(2024), (2024, Era::CommonEra));
+            assert_eq!(era(1), (1, Era::CommonEra));
+            assert_eq!(era(0), (1, Era::BeforeCommonEra));
+            assert_eq!(era(-43), (44, Era::BeforeCommonEra));
+
+            assert_eq!(Era::CommonEra.name(&EraNames::BCE_CE), "CE");
+            assert_eq!(Era::BeforeCommonEra.name(&EraNames::for_language("fr").unwrap()), "av. J.-C.");
+            assert_eq!(EraNames::for_language("pt-BR"), EraNames::for_language("pt"));
+        }
+
+        #[test]
+        fn month_names() {
+            use super::super::MonthNames;
+
+            assert_eq!(MonthNames::RUSSIAN.name(Month::March, true, false), "март");
+            assert_eq!(MonthNames::RUSSIAN.name(Month::March, true, true), "марта");
+            assert_eq!(MonthNames::RUSSIAN.name(Month::May, false, true), "мая");
+            assert_eq!(MonthNames::for_language("lt_LT"), Some(MonthNames::LITHUANIAN));
+            assert_eq!(MonthNames::for_language("en"), None);
+        }
+
+        #[test]
+        fn iso_weeks_agree_with_week_dates() {
+            for day in LocalDate::ymd(1995, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2035, Month::January, 1).unwrap().julian_day() {
+                let date = LocalDate::from_julian_day(day);
+                assert_eq!(LocalDate::ywd(date.iso_week_year(), date.iso_week() as i64, date.weekday()), Ok(date));
+            }
+        }
+
+        #[test]
+        fn iso_weeks_around_new_year() {
+            let date = LocalDate::ymd(2008, Month::December, 29).unwrap();
+            assert_eq!((date.iso_week_year(), date.iso_week()), (2009, 1));
+
+            let date = LocalDate::ymd(2010, Month::January, 3).unwrap();
+            assert_eq!((date.iso_week_year(), date.iso_week()), (2009, 53));
+
+            let date = LocalDate::ymd(2010, Month::January, 4).unwrap();
+            assert_eq!((date.iso_week_year(), date.iso_week()), (2010, 1));
+
+            let datetime = LocalDateTime::new(LocalDate::ymd(2016, Month::January, 1).unwrap(), LocalTime::midnight());
+            assert_eq!((datetime.iso_week_year(), datetime.iso_week()), (2015, 53));
+        }
+
+        #[test]
+        fn week_rules() {
+            let sunday = LocalDate::ymd(2023, Month::January, 1).unwrap();
+            assert_eq!(WeekRule::ISO.week_date(&sunday), (2022, 52));
+            assert_eq!(WeekRule::US.week_date(&sunday), (2023, 1));
+
+            // strftime’s %U numbering gives 0 until the first Sunday.
+            let percent_u = WeekRule { first_day: Weekday::Sunday, minimal_days_in_first_week: 7 };
+            let saturday = LocalDate::ymd(2022, Month::January, 1).unwrap();
+            assert_eq!(percent_u.week_of_year(&saturday), 0);
+            assert_eq!(percent_u.week_of_year(&LocalDate::ymd(2022, Month::January, 2).unwrap()), 1);
+            assert_eq!(percent_u.week_of_year(&LocalDate::ymd(2022, Month::December, 31).unwrap()), 52);
+
+            // The 31st of December 2024 is in week 1 of 2025 in both.
+            let tuesday = LocalDate::ymd(2024, Month::December, 31).unwrap();
+            assert_eq!(WeekRule::ISO.week_date(&tuesday), (2025, 1));
+            assert_eq!(WeekRule::US.week_date(&tuesday), (2025, 1));
+        }
+
+        #[test]
+        fn week_rules_agree_with_start_of_week() {
+            let rules = [
+                WeekRule::ISO,
+                WeekRule::US,
+                WeekRule { first_day: Weekday::Saturday, minimal_days_in_first_week: 1 },
+                WeekRule { first_day: Weekday::Sunday, minimal_days_in_first_week: 7 },
+            ];
+
+            for rule in &rules {
+                for day in LocalDate::ymd(1995, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2035, Month::January, 1).unwrap().julian_day() {
+                    let date = LocalDate::from_julian_day(day);
+                    let (year, week) = rule.week_date(&date);
+                    let start = rule.start_of_week(year, week).unwrap();
+                    assert_eq!(start.weekday(), rule.first_day);
+                    assert!(start <= date && date.julian_day() - start.julian_day() < 7, "{:?} {:?}", rule, date);
+                }
+            }
+
+            for year in -1000 .. 3000 {
+                assert_eq!(WeekRule::ISO.weeks_in_year(year), weeks_in_iso_year(year));
+            }
+        }
+
+        #[test]
+        fn new_year_weekdays() {
+            use super::super::new_year_weekday;
+
+            for year in -1000 .. 3000 {
+                assert_eq!(new_year_weekday(year), LocalDate::ymd(year, Month::January, 1).unwrap().weekday());
+            }
+        }
+
+        #[test]
+        fn extreme_years() {
+            assert!(!is_leap_year(i64::max_value()));
+            assert!(is_leap_year(i64::min_value()));
+            assert!(weeks_in_iso_year(i64::min_value()) >= 52);
+            assert!(weeks_in_iso_year(i64::max_value()) >= 52);
+        }
+    }
+
+    mod leap_seconds {
+        use super::*;
+        use super::super::Error;
+        use cal::TimePiece;
+        use instant::Instant;
+
+        #[test]
+        fn constructors() {
+            assert_eq!(LocalTime::hms_leap(23, 59, 60).unwrap().second(), 60);
+            assert_eq!(LocalTime::hms_ns_leap(23, 59, 60, 500).unwrap().nanosecond(), 500);
+            assert_eq!(LocalTime::hms_leap(12, 59, 60).unwrap().second(), 60);
+            assert_eq!(LocalTime::hms_leap(23, 59, 59), LocalTime::hms(23, 59, 59));
+        }
+
+        #[test]
+        fn only_at_the_end_of_an_hour() {
+            assert_eq!(LocalTime::hms_leap(23, 58, 60), Err(Error::SecondOutOfRange { given: 60 }));
+            assert_eq!(LocalTime::hms_leap(24, 59, 60), Err(Error::HourOutOfRange { given: 24 }));
+            assert_eq!(LocalTime::hms_leap(24, 0, 60), Err(Error::HourOutOfRange { given: 24 }));
+            assert_eq!(LocalTime::hms_ns_leap(23, 59, 60, 1_000_000_000), Err(Error::NanosecondOutOfRange { given: 1_000_000_000 }));
+        }
+
+        #[test]
+        fn only_when_asked_for() {
+            assert_eq!(LocalTime::hms(23, 59, 60), Err(Error::SecondOutOfRange { given: 60 }));
+            assert_eq!(LocalTime::hms_ms(23, 59, 60, 500), Err(Error::SecondOutOfRange { given: 60 }));
+            assert_eq!(LocalTime::hms_ns(23, 59, 60, 0), Err(Error::SecondOutOfRange { given: 60 }));
+            assert!(LocalDateTime::builder().hour(23).minute(59).second(60).build().is_err());
+        }
+
+        #[test]
+        fn display() {
+            assert_eq!(LocalTime::hms_leap(23, 59, 60).unwrap().to_string(), "23:59:60");
+        }
+
+        #[test]
+        fn parse() {
+            let datetime: LocalDateTime = "2016-12-31T23:59:60".parse().unwrap();
+            assert_eq!(datetime.time(), LocalTime::hms_leap(23, 59, 60).unwrap());
+            assert!("2016-12-31T23:58:60".parse::<LocalDateTime>().is_err());
+        }
+
+        #[test]
+        fn ordering() {
+            let before = LocalTime::hms_ms(23, 59, 59, 999).unwrap();
+            let leap = LocalTime::hms_leap(23, 59, 60).unwrap();
+            assert!(before < leap && leap < LocalTime::hms(24, 0, 0).unwrap());
+        }
+
+        #[test]
+        fn instants_ignore_leap_seconds() {
+            let date = LocalDate::ymd(2016, Month::December, 31).unwrap();
+            let leap = LocalDateTime::new(date, LocalTime::hms_ns_leap(23, 59, 60, 250_000_000).unwrap());
+            let before = LocalDateTime::new(date, LocalTime::hms_ms(23, 59, 59, 250).unwrap());
+            assert_eq!(leap.to_instant(), before.to_instant());
+            assert_eq!(leap.to_instant(), Instant::at_ms(1_483_228_799, 250));
+        }
+    }
+
+    mod months {
+        use super::*;
+
+        #[test]
+        fn all_in_order() {
+            let months: Vec<Month> = Month::all().collect();
+            assert_eq!(months.len(), 12);
+            assert!(months.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        #[test]
+        fn next_and_previous() {
+            for month in Month::all() {
+                assert_eq!(month.next().previous(), month);
+                assert_eq!(month.next().to_zero(), (month.to_zero() + 1) % 12);
+            }
+        }
+
+        #[test]
+        fn days_in_year() {
+            assert_eq!(Month::all().map(|m| m.days_in(2015) as i64).sum::<i64>(), 365);
+            assert_eq!(Month::all().map(|m| m.days_in(2000) as i64).sum::<i64>(), 366);
+            assert_eq!(Month::February.days_in(1900), 28);
+        }
+    }
+
+    mod weekdays {
+        use super::*;
+
+        fn all() -> Vec<Weekday> {
+            (0 .. 7).map(|n| Weekday::from_zero(n).unwrap()).collect()
+        }
+
+        #[test]
+        fn succ_and_pred() {
+            for weekday in all() {
+                assert_eq!(weekday.succ().pred(), weekday);
+                assert_eq!(weekday.days_until(weekday.succ()), 1);
+                assert_eq!(weekday.days_until(weekday.pred()), 6);
+            }
+        }
+
+        #[test]
+        fn numbering() {
+            assert_eq!(all().iter().map(|w| w.number_from_sunday()).collect::<Vec<_>>(), vec![ 1, 2, 3, 4, 5, 6, 7 ]);
+            assert_eq!(all().iter().map(|w| w.number_from_monday()).collect::<Vec<_>>(), vec![ 7, 1, 2, 3, 4, 5, 6 ]);
+        }
+
+        #[test]
+        fn days_until_agrees_with_dates() {
+            let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            for weekday in all() {
+                let days = date.weekday().days_until(weekday) as i64;
+                let later = LocalDate::from_julian_day(date.julian_day() + days);
+                assert_eq!(later.weekday(), weekday);
+            }
+        }
+    }
+
+    mod weekends {
+        use super::*;
+        use super::super::Weekend;
+
+        #[test]
+        fn saturday_and_sunday() {
+            let friday = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            let saturday = LocalDate::ymd(2015, Month::June, 27).unwrap();
+            let sunday = LocalDate::ymd(2015, Month::June, 28).unwrap();
+            assert!(!friday.is_weekend() && friday.is_weekday());
+            assert!(saturday.is_weekend() && !saturday.is_weekday());
+            assert!(sunday.is_weekend() && !sunday.is_weekday());
+        }
+
+        #[test]
+        fn friday_and_saturday() {
+            let friday = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            let sunday = LocalDateTime::new(LocalDate::ymd(2015, Month::June, 28).unwrap(), LocalTime::midnight());
+            assert!(friday.is_weekend_in(Weekend::FRIDAY_SATURDAY));
+            assert!(!sunday.is_weekend_in(Weekend::FRIDAY_SATURDAY));
+            assert!(sunday.is_weekday_in(Weekend::FRIDAY_SATURDAY));
+        }
+
+        #[test]
+        fn empty() {
+            let weekend = Weekend::of(&[]);
+            assert!((0 .. 7).all(|n| !weekend.contains(Weekday::from_zero(n).unwrap())));
+        }
+    }
+
+    mod combining {
+        use super::*;
 
-            assert_eq!(date, res)
+        #[test]
+        fn date_at_time() {
+            let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            let time = LocalTime::hms(14, 30, 0).unwrap();
+            assert_eq!(date.at(time), LocalDateTime::new(date, time));
         }
 
         #[test]
-        fn just_another_date() {
-            let date = LocalDateTime::at(146096 * 86400);
-            let res = LocalDateTime {
-                date: LocalDate {
-                    ymd: YMD { year: 2369, month: Month::December, day: 31, },
-                    weekday: Weekday::Wednesday, yearday: 365,
-                },
-                time: LocalTime::midnight(),
-            };
+        fn replacing_parts() {
+            let datetime = LocalDate::ymd(2015, Month::June, 26).unwrap().at(LocalTime::NOON);
+            let date = LocalDate::ymd(1999, Month::December, 31).unwrap();
+            assert_eq!(datetime.with_date(date), date.at(LocalTime::NOON));
+            assert_eq!(datetime.with_time(LocalTime::MAX).date(), datetime.date());
+            assert_eq!(datetime.with_time(LocalTime::MAX).time(), LocalTime::MAX);
+        }
 
-            assert_eq!(date, res)
+        #[test]
+        #[should_panic]
+        fn out_of_range() {
+            let _ = LocalDateTime::MIN.with_time(LocalTime::MIDNIGHT);
         }
     }
 
-    mod ymd_to_datetimes {
+    mod fields {
         use super::*;
+        use super::super::{Error, Fields};
+        use std::convert::TryFrom;
 
         #[test]
-        fn the_distant_past() {
-            let date = LocalDate::ymd(7, Month::April, 1).unwrap();
-            assert_eq!(7, date.year());
-            assert_eq!(Month::April, date.month());
-            assert_eq!(1, date.day());
+        fn round_trip() {
+            let datetime = LocalDateTime::new(LocalDate::ymd(1999, Month::December, 31).unwrap(), LocalTime::hms_ns_leap(23, 59, 60, 5).unwrap());
+            let fields = Fields::from(datetime);
+            assert_eq!(fields, Fields { year: 1999, month: 12, day: 31, hour: 23, minute: 59, second: 60, nanosecond: 5 });
+            assert_eq!(LocalDateTime::try_from(fields), Ok(datetime));
         }
 
         #[test]
-        fn the_distant_present() {
-            let date = LocalDate::ymd(2015, Month::January, 16).unwrap();
-            assert_eq!(2015, date.year());
-            assert_eq!(Month::January, date.month());
-            assert_eq!(16, date.day());
+        fn dates() {
+            let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            let fields = Fields { hour: 99, .. Fields::from(date) };
+            assert_eq!(Fields::from(date).hour, 0);
+            assert_eq!(LocalDate::try_from(fields), Ok(date));
         }
 
         #[test]
-        fn the_distant_future() {
-            let date = LocalDate::ymd(1048576, Month::October, 13).unwrap();
-            assert_eq!(1048576, date.year());
-            assert_eq!(Month::October, date.month());
-            assert_eq!(13, date.day());
-        }
-    }
-
-    #[test]
-    fn start_of_year_day() {
-        let date = LocalDate::ymd(2015, Month::January, 1).unwrap();
-        assert_eq!(date.yearday(), 1);
-    }
+        fn invalid() {
+            let fields = Fields { year: 2015, month: 13, day: 1, .. Fields::default() };
+            assert_eq!(LocalDateTime::try_from(fields), Err(Error::MonthOutOfRange { given: 13 }));
 
-    #[test]
-    fn end_of_year_day() {
-        let date = LocalDate::ymd(2015, Month::December, 31).unwrap();
-        assert_eq!(date.yearday(), 365);
-    }
+            let fields = Fields { year: 2015, month: 2, day: 29, .. Fields::default() };
+            assert_eq!(LocalDate::try_from(fields), Err(Error::DayOutOfRange { given: 29, max: 28 }));
 
-    #[test]
-    fn end_of_leap_year_day() {
-        let date = LocalDate::ymd(2016, Month::December, 31).unwrap();
-        assert_eq!(date.yearday(), 366);
+            let fields = Fields { year: 2015, month: 1, day: 1, minute: 60, .. Fields::default() };
+            assert_eq!(LocalDateTime::try_from(fields), Err(Error::MinuteOutOfRange { given: 60 }));
+        }
     }
 
-    #[test]
-    fn day_start_of_year() {
-        let date = LocalDate::yd(2015, 1).unwrap();
-        assert_eq!(2015, date.year());
-        assert_eq!(Month::January, date.month());
-        assert_eq!(1, date.day());
-    }
+    mod weekday_sets {
+        use super::*;
+        use super::super::WeekdaySet;
 
+        #[test]
+        fn operators() {
+            let weekdays = WeekdaySet::MONDAY_TO_FRIDAY;
+            let weekend = !weekdays;
+            assert_eq!(weekend, WeekdaySet::of(&[ Weekday::Saturday, Weekday::Sunday ]));
+            assert_eq!(weekdays | weekend, WeekdaySet::ALL);
+            assert_eq!(weekdays & weekend, WeekdaySet::EMPTY);
+            assert_eq!(WeekdaySet::ALL - weekend, weekdays);
+            assert!(weekend.is_subset(WeekdaySet::ALL));
+            assert!(!WeekdaySet::ALL.is_subset(weekend));
+        }
 
-    #[test]
-    fn leap_year_1600() {
-        let date = YMD { year: 1600, month: Month::January, day: 1 };
-        assert!(date.leap_year_calculations().1 == true)
-    }
+        #[test]
+        fn inserting_and_removing() {
+            let mut set = WeekdaySet::EMPTY;
+            assert!(set.insert(Weekday::Friday));
+            assert!(!set.insert(Weekday::Friday));
+            assert_eq!(set.len(), 1);
+            assert!(set.remove(Weekday::Friday));
+            assert!(!set.remove(Weekday::Friday));
+            assert!(set.is_empty());
+        }
 
-    #[test]
-    fn leap_year_1900() {
-        let date = YMD { year: 1900, month: Month::January, day: 1 };
-        assert!(date.leap_year_calculations().1 == false)
-    }
+        #[test]
+        fn iteration() {
+            let set: WeekdaySet = vec![ Weekday::Saturday, Weekday::Monday, Weekday::Sunday ].into_iter().collect();
+            assert_eq!(set.iter().collect::<Vec<_>>(), vec![ Weekday::Sunday, Weekday::Monday, Weekday::Saturday ]);
+            assert_eq!(format!("{:?}", set), "{Sunday, Monday, Saturday}");
+        }
 
-    #[test]
-    fn some_leap_years() {
-        for year in [2004,2008,2012,2016].iter() {
-            assert!(LocalDate::ymd(*year, Month::February, 29).is_ok());
-            assert!(LocalDate::ymd(*year + 1, Month::February, 29).is_err());
+        #[test]
+        fn next_from() {
+            let set = WeekdaySet::single(Weekday::Wednesday);
+            assert_eq!(set.next_from(Weekday::Wednesday), Some(Weekday::Wednesday));
+            assert_eq!(set.next_from(Weekday::Thursday), Some(Weekday::Wednesday));
+            assert_eq!(WeekdaySet::EMPTY.next_from(Weekday::Thursday), None);
         }
-        assert!(LocalDate::ymd(1600,Month::February,29).is_ok());
-        assert!(LocalDate::ymd(1601,Month::February,29).is_err());
-        assert!(LocalDate::ymd(1602,Month::February,29).is_err());
     }
 
+    mod numbers {
+        use super::*;
+        use super::super::Error;
+        use std::convert::TryFrom;
 
-    #[test]
-    fn parse_iso_ymd() {
-        let date_option = LocalDate::from_str("2015-06-26");
-        assert!(date_option.is_ok());
-        let date = date_option.unwrap();
-        assert!(date.year() == 2015);
-        assert!(date.month() == Month::June);
-        assert!(date.day() == 26);
-    }
+        #[test]
+        fn months_round_trip() {
+            for n in 1 .. 13u8 {
+                assert_eq!(u8::from(Month::try_from(n).unwrap()), n);
+                assert_eq!(Month::from_zero(Month::try_from(n).unwrap().to_zero()), Month::try_from(n));
+            }
+        }
 
-    #[test]
-    fn new() {
-        for year in 1..3000 {
-            assert!(LocalDate::ymd(year, Month::from_one( 1).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 2).unwrap(), 30).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 3).unwrap(), 32).is_err());
-            assert!(LocalDate::ymd(year, Month::from_one( 4).unwrap(), 31).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 5).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 6).unwrap(), 31).is_err());
-            assert!(LocalDate::ymd(year, Month::from_one( 7).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 8).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one( 9).unwrap(), 31).is_err());
-            assert!(LocalDate::ymd(year, Month::from_one(10).unwrap(), 32).is_err()); assert!(LocalDate::ymd(year, Month::from_one(11).unwrap(), 31).is_err()); assert!(LocalDate::ymd(year, Month::from_one(12).unwrap(), 32).is_err());
+        #[test]
+        fn weekdays_round_trip() {
+            for n in 1 .. 8i8 {
+                assert_eq!(i8::from(Weekday::try_from(n).unwrap()), n);
+                assert_eq!(Weekday::from_zero(Weekday::try_from(n).unwrap().to_zero()), Weekday::try_from(n));
+            }
         }
-    }
 
-    #[test]
-    fn to_from_days_since_epoch() {
-        let epoch_difference: i64 = 30 * 365 + 7 + 31 + 29;  // see EPOCH_DIFFERENCE
-        for date in  vec![
-            LocalDate::ymd(1970, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(  01, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1971, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1973, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1977, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1989, Month::from_one(11).unwrap(), 10).unwrap(),
-            LocalDate::ymd(1990, Month::from_one( 7).unwrap(),  8).unwrap(),
-            LocalDate::ymd(2014, Month::from_one( 7).unwrap(), 13).unwrap(),
-            LocalDate::ymd(2001, Month::from_one( 2).unwrap(), 03).unwrap()
-        ]{
-            assert_eq!( date,
-                LocalDate::from_days_since_epoch(
-                    date.ymd.to_days_since_epoch().unwrap() - epoch_difference));
+        #[test]
+        fn out_of_range() {
+            assert_eq!(Month::try_from(0u8), Err(Error::MonthOutOfRange { given: 0 }));
+            assert_eq!(Month::try_from(200u8), Err(Error::OutOfRange));
+            assert_eq!(Weekday::try_from(0i8), Err(Error::WeekdayOutOfRange { given: 0 }));
+            assert_eq!(Weekday::try_from(8u8), Err(Error::WeekdayOutOfRange { given: 8 }));
         }
     }
 
-    #[test]
-    fn from_yearday() {
-        for date in vec![
-            //LocalDate::ymd(1970, 01 , 01).unwrap(),
-            LocalDate::ymd(1971, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1973, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1977, Month::from_one(01).unwrap(), 01).unwrap(),
-            LocalDate::ymd(1989, Month::from_one(11).unwrap(), 10).unwrap(),
-            LocalDate::ymd(1990, Month::from_one( 7).unwrap(),  8).unwrap(),
-            LocalDate::ymd(2014, Month::from_one( 7).unwrap(), 13).unwrap(),
-            LocalDate::ymd(2001, Month::from_one( 2).unwrap(), 03).unwrap(),
-        ]{
-            let new_date = LocalDate::yd(date.year(), date.yearday() as i64).unwrap();
-            assert_eq!(new_date, date);
-            assert!(LocalDate::yd(2002, 1).is_ok());
+    mod builder {
+        use super::*;
+        use super::super::Error;
 
-            assert_eq!(new_date.yearday(), date.yearday());
+        #[test]
+        fn defaults_to_the_epoch() {
+            assert_eq!(LocalDateTime::builder().build(), Ok(LocalDateTime::at(0)));
         }
-    }
 
-    #[test]
-    fn yearday() {
-        for year in 1..2058 {
-            assert_eq!( LocalDate::ymd(year, Month::from_one(01).unwrap(), 31).unwrap().yearday() + 1,
-                        LocalDate::ymd(year, Month::from_one(02).unwrap(), 01).unwrap().yearday());
-            assert_eq!( LocalDate::ymd(year, Month::from_one(03).unwrap(), 31).unwrap().yearday() + 1,
-                        LocalDate::ymd(year, Month::from_one(04).unwrap(), 01).unwrap().yearday());
-            assert_eq!( LocalDate::ymd(year, Month::from_one(04).unwrap(), 30).unwrap().yearday() + 1,
-                        LocalDate::ymd(year, Month::from_one(05).unwrap(), 01).unwrap().yearday());
-            assert!(    LocalDate::ymd(year, Month::from_one(12).unwrap(), 31).unwrap().yearday() > 0);
+        #[test]
+        fn every_field() {
+            let datetime = LocalDateTime::builder().year(2015).month(Month::June).day(26)
+                                                   .hour(14).minute(30).second(15).nanosecond(123_456_789)
+                                                   .build();
+            let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            let time = LocalTime::hms_ns(14, 30, 15, 123_456_789).unwrap();
+            assert_eq!(datetime, Ok(LocalDateTime::new(date, time)));
         }
-        assert_eq!( LocalDate::ymd(1600, Month::from_one(02).unwrap(), 29).unwrap().yearday() + 1, // leap year
-                    LocalDate::ymd(1600, Month::from_one(03).unwrap(), 01).unwrap().yearday());
-        assert_eq!( LocalDate::ymd(1601, Month::from_one(02).unwrap(), 28).unwrap().yearday() + 1, // no leap year
-                    LocalDate::ymd(1601, Month::from_one(03).unwrap(), 01).unwrap().yearday());
 
-    }
+        #[test]
+        fn milliseconds() {
+            let datetime = LocalDateTime::builder().nanosecond(5).millisecond(250).build();
+            assert_eq!(datetime, Ok(LocalDateTime::at_ms(0, 250)));
+        }
 
-    #[test]
-    fn parse_month() {
-        assert_eq!( LocalDate::from_str("2015-01-26").unwrap().month(), Month::January);
-        assert_eq!( LocalDate::from_str("1970-01-26").unwrap().month(), Month::January);
-        assert_eq!( LocalDate::from_str("1969-01-26").unwrap().month(), Month::January);
+        #[test]
+        fn invalid_fields() {
+            assert_eq!(LocalDateTime::builder().day(32).build(), Err(Error::DayOutOfRange { given: 32, max: 31 }));
+            assert_eq!(LocalDateTime::builder().hour(24).build(), Err(Error::HourOutOfRange { given: 24 }));
+            assert_eq!(LocalDateTime::builder().millisecond(1000).build(), Err(Error::NanosecondOutOfRange { given: 1_000_000_000 }));
+        }
+
+        #[test]
+        fn past_the_last_second() {
+            let builder = LocalDateTime::builder().year(292_277_026_596).month(Month::December).day(4);
+            assert!(builder.hour(15).build().is_ok());
+            assert_eq!(builder.hour(16).build(), Err(Error::OutOfRange));
+        }
     }
 
-    #[test]
-    fn leap_year_2000() {
-        let date = YMD { year: 2000, month: Month::January, day: 1 };
-        assert!(date.leap_year_calculations().1 == true)
+    mod defaults {
+        use super::*;
+
+        #[test]
+        fn the_epoch() {
+            assert_eq!(LocalDate::default(), LocalDate::ymd(1970, Month::January, 1).unwrap());
+            assert_eq!(LocalTime::default(), LocalTime::midnight());
+            assert_eq!(LocalDateTime::default(), LocalDateTime::at(0));
+            assert_eq!(LocalDate::default().weekday(), Weekday::Thursday);
+        }
     }
 
-    mod datetimes_to_seconds {
-        pub use super::*;
+    mod errors {
+        use super::*;
+        use super::super::Error;
 
         #[test]
-        fn test_1970() {
-            let date = LocalDateTime::at(0);
-            let res = date.to_instant().seconds();
+        fn day_past_end_of_month() {
+            assert_eq!(LocalDate::ymd(2015, Month::January, 32), Err(Error::DayOutOfRange { given: 32, max: 31 }));
+            assert_eq!(LocalDate::ymd(2015, Month::February, 29), Err(Error::DayOutOfRange { given: 29, max: 28 }));
+            assert_eq!(LocalDate::ymd(2016, Month::February, 0), Err(Error::DayOutOfRange { given: 0, max: 29 }));
+        }
 
-            assert_eq!(res, 0)
+        #[test]
+        fn yearday() {
+            assert_eq!(LocalDate::yd(2015, 367), Err(Error::YeardayOutOfRange { given: 367 }));
         }
 
         #[test]
-        fn test_1971() {
-            let date = LocalDateTime::at(86400);
-            let res = date.to_instant().seconds();
+        fn months_and_weekdays() {
+            assert_eq!(Month::from_one(13), Err(Error::MonthOutOfRange { given: 13 }));
+            assert_eq!(Month::from_zero(-1), Err(Error::MonthOutOfRange { given: -1 }));
+            assert_eq!(Weekday::from_one(0), Err(Error::WeekdayOutOfRange { given: 0 }));
+        }
 
-            assert_eq!(res, 86400)
+        #[test]
+        fn time_fields() {
+            assert_eq!(LocalTime::hm(25, 0), Err(Error::HourOutOfRange { given: 25 }));
+            assert_eq!(LocalTime::hm(24, 30), Err(Error::HourOutOfRange { given: 24 }));
+            assert_eq!(LocalTime::hms(12, 60, 0), Err(Error::MinuteOutOfRange { given: 60 }));
+            assert_eq!(LocalTime::hms(12, 30, -1), Err(Error::SecondOutOfRange { given: -1 }));
+            assert_eq!(LocalTime::hms(12, 30, 60), Err(Error::SecondOutOfRange { given: 60 }));
+            assert_eq!(LocalTime::hms(12, 30, 61), Err(Error::SecondOutOfRange { given: 61 }));
+            assert_eq!(LocalTime::hms_ms(12, 30, 0, 1000), Err(Error::MillisecondOutOfRange { given: 1000 }));
+            assert_eq!(LocalTime::hms_ns(12, 30, 0, -1), Err(Error::NanosecondOutOfRange { given: -1 }));
         }
 
         #[test]
-        fn test_1972() {
-            let date = LocalDateTime::at(86400 * 365 * 2);
-            let res = date.to_instant().seconds();
+        fn first_bad_field_is_reported() {
+            assert_eq!(LocalTime::hms(24, 60, 60), Err(Error::HourOutOfRange { given: 24 }));
+        }
 
-            assert_eq!(0, 86400 * 365 * 2 - res)
+        #[test]
+        fn display() {
+            assert_eq!(Error::DayOutOfRange { given: 32, max: 31 }.to_string(), "day out of range (32, but the month has 31 days)");
+            assert_eq!(Error::HourOutOfRange { given: 25 }.to_string(), "hour out of range (25)");
         }
+    }
+
+    mod proleptic {
+        use super::*;
 
         #[test]
-        fn test_1973() {
-            let date = LocalDateTime::at(86400 * (365 * 3 + 1));
-            let res = date.to_instant().seconds();
+        fn year_zero_is_a_leap_year() {
+            assert!(LocalDate::ymd(0, Month::February, 29).is_ok());
+            assert!(LocalDate::ymd(-100, Month::February, 29).is_err());
+            assert!(LocalDate::ymd(-400, Month::February, 29).is_ok());
+        }
 
-            assert_eq!(0, 86400 * (365 * 3 + 1) - res)
+        #[test]
+        fn display() {
+            assert_eq!(LocalDate::ymd(-43, Month::March, 15).unwrap().to_string(), "-0043-03-15");
+            assert_eq!(LocalDate::ymd(0, Month::January, 1).unwrap().to_string(), "0000-01-01");
+            assert_eq!(LocalDate::ymd(-4713, Month::November, 24).unwrap().to_string(), "-4713-11-24");
+            assert_eq!(LocalDate::ymd(-12345, Month::March, 15).unwrap().to_string(), "-12345-03-15");
+            assert_eq!(LocalDate::ymd(9999, Month::December, 31).unwrap().to_string(), "9999-12-31");
+            assert_eq!(LocalDate::ymd(12345, Month::March, 15).unwrap().to_string(), "+12345-03-15");
         }
 
         #[test]
-        fn some_date() {
-            let date = LocalDateTime::at(1234567890);
-            let res = date.to_instant().seconds();
+        fn parse_round_trip() {
+            let date = LocalDate::ymd(-43, Month::March, 15).unwrap();
+            assert_eq!(LocalDate::from_str("-0043-03-15"), Ok(date));
 
-            assert_eq!(1234567890, res)
+            let datetime = LocalDateTime::new(date, LocalTime::hm(12, 0).unwrap());
+            assert_eq!(LocalDateTime::from_str(&datetime.to_string()), Ok(datetime));
         }
 
         #[test]
-        fn far_far_future() {
-            let date = LocalDateTime::at(54321234567890);
-            let res = date.to_instant().seconds();
+        fn expanded_years() {
+            use cal::parse::Error as ParseError;
+            use super::super::Error;
+
+            let date = LocalDate::ymd(12345, Month::March, 15).unwrap();
+            assert_eq!(LocalDate::from_str("+12345-03-15"), Ok(date));
+            assert_eq!(LocalDate::from_str("+12345-W11-4"), Ok(date));
+            assert_eq!(LocalDate::from_str("+12345-074"), Ok(date));
+            assert_eq!(LocalDate::from_str("-12345-03-15"), Ok(LocalDate::ymd(-12345, Month::March, 15).unwrap()));
+            assert_eq!(LocalDate::from_str("+00002-01-01"), Ok(LocalDate::ymd(2, Month::January, 1).unwrap()));
+            assert_eq!(LocalDate::from_str("+12345-02-29"), Err(ParseError::Date(Error::DayOutOfRange { given: 29, max: 28 })));
+            assert_eq!(LocalDate::from_str("+99999999999999999999-01-01"), Err(ParseError::Date(Error::YearOutOfRange { given: i64::max_value() })));
+        }
 
-            assert_eq!(54321234567890, res)
+        #[test]
+        fn extremes_round_trip() {
+            for &date in &[ LocalDate::MIN, LocalDate::MAX ] {
+                assert_eq!(LocalDate::from_str(&date.to_string()), Ok(date));
+            }
+
+            for &datetime in &[ LocalDateTime::MIN, LocalDateTime::MAX ] {
+                assert_eq!(LocalDateTime::from_str(&datetime.to_string()), Ok(datetime));
+            }
         }
 
         #[test]
-        fn the_distant_past() {
-            let date = LocalDateTime::at(-54321234567890);
-            let res = date.to_instant().seconds();
+        fn across_year_zero() {
+            let date = LocalDate::ymd(1, Month::January, 1).unwrap();
+            let datetime = LocalDateTime::new(date, LocalTime::midnight()).add_seconds(-1);
+            assert_eq!(datetime.to_string(), "0000-12-31T23:59:59");
+            assert_eq!(datetime.date().weekday(), Weekday::Sunday);
+        }
 
-            assert_eq!(-54321234567890, res)
+        #[test]
+        fn week_dates() {
+            // The 1st of January in 2 BCE was a Friday, so the first ISO
+            // week of the year starts on the following Monday.
+            assert_eq!(LocalDate::ywd(-1, 1, Weekday::Monday), LocalDate::ymd(-1, Month::January, 4));
         }
     }
 
@@ -1240,5 +4153,85 @@ mod test {
             let date = LocalDateTime::at(100000000);
             assert_eq!(LocalDateTime::at(99999999), date - Duration::of(1))
         }
+
+        #[test]
+        fn keeps_nanoseconds() {
+            let date = LocalDateTime::at_ns(10000, 123_456_789);
+            assert_eq!(LocalDateTime::at_ns(10001, 123_456_789), date + Duration::of(1))
+        }
+    }
+
+    mod nanoseconds {
+        use super::*;
+        use cal::TimePiece;
+
+        #[test]
+        fn derived_views() {
+            let time = LocalTime::hms_ns(1, 2, 3, 456_789_012).unwrap();
+            assert_eq!(time.millisecond(), 456);
+            assert_eq!(time.microsecond(), 456_789);
+            assert_eq!(time.nanosecond(), 456_789_012);
+        }
+
+        #[test]
+        fn from_milliseconds() {
+            let time = LocalTime::hms_ms(1, 2, 3, 456).unwrap();
+            assert_eq!(time.nanosecond(), 456_000_000);
+            assert_eq!(time, LocalTime::hms_ns(1, 2, 3, 456_000_000).unwrap());
+        }
+
+        #[test]
+        fn out_of_range() {
+            assert!(LocalTime::hms_ns(1, 2, 3, -1).is_err());
+            assert!(LocalTime::hms_ns(1, 2, 3, 1_000_000_000).is_err());
+        }
+
+        #[test]
+        fn instant_round_trip() {
+            use duration::Duration;
+            use instant::Instant;
+
+            let date = LocalDateTime::at_ns(-1_234_567_890, 987_654_321);
+            assert_eq!(date.to_instant(), Instant::at_ns(-1_234_567_890, 987_654_321));
+            assert_eq!(LocalDateTime::from_instant(date.to_instant()), date);
+            assert_eq!(date - LocalDateTime::at(-1_234_567_890), Duration::of_ns(0, 987_654_321));
+        }
+
+        #[test]
+        fn system_time_round_trip() {
+            use std::time::SystemTime;
+
+            let date = LocalDateTime::at_ns(-1_234_567_890, 987_654_321);
+            assert_eq!(LocalDateTime::from(SystemTime::from(date)), date);
+        }
+
+        #[test]
+        fn datetime_round_trip() {
+            let date = LocalDateTime::at_ns(1_234_567_890, 987_654_321);
+            assert_eq!(date.second(), 30);
+            assert_eq!(date.nanosecond(), 987_654_321);
+            assert_eq!(date.to_instant().milliseconds(), 987);
+        }
+
+        #[test]
+        fn negative_nanoseconds_carry() {
+            let date = LocalDateTime::at_ns(0, -5);
+            assert_eq!(date, LocalDateTime::at_ns(-1, 999_999_995));
+            assert_eq!(date.date(), LocalDate::ymd(1969, Month::December, 31).unwrap());
+            assert_eq!(date.time(), LocalTime::hms_ns(23, 59, 59, 999_999_995).unwrap());
+        }
+
+        #[test]
+        fn whole_seconds_of_nanoseconds_carry() {
+            assert_eq!(LocalDateTime::at_ns(0, 2_000_000_000), LocalDateTime::at(2));
+            assert_eq!(LocalDateTime::at_ns(86399, 1_500_000_000), LocalDateTime::at_ns(86400, 500_000_000));
+            assert_eq!(LocalDateTime::at_ms(10, -250), LocalDateTime::at_ns(9, 750_000_000));
+        }
+
+        #[test]
+        fn time_nanoseconds_carry() {
+            assert_eq!(LocalTime::from_seconds_and_nanoseconds_since_midnight(5, -5), LocalTime::hms_ns(0, 0, 4, 999_999_995).unwrap());
+            assert_eq!(LocalTime::from_seconds_and_nanoseconds_since_midnight(59, 1_000_000_001), LocalTime::hms_ns(0, 1, 0, 1).unwrap());
+        }
     }
 }
@@ -0,0 +1,239 @@
+//! The date of Easter, and the feasts whose dates depend on it.
+//!
+//! Easter falls on the first Sunday after the first ecclesiastical full
+//! moon on or after the 21st of March, where the full moon comes from a
+//! table rather than from astronomy. Western churches work this out using
+//! the Gregorian calendar, and most Eastern Orthodox churches still use the
+//! Julian one, so the two usually fall on different days.
+//!
+//! Many public holidays are a fixed number of days away from Easter, so
+//! `MovableFeast` gives those too:
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month};
+//! use datetime::easter::{self, MovableFeast};
+//!
+//! let easter = easter::gregorian(2024);
+//! assert_eq!(easter, LocalDate::ymd(2024, Month::March, 31).unwrap());
+//! assert_eq!(MovableFeast::GoodFriday.date(easter), LocalDate::ymd(2024, Month::March, 29).unwrap());
+//! ```
+
+use cal::calendar::{CalendarSystem, Julian};
+use cal::datetime::{LocalDate, Month};
+use cal::iter::MonthDay;
+
+
+/// Returns the date of Easter Sunday in the given year, as celebrated by
+/// Western churches, using the anonymous Gregorian algorithm.
+///
+/// The Gregorian calendar was only introduced in 1583, but this works for
+/// any year, like the rest of this library does.
+///
+/// ### Panics
+///
+/// Panics if the year is outside the range of dates that can be
+/// represented. Use `checked_gregorian` to handle that case instead.
+pub fn gregorian(year: i64) -> LocalDate {
+    checked_gregorian(year).expect("year out of range")
+}
+
+/// Returns the date of Easter Sunday in the given year, as celebrated by
+/// Western churches, or `None` if it falls outside the range of dates that
+/// can be represented.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::easter;
+///
+/// assert_eq!(easter::checked_gregorian(2024), LocalDate::ymd(2024, Month::March, 31).ok());
+/// assert_eq!(easter::checked_gregorian(i64::max_value()), None);
+/// ```
+pub fn checked_gregorian(year: i64) -> Option<LocalDate> {
+    let golden = year.rem_euclid(19);
+    let century = year.div_euclid(100);
+    let year_of_century = year.rem_euclid(100);
+
+    let skipped_leap_days = century.div_euclid(4);
+    let lunar_correction = (century - (century + 8).div_euclid(25) + 1).div_euclid(3);
+    let epact = (19 * golden + century - skipped_leap_days - lunar_correction + 15).rem_euclid(30);
+    let weekday = (32 + 2 * century.rem_euclid(4) + 2 * (year_of_century / 4) - epact - year_of_century % 4).rem_euclid(7);
+    let adjustment = (golden + 11 * epact + 22 * weekday) / 451;
+
+    let days = epact + weekday - 7 * adjustment + 114;
+    date(year, days / 31, days % 31 + 1)
+}
+
+/// Returns the month and day of Easter Sunday in the given year in the
+/// Julian calendar, using Meeus’s Julian algorithm.
+///
+/// This is the date as written in the Julian calendar; use `orthodox` for
+/// the `LocalDate` it falls on.
+pub fn julian(year: i64) -> MonthDay {
+    let epact = (19 * year.rem_euclid(19) + 15) % 30;
+    let weekday = (2 * year.rem_euclid(4) + 4 * year.rem_euclid(7) - epact + 34).rem_euclid(7);
+
+    let days = epact + weekday + 114;
+    let month = Month::from_one((days / 31) as i8).unwrap();
+    MonthDay::new(month, (days % 31 + 1) as i8).unwrap()
+}
+
+/// Returns the date of Easter Sunday in the given year, as celebrated by
+/// the Eastern Orthodox churches that use the Julian calendar, converted
+/// to the Gregorian calendar.
+///
+/// ### Panics
+///
+/// Panics if the year is outside the range of dates that can be
+/// represented. Use `checked_orthodox` to handle that case instead.
+pub fn orthodox(year: i64) -> LocalDate {
+    checked_orthodox(year).expect("year out of range")
+}
+
+/// Returns the date of Easter Sunday in the given year, as celebrated by
+/// the Eastern Orthodox churches, or `None` if it falls outside the range
+/// of dates that can be represented.
+pub fn checked_orthodox(year: i64) -> Option<LocalDate> {
+    let easter = julian(year);
+    Julian.from_ymd(year, easter.month().to_one(), easter.day()).ok()
+}
+
+/// Returns the date with the given month and day, which are known to be
+/// valid, in the given year, if the year is in range.
+fn date(year: i64, month: i64, day: i64) -> Option<LocalDate> {
+    let month = Month::from_one(month as i8).unwrap();
+    LocalDate::ymd(year, month, day as i8).ok()
+}
+
+
+/// A feast whose date is a fixed number of days from Easter Sunday.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum MovableFeast {
+
+    /// Shrove Tuesday, or Mardi Gras, the day before Lent starts.
+    ShroveTuesday,
+
+    /// Ash Wednesday, the first day of Lent.
+    AshWednesday,
+
+    /// Palm Sunday, the Sunday before Easter.
+    PalmSunday,
+
+    /// Maundy Thursday, or Holy Thursday.
+    MaundyThursday,
+
+    /// Good Friday.
+    GoodFriday,
+
+    /// Holy Saturday, or Easter Eve.
+    HolySaturday,
+
+    /// Easter Sunday itself.
+    EasterSunday,
+
+    /// Easter Monday.
+    EasterMonday,
+
+    /// Ascension Day, the Thursday forty days after Easter, counting
+    /// Easter itself.
+    Ascension,
+
+    /// Pentecost, or Whit Sunday, seven weeks after Easter.
+    Pentecost,
+
+    /// Whit Monday, the day after Pentecost.
+    WhitMonday,
+
+    /// Trinity Sunday, the Sunday after Pentecost.
+    TrinitySunday,
+
+    /// Corpus Christi, the Thursday after Trinity Sunday.
+    CorpusChristi,
+}
+
+impl MovableFeast {
+
+    /// Returns the number of days after Easter Sunday that this feast
+    /// falls on, which is negative for the ones before it.
+    pub const fn days_after_easter(self) -> i64 {
+        match self {
+            MovableFeast::ShroveTuesday   => -47,
+            MovableFeast::AshWednesday    => -46,
+            MovableFeast::PalmSunday      => -7,
+            MovableFeast::MaundyThursday  => -3,
+            MovableFeast::GoodFriday      => -2,
+            MovableFeast::HolySaturday    => -1,
+            MovableFeast::EasterSunday    => 0,
+            MovableFeast::EasterMonday    => 1,
+            MovableFeast::Ascension       => 39,
+            MovableFeast::Pentecost       => 49,
+            MovableFeast::WhitMonday      => 50,
+            MovableFeast::TrinitySunday   => 56,
+            MovableFeast::CorpusChristi   => 60,
+        }
+    }
+
+    /// Returns the date of this feast in the year that Easter Sunday falls
+    /// on the given date, whichever way that was worked out.
+    pub fn date(self, easter: LocalDate) -> LocalDate {
+        LocalDate::from_julian_day(easter.julian_day() + self.days_after_easter())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::DatePiece;
+    pub use cal::datetime::Weekday;
+
+    #[test]
+    fn western() {
+        assert_eq!(gregorian(1818), LocalDate::ymd(1818, Month::March, 22).unwrap());
+        assert_eq!(gregorian(1943), LocalDate::ymd(1943, Month::April, 25).unwrap());
+        assert_eq!(gregorian(2000), LocalDate::ymd(2000, Month::April, 23).unwrap());
+        assert_eq!(gregorian(2019), LocalDate::ymd(2019, Month::April, 21).unwrap());
+        assert_eq!(gregorian(2024), LocalDate::ymd(2024, Month::March, 31).unwrap());
+        assert_eq!(gregorian(2025), LocalDate::ymd(2025, Month::April, 20).unwrap());
+        assert_eq!(gregorian(2285), LocalDate::ymd(2285, Month::March, 22).unwrap());
+    }
+
+    #[test]
+    fn eastern() {
+        assert_eq!(julian(2024), MonthDay::new(Month::April, 22).unwrap());
+        assert_eq!(orthodox(2019), LocalDate::ymd(2019, Month::April, 28).unwrap());
+        assert_eq!(orthodox(2023), LocalDate::ymd(2023, Month::April, 16).unwrap());
+        assert_eq!(orthodox(2024), LocalDate::ymd(2024, Month::May, 5).unwrap());
+        assert_eq!(orthodox(2025), LocalDate::ymd(2025, Month::April, 20).unwrap());
+    }
+
+    #[test]
+    fn always_a_sunday() {
+        for year in -1000 .. 3000 {
+            assert_eq!(gregorian(year).weekday(), Weekday::Sunday, "in {}", year);
+            assert_eq!(orthodox(year).weekday(), Weekday::Sunday, "in {}", year);
+
+            let easter = gregorian(year);
+            assert!(easter >= LocalDate::ymd(year, Month::March, 22).unwrap() && easter <= LocalDate::ymd(year, Month::April, 25).unwrap(), "in {}", year);
+        }
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(checked_gregorian(LocalDate::MAX.year() + 1), None);
+        assert_eq!(checked_orthodox(LocalDate::MIN.year() - 1), None);
+        assert_eq!(checked_gregorian(i64::min_value()), None);
+        assert_eq!(checked_orthodox(i64::max_value()), None);
+    }
+
+    #[test]
+    fn movable_feasts() {
+        let easter = gregorian(2024);
+        assert_eq!(MovableFeast::AshWednesday.date(easter), LocalDate::ymd(2024, Month::February, 14).unwrap());
+        assert_eq!(MovableFeast::EasterSunday.date(easter), easter);
+        assert_eq!(MovableFeast::Ascension.date(easter), LocalDate::ymd(2024, Month::May, 9).unwrap());
+        assert_eq!(MovableFeast::WhitMonday.date(easter), LocalDate::ymd(2024, Month::May, 20).unwrap());
+        assert_eq!(MovableFeast::CorpusChristi.date(easter), LocalDate::ymd(2024, Month::May, 30).unwrap());
+    }
+}
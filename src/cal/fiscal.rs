@@ -0,0 +1,299 @@
+//! Fiscal years, and the quarters and periods they’re split into.
+//!
+//! Businesses and governments often keep their accounts in years that
+//! don’t start in January. A `FiscalCalendar` is configured with the month
+//! its years start in, and works out which fiscal year, quarter, and
+//! period a date falls in:
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month, FiscalCalendar};
+//!
+//! // The United States federal government’s fiscal years start in October.
+//! let calendar = FiscalCalendar::starting(Month::October);
+//! let date = calendar.fiscal_date(LocalDate::ymd(2024, Month::November, 5).unwrap());
+//! assert_eq!((date.year(), date.quarter(), date.period()), (2025, 1, 2));
+//! ```
+//!
+//! Fiscal years are numbered by the calendar year they end in, so the year
+//! from October 2024 to September 2025 is fiscal year 2025.
+//!
+//! Retailers often use years made up of whole weeks instead, which always
+//! end on the same day of the week, so that each period has the same
+//! number of weekends in it. Each quarter is split into periods of four,
+//! four, and five weeks, or some other order of them, and the extra week
+//! that comes around every five or six years goes in the last period. Use
+//! `FiscalCalendar::with_weeks` for these.
+
+use cal::DatePiece;
+use cal::datetime::{LocalDate, Month, Weekday};
+
+
+/// A way of dividing up fiscal years into periods.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct FiscalCalendar {
+
+    /// The month that each fiscal year starts in.
+    start: Month,
+
+    /// The day of the week that each fiscal year ends on, and how many
+    /// weeks go in each period, for calendars made up of whole weeks.
+    weeks: Option<(Weekday, WeekPattern)>,
+}
+
+/// The number of weeks in each of the three periods of a quarter, in a
+/// fiscal calendar made up of whole weeks.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum WeekPattern {
+
+    /// Four weeks, then four weeks, then five weeks.
+    FourFourFive,
+
+    /// Four weeks, then five weeks, then four weeks.
+    FourFiveFour,
+
+    /// Five weeks, then four weeks, then four weeks.
+    FiveFourFour,
+}
+
+impl WeekPattern {
+
+    /// Returns the number of weeks in each period of a quarter, in order.
+    pub fn weeks(self) -> [i64; 3] {
+        match self {
+            WeekPattern::FourFourFive  => [ 4, 4, 5 ],
+            WeekPattern::FourFiveFour  => [ 4, 5, 4 ],
+            WeekPattern::FiveFourFour  => [ 5, 4, 4 ],
+        }
+    }
+}
+
+impl FiscalCalendar {
+
+    /// Creates a fiscal calendar with years that start on the 1st of the
+    /// given month, split into one period for each month.
+    pub const fn starting(month: Month) -> FiscalCalendar {
+        FiscalCalendar { start: month, weeks: None }
+    }
+
+    /// Returns this calendar changed to use years made up of whole weeks,
+    /// each ending on the last given weekday of the month before the one
+    /// the years start in, and split into periods using the given pattern.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month, Weekday, FiscalCalendar, WeekPattern};
+    ///
+    /// // Years ending on the last Saturday in January.
+    /// let calendar = FiscalCalendar::starting(Month::February).with_weeks(Weekday::Saturday, WeekPattern::FourFourFive);
+    /// assert_eq!(calendar.start_of_year(2026), LocalDate::ymd(2025, Month::January, 26).unwrap());
+    /// assert_eq!(calendar.weeks_in_year(2026), Some(53));
+    /// ```
+    pub const fn with_weeks(self, ends_on: Weekday, pattern: WeekPattern) -> FiscalCalendar {
+        FiscalCalendar { start: self.start, weeks: Some((ends_on, pattern)) }
+    }
+
+    /// Returns the month that this calendar’s years start in.
+    pub fn start_month(&self) -> Month {
+        self.start
+    }
+
+    /// Returns the first day of the given fiscal year.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the year is outside the range of dates that can be
+    /// represented.
+    pub fn start_of_year(&self, year: i64) -> LocalDate {
+        add_days(self.end_of_year(year - 1), 1)
+    }
+
+    /// Returns the last day of the given fiscal year.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the year is outside the range of dates that can be
+    /// represented.
+    pub fn end_of_year(&self, year: i64) -> LocalDate {
+        // Whichever month the years start in, the month before it is in the
+        // calendar year the fiscal year is numbered after.
+        let month = self.start.previous();
+        let last = LocalDate::ymd(year, month, month.days_in(year)).expect("year out of range");
+
+        match self.weeks {
+            None               => last,
+            Some((ends_on, _)) => add_days(last, -(ends_on.days_until(last.weekday()) as i64)),
+        }
+    }
+
+    /// Returns the number of weeks in the given fiscal year, which is 52 or
+    /// 53, or `None` if this calendar’s years aren’t made up of whole
+    /// weeks.
+    pub fn weeks_in_year(&self, year: i64) -> Option<i64> {
+        if self.weeks.is_none() {
+            return None;
+        }
+
+        Some((self.end_of_year(year).julian_day() - self.end_of_year(year - 1).julian_day()) / 7)
+    }
+
+    /// Returns the first day of the given period, from 1 to 12, of the
+    /// given fiscal year.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the period is out of range, or if the year is outside the
+    /// range of dates that can be represented.
+    pub fn start_of_period(&self, year: i64, period: i8) -> LocalDate {
+        assert!(period >= 1 && period <= 12, "period out of range");
+        let start = self.start_of_year(year);
+
+        match self.weeks {
+            None => {
+                let month = Month::from_one((self.start.months_from_january() as i8 + period - 1) % 12 + 1).unwrap();
+                LocalDate::ymd(start.year() + (month < self.start) as i64, month, 1).expect("year out of range")
+            },
+            Some((_, pattern)) => {
+                let weeks: i64 = (0 .. period as usize - 1).map(|p| pattern.weeks()[p % 3]).sum();
+                add_days(start, weeks * 7)
+            },
+        }
+    }
+
+    /// Returns the fiscal year, quarter, and period that the given date
+    /// falls in.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the date is so close to the edge of the range of dates
+    /// that can be represented that its fiscal year isn’t.
+    pub fn fiscal_date(&self, date: LocalDate) -> FiscalDate {
+        let year = if date > self.end_of_year(date.year()) { date.year() + 1 } else { date.year() };
+
+        let period = match self.weeks {
+            None => {
+                (date.month().months_from_january() as i8 - self.start.months_from_january() as i8).rem_euclid(12) + 1
+            },
+            Some((_, pattern)) => {
+                let mut week = (date.julian_day() - self.start_of_year(year).julian_day()) / 7;
+                let mut period = 1;
+                while period < 12 && week >= pattern.weeks()[(period as usize - 1) % 3] {
+                    week -= pattern.weeks()[(period as usize - 1) % 3];
+                    period += 1;
+                }
+                period
+            },
+        };
+
+        FiscalDate { year: year, period: period }
+    }
+}
+
+
+/// Where a date falls in a fiscal calendar.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct FiscalDate {
+    year: i64,
+    period: i8,
+}
+
+impl FiscalDate {
+
+    /// Returns the fiscal year, numbered by the calendar year it ends in.
+    pub fn year(&self) -> i64 {
+        self.year
+    }
+
+    /// Returns the quarter of the fiscal year, from 1 to 4.
+    pub fn quarter(&self) -> i8 {
+        (self.period - 1) / 3 + 1
+    }
+
+    /// Returns the period of the fiscal year, from 1 to 12.
+    pub fn period(&self) -> i8 {
+        self.period
+    }
+}
+
+
+fn add_days(date: LocalDate, days: i64) -> LocalDate {
+    LocalDate::from_julian_day(date.julian_day() + days)
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    fn lookup(calendar: FiscalCalendar, date: LocalDate) -> (i64, i8, i8) {
+        let date = calendar.fiscal_date(date);
+        (date.year(), date.quarter(), date.period())
+    }
+
+    #[test]
+    fn calendar_years() {
+        let calendar = FiscalCalendar::starting(Month::January);
+        assert_eq!(lookup(calendar, LocalDate::ymd(2024, Month::January, 1).unwrap()), (2024, 1, 1));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2024, Month::May, 5).unwrap()), (2024, 2, 5));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2024, Month::December, 31).unwrap()), (2024, 4, 12));
+        assert_eq!(calendar.start_of_year(2024), LocalDate::ymd(2024, Month::January, 1).unwrap());
+        assert_eq!(calendar.weeks_in_year(2024), None);
+    }
+
+    #[test]
+    fn october() {
+        let calendar = FiscalCalendar::starting(Month::October);
+        assert_eq!(lookup(calendar, LocalDate::ymd(2024, Month::September, 30).unwrap()), (2024, 4, 12));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2024, Month::October, 1).unwrap()), (2025, 1, 1));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2025, Month::January, 15).unwrap()), (2025, 2, 4));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2025, Month::September, 30).unwrap()), (2025, 4, 12));
+
+        assert_eq!(calendar.start_of_year(2025), LocalDate::ymd(2024, Month::October, 1).unwrap());
+        assert_eq!(calendar.end_of_year(2025), LocalDate::ymd(2025, Month::September, 30).unwrap());
+        assert_eq!(calendar.start_of_period(2025, 3), LocalDate::ymd(2024, Month::December, 1).unwrap());
+        assert_eq!(calendar.start_of_period(2025, 4), LocalDate::ymd(2025, Month::January, 1).unwrap());
+    }
+
+    #[test]
+    fn weeks() {
+        let calendar = FiscalCalendar::starting(Month::February).with_weeks(Weekday::Saturday, WeekPattern::FourFourFive);
+        assert_eq!(calendar.end_of_year(2024), LocalDate::ymd(2024, Month::January, 27).unwrap());
+        assert_eq!(calendar.start_of_year(2024), LocalDate::ymd(2023, Month::January, 29).unwrap());
+        assert_eq!(calendar.weeks_in_year(2024), Some(52));
+        assert_eq!(calendar.weeks_in_year(2026), Some(53));
+
+        assert_eq!(calendar.start_of_period(2026, 2), LocalDate::ymd(2025, Month::February, 23).unwrap());
+        assert_eq!(calendar.start_of_period(2026, 3), LocalDate::ymd(2025, Month::March, 23).unwrap());
+        assert_eq!(calendar.start_of_period(2026, 12), LocalDate::ymd(2025, Month::December, 21).unwrap());
+
+        assert_eq!(lookup(calendar, LocalDate::ymd(2025, Month::January, 25).unwrap()), (2025, 4, 12));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2025, Month::January, 26).unwrap()), (2026, 1, 1));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2025, Month::March, 22).unwrap()), (2026, 1, 2));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2025, Month::March, 23).unwrap()), (2026, 1, 3));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2026, Month::January, 31).unwrap()), (2026, 4, 12));
+        assert_eq!(lookup(calendar, LocalDate::ymd(2026, Month::February, 1).unwrap()), (2027, 1, 1));
+    }
+
+    #[test]
+    fn every_date_is_in_its_period() {
+        let calendars = [
+            FiscalCalendar::starting(Month::April),
+            FiscalCalendar::starting(Month::July).with_weeks(Weekday::Sunday, WeekPattern::FiveFourFour),
+        ];
+
+        for calendar in &calendars {
+            for day in LocalDate::ymd(2019, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2031, Month::January, 1).unwrap().julian_day() {
+                let date = LocalDate::from_julian_day(day);
+                let fiscal = calendar.fiscal_date(date);
+                assert!(calendar.start_of_period(fiscal.year(), fiscal.period()) <= date, "{:?}", date);
+
+                if fiscal.period() < 12 {
+                    assert!(calendar.start_of_period(fiscal.year(), fiscal.period() + 1) > date, "{:?}", date);
+                }
+                else {
+                    assert!(calendar.end_of_year(fiscal.year()) >= date, "{:?}", date);
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,24 @@
 //! Datetime-to-string routines.
 
-use num::Integer;
-
-use std::fmt::Display;
+use std::error::Error as ErrorTrait;
+use std::fmt;
 use std::io;
 use std::io::Write;
 use std::str::CharIndices;
 
 use cal::{DatePiece, TimePiece};
+use cal::calendar::{CalendarSystem, EraYear};
+use cal::datetime::{Era, EraNames, LocalDate, Month, MonthNames, WeekRule, Weekday};
 
 use locale;
 use pad::{PadStr, Alignment};
 
 
+/// Every two-digit number from `00` to `99`, so that integers can be
+/// written out two digits at a time.
+const DIGIT_PAIRS: &'static [u8; 200] = b"00010203040506070809101112131415161718192021222324252627282930313233343536373839404142434445464748495051525354555657585960616263646566676869707172737475767778798081828384858687888990919293949596979899";
+
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum Field<'a> {
     Literal(&'a str),
@@ -20,32 +26,222 @@ pub enum Field<'a> {
     Year(NumArguments),
     YearOfCentury(NumArguments),
 
+    /// The name of the month in the form used in a date, which is `{:M}`,
+    /// or `{_:M}` for its full name. This is the genitive form, in
+    /// languages whose month names have one.
     MonthName(bool, TextArguments),
 
+    /// The name of the month in the form used on its own, which is `{:L}`,
+    /// or `{_:L}` for its full name.
+    StandaloneMonthName(bool, TextArguments),
+
     Day(NumArguments),
     WeekdayName(bool, TextArguments),
 
+    /// The name of the era, which is `{:N}`. For calendars that number
+    /// years within eras, `{:N}` is written in the Latin alphabet, and
+    /// `{_:N}` in the calendar’s own script. For the others, it’s whether
+    /// the year is BC or AD, or whichever names were given for them.
+    EraName(bool, TextArguments),
+
+    /// The year in the era, which is `{:n}`. For calendars without eras of
+    /// their own, this is the year counting backwards from 1 BC for the
+    /// years before 1 AD.
+    EraYear(NumArguments),
+
+    /// The number of the week in its own calendar year, where any days
+    /// before week 1 are in week 0: `{:U}` uses weeks that start on Sunday,
+    /// and `{:W}` ones that start on Monday, as `strftime` does.
+    WeekOfYear(WeekRule, NumArguments),
+
+    /// The number of the week in the year it’s numbered in, which is
+    /// `{:V}` for ISO weeks.
+    WeekNumber(WeekRule, NumArguments),
+
+    /// The year that the week is numbered in, which is `{:G}` for ISO
+    /// weeks.
+    WeekYear(WeekRule, NumArguments),
+
     Hour(NumArguments),
     Minute(NumArguments),
     Second(NumArguments),
 }
 
+/// The year, month, and day of the value being formatted, in whichever
+/// calendar it’s being formatted in.
+struct CalendarFields<'c> {
+    year: i64,
+    month: i8,
+    day: i8,
+
+    /// The calendar’s own name for the month, if it has one.
+    month_name: Option<&'c str>,
+
+    /// The era and the year in it, if the calendar has eras.
+    era: Option<EraYear<'c>>,
+}
+
 impl<'a> Field<'a> {
-    fn format<T>(&self, when: &T, w: &mut Vec<u8>, locale: &locale::Time) -> io::Result<()> where T: DatePiece+TimePiece {
+    fn format<T>(&self, when: &T, fields: &CalendarFields, options: &FormatOptions, w: &mut Vec<u8>, locale: &locale::Time) -> io::Result<()> where T: DatePiece+TimePiece {
         match *self {
             Field::Literal(s)             => w.write_all(s.as_bytes()),
-            Field::Year(a)                => a.format(w, when.year()),
-            Field::YearOfCentury(a)       => a.format(w, when.year_of_century()),
-            Field::MonthName(true, a)     => a.format(w, &locale.long_month_name(when.month() as usize - 1)[..]),
-            Field::MonthName(false, a)    => a.format(w, &locale.short_month_name(when.month() as usize - 1)[..]),
-            Field::Day(a)                 => a.format(w, when.day()),
-            Field::WeekdayName(true, a)   => a.format(w, &locale.long_day_name(when.weekday() as usize)[..]),
-            Field::WeekdayName(false, a)  => a.format(w, &locale.short_day_name(when.weekday() as usize)[..]),
-            Field::Hour(a)                => a.format(w, when.hour()),
-            Field::Minute(a)              => a.format(w, when.minute()),
-            Field::Second(a)              => a.format(w, when.second()),
+            Field::Year(a)                => a.format(w, fields.year, &options.digits),
+            Field::YearOfCentury(a)       => a.format(w, fields.year % 100, &options.digits),
+            Field::MonthName(long, a)            => a.format(w, &month_name(fields, long, true, options, locale)),
+            Field::StandaloneMonthName(long, a)  => a.format(w, &month_name(fields, long, false, options, locale)),
+            Field::Day(a)                 => a.format(w, fields.day as i64, &options.digits),
+            Field::WeekdayName(true, a)   => a.format(w, &when.weekday().name(locale)[..]),
+            Field::WeekdayName(false, a)  => a.format(w, &when.weekday().short_name(locale)[..]),
+            Field::EraName(native, a)     => a.format(w, fields.era.map_or(Era::of_year(fields.year).name(&options.eras), |era| if native { era.native_name } else { era.name })),
+            Field::EraYear(a)             => a.format(w, fields.era.map_or(Era::year_of_era(fields.year), |era| era.year), &options.digits),
+            Field::WeekOfYear(rule, a)    => a.format(w, rule.week_of_year(when) as i64, &options.digits),
+            Field::WeekNumber(rule, a)    => a.format(w, when.week_in(rule) as i64, &options.digits),
+            Field::WeekYear(rule, a)      => a.format(w, when.week_year_in(rule), &options.digits),
+            Field::Hour(a)                => a.format(w, when.hour() as i64, &options.digits),
+            Field::Minute(a)              => a.format(w, when.minute() as i64, &options.digits),
+            Field::Second(a)              => a.format(w, when.second() as i64, &options.digits),
+        }
+    }
+}
+
+
+/// The era names, month names, and digits to use when formatting, which
+/// depend on the language that the text is in.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct FormatOptions<'a> {
+
+    /// The names of the eras, for the `{:N}` field in calendars without
+    /// eras of their own.
+    pub eras: EraNames<'a>,
+
+    /// The digits to write numbers with.
+    pub digits: Digits,
+
+    /// The names of the months, for languages where they change form
+    /// depending on how they’re used, and the ones in the locale can’t be
+    /// used. Months use the locale’s names when this is `None`.
+    pub month_names: Option<MonthNames<'a>>,
+}
+
+impl FormatOptions<'static> {
+
+    /// Returns the era names, month names, and digits usually used in the
+    /// language with the given ISO 639-1 code, such as `"ar"`, falling
+    /// back to the English era names, the locale’s month names, and ASCII
+    /// digits.
+    pub fn for_language(code: &str) -> FormatOptions<'static> {
+        FormatOptions {
+            eras:         EraNames::for_language(code).unwrap_or(EraNames::BC_AD),
+            digits:       Digits::for_language(code),
+            month_names:  MonthNames::for_language(code),
+        }
+    }
+}
+
+impl Default for FormatOptions<'static> {
+
+    /// Returns the English era names, the locale’s month names, and ASCII
+    /// digits.
+    fn default() -> FormatOptions<'static> {
+        FormatOptions { eras: EraNames::BC_AD, digits: Digits::ASCII, month_names: None }
+    }
+}
+
+
+/// The ten characters to write the digits from 0 to 9 with.
+///
+/// Most scripts that have their own digits have them one after the other
+/// in Unicode, so `Digits::from_zero` can make a set from just the zero,
+/// but any ten characters can be used.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Digits {
+
+    /// The characters for each digit, starting from 0.
+    pub chars: [char; 10],
+}
+
+impl Digits {
+
+    /// The ASCII digits, which are the ones used by default.
+    pub const ASCII: Digits = Digits::from_zero('0');
+
+    /// The Arabic-Indic digits, ٠ to ٩, used with Arabic in much of the
+    /// Middle East.
+    pub const ARABIC_INDIC: Digits = Digits::from_zero('\u{0660}');
+
+    /// The Extended Arabic-Indic digits, ۰ to ۹, used with Persian and Urdu.
+    pub const EXTENDED_ARABIC_INDIC: Digits = Digits::from_zero('\u{06F0}');
+
+    /// The Devanagari digits, ० to ९, used with Hindi, Marathi, and Nepali.
+    pub const DEVANAGARI: Digits = Digits::from_zero('\u{0966}');
+
+    /// The Bengali digits, ০ to ৯.
+    pub const BENGALI: Digits = Digits::from_zero('\u{09E6}');
+
+    /// The Thai digits, ๐ to ๙.
+    pub const THAI: Digits = Digits::from_zero('\u{0E50}');
+
+    /// The full-width digits, ０ to ９, used alongside Chinese and Japanese
+    /// text.
+    pub const FULLWIDTH: Digits = Digits::from_zero('\u{FF10}');
+
+    /// Creates a set of digits from the given zero and the nine characters
+    /// that come after it.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if there aren’t nine valid characters after the zero.
+    pub const fn from_zero(zero: char) -> Digits {
+        let mut chars = [zero; 10];
+        let mut i = 1;
+        while i < 10 {
+            chars[i] = match char::from_u32(zero as u32 + i as u32) {
+                Some(c) => c,
+                None    => panic!("invalid digit"),
+            };
+            i += 1;
+        }
+
+        Digits { chars: chars }
+    }
+
+    /// Returns the digits that are usually used in the language with the
+    /// given ISO 639-1 code, such as `"ar"` or `"ne"`, which are the ASCII
+    /// ones for most languages. Any region after the language code is
+    /// ignored.
+    pub fn for_language(code: &str) -> Digits {
+        match code.split(|c| c == '_' || c == '-').next().unwrap_or(code) {
+            "ar"               => Digits::ARABIC_INDIC,
+            "fa" | "ps"        => Digits::EXTENDED_ARABIC_INDIC,
+            "mr" | "ne"        => Digits::DEVANAGARI,
+            "bn"               => Digits::BENGALI,
+            _                  => Digits::ASCII,
         }
     }
+
+    /// Returns the character for the given digit, from 0 to 9.
+    pub fn digit(&self, digit: u8) -> char {
+        self.chars[digit as usize]
+    }
+}
+
+impl Default for Digits {
+
+    /// Returns the ASCII digits.
+    fn default() -> Digits {
+        Digits::ASCII
+    }
+}
+
+/// Returns the calendar’s own name for the month if it has one, or else
+/// the name from the month names in the options, or from the locale.
+fn month_name(fields: &CalendarFields, long: bool, genitive: bool, options: &FormatOptions, locale: &locale::Time) -> String {
+    match (fields.month_name, Month::from_one(fields.month), options.month_names) {
+        (Some(name), _, _)             => name.to_string(),
+        (None, Ok(month), Some(names)) => names.name(month, long, genitive).to_string(),
+        (None, Ok(month), None)        => if long { month.name(locale) } else { month.short_name(locale) },
+        (None, Err(_), _)              => fields.month.to_string(),
+    }
 }
 
 
@@ -55,6 +251,7 @@ pub struct DateFormat<'a> {
 }
 
 
+#[non_exhaustive]
 #[derive(PartialEq, Eq, Clone, Debug, Copy)]
 pub enum FormatError {
     InvalidChar { c: char, colon: bool, pos: Pos },
@@ -65,6 +262,32 @@ pub enum FormatError {
     DoubleWidth { open_pos: Pos, current_width: Width },
 }
 
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            FormatError::InvalidChar { c, pos, .. }       => write!(f, "{} ({:?} at position {})", self.description(), c, pos),
+            FormatError::OpenCurlyBrace { open_pos }      => write!(f, "{} (at position {})", self.description(), open_pos),
+            FormatError::CloseCurlyBrace { close_pos }    => write!(f, "{} (at position {})", self.description(), close_pos),
+            FormatError::MissingField { open_pos, .. }    => write!(f, "{} (at position {})", self.description(), open_pos),
+            FormatError::DoubleAlignment { open_pos, .. } => write!(f, "{} (at position {})", self.description(), open_pos),
+            FormatError::DoubleWidth { open_pos, .. }     => write!(f, "{} (at position {})", self.description(), open_pos),
+        }
+    }
+}
+
+impl ErrorTrait for FormatError {
+    fn description(&self) -> &str {
+        match *self {
+            FormatError::InvalidChar { .. }     => "invalid character in format string",
+            FormatError::OpenCurlyBrace { .. }  => "unclosed brace in format string",
+            FormatError::CloseCurlyBrace { .. } => "unopened brace in format string",
+            FormatError::MissingField { .. }    => "missing field in format string",
+            FormatError::DoubleAlignment { .. } => "alignment given twice in format string",
+            FormatError::DoubleWidth { .. }     => "width given twice in format string",
+        }
+    }
+}
+
 pub type Width = usize;
 pub type Pos = usize;
 
@@ -109,12 +332,51 @@ impl Arguments {
     }
 
     fn format(self, w: &mut Vec<u8>, string: &str) -> io::Result<()> {
-        let width     = self.width.unwrap_or(0);
-        let pad_char  = self.pad_char.unwrap_or(' ');
-        let alignment = self.alignment.unwrap_or(Alignment::Left);
-        let s         = string.pad(width, pad_char, alignment, false);
 
-        w.write_all(s.as_bytes())
+        // Non-ASCII text can have characters that take up zero or two
+        // columns, which only the `pad` crate knows how to measure.
+        if !string.is_ascii() {
+            let width     = self.width.unwrap_or(0);
+            let pad_char  = self.pad_char.unwrap_or(' ');
+            let alignment = self.alignment.unwrap_or(Alignment::Left);
+            return w.write_all(string.pad(width, pad_char, alignment, false).as_bytes());
+        }
+
+        self.format_ascii(w, string.as_bytes())
+    }
+
+    /// Writes the given ASCII text padded to the width, the same as the
+    /// `pad` crate does, but without building a new string to hold it.
+    fn format_ascii(self, w: &mut Vec<u8>, text: &[u8]) -> io::Result<()> {
+        self.format_padded(w, text, text.len())
+    }
+
+    /// Writes the given text padded to the width, counting it as the given
+    /// number of columns wide.
+    fn format_padded(self, w: &mut Vec<u8>, text: &[u8], columns: usize) -> io::Result<()> {
+        let diff = self.width.unwrap_or(0).saturating_sub(columns);
+
+        let (left_pad, right_pad) = match self.alignment.unwrap_or(Alignment::Left) {
+            Alignment::Left         => (0, diff),
+            Alignment::Right        => (diff, 0),
+            Alignment::Middle       => (diff / 2, diff - diff / 2),
+            Alignment::MiddleRight  => (diff - diff / 2, diff / 2),
+        };
+
+        let mut pad_bytes = [0; 4];
+        let pad_bytes = self.pad_char.unwrap_or(' ').encode_utf8(&mut pad_bytes).as_bytes();
+
+        for _ in 0 .. left_pad {
+            try!(w.write_all(pad_bytes));
+        }
+
+        try!(w.write_all(text));
+
+        for _ in 0 .. right_pad {
+            try!(w.write_all(pad_bytes));
+        }
+
+        Ok(())
     }
 
     pub fn is_empty(&self) -> bool {
@@ -147,19 +409,144 @@ impl NumArguments {
         NumArguments(Arguments::empty())
     }
 
-    fn format<N: Integer + Display>(self, w: &mut Vec<u8>, number: N) -> io::Result<()> {
-        self.0.format(w, &number.to_string())
+    fn format(self, w: &mut Vec<u8>, number: i64, digits: &Digits) -> io::Result<()> {
+        let mut buf = [0; 20];
+        let ascii = integer_digits(number, &mut buf);
+        if *digits == Digits::ASCII {
+            return self.0.format_ascii(w, ascii);
+        }
+
+        // Padding with zeroes pads with the zero from the digit set. Every
+        // digit counts as one column, even in scripts where they’re wider,
+        // so that numbers get padded to the same number of digits.
+        let mut args = self.0;
+        if args.pad_char == Some('0') {
+            args.pad_char = Some(digits.digit(0));
+        }
+
+        let text: String = ascii.iter().map(|&b| if b.is_ascii_digit() { digits.digit(b - b'0') } else { b as char }).collect();
+        args.format_padded(w, text.as_bytes(), ascii.len())
+    }
+}
+
+/// Writes the digits of an integer, with a minus sign if it’s negative,
+/// into the end of the given buffer, and returns the part that was used.
+/// Twenty bytes is enough for any `i64`.
+fn integer_digits(number: i64, buf: &mut [u8; 20]) -> &[u8] {
+    let mut remaining = number.unsigned_abs();
+    let mut start = buf.len();
+
+    while remaining >= 100 {
+        let pair = (remaining % 100) as usize * 2;
+        remaining /= 100;
+        start -= 2;
+        buf[start .. start + 2].copy_from_slice(&DIGIT_PAIRS[pair .. pair + 2]);
+    }
+
+    if remaining >= 10 {
+        let pair = remaining as usize * 2;
+        start -= 2;
+        buf[start .. start + 2].copy_from_slice(&DIGIT_PAIRS[pair .. pair + 2]);
+    }
+    else {
+        start -= 1;
+        buf[start] = b'0' + remaining as u8;
     }
+
+    if number < 0 {
+        start -= 1;
+        buf[start] = b'-';
+    }
+
+    &buf[start ..]
 }
 
 impl<'a> DateFormat<'a> {
     pub fn format<T>(&self, when: &T, locale: &locale::Time) -> String where T: DatePiece+TimePiece{
+        self.format_with_options(when, &FormatOptions::default(), locale)
+    }
+
+    /// Formats the given value, using the given names for the eras in the
+    /// `{:N}` field.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// extern crate locale;
+    /// extern crate datetime;
+    /// use datetime::{LocalDate, LocalDateTime, LocalTime, Month, EraNames};
+    /// use datetime::format::DateFormat;
+    ///
+    /// # fn main() {
+    /// let when = LocalDateTime::new(LocalDate::ymd(-43, Month::March, 15).unwrap(), LocalTime::midnight());
+    /// let pattern = DateFormat::parse("{:D} {_:M} {:n} {:N}").unwrap();
+    /// assert_eq!(pattern.format_with_eras(&when, &EraNames::BCE_CE, &locale::Time::english()), "15 March 44 BCE");
+    /// # }
+    /// ```
+    pub fn format_with_eras<T>(&self, when: &T, eras: &EraNames, locale: &locale::Time) -> String where T: DatePiece+TimePiece {
+        self.format_with_options(when, &FormatOptions { eras: *eras, .. FormatOptions::default() }, locale)
+    }
+
+    /// Formats the given value, using the given era names, month names,
+    /// and digits.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// extern crate locale;
+    /// extern crate datetime;
+    /// use datetime::{LocalDate, LocalDateTime, LocalTime, Month};
+    /// use datetime::format::{DateFormat, FormatOptions};
+    ///
+    /// # fn main() {
+    /// let when = LocalDateTime::new(LocalDate::ymd(2024, Month::March, 7).unwrap(), LocalTime::midnight());
+    /// let pattern = DateFormat::parse("{:D}/{:Y}").unwrap();
+    /// assert_eq!(pattern.format_with_options(&when, &FormatOptions::for_language("ar"), &locale::Time::english()), "٧/٢٠٢٤");
+    /// # }
+    /// ```
+    pub fn format_with_options<T>(&self, when: &T, options: &FormatOptions, locale: &locale::Time) -> String where T: DatePiece+TimePiece {
+        let fields = CalendarFields { year: when.year(), month: when.month().to_one(), day: when.day(), month_name: None, era: None };
+        self.format_fields(when, &fields, options, locale)
+    }
+
+    /// Formats the given value with its year, month, and day taken from
+    /// the given calendar rather than the Gregorian one. Months that the
+    /// calendar has no names for use the locale’s names for the Gregorian
+    /// month with the same number, and calendars without eras of their own
+    /// use BC and AD.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the value’s date is outside the range of dates that can
+    /// be represented.
+    pub fn format_in<T, C>(&self, when: &T, calendar: &C, locale: &locale::Time) -> String
+    where T: DatePiece+TimePiece, C: CalendarSystem+?Sized {
+        self.format_in_with_options(when, calendar, &FormatOptions::default(), locale)
+    }
+
+    /// Formats the given value with its year, month, and day taken from
+    /// the given calendar, using the given era names, month names, and
+    /// digits.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the value’s date is outside the range of dates that can
+    /// be represented.
+    pub fn format_in_with_options<T, C>(&self, when: &T, calendar: &C, options: &FormatOptions, locale: &locale::Time) -> String
+    where T: DatePiece+TimePiece, C: CalendarSystem+?Sized {
+        let date = LocalDate::ymd(when.year(), when.month(), when.day()).expect("date out of range");
+        let (year, month, day) = calendar.to_ymd(date);
+        let fields = CalendarFields { year: year, month: month, day: day, month_name: calendar.month_name(year, month), era: calendar.era_year(date) };
+        self.format_fields(when, &fields, options, locale)
+    }
+
+    fn format_fields<T>(&self, when: &T, fields: &CalendarFields, options: &FormatOptions, locale: &locale::Time) -> String where T: DatePiece+TimePiece {
         let mut buf = Vec::<u8>::new();
 
         for field in &self.fields {
             // It's safe to just ignore the error when writing to an in-memory
             // Vec<u8> buffer. If it fails then you have bigger problems
-            match field.format(when, &mut buf, locale) { _ => {} }
+            match field.format(when, fields, options, &mut buf, locale) { _ => {} }
         }
 
         String::from_utf8(buf).unwrap()  // Assume UTF-8
@@ -312,8 +699,15 @@ impl<'a> FormatParser<'a> {
                         Some((_, 'Y')) => Field::Year(NumArguments(args)),
                         Some((_, 'y')) => Field::YearOfCentury(NumArguments(args)),
                         Some((_, 'M')) => Field::MonthName(long, TextArguments(args)),
+                        Some((_, 'L')) => Field::StandaloneMonthName(long, TextArguments(args)),
                         Some((_, 'D')) => Field::Day(NumArguments(args)),
                         Some((_, 'E')) => Field::WeekdayName(long, TextArguments(args)),
+                        Some((_, 'N')) => Field::EraName(long, TextArguments(args)),
+                        Some((_, 'n')) => Field::EraYear(NumArguments(args)),
+                        Some((_, 'U')) => Field::WeekOfYear(WeekRule { first_day: Weekday::Sunday, minimal_days_in_first_week: 7 }, NumArguments(args)),
+                        Some((_, 'W')) => Field::WeekOfYear(WeekRule { first_day: Weekday::Monday, minimal_days_in_first_week: 7 }, NumArguments(args)),
+                        Some((_, 'V')) => Field::WeekNumber(WeekRule::ISO, NumArguments(args)),
+                        Some((_, 'G')) => Field::WeekYear(WeekRule::ISO, NumArguments(args)),
                         Some((_, 'h')) => Field::Hour(NumArguments(args)),
                         Some((_, 'm')) => Field::Minute(NumArguments(args)),
                         Some((_, 's')) => Field::Second(NumArguments(args)),
@@ -346,6 +740,140 @@ mod test {
 
     pub use pad::Alignment;
 
+    mod format {
+        pub use super::*;
+        use super::super::integer_digits;
+        use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+        use locale;
+
+        fn format(pattern: &str, datetime: LocalDateTime) -> String {
+            DateFormat::parse(pattern).unwrap().format(&datetime, &locale::Time::english())
+        }
+
+        fn datetime(year: i64) -> LocalDateTime {
+            LocalDateTime::new(LocalDate::ymd(year, Month::March, 7).unwrap(), LocalTime::hms(9, 5, 0).unwrap())
+        }
+
+        #[test]
+        fn padded_numbers() {
+            assert_eq!(format("{:Y}-{0>2:D} {0>2:h}:{0>2:m}:{0>2:s}", datetime(2015)), "2015-07 09:05:00");
+            assert_eq!(format("[{<4:D}]", datetime(2015)), "[7   ]");
+            assert_eq!(format("[{^4:D}]", datetime(2015)), "[ 7  ]");
+            assert_eq!(format("[{>1:Y}]", datetime(2015)), "[2015]");
+        }
+
+        #[test]
+        fn negative_years() {
+            assert_eq!(format("{:Y}", datetime(-44)), "-44");
+            assert_eq!(format("{0>6:Y}", datetime(-44)), "000-44");
+        }
+
+        #[test]
+        fn padded_text() {
+            assert_eq!(format("[{>5:M}]", datetime(2015)), "[  Mar]");
+            assert_eq!(format("{_:M}", datetime(2015)), "March");
+        }
+
+        #[test]
+        fn other_calendars() {
+            use cal::calendar::{CalendarSystem, Gregorian};
+            use cal::datetime::Error;
+
+            // A calendar that’s the Gregorian one shifted by a year.
+            struct Shifted;
+
+            impl CalendarSystem for Shifted {
+                fn name(&self) -> &str { "Shifted" }
+                fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) { let (y, m, d) = Gregorian.to_ymd(date); (y + 1, m, d) }
+                fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> { Gregorian.from_ymd(year - 1, month, day) }
+                fn days_in_month(&self, year: i64, month: i8) -> i8 { Gregorian.days_in_month(year - 1, month) }
+                fn month_name(&self, _year: i64, month: i8) -> Option<&str> { if month == 3 { Some("Third") } else { None } }
+            }
+
+            let pattern = DateFormat::parse("{:D} {:M} {:Y}").unwrap();
+            let english = locale::Time::english();
+            assert_eq!(pattern.format_in(&datetime(2015), &Gregorian, &english), "7 Mar 2015");
+            assert_eq!(pattern.format_in(&datetime(2015), &Shifted, &english), "7 Third 2016");
+            assert_eq!(pattern.format_in(&LocalDateTime::new(LocalDate::ymd(2015, Month::April, 7).unwrap(), LocalTime::midnight()), &Shifted, &english), "7 Apr 2016");
+        }
+
+        #[test]
+        fn eras() {
+            use cal::calendar::Gregorian;
+            use cal::japanese::JapaneseCalendar;
+
+            let english = locale::Time::english();
+            let when = LocalDateTime::new(LocalDate::ymd(2024, Month::June, 1).unwrap(), LocalTime::midnight());
+            assert_eq!(DateFormat::parse("{:N} {:n}").unwrap().format_in(&when, &JapaneseCalendar::MODERN, &english), "Reiwa 6");
+            assert_eq!(DateFormat::parse("{_:N}{:n}年").unwrap().format_in(&when, &JapaneseCalendar::MODERN, &english), "令和6年");
+            assert_eq!(DateFormat::parse("{:n} {:N}").unwrap().format_in(&when, &Gregorian, &english), "2024 AD");
+        }
+
+        #[test]
+        fn digit_sets() {
+            use super::super::{Digits, FormatOptions};
+
+            let english = locale::Time::english();
+            let pattern = DateFormat::parse("{:D}/{:Y} {0>2:h}:{0>2:m} [{>4:D}]").unwrap();
+            let format = |digits| pattern.format_with_options(&datetime(2015), &FormatOptions { digits: digits, .. FormatOptions::default() }, &english);
+
+            assert_eq!(format(Digits::ASCII), "7/2015 09:05 [   7]");
+            assert_eq!(format(Digits::ARABIC_INDIC), "٧/٢٠١٥ ٠٩:٠٥ [   ٧]");
+            assert_eq!(format(Digits::DEVANAGARI), "७/२०१५ ०९:०५ [   ७]");
+            assert_eq!(format(Digits { chars: [ '〇', '一', '二', '三', '四', '五', '六', '七', '八', '九' ] }), "七/二〇一五 〇九:〇五 [   七]");
+            assert_eq!(DateFormat::parse("{:Y}").unwrap().format_with_options(&datetime(-44), &FormatOptions::for_language("fa_IR"), &english), "-۴۴");
+
+            assert_eq!(Digits::for_language("hi"), Digits::ASCII);
+            assert_eq!(FormatOptions::for_language("ne").digits, Digits::DEVANAGARI);
+        }
+
+        #[test]
+        fn month_forms() {
+            use super::super::FormatOptions;
+
+            let english = locale::Time::english();
+            let format = |pattern, language| DateFormat::parse(pattern).unwrap().format_with_options(&datetime(2015), &FormatOptions::for_language(language), &english);
+
+            assert_eq!(format("{:D} {_:M} {:Y}", "ru"), "7 марта 2015");
+            assert_eq!(format("{_:L} {:Y}", "ru"), "март 2015");
+            assert_eq!(format("{:D} {:M}", "ru"), "7 мар.");
+            assert_eq!(format("{:L}", "ru"), "март");
+            assert_eq!(format("{:D} {_:M}, {_:L}", "pl"), "7 marca, marzec");
+            assert_eq!(format("{:D} {_:M}, {_:L}", "en"), "7 March, March");
+        }
+
+        #[test]
+        fn bc_and_ad() {
+            use cal::datetime::EraNames;
+
+            let english = locale::Time::english();
+            let pattern = DateFormat::parse("{:n} {:N}").unwrap();
+            assert_eq!(pattern.format(&datetime(2015), &english), "2015 AD");
+            assert_eq!(pattern.format(&datetime(0), &english), "1 BC");
+            assert_eq!(pattern.format(&datetime(-44), &english), "45 BC");
+            assert_eq!(pattern.format_with_eras(&datetime(-44), &EraNames::BCE_CE, &english), "45 BCE");
+            assert_eq!(pattern.format_with_eras(&datetime(-44), &EraNames::for_language("fr").unwrap(), &english), "45 av. J.-C.");
+        }
+
+        #[test]
+        fn weeks() {
+            // The 1st of January 2023 was a Sunday.
+            let sunday = LocalDateTime::new(LocalDate::ymd(2023, Month::January, 1).unwrap(), LocalTime::midnight());
+            assert_eq!(format("{0>2:U} {0>2:W} {:G}-W{0>2:V}", sunday), "01 00 2022-W52");
+
+            let saturday = LocalDateTime::new(LocalDate::ymd(2022, Month::December, 31).unwrap(), LocalTime::midnight());
+            assert_eq!(format("{0>2:U} {0>2:W} {:G}-W{0>2:V}", saturday), "52 52 2022-W52");
+        }
+
+        #[test]
+        fn digits() {
+            let mut buf = [0; 20];
+            for &number in &[ 0, 7, 10, 99, 100, 12345, -1, -100, i64::max_value(), i64::min_value() ] {
+                assert_eq!(integer_digits(number, &mut buf), number.to_string().as_bytes());
+            }
+        }
+    }
+
     mod parse {
         pub use super::*;
 
@@ -0,0 +1,291 @@
+//! The Hebrew calendar, used for Jewish religious dates.
+//!
+//! Its months follow the moon, and its years follow the sun, so seven
+//! years in every nineteen have a thirteenth month, a second month of Adar.
+//! Years are numbered from the traditional date of the creation of the
+//! world, and start in the autumn, on the 1st of Tishrei.
+//!
+//! This is only available with the `hebrew` feature.
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month};
+//! use datetime::calendar::{CalendarDate, CalendarSystem};
+//! use datetime::hebrew::{Hebrew, HebrewMonth};
+//!
+//! let date = CalendarDate::from_date(Hebrew, LocalDate::ymd(2024, Month::October, 3).unwrap());
+//! assert_eq!((date.year(), date.month(), date.day()), (5785, 1, 1));
+//! assert_eq!(date.month_name(), Some("Tishrei"));
+//!
+//! // The first day of Passover.
+//! let month = Hebrew.month_number(5784, HebrewMonth::Nisan).unwrap();
+//! assert_eq!(Hebrew.from_ymd(5784, month, 15), LocalDate::ymd(2024, Month::April, 23));
+//! ```
+//!
+//! Months are numbered in the order they come in the year, starting from
+//! Tishrei, so in a leap year every month from Nisan onwards has a number
+//! one higher than it does in other years. Use `Hebrew::month` and
+//! `Hebrew::month_number` to convert between the numbers and the months.
+
+use cal::DatePiece;
+use cal::calendar::{CalendarSystem, check_ymd, date_from_julian_day};
+use cal::datetime::{LocalDate, Error};
+
+
+/// The Julian day of the 1st of Tishrei in the year 1.
+const EPOCH: i64 = 347_998;
+
+/// The number of parts, a 1080th of an hour, in a day.
+const PARTS_PER_DAY: i64 = 25_920;
+
+/// The Hebrew calendar, with years counted from the creation of the world.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub struct Hebrew;
+
+/// A month of the Hebrew calendar.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum HebrewMonth {
+    Tishrei,
+    Cheshvan,
+    Kislev,
+    Tevet,
+    Shevat,
+
+    /// The month of Adar, in years that only have one of them.
+    Adar,
+
+    /// The first month of Adar, which is the extra month in a leap year.
+    AdarI,
+
+    /// The second month of Adar, in a leap year, which is when the
+    /// festivals of Adar are held.
+    AdarII,
+
+    Nisan,
+    Iyar,
+    Sivan,
+    Tammuz,
+    Av,
+    Elul,
+}
+
+impl HebrewMonth {
+
+    /// Returns the name of this month written in the Latin alphabet, such
+    /// as “Tishrei”.
+    pub fn name(self) -> &'static str {
+        match self {
+            HebrewMonth::Tishrei   => "Tishrei",
+            HebrewMonth::Cheshvan  => "Cheshvan",
+            HebrewMonth::Kislev    => "Kislev",
+            HebrewMonth::Tevet     => "Tevet",
+            HebrewMonth::Shevat    => "Shevat",
+            HebrewMonth::Adar      => "Adar",
+            HebrewMonth::AdarI     => "Adar I",
+            HebrewMonth::AdarII    => "Adar II",
+            HebrewMonth::Nisan     => "Nisan",
+            HebrewMonth::Iyar      => "Iyar",
+            HebrewMonth::Sivan     => "Sivan",
+            HebrewMonth::Tammuz    => "Tammuz",
+            HebrewMonth::Av        => "Av",
+            HebrewMonth::Elul      => "Elul",
+        }
+    }
+
+    /// Returns the name of this month written in Hebrew, such as “תשרי”.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            HebrewMonth::Tishrei   => "תשרי",
+            HebrewMonth::Cheshvan  => "חשוון",
+            HebrewMonth::Kislev    => "כסלו",
+            HebrewMonth::Tevet     => "טבת",
+            HebrewMonth::Shevat    => "שבט",
+            HebrewMonth::Adar      => "אדר",
+            HebrewMonth::AdarI     => "אדר א׳",
+            HebrewMonth::AdarII    => "אדר ב׳",
+            HebrewMonth::Nisan     => "ניסן",
+            HebrewMonth::Iyar      => "אייר",
+            HebrewMonth::Sivan     => "סיוון",
+            HebrewMonth::Tammuz    => "תמוז",
+            HebrewMonth::Av        => "אב",
+            HebrewMonth::Elul      => "אלול",
+        }
+    }
+}
+
+impl Hebrew {
+
+    /// Returns whether the given year has thirteen months.
+    pub fn is_leap_year(&self, year: i64) -> bool {
+        (7 * year + 1).rem_euclid(19) < 7
+    }
+
+    /// Returns the month with the given number in the given year.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the year doesn’t have a month with that number.
+    pub fn month(&self, year: i64, month: i8) -> HebrewMonth {
+        use self::HebrewMonth::*;
+        const COMMON: [HebrewMonth; 12] = [ Tishrei, Cheshvan, Kislev, Tevet, Shevat, Adar, Nisan, Iyar, Sivan, Tammuz, Av, Elul ];
+        const LEAP: [HebrewMonth; 13] = [ Tishrei, Cheshvan, Kislev, Tevet, Shevat, AdarI, AdarII, Nisan, Iyar, Sivan, Tammuz, Av, Elul ];
+
+        let months: &[HebrewMonth] = if self.is_leap_year(year) { &LEAP } else { &COMMON };
+        assert!(month >= 1 && month as usize <= months.len(), "month out of range");
+        months[month as usize - 1]
+    }
+
+    /// Returns the number of the given month in the given year, or `None`
+    /// if the year doesn’t have that month: `Adar` is only in common
+    /// years, and `AdarI` and `AdarII` are only in leap years.
+    pub fn month_number(&self, year: i64, month: HebrewMonth) -> Option<i8> {
+        (1 ..= self.months_in_year(year)).find(|&number| self.month(year, number) == month)
+    }
+
+    /// Returns the Julian day of the 1st of Tishrei in the given year.
+    fn new_year(&self, year: i64) -> i64 {
+        let elapsed = elapsed_days(year);
+
+        // The year is lengthened or shortened by a day when the new year
+        // after it, or the one before it, would be postponed by so much
+        // that a year would be too long or too short.
+        let correction = if elapsed_days(year + 1) - elapsed == 356 { 2 }
+                    else if elapsed - elapsed_days(year - 1) == 382 { 1 }
+                    else { 0 };
+
+        EPOCH + elapsed + correction
+    }
+}
+
+/// Returns the number of days from the epoch to the new year in the given
+/// year, not counting the postponements that keep the years the right
+/// lengths.
+fn elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let days = 29 * months + parts.div_euclid(PARTS_PER_DAY);
+
+    // The new year can’t be on a Sunday, Wednesday, or Friday.
+    if (3 * (days + 1)).rem_euclid(7) < 3 { days + 1 } else { days }
+}
+
+impl CalendarSystem for Hebrew {
+    fn name(&self) -> &str {
+        "Hebrew"
+    }
+
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        let day = date.julian_day();
+
+        // Estimate the year from the average length of a year, which is
+        // 35975351 / 98496 days, then correct it if it’s one out.
+        let mut year = ((day - EPOCH) as i128 * 98_496).div_euclid(35_975_351) as i64 + 1;
+        if self.new_year(year) > day {
+            year -= 1;
+        }
+        else if self.new_year(year + 1) <= day {
+            year += 1;
+        }
+
+        let mut start = self.new_year(year);
+        let mut month = 1;
+        while day >= start + self.days_in_month(year, month) as i64 {
+            start += self.days_in_month(year, month) as i64;
+            month += 1;
+        }
+
+        (year, month, (day - start + 1) as i8)
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        if year < LocalDate::MIN.year() || year > LocalDate::MAX.year() + 3761 {
+            return Err(Error::YearOutOfRange { given: year });
+        }
+
+        try!(check_ymd(self, year, month, day));
+        let before: i64 = (1 .. month).map(|month| self.days_in_month(year, month) as i64).sum();
+        date_from_julian_day(self.new_year(year) + before + day as i64 - 1, year)
+    }
+
+    fn months_in_year(&self, year: i64) -> i8 {
+        if self.is_leap_year(year) { 13 } else { 12 }
+    }
+
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        match self.month(year, month) {
+            HebrewMonth::Cheshvan  => if self.days_in_year(year) % 10 == 5 { 30 } else { 29 },
+            HebrewMonth::Kislev    => if self.days_in_year(year) % 10 == 3 { 29 } else { 30 },
+            HebrewMonth::Tishrei | HebrewMonth::Shevat | HebrewMonth::AdarI | HebrewMonth::Nisan | HebrewMonth::Sivan | HebrewMonth::Av  => 30,
+            HebrewMonth::Tevet | HebrewMonth::Adar | HebrewMonth::AdarII | HebrewMonth::Iyar | HebrewMonth::Tammuz | HebrewMonth::Elul  => 29,
+        }
+    }
+
+    fn days_in_year(&self, year: i64) -> i16 {
+        (self.new_year(year + 1) - self.new_year(year)) as i16
+    }
+
+    fn month_name(&self, year: i64, month: i8) -> Option<&str> {
+        Some(self.month(year, month).name())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::Month;
+
+    #[test]
+    fn new_years() {
+        assert_eq!(Hebrew.from_ymd(5784, 1, 1), Ok(LocalDate::ymd(2023, Month::September, 16).unwrap()));
+        assert_eq!(Hebrew.from_ymd(5785, 1, 1), Ok(LocalDate::ymd(2024, Month::October, 3).unwrap()));
+        assert_eq!(Hebrew.from_ymd(5786, 1, 1), Ok(LocalDate::ymd(2025, Month::September, 23).unwrap()));
+        assert_eq!(Hebrew.from_ymd(5760, 1, 1), Ok(LocalDate::ymd(1999, Month::September, 11).unwrap()));
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(Hebrew.is_leap_year(5784));
+        assert!(!Hebrew.is_leap_year(5785));
+        assert_eq!(Hebrew.months_in_year(5784), 13);
+        assert_eq!(Hebrew.days_in_year(5784), 383);
+        assert_eq!(Hebrew.days_in_year(5785), 355);
+
+        assert_eq!(Hebrew.month(5784, 6), HebrewMonth::AdarI);
+        assert_eq!(Hebrew.month(5784, 7), HebrewMonth::AdarII);
+        assert_eq!(Hebrew.month(5785, 7), HebrewMonth::Nisan);
+        assert_eq!(Hebrew.month_number(5785, HebrewMonth::AdarII), None);
+
+        // Purim.
+        assert_eq!(Hebrew.from_ymd(5784, 7, 14), Ok(LocalDate::ymd(2024, Month::March, 24).unwrap()));
+        assert_eq!(Hebrew.from_ymd(5785, 6, 14), Ok(LocalDate::ymd(2025, Month::March, 14).unwrap()));
+        assert_eq!(Hebrew.from_ymd(5785, 13, 1), Err(Error::MonthOutOfRange { given: 13 }));
+    }
+
+    #[test]
+    fn year_lengths() {
+        for year in 5600 .. 6000 {
+            let length = Hebrew.days_in_year(year);
+            if Hebrew.is_leap_year(year) {
+                assert!(length == 383 || length == 384 || length == 385, "{} has {} days", year, length);
+            }
+            else {
+                assert!(length == 353 || length == 354 || length == 355, "{} has {} days", year, length);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for day in LocalDate::ymd(1900, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2100, Month::January, 1).unwrap().julian_day() {
+            let date = LocalDate::from_julian_day(day);
+            let (year, month, day) = Hebrew.to_ymd(date);
+            assert_eq!(Hebrew.from_ymd(year, month, day), Ok(date));
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Hebrew.month_name(5784, 7), Some("Adar II"));
+        assert_eq!(HebrewMonth::Tishrei.native_name(), "תשרי");
+    }
+}
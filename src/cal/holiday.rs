@@ -0,0 +1,491 @@
+//! Public holidays, and counting the business days between them.
+//!
+//! A `HolidayCalendar` says which dates are holidays. Most real calendars
+//! can be described as a list of `Holiday` rules—a fixed date, the *n*th
+//! weekday of a month, or a number of days from Easter—along with what to
+//! do when one falls on a weekend, which is what a `RuleCalendar` is. A
+//! couple are included in this module as examples.
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month, HolidayCalendar};
+//! use datetime::holiday::ENGLAND_AND_WALES;
+//!
+//! // Christmas 2022 was on a Sunday, so the day off was on Tuesday.
+//! let christmas_eve = LocalDate::ymd(2022, Month::December, 24).unwrap();
+//! assert!(ENGLAND_AND_WALES.is_holiday(LocalDate::ymd(2022, Month::December, 27).unwrap()));
+//! assert_eq!(ENGLAND_AND_WALES.add_business_days(christmas_eve, 1), LocalDate::ymd(2022, Month::December, 28).unwrap());
+//! ```
+
+use alloc::vec::Vec;
+
+use cal::DatePiece;
+use cal::datetime::{LocalDate, Month, Weekday, Weekend, days_in_month};
+use cal::easter::{self, MovableFeast};
+
+
+/// A set of dates that are holidays, such as the public holidays of a
+/// country, or the days a stock exchange is closed.
+pub trait HolidayCalendar {
+
+    /// Returns every holiday in the given year, in order, with no date
+    /// given twice.
+    fn holidays_in(&self, year: i64) -> Vec<LocalDate>;
+
+    /// Returns whether the given date is a holiday.
+    fn is_holiday(&self, date: LocalDate) -> bool {
+        self.holidays_in(date.year()).binary_search(&date).is_ok()
+    }
+
+    /// Returns the days of the week that are never business days. This is
+    /// Saturday and Sunday unless the calendar says otherwise.
+    fn weekend(&self) -> Weekend {
+        Weekend::SATURDAY_SUNDAY
+    }
+
+    /// Returns whether the given date is a business day, which is one that
+    /// is neither a holiday nor part of the weekend.
+    fn is_business_day(&self, date: LocalDate) -> bool {
+        !date.is_weekend_in(self.weekend()) && !self.is_holiday(date)
+    }
+
+    /// Returns the date that is the given number of business days after
+    /// the given one, or before it if the number is negative. The date
+    /// itself doesn’t have to be a business day, and is returned unchanged
+    /// when adding zero.
+    fn add_business_days(&self, date: LocalDate, days: i64) -> LocalDate {
+        let step = if days < 0 { -1 } else { 1 };
+        let mut date = date;
+        let mut remaining = days.abs();
+
+        while remaining > 0 {
+            date = add_days(date, step);
+            if self.is_business_day(date) {
+                remaining -= 1;
+            }
+        }
+
+        date
+    }
+
+    /// Returns the number of business days from the start date up to, but
+    /// not including, the end date, which is negative if the end comes
+    /// before the start.
+    fn business_days_between(&self, start: LocalDate, end: LocalDate) -> i64 {
+        if end < start {
+            return -self.business_days_between(end, start);
+        }
+
+        (start.julian_day() .. end.julian_day())
+            .filter(|&day| self.is_business_day(LocalDate::from_julian_day(day)))
+            .count() as i64
+    }
+}
+
+
+/// The rule that gives the date of a holiday in a particular year.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Rule {
+
+    /// The same day of the same month every year. A rule for the 29th of
+    /// February only gives a date in leap years.
+    Fixed(Month, i8),
+
+    /// The *n*th occurrence of a weekday in a month, where 1 is the first,
+    /// −1 is the last, −2 is the second-to-last, and so on. A rule for the
+    /// fifth occurrence only gives a date in months that have one.
+    NthWeekday(Month, Weekday, i8),
+
+    /// The given number of days after Easter Sunday in the Gregorian
+    /// calendar, or before it if the number is negative.
+    AfterEaster(i64),
+
+    /// The given number of days after Easter Sunday as celebrated by the
+    /// Orthodox churches, or before it if the number is negative.
+    AfterOrthodoxEaster(i64),
+}
+
+impl Rule {
+
+    /// Returns the rule for the given feast in the Gregorian calendar.
+    pub const fn feast(feast: MovableFeast) -> Rule {
+        Rule::AfterEaster(feast.days_after_easter())
+    }
+
+    /// Returns the rule for the given feast as celebrated by the Orthodox
+    /// churches.
+    pub const fn orthodox_feast(feast: MovableFeast) -> Rule {
+        Rule::AfterOrthodoxEaster(feast.days_after_easter())
+    }
+
+    /// Returns the date this rule gives in the given year, if it gives one.
+    /// There is no date for a year outside the range of dates that can be
+    /// represented, or for one whose date for this rule is outside it.
+    pub fn date_in(&self, year: i64) -> Option<LocalDate> {
+        match *self {
+            Rule::Fixed(month, day) => {
+                LocalDate::ymd(year, month, day).ok()
+            },
+
+            Rule::NthWeekday(month, weekday, n) => {
+                // The first and last years that can be represented only
+                // have some of their days, so the weekday is taken from
+                // whichever end of the month exists. The arithmetic is
+                // done in `i64`, as seven times a large `n` doesn’t fit in
+                // an `i8`.
+                let count = days_in_month(year, month) as i64;
+                let (known, day_of_known) = match LocalDate::ymd(year, month, 1) {
+                    Ok(first)  => (first, 1),
+                    Err(_)     => match LocalDate::ymd(year, month, count as i8) {
+                        Ok(last)  => (last, count),
+                        Err(_)    => return None,
+                    },
+                };
+
+                let first_weekday = (day_of_known - 1 + known.weekday().days_until(weekday) as i64) % 7 + 1;
+                let n = n as i64;
+                let day = if n > 0 {
+                    first_weekday + 7 * (n - 1)
+                }
+                else if n < 0 {
+                    let last_weekday = first_weekday + 7 * ((count - first_weekday) / 7);
+                    last_weekday + 7 * (n + 1)
+                }
+                else {
+                    return None;
+                };
+
+                if day >= 1 && day <= count { LocalDate::ymd(year, month, day as i8).ok() } else { None }
+            },
+
+            Rule::AfterEaster(days)         => easter::checked_gregorian(year).and_then(|easter| checked_add_days(easter, days)),
+            Rule::AfterOrthodoxEaster(days) => easter::checked_orthodox(year).and_then(|easter| checked_add_days(easter, days)),
+        }
+    }
+}
+
+
+/// What happens when a holiday falls on a weekend.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Observance {
+
+    /// The holiday stays where it is, even on a weekend.
+    Actual,
+
+    /// The holiday moves to the closest day that isn’t on the weekend,
+    /// trying the day before first: with a Saturday and Sunday weekend,
+    /// Saturday’s holidays are observed on Friday, and Sunday’s on Monday.
+    /// This is how the United States observes its federal holidays.
+    NearestWeekday,
+
+    /// The holiday moves forwards to the next day that is not on the
+    /// weekend and is not already a holiday, so two holidays on the same
+    /// weekend get a day each. This is how the United Kingdom gives
+    /// substitute days.
+    NextWeekday,
+}
+
+
+/// One holiday in a `RuleCalendar`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Holiday<'a> {
+
+    /// The name of the holiday, such as “Christmas Day”.
+    pub name: &'a str,
+
+    /// The rule that gives the holiday’s date each year.
+    pub rule: Rule,
+
+    /// What happens when the holiday falls on a weekend.
+    pub observance: Observance,
+}
+
+
+/// A holiday calendar described by a list of rules, which can be written
+/// as a constant.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month, Weekday, Weekend, HolidayCalendar};
+/// use datetime::easter::MovableFeast;
+/// use datetime::holiday::{RuleCalendar, Holiday, Rule, Observance};
+///
+/// const OFFICE: RuleCalendar<'static> = RuleCalendar {
+///     name: "Office",
+///     weekend: Weekend::SATURDAY_SUNDAY,
+///     holidays: &[
+///         Holiday { name: "Founders’ Day", rule: Rule::NthWeekday(Month::June, Weekday::Friday, 1), observance: Observance::Actual },
+///         Holiday { name: "Good Friday", rule: Rule::feast(MovableFeast::GoodFriday), observance: Observance::Actual },
+///     ],
+/// };
+///
+/// assert_eq!(OFFICE.holidays_in(2024), vec![
+///     LocalDate::ymd(2024, Month::March, 29).unwrap(),
+///     LocalDate::ymd(2024, Month::June, 7).unwrap(),
+/// ]);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct RuleCalendar<'a> {
+
+    /// The name of this calendar.
+    pub name: &'a str,
+
+    /// The days of the week that are never business days.
+    pub weekend: Weekend,
+
+    /// The holidays in this calendar. These can be in any order.
+    pub holidays: &'a [Holiday<'a>],
+}
+
+impl<'a> RuleCalendar<'a> {
+
+    /// Returns each holiday observed in the given year along with its
+    /// name, ordered by date. Two holidays observed on the same date are
+    /// both included.
+    ///
+    /// A holiday can be observed in a different year from the one its rule
+    /// gives, such as when the 1st of January falls on a Saturday and is
+    /// observed on the Friday before; it is returned for the year it is
+    /// observed in.
+    pub fn named_holidays_in(&self, year: i64) -> Vec<(LocalDate, &'a str)> {
+        let mut actual = Vec::new();
+        for rule_year in year.saturating_sub(1) .. year.saturating_add(2) {
+            for holiday in self.holidays {
+                if let Some(date) = holiday.rule.date_in(rule_year) {
+                    actual.push((date, holiday));
+                }
+            }
+        }
+        actual.sort_by_key(|&(date, _)| date);
+
+        // Holidays that stay where they are take their dates first, and the
+        // substitute days are then handed out in date order, each skipping
+        // over the dates already taken.
+        let mut observed: Vec<(LocalDate, &'a str)> = Vec::with_capacity(actual.len());
+        let mut substitutes = Vec::new();
+        for (date, holiday) in actual {
+            match holiday.observance {
+                Observance::Actual          => observed.push((date, holiday.name)),
+                Observance::NearestWeekday  => observed.push((self.nearest_weekday(date), holiday.name)),
+                Observance::NextWeekday     => {
+                    if date.is_weekend_in(self.weekend) {
+                        substitutes.push((date, holiday.name));
+                    }
+                    else {
+                        observed.push((date, holiday.name));
+                    }
+                },
+            }
+        }
+
+        // A substitute day that would fall after the last date that can be
+        // represented is left out.
+        'substitutes: for (date, name) in substitutes {
+            let mut date = date;
+            while date.is_weekend_in(self.weekend) || observed.iter().any(|&(taken, _)| taken == date) {
+                date = match checked_add_days(date, 1) {
+                    Some(next)  => next,
+                    None        => continue 'substitutes,
+                };
+            }
+
+            observed.push((date, name));
+        }
+
+        observed.retain(|&(date, _)| date.year() == year);
+        observed.sort_by_key(|&(date, _)| date);
+        observed
+    }
+
+    /// Returns the closest date to the given one that isn’t on the
+    /// weekend, trying the earlier one first.
+    fn nearest_weekday(&self, date: LocalDate) -> LocalDate {
+        for distance in 0 .. 7 {
+            for candidate in checked_add_days(date, -distance).into_iter().chain(checked_add_days(date, distance)) {
+                if !candidate.is_weekend_in(self.weekend) {
+                    return candidate;
+                }
+            }
+        }
+
+        date
+    }
+}
+
+impl<'a> HolidayCalendar for RuleCalendar<'a> {
+    fn holidays_in(&self, year: i64) -> Vec<LocalDate> {
+        let mut dates: Vec<LocalDate> = self.named_holidays_in(year).into_iter().map(|(date, _)| date).collect();
+        dates.dedup();
+        dates
+    }
+
+    fn weekend(&self) -> Weekend {
+        self.weekend
+    }
+}
+
+
+/// The federal holidays of the United States, under the rules in force
+/// since Juneteenth was added in 2021.
+pub const UNITED_STATES: RuleCalendar<'static> = RuleCalendar {
+    name: "United States",
+    weekend: Weekend::SATURDAY_SUNDAY,
+    holidays: &[
+        Holiday { name: "New Year’s Day",              rule: Rule::Fixed(Month::January, 1),                             observance: Observance::NearestWeekday },
+        Holiday { name: "Birthday of Martin Luther King, Jr.", rule: Rule::NthWeekday(Month::January, Weekday::Monday, 3), observance: Observance::Actual },
+        Holiday { name: "Washington’s Birthday",       rule: Rule::NthWeekday(Month::February, Weekday::Monday, 3),      observance: Observance::Actual },
+        Holiday { name: "Memorial Day",                rule: Rule::NthWeekday(Month::May, Weekday::Monday, -1),          observance: Observance::Actual },
+        Holiday { name: "Juneteenth",                  rule: Rule::Fixed(Month::June, 19),                               observance: Observance::NearestWeekday },
+        Holiday { name: "Independence Day",            rule: Rule::Fixed(Month::July, 4),                                observance: Observance::NearestWeekday },
+        Holiday { name: "Labor Day",                   rule: Rule::NthWeekday(Month::September, Weekday::Monday, 1),     observance: Observance::Actual },
+        Holiday { name: "Columbus Day",                rule: Rule::NthWeekday(Month::October, Weekday::Monday, 2),       observance: Observance::Actual },
+        Holiday { name: "Veterans Day",                rule: Rule::Fixed(Month::November, 11),                           observance: Observance::NearestWeekday },
+        Holiday { name: "Thanksgiving Day",            rule: Rule::NthWeekday(Month::November, Weekday::Thursday, 4),    observance: Observance::Actual },
+        Holiday { name: "Christmas Day",               rule: Rule::Fixed(Month::December, 25),                           observance: Observance::NearestWeekday },
+    ],
+};
+
+/// The regular bank holidays of England and Wales. One-off holidays, such
+/// as for coronations, and years when a bank holiday was moved for a
+/// jubilee or an anniversary, aren’t included.
+pub const ENGLAND_AND_WALES: RuleCalendar<'static> = RuleCalendar {
+    name: "England and Wales",
+    weekend: Weekend::SATURDAY_SUNDAY,
+    holidays: &[
+        Holiday { name: "New Year’s Day",              rule: Rule::Fixed(Month::January, 1),                             observance: Observance::NextWeekday },
+        Holiday { name: "Good Friday",                 rule: Rule::feast(MovableFeast::GoodFriday),                      observance: Observance::Actual },
+        Holiday { name: "Easter Monday",               rule: Rule::feast(MovableFeast::EasterMonday),                    observance: Observance::Actual },
+        Holiday { name: "Early May bank holiday",      rule: Rule::NthWeekday(Month::May, Weekday::Monday, 1),           observance: Observance::Actual },
+        Holiday { name: "Spring bank holiday",         rule: Rule::NthWeekday(Month::May, Weekday::Monday, -1),          observance: Observance::Actual },
+        Holiday { name: "Summer bank holiday",         rule: Rule::NthWeekday(Month::August, Weekday::Monday, -1),       observance: Observance::Actual },
+        Holiday { name: "Christmas Day",               rule: Rule::Fixed(Month::December, 25),                           observance: Observance::NextWeekday },
+        Holiday { name: "Boxing Day",                  rule: Rule::Fixed(Month::December, 26),                           observance: Observance::NextWeekday },
+    ],
+};
+
+
+fn add_days(date: LocalDate, days: i64) -> LocalDate {
+    LocalDate::from_julian_day(date.julian_day() + days)
+}
+
+fn checked_add_days(date: LocalDate, days: i64) -> Option<LocalDate> {
+    date.julian_day().checked_add(days).and_then(LocalDate::checked_from_julian_day)
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    #[test]
+    fn nth_weekdays() {
+        assert_eq!(Rule::NthWeekday(Month::November, Weekday::Thursday, 4).date_in(2024), Some(LocalDate::ymd(2024, Month::November, 28).unwrap()));
+        assert_eq!(Rule::NthWeekday(Month::May, Weekday::Monday, -1).date_in(2024), Some(LocalDate::ymd(2024, Month::May, 27).unwrap()));
+        assert_eq!(Rule::NthWeekday(Month::May, Weekday::Friday, -1).date_in(2024), Some(LocalDate::ymd(2024, Month::May, 31).unwrap()));
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Saturday, 1).date_in(2024), Some(LocalDate::ymd(2024, Month::June, 1).unwrap()));
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Saturday, 5).date_in(2024), Some(LocalDate::ymd(2024, Month::June, 29).unwrap()));
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, 5).date_in(2024), None);
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, 0).date_in(2024), None);
+    }
+
+    #[test]
+    fn nth_weekdays_out_of_range() {
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, 20).date_in(2024), None);
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, -20).date_in(2024), None);
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, i8::max_value()).date_in(2024), None);
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, i8::min_value()).date_in(2024), None);
+        assert_eq!(Rule::NthWeekday(Month::June, Weekday::Monday, 1).date_in(i64::max_value()), None);
+    }
+
+    #[test]
+    fn nth_weekdays_at_the_ends_of_the_range() {
+        let max = LocalDate::MAX;
+        let min = LocalDate::MIN;
+        assert_eq!(Rule::NthWeekday(max.month(), max.weekday(), 1).date_in(max.year()), Some(max));
+        assert_eq!(Rule::NthWeekday(max.month(), max.weekday(), -1).date_in(max.year()), None);
+        assert_eq!(Rule::NthWeekday(min.month(), min.weekday(), 4).date_in(min.year()), Some(min));
+        assert_eq!(Rule::NthWeekday(min.month(), min.weekday(), -1).date_in(min.year()), Some(min));
+        assert_eq!(Rule::NthWeekday(min.month(), min.weekday(), 1).date_in(min.year()), None);
+    }
+
+    #[test]
+    fn ends_of_the_range() {
+        assert!(!ENGLAND_AND_WALES.is_holiday(LocalDate::MAX));
+        assert!(!ENGLAND_AND_WALES.is_holiday(LocalDate::MIN));
+        assert!(!UNITED_STATES.is_holiday(LocalDate::MAX));
+        assert!(!UNITED_STATES.is_holiday(LocalDate::MIN));
+        assert!(Rule::feast(MovableFeast::EasterSunday).date_in(LocalDate::MAX.year()).is_some());
+        assert_eq!(Rule::feast(MovableFeast::EasterSunday).date_in(LocalDate::MAX.year() + 1), None);
+        assert_eq!(Rule::orthodox_feast(MovableFeast::EasterSunday).date_in(LocalDate::MIN.year() - 1), None);
+        assert_eq!(UNITED_STATES.holidays_in(i64::max_value()), vec![]);
+    }
+
+    #[test]
+    fn fixed_leap_day() {
+        assert_eq!(Rule::Fixed(Month::February, 29).date_in(2024), Some(LocalDate::ymd(2024, Month::February, 29).unwrap()));
+        assert_eq!(Rule::Fixed(Month::February, 29).date_in(2025), None);
+    }
+
+    #[test]
+    fn united_states() {
+        assert_eq!(UNITED_STATES.holidays_in(2021), vec![
+            LocalDate::ymd(2021, Month::January, 1).unwrap(),
+            LocalDate::ymd(2021, Month::January, 18).unwrap(),
+            LocalDate::ymd(2021, Month::February, 15).unwrap(),
+            LocalDate::ymd(2021, Month::May, 31).unwrap(),
+            LocalDate::ymd(2021, Month::June, 18).unwrap(),
+            LocalDate::ymd(2021, Month::July, 5).unwrap(),
+            LocalDate::ymd(2021, Month::September, 6).unwrap(),
+            LocalDate::ymd(2021, Month::October, 11).unwrap(),
+            LocalDate::ymd(2021, Month::November, 11).unwrap(),
+            LocalDate::ymd(2021, Month::November, 25).unwrap(),
+            LocalDate::ymd(2021, Month::December, 24).unwrap(),
+            LocalDate::ymd(2021, Month::December, 31).unwrap(),  // New Year’s Day 2022, a Saturday
+        ]);
+
+        assert_eq!(UNITED_STATES.holidays_in(2022)[0], LocalDate::ymd(2022, Month::January, 17).unwrap());
+    }
+
+    #[test]
+    fn england_and_wales() {
+        assert_eq!(ENGLAND_AND_WALES.named_holidays_in(2022), vec![
+            (LocalDate::ymd(2022, Month::January, 3).unwrap(),   "New Year’s Day"),
+            (LocalDate::ymd(2022, Month::April, 15).unwrap(),    "Good Friday"),
+            (LocalDate::ymd(2022, Month::April, 18).unwrap(),    "Easter Monday"),
+            (LocalDate::ymd(2022, Month::May, 2).unwrap(),       "Early May bank holiday"),
+            (LocalDate::ymd(2022, Month::May, 30).unwrap(),      "Spring bank holiday"),
+            (LocalDate::ymd(2022, Month::August, 29).unwrap(),   "Summer bank holiday"),
+            (LocalDate::ymd(2022, Month::December, 26).unwrap(), "Boxing Day"),
+            (LocalDate::ymd(2022, Month::December, 27).unwrap(), "Christmas Day"),
+        ]);
+    }
+
+    #[test]
+    fn business_days() {
+        let calendar = ENGLAND_AND_WALES;
+        let thursday = LocalDate::ymd(2024, Month::March, 28).unwrap();
+
+        assert!(calendar.is_business_day(thursday));
+        assert!(!calendar.is_business_day(LocalDate::ymd(2024, Month::March, 29).unwrap()));
+        assert_eq!(calendar.add_business_days(thursday, 0), thursday);
+        assert_eq!(calendar.add_business_days(thursday, 1), LocalDate::ymd(2024, Month::April, 2).unwrap());
+        assert_eq!(calendar.add_business_days(LocalDate::ymd(2024, Month::April, 2).unwrap(), -1), thursday);
+        assert_eq!(calendar.business_days_between(thursday, LocalDate::ymd(2024, Month::April, 3).unwrap()), 2);
+        assert_eq!(calendar.business_days_between(LocalDate::ymd(2024, Month::April, 3).unwrap(), thursday), -2);
+    }
+
+    #[test]
+    fn other_weekends() {
+        const CALENDAR: RuleCalendar<'static> = RuleCalendar {
+            name: "Test",
+            weekend: Weekend::FRIDAY_SATURDAY,
+            holidays: &[
+                Holiday { name: "Test Day", rule: Rule::Fixed(Month::March, 1), observance: Observance::NearestWeekday },
+            ],
+        };
+
+        // The 1st of March 2024 was a Friday.
+        assert_eq!(CALENDAR.holidays_in(2024), vec![ LocalDate::ymd(2024, Month::February, 29).unwrap() ]);
+        assert!(CALENDAR.is_business_day(LocalDate::ymd(2024, Month::March, 3).unwrap()));
+    }
+}
@@ -0,0 +1,482 @@
+//! The Islamic, or Hijri, calendar.
+//!
+//! Its years have twelve months that follow the moon, and so are about
+//! eleven days shorter than the solar year. Years are numbered from the
+//! Hijra in 622.
+//!
+//! This is only available with the `islamic` feature.
+//!
+//! Religious dates depend on the new moon being sighted, so can’t be
+//! worked out in advance. This module has two ways of getting close:
+//!
+//! - The **tabular** calendar, `TabularIslamic`, which gives months
+//!   alternating lengths of 30 and 29 days, and adds a leap day to 11
+//!   years in every 30, so it can be worked out for any year.
+//! - An `IslamicTable`, which reads the lengths of the months from a
+//!   published table. The one for the Umm al-Qura calendar that Saudi
+//!   Arabia uses for civil purposes is included as `UMM_AL_QURA`.
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month};
+//! use datetime::calendar::{CalendarDate, CalendarSystem};
+//! use datetime::islamic::TabularIslamic;
+//!
+//! let date = CalendarDate::from_date(TabularIslamic::CIVIL, LocalDate::ymd(1979, Month::November, 21).unwrap());
+//! assert_eq!((date.year(), date.month(), date.day()), (1400, 1, 1));
+//! assert_eq!(date.month_name(), Some("Muharram"));
+//! ```
+
+use cal::DatePiece;
+use cal::calendar::{CalendarSystem, check_ymd, date_from_julian_day};
+use cal::datetime::{LocalDate, Month, Error};
+
+
+/// The day that a tabular Islamic calendar counts its years from.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum IslamicEpoch {
+
+    /// Friday the 16th of July, 622, in the Julian calendar, which is what
+    /// most civil uses of the tabular calendar count from.
+    Civil,
+
+    /// Thursday the 15th of July, 622, in the Julian calendar, a day
+    /// earlier, which astronomers count from.
+    Astronomical,
+}
+
+impl IslamicEpoch {
+
+    /// Returns the Julian day of the 1st of Muharram in the year 1.
+    fn julian_day(self) -> i64 {
+        match self {
+            IslamicEpoch::Civil         => 1_948_440,
+            IslamicEpoch::Astronomical  => 1_948_439,
+        }
+    }
+}
+
+/// The tabular Islamic calendar, where the odd-numbered months have 30
+/// days and the even-numbered ones have 29, except that the last month has
+/// 30 days in the 2nd, 5th, 7th, 10th, 13th, 16th, 18th, 21st, 24th, 26th,
+/// and 29th years of every 30.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct TabularIslamic {
+
+    /// The day that this calendar counts years from.
+    pub epoch: IslamicEpoch,
+}
+
+impl TabularIslamic {
+
+    /// The tabular calendar counting from the civil epoch.
+    pub const CIVIL: TabularIslamic = TabularIslamic { epoch: IslamicEpoch::Civil };
+
+    /// The tabular calendar counting from the astronomical epoch.
+    pub const ASTRONOMICAL: TabularIslamic = TabularIslamic { epoch: IslamicEpoch::Astronomical };
+
+    /// Returns whether the given year has 355 days, rather than 354.
+    pub fn is_leap_year(&self, year: i64) -> bool {
+        (14 + 11 * year).rem_euclid(30) < 11
+    }
+
+    /// Returns the Julian day of the given day, which must be valid.
+    fn julian_day(&self, year: i64, month: i8, day: i8) -> i64 {
+        let month = month as i64;
+        self.epoch.julian_day() - 1 + (year - 1) * 354 + (3 + 11 * year).div_euclid(30) + 29 * (month - 1) + month / 2 + day as i64
+    }
+}
+
+impl CalendarSystem for TabularIslamic {
+    fn name(&self) -> &str {
+        "Islamic"
+    }
+
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        let day = date.julian_day();
+        let year = (30 * (day - self.epoch.julian_day()) + 10_646).div_euclid(10_631);
+        let month = ((11 * (day - self.julian_day(year, 1, 1)) + 330) / 325) as i8;
+        (year, month, (day - self.julian_day(year, month, 1) + 1) as i8)
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        try!(check_year(year));
+        try!(check_ymd(self, year, month, day));
+        date_from_julian_day(self.julian_day(year, month, day), year)
+    }
+
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        assert!(month >= 1 && month <= 12, "month out of range");
+        if month % 2 == 1 || (month == 12 && self.is_leap_year(year)) { 30 } else { 29 }
+    }
+
+    fn days_in_year(&self, year: i64) -> i16 {
+        if self.is_leap_year(year) { 355 } else { 354 }
+    }
+
+    fn month_name(&self, _year: i64, month: i8) -> Option<&str> {
+        month_name(month)
+    }
+}
+
+
+/// An Islamic calendar that takes the lengths of its months from a table,
+/// such as the one that the King Abdulaziz City for Science and Technology
+/// publishes for the Umm al-Qura calendar.
+///
+/// Dates outside the years in the table are an error, unless a `fallback`
+/// calendar is given to use for them instead.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::calendar::CalendarSystem;
+/// use datetime::islamic::IslamicTable;
+///
+/// const CALENDAR: IslamicTable = IslamicTable {
+///     name: "Umm al-Qura",
+///     first_year: 1445,
+///     start: match LocalDate::ymd(2023, Month::July, 19) {
+///         Ok(date) => date,
+///         Err(_)   => panic!("invalid start of table"),
+///     },
+///     month_lengths: &[ 0b1001_0010_1110 ],
+///     fallback: None,
+/// };
+///
+/// // The first day of Ramadan, the 9th month, in 1445.
+/// assert_eq!(CALENDAR.from_ymd(1445, 9, 1), LocalDate::ymd(2024, Month::March, 11));
+/// assert!(CALENDAR.from_ymd(1446, 1, 1).is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct IslamicTable<'a> {
+
+    /// The name of the calendar that the table is for.
+    pub name: &'a str,
+
+    /// The first year in the table.
+    pub first_year: i64,
+
+    /// The 1st of Muharram in the first year in the table.
+    pub start: LocalDate,
+
+    /// Which months have 30 days in each year in the table, starting from
+    /// the first one: the lowest bit is for Muharram, the next one for
+    /// Safar, and so on up to the twelfth bit for Dhuʻl-Hijjah.
+    pub month_lengths: &'a [u16],
+
+    /// The calendar to use for dates outside the years in the table, if
+    /// there is one. The civil tabular calendar is usually the same as the
+    /// Umm al-Qura one, or a day or two out.
+    pub fallback: Option<TabularIslamic>,
+}
+
+impl<'a> IslamicTable<'a> {
+
+    /// Returns the year, month, and day of the given date, or `None` if
+    /// it’s outside the years in the table. The fallback calendar isn’t
+    /// used.
+    pub fn checked_to_ymd(&self, date: LocalDate) -> Option<(i64, i8, i8)> {
+        let mut day = date.julian_day() - self.start.julian_day();
+        if day < 0 {
+            return None;
+        }
+
+        for (year, &lengths) in (self.first_year ..).zip(self.month_lengths) {
+            if day >= year_length(lengths) {
+                day -= year_length(lengths);
+                continue;
+            }
+
+            let mut month = 1;
+            while day >= month_length(lengths, month) as i64 {
+                day -= month_length(lengths, month) as i64;
+                month += 1;
+            }
+
+            return Some((year, month, day as i8 + 1));
+        }
+
+        None
+    }
+
+    /// Returns which months have 30 days in the given year, if it’s in the
+    /// table.
+    fn lengths(&self, year: i64) -> Option<u16> {
+        if year < self.first_year || year - self.first_year >= self.month_lengths.len() as i64 {
+            None
+        }
+        else {
+            Some(self.month_lengths[(year - self.first_year) as usize])
+        }
+    }
+
+    /// Returns the Julian day of the 1st of Muharram in the given year,
+    /// which must be in the table or the year after it.
+    fn start_of_year(&self, year: i64) -> i64 {
+        let years = &self.month_lengths[.. (year - self.first_year) as usize];
+        self.start.julian_day() + years.iter().map(|&lengths| year_length(lengths)).sum::<i64>()
+    }
+}
+
+impl<'a> CalendarSystem for IslamicTable<'a> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Returns the year, month, and day of the given date.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the date is outside the years in the table and there is
+    /// no fallback calendar. Use `checked_to_ymd` to handle that case
+    /// instead.
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        match (self.checked_to_ymd(date), self.fallback) {
+            (Some(ymd), _)         => ymd,
+            (None, Some(tabular))  => tabular.to_ymd(date),
+            (None, None)           => panic!("date outside the Islamic calendar table"),
+        }
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        let lengths = match (self.lengths(year), self.fallback) {
+            (Some(lengths), _)     => lengths,
+            (None, Some(tabular))  => return tabular.from_ymd(year, month, day),
+            (None, None)           => return Err(Error::YearOutOfRange { given: year }),
+        };
+
+        try!(check_ymd(self, year, month, day));
+        let before: i64 = (1 .. month).map(|month| month_length(lengths, month) as i64).sum();
+        date_from_julian_day(self.start_of_year(year) + before + day as i64 - 1, year)
+    }
+
+    /// Returns the number of days in the given month.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the month is out of range, or if the year is outside the
+    /// table and there is no fallback calendar.
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        assert!(month >= 1 && month <= 12, "month out of range");
+        match (self.lengths(year), self.fallback) {
+            (Some(lengths), _)     => month_length(lengths, month),
+            (None, Some(tabular))  => tabular.days_in_month(year, month),
+            (None, None)           => panic!("year outside the Islamic calendar table"),
+        }
+    }
+
+    fn month_name(&self, _year: i64, month: i8) -> Option<&str> {
+        month_name(month)
+    }
+}
+
+/// The Umm al-Qura calendar, as published by the King Abdulaziz City for
+/// Science and Technology, for the years 1300 to 1600, which start on the
+/// 12th of November 1882 and end on the 25th of November 2174. Dates
+/// outside those years use the civil tabular calendar.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::calendar::CalendarSystem;
+/// use datetime::islamic::UMM_AL_QURA;
+///
+/// // The first day of Ramadan in 1445.
+/// assert_eq!(UMM_AL_QURA.from_ymd(1445, 9, 1), LocalDate::ymd(2024, Month::March, 11));
+/// assert_eq!(UMM_AL_QURA.to_ymd(LocalDate::ymd(2000, Month::January, 1).unwrap()), (1420, 9, 24));
+/// ```
+pub const UMM_AL_QURA: IslamicTable<'static> = IslamicTable {
+    name: "Umm al-Qura",
+    first_year: 1300,
+    start: match LocalDate::ymd(1882, Month::November, 12) {
+        Ok(date) => date,
+        Err(_)   => panic!("invalid start of table"),
+    },
+    month_lengths: &[
+        0x555, 0x2AB, 0x937, 0x2B6, 0x576, 0x36C, 0xB55, 0xAAA, 0x956, 0x49E,  // 1300
+        0x95D, 0x2BA, 0x5B5, 0x3AA, 0xB4B, 0xA96, 0x52E, 0x2AD, 0x56D, 0xB5A,  // 1310
+        0x752, 0xF25, 0xE8A, 0xD16, 0xA56, 0xAB5, 0x6B4, 0xDA9, 0xB92, 0xB25,  // 1320
+        0x64B, 0xA9B, 0x35A, 0x6D9, 0x5D4, 0xDA5, 0xD4A, 0xA95, 0x536, 0x975,  // 1330
+        0x2F4, 0x6E9, 0x6D4, 0x6A9, 0x535, 0x25D, 0x4BD, 0x9BA, 0x3B4, 0xB69,  // 1340
+        0xB2A, 0xA55, 0x4AD, 0xA5D, 0x2DA, 0x6D9, 0xEAA, 0xE94, 0xD2A, 0xC56,  // 1350
+        0x4AE, 0xA6D, 0x56A, 0xD55, 0xD4A, 0xA93, 0x52B, 0xA5B, 0x53A, 0x6B5,  // 1360
+        0xEA9, 0xD52, 0xD29, 0xA55, 0x4AD, 0x56D, 0xAEA, 0x6E4, 0xED1, 0xDA2,  // 1370
+        0xAAA, 0x95A, 0x2DA, 0x5B9, 0xBB2, 0x764, 0x6C9, 0x555, 0x2AB, 0x4DB,  // 1380
+        0xABA, 0x5B4, 0xDA9, 0xD52, 0xAA5, 0x92D, 0x26D, 0x8ED, 0x2DA, 0xAD5,  // 1390
+        0xAA5, 0xA4B, 0x497, 0x937, 0x2B6, 0x975, 0xD69, 0xD52, 0xC95, 0x92B,  // 1400
+        0x25B, 0x4DB, 0x9D5, 0x5D2, 0xDA5, 0xD4A, 0xA95, 0x54D, 0xAAD, 0x3AA,  // 1410
+        0xBD2, 0xBC4, 0xB89, 0xA95, 0x52D, 0x5AD, 0xB6A, 0x6D4, 0xDC9, 0xD92,  // 1420
+        0xAA6, 0x956, 0x2AE, 0x56D, 0x36A, 0xB55, 0xAAA, 0x94D, 0x49D, 0x95D,  // 1430
+        0x2BA, 0x5B5, 0x5AA, 0xD55, 0xA9A, 0x92E, 0x26E, 0x55D, 0xADA, 0x6D4,  // 1440
+        0x6A5, 0xB27, 0xA4D, 0x4AD, 0x56D, 0xB5A, 0x754, 0xF49, 0xE92, 0xD26,  // 1450
+        0xA56, 0x356, 0x6B5, 0xBAA, 0xB92, 0xB25, 0x68B, 0xA9B, 0x55A, 0xADA,  // 1460
+        0x5B4, 0xDA9, 0xB52, 0xA9A, 0x536, 0x276, 0x575, 0xAF2, 0x6D4, 0x6A9,  // 1470
+        0x555, 0x2AD, 0x4BD, 0x9BA, 0x574, 0xB69, 0xB52, 0xA95, 0x52D, 0xA5D,  // 1480
+        0x4DA, 0xAD9, 0x6B2, 0xE95, 0xE2A, 0xC96, 0x92E, 0xAAD, 0x56A, 0xD65,  // 1490
+        0xD4A, 0xD15, 0x62B, 0xC5B, 0x53A, 0x6B5, 0xDB2, 0xD64, 0xD29, 0xA55,  // 1500
+        0x4AD, 0x96D, 0xAEA, 0x6E8, 0xED1, 0xDA4, 0xD4A, 0xA6A, 0x2DA, 0x5B9,  // 1510
+        0xB72, 0xB68, 0x6D1, 0x655, 0x4AB, 0x95B, 0x2BA, 0x5B5, 0xDA9, 0xD52,  // 1520
+        0xCA6, 0x94E, 0x46E, 0x95D, 0x4DA, 0xAD5, 0xAAA, 0xA4D, 0x49B, 0x937,  // 1530
+        0x4B6, 0x975, 0xD6A, 0xD52, 0xAA5, 0x94B, 0x2AB, 0x55B, 0xAD9, 0x5D2,  // 1540
+        0xDC5, 0xD92, 0xB25, 0x555, 0xAB5, 0x5B4, 0xBA9, 0x7A2, 0x745, 0x593,  // 1550
+        0xAAB, 0x4D6, 0x9D6, 0x5D2, 0xBA5, 0xB4A, 0xA95, 0x4AD, 0x15D, 0x2DD,  // 1560
+        0x9DA, 0x5B4, 0x5A9, 0x52D, 0x25B, 0x8B7, 0x176, 0x56D, 0xB6A, 0xACA,  // 1570
+        0xA96, 0x52B, 0x15B, 0x2BB, 0x5B6, 0xDAA, 0xB94, 0xD46, 0xA8D, 0x52D,  // 1580
+        0xA9D, 0x55A, 0x755, 0x749, 0xF13, 0xE4A, 0xA96, 0x556, 0x6B5, 0xBAA,  // 1590
+        0xB94,  // 1600
+    ],
+    fallback: Some(TabularIslamic::CIVIL),
+};
+
+/// Returns the length of the given month in a year of an `IslamicTable`.
+fn month_length(lengths: u16, month: i8) -> i8 {
+    if lengths & (1 << (month - 1)) != 0 { 30 } else { 29 }
+}
+
+/// Returns the number of days in a year of an `IslamicTable`.
+fn year_length(lengths: u16) -> i64 {
+    12 * 29 + (lengths & 0xFFF).count_ones() as i64
+}
+
+/// Returns an error if the year is so far away that working out the Julian
+/// days in it could overflow.
+fn check_year(year: i64) -> Result<(), Error> {
+    if year < LocalDate::MIN.year() * 2 || year > LocalDate::MAX.year() * 2 {
+        Err(Error::YearOutOfRange { given: year })
+    }
+    else {
+        Ok(())
+    }
+}
+
+/// Returns the name of the given month, which is the same in every
+/// variant of the calendar.
+fn month_name(month: i8) -> Option<&'static str> {
+    const NAMES: [&str; 12] = [
+        "Muharram", "Safar", "Rabiʻ I", "Rabiʻ II", "Jumada I", "Jumada II",
+        "Rajab", "Shaʻban", "Ramadan", "Shawwal", "Dhuʻl-Qiʻdah", "Dhuʻl-Hijjah",
+    ];
+
+    if month >= 1 && month <= 12 { Some(NAMES[month as usize - 1]) } else { None }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::Month;
+
+    /// The months of 1445 and 1446, without a fallback.
+    const TWO_YEARS: IslamicTable<'static> = IslamicTable {
+        name: "Umm al-Qura",
+        first_year: 1445,
+        start: match LocalDate::ymd(2023, Month::July, 19) {
+            Ok(date) => date,
+            Err(_)   => panic!("invalid start of table"),
+        },
+        month_lengths: &[ 0b1001_0010_1110, 0b0010_0110_1110 ],
+        fallback: None,
+    };
+
+    #[test]
+    fn tabular() {
+        let calendar = TabularIslamic::CIVIL;
+        assert_eq!(calendar.from_ymd(1, 1, 1), Ok(LocalDate::ymd(622, Month::July, 19).unwrap()));
+        assert_eq!(calendar.from_ymd(1400, 1, 1), Ok(LocalDate::ymd(1979, Month::November, 21).unwrap()));
+        assert_eq!(TabularIslamic::ASTRONOMICAL.from_ymd(1400, 1, 1), Ok(LocalDate::ymd(1979, Month::November, 20).unwrap()));
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(1979, Month::November, 20).unwrap()), (1399, 12, 29));
+    }
+
+    #[test]
+    fn leap_years() {
+        let calendar = TabularIslamic::CIVIL;
+        let leap_years: Vec<i64> = (1 ..= 30).filter(|&year| calendar.is_leap_year(year)).collect();
+        assert_eq!(leap_years, vec![ 2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29 ]);
+
+        assert_eq!(calendar.days_in_month(1401, 12), 30);
+        assert_eq!(calendar.days_in_month(1402, 12), 29);
+        assert_eq!(calendar.from_ymd(1402, 12, 30), Err(Error::DayOutOfRange { given: 30, max: 29 }));
+        assert_eq!(calendar.days_in_year(1401), 355);
+    }
+
+    #[test]
+    fn tabular_round_trip() {
+        for calendar in &[ TabularIslamic::CIVIL, TabularIslamic::ASTRONOMICAL ] {
+            for day in LocalDate::ymd(-100, Month::January, 1).unwrap().julian_day() .. LocalDate::ymd(2100, Month::January, 1).unwrap().julian_day() {
+                let date = LocalDate::from_julian_day(day);
+                let (year, month, day) = calendar.to_ymd(date);
+                assert_eq!(calendar.from_ymd(year, month, day), Ok(date));
+            }
+        }
+    }
+
+    #[test]
+    fn umm_al_qura() {
+        let calendar = UMM_AL_QURA;
+        assert_eq!(calendar.from_ymd(1300, 1, 1), Ok(LocalDate::ymd(1882, Month::November, 12).unwrap()));
+        assert_eq!(calendar.from_ymd(1400, 9, 1), Ok(LocalDate::ymd(1980, Month::July, 14).unwrap()));
+        assert_eq!(calendar.from_ymd(1420, 1, 1), Ok(LocalDate::ymd(1999, Month::April, 17).unwrap()));
+        assert_eq!(calendar.from_ymd(1445, 9, 1), Ok(LocalDate::ymd(2024, Month::March, 11).unwrap()));
+        assert_eq!(calendar.from_ymd(1445, 10, 1), Ok(LocalDate::ymd(2024, Month::April, 10).unwrap()));
+        assert_eq!(calendar.from_ymd(1446, 1, 1), Ok(LocalDate::ymd(2024, Month::July, 7).unwrap()));
+        assert_eq!(calendar.from_ymd(1446, 9, 1), Ok(LocalDate::ymd(2025, Month::March, 1).unwrap()));
+        assert_eq!(calendar.from_ymd(1500, 1, 1), Ok(LocalDate::ymd(2076, Month::November, 28).unwrap()));
+        assert_eq!(calendar.from_ymd(1600, 1, 1), Ok(LocalDate::ymd(2173, Month::December, 7).unwrap()));
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(2024, Month::April, 9).unwrap()), (1445, 9, 30));
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(2174, Month::November, 25).unwrap()), (1600, 12, 30));
+        assert_eq!(calendar.days_in_month(1446, 4), 30);
+        assert_eq!(calendar.days_in_month(1446, 5), 29);
+        assert_eq!(calendar.days_in_year(1445), 354);
+        assert_eq!(calendar.name(), "Umm al-Qura");
+    }
+
+    #[test]
+    fn umm_al_qura_matches_the_short_table() {
+        for year in 1445 .. 1447 {
+            for month in 1 ..= 12 {
+                assert_eq!(UMM_AL_QURA.from_ymd(year, month, 1), TWO_YEARS.from_ymd(year, month, 1));
+            }
+        }
+    }
+
+    #[test]
+    fn outside_the_table() {
+        let calendar = TWO_YEARS;
+        assert_eq!(calendar.from_ymd(1400, 1, 1), Err(Error::YearOutOfRange { given: 1400 }));
+        assert_eq!(calendar.from_ymd(1447, 1, 1), Err(Error::YearOutOfRange { given: 1447 }));
+        assert_eq!(calendar.checked_to_ymd(LocalDate::ymd(1979, Month::November, 21).unwrap()), None);
+        assert_eq!(calendar.checked_to_ymd(LocalDate::ymd(2025, Month::June, 26).unwrap()), None);
+        assert_eq!(calendar.checked_to_ymd(LocalDate::ymd(2025, Month::June, 25).unwrap()), Some((1446, 12, 29)));
+        assert_eq!(UMM_AL_QURA.checked_to_ymd(LocalDate::ymd(1882, Month::November, 11).unwrap()), None);
+        assert_eq!(UMM_AL_QURA.checked_to_ymd(LocalDate::ymd(2174, Month::November, 26).unwrap()), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn outside_the_table_without_a_fallback() {
+        let _ = TWO_YEARS.to_ymd(LocalDate::ymd(1979, Month::November, 21).unwrap());
+    }
+
+    #[test]
+    fn fallback() {
+        let calendar = UMM_AL_QURA;
+        assert_eq!(calendar.from_ymd(1200, 1, 1), TabularIslamic::CIVIL.from_ymd(1200, 1, 1));
+        assert_eq!(calendar.from_ymd(1601, 1, 1), TabularIslamic::CIVIL.from_ymd(1601, 1, 1));
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(1800, Month::January, 1).unwrap()), TabularIslamic::CIVIL.to_ymd(LocalDate::ymd(1800, Month::January, 1).unwrap()));
+        assert_eq!(calendar.to_ymd(LocalDate::ymd(1979, Month::November, 21).unwrap()), (1400, 1, 1));
+    }
+
+    #[test]
+    fn umm_al_qura_round_trip() {
+        let calendar = UMM_AL_QURA;
+        for day in LocalDate::ymd(1882, Month::November, 12).unwrap().julian_day() .. LocalDate::ymd(2174, Month::November, 26).unwrap().julian_day() {
+            let date = LocalDate::from_julian_day(day);
+            let (year, month, day) = calendar.to_ymd(date);
+            assert!(year >= 1300 && year <= 1600);
+            assert_eq!(calendar.checked_to_ymd(date), Some((year, month, day)));
+            assert_eq!(calendar.from_ymd(year, month, day), Ok(date));
+        }
+    }
+}
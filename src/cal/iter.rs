@@ -1,20 +1,69 @@
 //! Iterators through multiple datetimes.
 
-use std::ops::{Range, RangeFrom, RangeTo, RangeFull};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub, Range, RangeFrom, RangeTo, RangeFull};
 use std::slice::Iter as SliceIter;
 
-use cal::datetime::{LocalDate, Month, YMD};
+use cal::DatePiece;
+use cal::datetime::{LocalDate, Month, Weekday, WeekRule, is_leap_year, days_in_year};
 use cal::datetime::Error as DateTimeError;
 
 
-#[derive(PartialEq, Debug, Copy, Clone)]
+/// A year on its own, such as 2024.
+///
+/// Years are written and parsed in the ISO 8601 format, with at least four
+/// digits, and a minus sign for years before 1 BCE.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::iter::Year;
+///
+/// let year: Year = "2024".parse().unwrap();
+/// assert!(year.is_leap());
+/// assert_eq!(year.length(), 366);
+/// assert_eq!(year.first_day(), LocalDate::ymd(2024, Month::January, 1).unwrap());
+/// assert_eq!(year.months(..).count(), 12);
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
 pub struct Year(pub i64);
 
 impl Year {
     pub fn is_leap_year(&self) -> bool {
-        YMD { year: self.0, month: Month::January, day: 1 }
-            .leap_year_calculations()
-            .1
+        is_leap_year(self.0)
+    }
+
+    /// Returns whether this is a leap year. This is the same as
+    /// `is_leap_year`.
+    pub fn is_leap(&self) -> bool {
+        self.is_leap_year()
+    }
+
+    /// Returns the number of days in this year, either 365 or 366.
+    pub fn length(&self) -> i16 {
+        days_in_year(self.0)
+    }
+
+    /// Returns the 1st of January of this year.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented.
+    pub fn first_day(&self) -> LocalDate {
+        self.month(Month::January).first_day()
+    }
+
+    /// Returns the 31st of December of this year.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented.
+    pub fn last_day(&self) -> LocalDate {
+        self.month(Month::December).last_day()
     }
 
     pub fn months<S: MonthSpan>(&self, span: S) -> YearMonths {
@@ -61,12 +110,7 @@ impl MonthSpan for Range<Month> {
     }
 }
 
-static MONTHS: &'static [Month] = &[
-    Month::January,  Month::February,  Month::March,
-    Month::April,    Month::May,       Month::June,
-    Month::July,     Month::August,    Month::September,
-    Month::October,  Month::November,  Month::December,
-];
+static MONTHS: &'static [Month] = &Month::ALL;
 
 
 pub struct YearMonths {
@@ -94,13 +138,127 @@ impl DoubleEndedIterator for YearMonths {
     }
 }
 
-#[derive(PartialEq, Debug, Copy, Clone)]
+impl fmt::Display for Year {
+
+    /// Formats this year in the ISO 8601 format, such as `2024`,
+    /// `-0043`, or `+12345`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.0 < 0 {
+            write!(f, "{:05}", self.0)
+        }
+        else if self.0 > 9999 {
+            write!(f, "+{}", self.0)
+        }
+        else {
+            write!(f, "{:04}", self.0)
+        }
+    }
+}
+
+impl From<LocalDate> for Year {
+    fn from(date: LocalDate) -> Year {
+        Year(date.year())
+    }
+}
+
+
+/// A month in a particular year, such as July 2024.
+///
+/// Year-months are ordered chronologically, can have a number of months
+/// added to or subtracted from them, and are written and parsed in the
+/// ISO 8601 format, such as `2024-07`.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::iter::YearMonth;
+///
+/// let billing: YearMonth = "2024-11".parse().unwrap();
+/// assert_eq!((billing + 3).to_string(), "2025-02");
+/// assert_eq!((billing + 3).last_day(), LocalDate::ymd(2025, Month::February, 28).unwrap());
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
 pub struct YearMonth {
     year: Year,
     month: Month,
 }
 
 impl YearMonth {
+
+    /// Creates a year-month from a year and a month.
+    pub const fn new(year: i64, month: Month) -> YearMonth {
+        YearMonth { year: Year(year), month: month }
+    }
+
+    /// Returns the year that this month is in.
+    pub fn year(&self) -> i64 {
+        self.year.0
+    }
+
+    /// Returns the month of the year.
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    /// Returns the first day of this month.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented, which can only happen in the first and last years.
+    pub fn first_day(&self) -> LocalDate {
+        self.day(1).expect("date out of range")
+    }
+
+    /// Returns the last day of this month.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented, which can only happen in the first and last years.
+    pub fn last_day(&self) -> LocalDate {
+        self.day(self.day_count()).expect("date out of range")
+    }
+
+    /// Adds a number of months to this year-month, which may be negative,
+    /// returning `None` if the year would overflow.
+    pub fn checked_add(&self, months: i64) -> Option<YearMonth> {
+        let total = self.year.0.checked_mul(12)?
+                        .checked_add(self.month.months_from_january() as i64)?
+                        .checked_add(months)?;
+        let month = Month::from_zero(total.rem_euclid(12) as i8).unwrap();
+        Some(YearMonth::new(total.div_euclid(12), month))
+    }
+
+    /// Subtracts a number of months from this year-month, which may be
+    /// negative, returning `None` if the year would overflow.
+    pub fn checked_sub(&self, months: i64) -> Option<YearMonth> {
+        self.checked_add(months.checked_neg()?)
+    }
+
+    /// Returns the number of months from the given year-month until this
+    /// one, which is negative if the other one is later.
+    pub fn months_since(&self, earlier: YearMonth) -> i64 {
+        let months = |ym: &YearMonth| ym.year.0 as i128 * 12 + ym.month.months_from_january() as i128;
+        (months(self) - months(&earlier)) as i64
+    }
+
+    /// Returns an iterator over every month from this one up to, but not
+    /// including, the given one.
+    ///
+    /// ```rust
+    /// use datetime::Month;
+    /// use datetime::iter::YearMonth;
+    ///
+    /// let start = YearMonth::new(2024, Month::November);
+    /// let quarter: Vec<_> = start.until(start + 3).map(|ym| ym.to_string()).collect();
+    /// assert_eq!(quarter, vec![ "2024-11", "2024-12", "2025-01" ]);
+    /// ```
+    pub fn until(&self, end: YearMonth) -> YearMonthRange {
+        YearMonthRange { next: *self, end: end }
+    }
+
     pub fn day_count(&self) -> i8 {
         self.month.days_in_month(self.year.is_leap_year())
     }
@@ -117,6 +275,86 @@ impl YearMonth {
     }
 }
 
+impl fmt::Display for YearMonth {
+
+    /// Formats this year-month in the ISO 8601 format, such as `2024-07`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}-{:02}", self.year, self.month.to_one())
+    }
+}
+
+impl Add<i64> for YearMonth {
+    type Output = YearMonth;
+
+    fn add(self, months: i64) -> YearMonth {
+        self.checked_add(months).expect("year-month out of range")
+    }
+}
+
+impl Sub<i64> for YearMonth {
+    type Output = YearMonth;
+
+    fn sub(self, months: i64) -> YearMonth {
+        self.checked_sub(months).expect("year-month out of range")
+    }
+}
+
+impl Sub<YearMonth> for YearMonth {
+    type Output = i64;
+
+    fn sub(self, earlier: YearMonth) -> i64 {
+        self.months_since(earlier)
+    }
+}
+
+impl From<LocalDate> for YearMonth {
+    fn from(date: LocalDate) -> YearMonth {
+        YearMonth::new(date.year(), date.month())
+    }
+}
+
+
+/// An iterator over consecutive year-months, returned by
+/// `YearMonth::until`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct YearMonthRange {
+    next: YearMonth,
+    end: YearMonth,
+}
+
+impl Iterator for YearMonthRange {
+    type Item = YearMonth;
+
+    fn next(&mut self) -> Option<YearMonth> {
+        if self.next < self.end {
+            let current = self.next;
+            self.next = current + 1;
+            Some(current)
+        }
+        else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end.months_since(self.next).max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for YearMonthRange {
+    fn next_back(&mut self) -> Option<YearMonth> {
+        if self.next < self.end {
+            self.end = self.end - 1;
+            Some(self.end)
+        }
+        else {
+            None
+        }
+    }
+}
+
+
 pub trait DaySpan {
     fn get_range(&self, ym: &YearMonth) -> Range<i8>;
 }
@@ -168,6 +406,393 @@ impl<'ym> DoubleEndedIterator for MonthDays<'ym> {
     }
 }
 
+
+/// A day of the year without a year attached, such as a birthday or the
+/// date a subscription renews.
+///
+/// Any day that can happen in some year is valid, including the 29th of
+/// February, which only happens in leap years. What to do with it in
+/// other years is up to the caller: see `LeapDayPolicy`. Month-days are
+/// written and parsed in the ISO 8601 format, such as `--02-29`.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::iter::{MonthDay, LeapDayPolicy};
+///
+/// let birthday = MonthDay::new(Month::February, 29).unwrap();
+/// let today = LocalDate::ymd(2025, Month::January, 10).unwrap();
+///
+/// assert_eq!(birthday.next_occurrence(today), LocalDate::ymd(2028, Month::February, 29).unwrap());
+/// assert_eq!(birthday.next_occurrence_with(today, LeapDayPolicy::March1), LocalDate::ymd(2025, Month::March, 1).unwrap());
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
+pub struct MonthDay {
+    month: Month,
+    day: i8,
+}
+
+/// What to do with the 29th of February in a year that isn’t a leap year.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum LeapDayPolicy {
+
+    /// Skip the year entirely, so the day only happens in leap years.
+    Skip,
+
+    /// Use the 28th of February instead.
+    February28,
+
+    /// Use the 1st of March instead.
+    March1,
+}
+
+impl MonthDay {
+
+    /// Creates a month-day, returning an error if the day can’t happen in
+    /// that month in any year.
+    pub fn new(month: Month, day: i8) -> Result<MonthDay, DateTimeError> {
+        let max = month.days_in_month(true);
+        if day >= 1 && day <= max {
+            Ok(MonthDay { month: month, day: day })
+        }
+        else {
+            Err(DateTimeError::DayOutOfRange { given: day, max: max })
+        }
+    }
+
+    /// Returns the month.
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    /// Returns the day of the month.
+    pub fn day(&self) -> i8 {
+        self.day
+    }
+
+    /// Returns whether this is the 29th of February.
+    pub fn is_leap_day(&self) -> bool {
+        self.month == Month::February && self.day == 29
+    }
+
+    /// Returns whether this day happens in the given year, which is true
+    /// for every day except the 29th of February in years that aren’t leap
+    /// years.
+    pub fn is_valid_in(&self, year: i64) -> bool {
+        !self.is_leap_day() || is_leap_year(year)
+    }
+
+    /// Returns this day in the given year, or an error if it doesn’t
+    /// happen that year.
+    pub fn in_year(&self, year: i64) -> Result<LocalDate, DateTimeError> {
+        LocalDate::ymd(year, self.month, self.day)
+    }
+
+    /// Returns this day in the given year, using the policy to pick a day
+    /// for the 29th of February outside leap years. This returns `None`
+    /// only if the policy says to skip the year.
+    pub fn in_year_with(&self, year: i64, policy: LeapDayPolicy) -> Option<LocalDate> {
+        if self.is_valid_in(year) {
+            return self.in_year(year).ok();
+        }
+
+        match policy {
+            LeapDayPolicy::Skip        => None,
+            LeapDayPolicy::February28  => LocalDate::ymd(year, Month::February, 28).ok(),
+            LeapDayPolicy::March1      => LocalDate::ymd(year, Month::March, 1).ok(),
+        }
+    }
+
+    /// Returns the first date after the given one that falls on this day,
+    /// skipping years that it doesn’t happen in.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if there’s no such date that can be represented.
+    pub fn next_occurrence(&self, after: LocalDate) -> LocalDate {
+        self.next_occurrence_with(after, LeapDayPolicy::Skip)
+    }
+
+    /// Returns the first date after the given one that falls on this day,
+    /// using the policy to pick a day for the 29th of February outside
+    /// leap years.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if there’s no such date that can be represented.
+    pub fn next_occurrence_with(&self, after: LocalDate, policy: LeapDayPolicy) -> LocalDate {
+        // Leap years are never more than eight years apart, so there’s
+        // always a date within nine years of any other.
+        (after.year() .. after.year() + 9)
+            .filter_map(|year| self.in_year_with(year, policy))
+            .find(|date| *date > after)
+            .expect("date out of range")
+    }
+}
+
+impl fmt::Display for MonthDay {
+
+    /// Formats this month-day in the ISO 8601 format, such as `--02-29`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "--{:02}-{:02}", self.month.to_one(), self.day)
+    }
+}
+
+impl From<LocalDate> for MonthDay {
+    fn from(date: LocalDate) -> MonthDay {
+        MonthDay { month: date.month(), day: date.day() }
+    }
+}
+
+
+/// A week of seven days, numbered within a year. Unless another `WeekRule`
+/// is given, this is an ISO 8601 week, from a Monday to a Sunday.
+///
+/// Week 1 of an ISO year is the week with that year’s first Thursday in
+/// it, so the first few days of January can be in the last week of the
+/// year before, and the last few days of December can be in week 1 of the
+/// year after. Weeks are ordered chronologically, and are written and
+/// parsed in the ISO 8601 format, such as `2024-W05`.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::iter::Week;
+///
+/// let week = Week::from(LocalDate::ymd(2021, Month::January, 1).unwrap());
+/// assert_eq!(week.to_string(), "2020-W53");
+/// assert_eq!(week.first_day(), LocalDate::ymd(2020, Month::December, 28).unwrap());
+/// assert_eq!(week.days().count(), 7);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct Week {
+    year: Year,
+    week: i8,
+    rule: WeekRule,
+}
+
+impl Week {
+
+    /// Creates a week from an ISO year and a week number, returning an
+    /// error if the year doesn’t have that many weeks.
+    pub fn new(iso_year: i64, week: i8) -> Result<Week, DateTimeError> {
+        Week::new_in(WeekRule::ISO, iso_year, week)
+    }
+
+    /// Creates a week from a year and a week number using the given way of
+    /// numbering weeks, returning an error if the year doesn’t have that
+    /// many weeks.
+    pub fn new_in(rule: WeekRule, year: i64, week: i8) -> Result<Week, DateTimeError> {
+        let max = rule.weeks_in_year(year);
+        if week >= 1 && week <= max {
+            Ok(Week { year: Year(year), week: week, rule: rule })
+        }
+        else {
+            Err(DateTimeError::WeekOutOfRange { given: week, max: max })
+        }
+    }
+
+    /// Returns the week that the given date is in, using the given way of
+    /// numbering weeks.
+    pub fn containing(date: LocalDate, rule: WeekRule) -> Week {
+        let (year, week) = rule.week_date(&date);
+        Week { year: Year(year), week: week, rule: rule }
+    }
+
+    /// Returns the year that this week is numbered in, which isn’t always
+    /// the year that all of its days are in. For ISO weeks, this is the
+    /// ISO year.
+    pub fn iso_year(&self) -> i64 {
+        self.year.0
+    }
+
+    /// Returns the number of this week in its year, from 1 to 53.
+    pub fn week_number(&self) -> i8 {
+        self.week
+    }
+
+    /// Returns the way of numbering weeks that this week uses.
+    pub fn rule(&self) -> WeekRule {
+        self.rule
+    }
+
+    /// Returns the given day of this week.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented, which can only happen in the first and last years.
+    pub fn day(&self, weekday: Weekday) -> LocalDate {
+        let offset = self.rule.first_day.days_until(weekday) as i64;
+        LocalDate::from_julian_day(self.first_day().julian_day() + offset)
+    }
+
+    /// Returns the day that starts this week, which is a Monday for ISO
+    /// weeks.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented, which can only happen in the first and last years.
+    pub fn first_day(&self) -> LocalDate {
+        self.rule.start_of_week(self.year.0, self.week).expect("date out of range")
+    }
+
+    /// Returns the day that ends this week, which is a Sunday for ISO
+    /// weeks.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the day is outside the range of dates that can be
+    /// represented, which can only happen in the first and last years.
+    pub fn last_day(&self) -> LocalDate {
+        LocalDate::from_julian_day(self.first_day().julian_day() + 6)
+    }
+
+    /// Returns an iterator over the seven days of this week, in order from
+    /// its first day.
+    pub fn days(&self) -> WeekDays {
+        WeekDays {
+            first: self.first_day().julian_day(),
+            range: 0 .. 7,
+        }
+    }
+}
+
+impl PartialOrd for Week {
+    fn partial_cmp(&self, other: &Week) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Week {
+
+    /// Weeks are ordered by year and week number, so weeks that use the
+    /// same rule are in chronological order. Weekdays have no order of
+    /// their own, so weeks that only differ by rule are ordered by the
+    /// number of their first day from Sunday.
+    fn cmp(&self, other: &Week) -> Ordering {
+        let key = |week: &Week| (week.year, week.week, week.rule.first_day.number_from_sunday(), week.rule.minimal_days_in_first_week);
+        key(self).cmp(&key(other))
+    }
+}
+
+impl fmt::Display for Week {
+
+    /// Formats this week in the ISO 8601 format, such as `2024-W05`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}-W{:02}", self.year, self.week)
+    }
+}
+
+impl From<LocalDate> for Week {
+
+    /// Returns the week that the given date is in.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the Thursday of that week is outside the range of dates
+    /// that can be represented, which can only happen in the first and
+    /// last years.
+    fn from(date: LocalDate) -> Week {
+        Week::containing(date, WeekRule::ISO)
+    }
+}
+
+
+/// An iterator over the days of a week, returned by `Week::days`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct WeekDays {
+    first: i64,
+    range: Range<i64>,
+}
+
+impl Iterator for WeekDays {
+    type Item = LocalDate;
+
+    fn next(&mut self) -> Option<LocalDate> {
+        self.range.next().map(|offset| LocalDate::from_julian_day(self.first + offset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for WeekDays {
+    fn next_back(&mut self) -> Option<LocalDate> {
+        self.range.next_back().map(|offset| LocalDate::from_julian_day(self.first + offset))
+    }
+}
+
+/// Turns a sequence of boundary values into consecutive **half-open
+/// windows**, as `(start, end)` pairs.
+///
+/// Each boundary other than the first and last is used twice: once as
+/// the end of one window, and once as the start of the next. As the
+/// windows are half-open, the start value is included in the window but
+/// the end value isn’t, meaning that there are no gaps or overlaps
+/// between them. This is the shape that range queries usually want.
+///
+/// ```rust
+/// use datetime::{LocalDate, Month};
+/// use datetime::iter::{Year, windows};
+///
+/// let march = Year(2015).month(Month::March);
+/// let mut weeks = windows(march.days(..).step_by(7));
+///
+/// assert_eq!(weeks.next(), Some((LocalDate::ymd(2015, Month::March, 1).unwrap(),
+///                                LocalDate::ymd(2015, Month::March, 8).unwrap())));
+/// ```
+pub fn windows<I>(boundaries: I) -> Windows<I::IntoIter>
+where I: IntoIterator, I::Item: Clone {
+    Windows {
+        iter: boundaries.into_iter(),
+        previous: None,
+    }
+}
+
+/// An iterator over the half-open windows between boundary values. See
+/// the `windows` function for more information.
+#[derive(Debug, Clone)]
+pub struct Windows<I: Iterator> {
+    iter: I,
+    previous: Option<I::Item>,
+}
+
+impl<I> Iterator for Windows<I>
+where I: Iterator, I::Item: Clone {
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The first window has to take its start value from the
+        // iterator, but every other one re-uses the last end value.
+        let start = match self.previous.take().or_else(|| self.iter.next()) {
+            Some(start) => start,
+            None        => return None,
+        };
+
+        self.iter.next().map(|end| {
+            self.previous = Some(end.clone());
+            (start, end)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        if self.previous.is_some() {
+            (lower, upper)
+        }
+        else {
+            (lower.saturating_sub(1), upper.map(|u| u.saturating_sub(1)))
+        }
+    }
+}
+
+
 #[cfg(test)]
 mod test {
     pub use super::*;
@@ -285,4 +910,268 @@ mod test {
             assert_eq!(day, LocalDate::ymd(1066, October, 14));
         }
     }
+
+    mod years {
+        use super::*;
+        use cal::datetime::Month::*;
+
+        #[test]
+        fn lengths() {
+            assert!(Year(2000).is_leap());
+            assert!(!Year(1900).is_leap());
+            assert_eq!(Year(1900).length(), 365);
+            assert_eq!(Year(2000).length(), 366);
+        }
+
+        #[test]
+        fn first_and_last_days() {
+            assert_eq!(Year(1999).first_day(), LocalDate::ymd(1999, January, 1).unwrap());
+            assert_eq!(Year(1999).last_day(), LocalDate::ymd(1999, December, 31).unwrap());
+            assert_eq!(Year::from(Year(1999).last_day()), Year(1999));
+        }
+
+        #[test]
+        fn formatting() {
+            assert_eq!(Year(2024).to_string(), "2024");
+            assert_eq!(Year(33).to_string(), "0033");
+            assert_eq!(Year(-43).to_string(), "-0043");
+            assert_eq!(Year(12345).to_string(), "+12345");
+        }
+
+        #[test]
+        fn parsing() {
+            assert_eq!("2024".parse(), Ok(Year(2024)));
+            assert_eq!("-0043".parse(), Ok(Year(-43)));
+            assert_eq!("+12345".parse(), Ok(Year(12345)));
+            assert!("24".parse::<Year>().is_err());
+            assert!("20x4".parse::<Year>().is_err());
+            assert!("".parse::<Year>().is_err());
+        }
+    }
+
+    mod year_months {
+        use super::*;
+        use cal::parse::Error as ParseError;
+        use cal::datetime::Month::*;
+
+        #[test]
+        fn arithmetic() {
+            let ym = YearMonth::new(2024, January);
+            assert_eq!(ym - 1, YearMonth::new(2023, December));
+            assert_eq!(ym + 25, YearMonth::new(2026, February));
+            assert_eq!(ym + (-13), YearMonth::new(2022, December));
+            assert_eq!(YearMonth::new(2026, February) - ym, 25);
+            assert_eq!(YearMonth::new(i64::max_value(), December).checked_add(1), None);
+        }
+
+        #[test]
+        fn first_and_last_days() {
+            let february = YearMonth::new(2024, February);
+            assert_eq!(february.first_day(), LocalDate::ymd(2024, February, 1).unwrap());
+            assert_eq!(february.last_day(), LocalDate::ymd(2024, February, 29).unwrap());
+            assert_eq!(YearMonth::from(february.last_day()), february);
+        }
+
+        #[test]
+        fn ordering() {
+            assert!(YearMonth::new(2023, December) < YearMonth::new(2024, January));
+            assert!(YearMonth::new(2024, March) > YearMonth::new(2024, February));
+        }
+
+        #[test]
+        fn formatting() {
+            assert_eq!(YearMonth::new(2024, July).to_string(), "2024-07");
+            assert_eq!(YearMonth::new(-43, March).to_string(), "-0043-03");
+            assert_eq!(YearMonth::new(12345, October).to_string(), "+12345-10");
+        }
+
+        #[test]
+        fn parsing() {
+            assert_eq!("2024-07".parse(), Ok(YearMonth::new(2024, July)));
+            assert_eq!("-0043-03".parse(), Ok(YearMonth::new(-43, March)));
+            assert_eq!("+12345-10".parse(), Ok(YearMonth::new(12345, October)));
+            assert_eq!("2024-13".parse::<YearMonth>(), Err(ParseError::Date(DateTimeError::MonthOutOfRange { given: 13 })));
+            assert!("2024-7".parse::<YearMonth>().is_err());
+            assert!("24-07".parse::<YearMonth>().is_err());
+            assert!("2024-07-01".parse::<YearMonth>().is_err());
+            assert!("--2024-07".parse::<YearMonth>().is_err());
+            assert!("2024".parse::<YearMonth>().is_err());
+        }
+
+        #[test]
+        fn iteration() {
+            let start = YearMonth::new(2023, November);
+            let months = start.until(start + 4);
+            assert_eq!(months.size_hint(), (4, Some(4)));
+            assert_eq!(months.rev().collect::<Vec<_>>(), vec![
+                YearMonth::new(2024, February), YearMonth::new(2024, January),
+                YearMonth::new(2023, December), YearMonth::new(2023, November),
+            ]);
+            assert_eq!(start.until(start).next(), None);
+            assert_eq!(start.until(start - 1).next(), None);
+        }
+    }
+
+    mod month_days {
+        use super::*;
+        use cal::datetime::Month::*;
+
+        #[test]
+        fn validity() {
+            assert!(MonthDay::new(February, 29).is_ok());
+            assert_eq!(MonthDay::new(February, 30), Err(DateTimeError::DayOutOfRange { given: 30, max: 29 }));
+            assert_eq!(MonthDay::new(April, 31), Err(DateTimeError::DayOutOfRange { given: 31, max: 30 }));
+
+            let leap_day = MonthDay::new(February, 29).unwrap();
+            assert!(leap_day.is_valid_in(2024));
+            assert!(!leap_day.is_valid_in(2100));
+            assert!(leap_day.in_year(2023).is_err());
+        }
+
+        #[test]
+        fn policies() {
+            let leap_day = MonthDay::new(February, 29).unwrap();
+            assert_eq!(leap_day.in_year_with(2023, LeapDayPolicy::Skip), None);
+            assert_eq!(leap_day.in_year_with(2023, LeapDayPolicy::February28), LocalDate::ymd(2023, February, 28).ok());
+            assert_eq!(leap_day.in_year_with(2023, LeapDayPolicy::March1), LocalDate::ymd(2023, March, 1).ok());
+            assert_eq!(leap_day.in_year_with(2024, LeapDayPolicy::March1), LocalDate::ymd(2024, February, 29).ok());
+        }
+
+        #[test]
+        fn next_occurrence() {
+            let christmas = MonthDay::new(December, 25).unwrap();
+            let eve = LocalDate::ymd(2024, December, 24).unwrap();
+            assert_eq!(christmas.next_occurrence(eve), LocalDate::ymd(2024, December, 25).unwrap());
+
+            let day = LocalDate::ymd(2024, December, 25).unwrap();
+            assert_eq!(christmas.next_occurrence(day), LocalDate::ymd(2025, December, 25).unwrap());
+        }
+
+        #[test]
+        fn leap_day_across_a_century() {
+            let leap_day = MonthDay::new(February, 29).unwrap();
+            let after = LocalDate::ymd(2096, March, 1).unwrap();
+            assert_eq!(leap_day.next_occurrence(after), LocalDate::ymd(2104, February, 29).unwrap());
+        }
+
+        #[test]
+        fn formatting_and_parsing() {
+            let leap_day = MonthDay::new(February, 29).unwrap();
+            assert_eq!(leap_day.to_string(), "--02-29");
+            assert_eq!("--02-29".parse(), Ok(leap_day));
+            assert!("--02-30".parse::<MonthDay>().is_err());
+            assert!("02-29".parse::<MonthDay>().is_err());
+            assert!("--2-29".parse::<MonthDay>().is_err());
+        }
+
+        #[test]
+        fn from_date() {
+            let date = LocalDate::ymd(2015, June, 26).unwrap();
+            assert_eq!(MonthDay::from(date), MonthDay::new(June, 26).unwrap());
+        }
+    }
+
+    mod weeks {
+        use super::*;
+        use cal::parse::Error as ParseError;
+        use cal::datetime::Month::*;
+
+        #[test]
+        fn validity() {
+            assert!(Week::new(2020, 53).is_ok());
+            assert_eq!(Week::new(2021, 53), Err(DateTimeError::WeekOutOfRange { given: 53, max: 52 }));
+            assert_eq!(Week::new(2021, 0), Err(DateTimeError::WeekOutOfRange { given: 0, max: 52 }));
+        }
+
+        #[test]
+        fn from_dates_across_years() {
+            assert_eq!(Week::from(LocalDate::ymd(2008, December, 29).unwrap()), Week::new(2009, 1).unwrap());
+            assert_eq!(Week::from(LocalDate::ymd(2010, January, 3).unwrap()), Week::new(2009, 53).unwrap());
+            assert_eq!(Week::from(LocalDate::ymd(2024, June, 26).unwrap()), Week::new(2024, 26).unwrap());
+        }
+
+        #[test]
+        fn days() {
+            let week = Week::new(2009, 53).unwrap();
+            let days: Vec<_> = week.days().collect();
+            assert_eq!(days.len(), 7);
+            assert_eq!(days[0], LocalDate::ymd(2009, December, 28).unwrap());
+            assert_eq!(days[6], LocalDate::ymd(2010, January, 3).unwrap());
+            assert_eq!(week.days().rev().next(), Some(week.last_day()));
+            assert!(days.iter().all(|&day| Week::from(day) == week));
+        }
+
+        #[test]
+        fn formatting_and_parsing() {
+            let week = Week::new(2024, 5).unwrap();
+            assert_eq!(week.to_string(), "2024-W05");
+            assert_eq!("2024-W05".parse(), Ok(week));
+            assert_eq!("2024W05".parse(), Ok(week));
+            assert!("2024-W5".parse::<Week>().is_err());
+            assert!("2024-05".parse::<Week>().is_err());
+            assert_eq!("2021-W53".parse::<Week>(), Err(ParseError::Date(DateTimeError::WeekOutOfRange { given: 53, max: 52 })));
+        }
+
+        #[test]
+        fn ordering() {
+            assert!(Week::new(2020, 53).unwrap() < Week::new(2021, 1).unwrap());
+        }
+
+        #[test]
+        fn other_rules() {
+            let week = Week::containing(LocalDate::ymd(2022, January, 1).unwrap(), WeekRule::US);
+            assert_eq!(week, Week::new_in(WeekRule::US, 2022, 1).unwrap());
+            assert_eq!(week.first_day(), LocalDate::ymd(2021, December, 26).unwrap());
+            assert_eq!(week.day(Weekday::Monday), LocalDate::ymd(2021, December, 27).unwrap());
+            assert_eq!(week.last_day(), LocalDate::ymd(2022, January, 1).unwrap());
+            assert!(week.days().all(|day| Week::containing(day, WeekRule::US) == week));
+
+            assert!(Week::new_in(WeekRule::US, 2022, 53).is_ok());
+            assert_eq!(Week::new_in(WeekRule::US, 2021, 53), Err(DateTimeError::WeekOutOfRange { given: 53, max: 52 }));
+        }
+    }
+
+    mod windows {
+        use super::*;
+        use cal::datetime::LocalDate;
+        use cal::datetime::Month::*;
+
+        #[test]
+        fn consecutive_pairs() {
+            let days: Vec<_> = Year(2016).month(February).days(27..).collect();
+            let pairs: Vec<_> = windows(days.clone()).collect();
+            assert_eq!(pairs, vec![
+                (days[0], days[1]),
+                (days[1], days[2]),
+            ]);
+        }
+
+        #[test]
+        fn across_months() {
+            let firsts = Year(2015).months(..).map(|ym| ym.day(1).unwrap());
+            let months: Vec<_> = windows(firsts).collect();
+            assert_eq!(months.len(), 11);
+            assert_eq!(months[10], (LocalDate::ymd(2015, November, 1).unwrap(),
+                                    LocalDate::ymd(2015, December, 1).unwrap()));
+        }
+
+        #[test]
+        fn one_boundary() {
+            let pairs: Vec<(i8, i8)> = windows(vec![ 1 ]).collect();
+            assert!(pairs.is_empty());
+        }
+
+        #[test]
+        fn no_boundaries() {
+            let pairs: Vec<(i8, i8)> = windows(Vec::new()).collect();
+            assert!(pairs.is_empty());
+        }
+
+        #[test]
+        fn size_hint() {
+            let march = Year(2015).month(March);
+            let iter = windows(march.days(..).collect::<Vec<_>>());
+            assert_eq!(iter.size_hint(), (30, Some(30)));
+        }
+    }
 }
@@ -0,0 +1,197 @@
+//! The Japanese calendar, which numbers years within the era of each
+//! emperor’s reign.
+//!
+//! Japan has used the Gregorian calendar’s months and days since 1873, but
+//! counts its years from the start of the current era, so 2024 is the 6th
+//! year of the Reiwa era, written “Reiwa 6” or “令和6年”. A
+//! `JapaneseCalendar` finds the era a date is in from its table of eras:
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month, JapaneseCalendar};
+//!
+//! let date = LocalDate::ymd(2024, Month::June, 1).unwrap();
+//! let (era, year) = JapaneseCalendar::MODERN.era_of(date).unwrap();
+//! assert_eq!((era.name, era.native_name, year), ("Reiwa", "令和", 6));
+//! ```
+//!
+//! It also implements `CalendarSystem`, so `DateFormat::format_in` can use
+//! it to fill in the `{:N}` and `{:n}` fields with the era and the year in
+//! it.
+//!
+//! A new era starts whenever a new emperor takes the throne, and its name
+//! is only announced shortly beforehand, so the table of eras is something
+//! that can be given, rather than being fixed in this library:
+//!
+//! ```rust
+//! use datetime::{LocalDate, Month, JapaneseCalendar, JapaneseEra};
+//!
+//! const NEXT: JapaneseEra = JapaneseEra {
+//!     name: "Example",
+//!     native_name: "例",
+//!     start: match LocalDate::ymd(2060, Month::April, 1) {
+//!         Ok(date) => date,
+//!         Err(_)   => panic!("invalid era start"),
+//!     },
+//! };
+//!
+//! let eras = [ JapaneseCalendar::MODERN.eras[4], NEXT ];
+//! let calendar = JapaneseCalendar { eras: &eras };
+//!
+//! let (era, year) = calendar.era_of(LocalDate::ymd(2061, Month::January, 1).unwrap()).unwrap();
+//! assert_eq!((era.name, year), ("Example", 2));
+//! ```
+
+use cal::DatePiece;
+use cal::calendar::{CalendarSystem, EraYear, Gregorian};
+use cal::datetime::{LocalDate, Month, Error};
+
+
+/// One era in a `JapaneseCalendar`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct JapaneseEra<'a> {
+
+    /// The name of the era written in the Latin alphabet, such as “Reiwa”.
+    pub name: &'a str,
+
+    /// The name of the era written in kanji, such as “令和”.
+    pub native_name: &'a str,
+
+    /// The first day of the era, which is in its year 1.
+    pub start: LocalDate,
+}
+
+/// The Japanese calendar, with the given table of eras.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct JapaneseCalendar<'a> {
+
+    /// The eras in this calendar, in the order they started in. Each one
+    /// lasts until the next one starts.
+    pub eras: &'a [JapaneseEra<'a>],
+}
+
+impl JapaneseCalendar<'static> {
+
+    /// The eras from the Meiji era onwards, up to the Reiwa era that
+    /// started in 2019.
+    pub const MODERN: JapaneseCalendar<'static> = JapaneseCalendar {
+        eras: &[
+            JapaneseEra { name: "Meiji",   native_name: "明治", start: date(1868, Month::October,  23) },
+            JapaneseEra { name: "Taishō",  native_name: "大正", start: date(1912, Month::July,     30) },
+            JapaneseEra { name: "Shōwa",   native_name: "昭和", start: date(1926, Month::December, 25) },
+            JapaneseEra { name: "Heisei",  native_name: "平成", start: date(1989, Month::January,   8) },
+            JapaneseEra { name: "Reiwa",   native_name: "令和", start: date(2019, Month::May,       1) },
+        ],
+    };
+}
+
+impl<'a> JapaneseCalendar<'a> {
+
+    /// Returns the era that the given date is in and the date’s year in
+    /// it, or `None` if it’s before the first era in the table.
+    pub fn era_of(&self, date: LocalDate) -> Option<(&'a JapaneseEra<'a>, i64)> {
+        self.eras.iter()
+                 .rev()
+                 .find(|era| era.start <= date)
+                 .map(|era| (era, date.year() - era.start.year() + 1))
+    }
+
+    /// Returns the era with the given name, in either the Latin alphabet
+    /// or kanji.
+    pub fn era_named(&self, name: &str) -> Option<&'a JapaneseEra<'a>> {
+        self.eras.iter().find(|era| era.name == name || era.native_name == name)
+    }
+
+    /// Returns the date with the given year in the given era, month, and
+    /// day, returning an error if there’s no such day, or if it isn’t in
+    /// that era.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month, JapaneseCalendar};
+    ///
+    /// let calendar = JapaneseCalendar::MODERN;
+    /// let heisei = calendar.era_named("平成").unwrap();
+    /// assert_eq!(calendar.date(heisei, 31, 4, 30), LocalDate::ymd(2019, Month::April, 30));
+    /// assert!(calendar.date(heisei, 31, 5, 1).is_err());
+    /// ```
+    pub fn date(&self, era: &JapaneseEra, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        let date = try!(Gregorian.from_ymd(era.start.year() + year - 1, month, day));
+        match self.era_of(date) {
+            Some((found, _)) if found == era  => Ok(date),
+            _                                 => Err(Error::YearOutOfRange { given: year }),
+        }
+    }
+}
+
+impl<'a> CalendarSystem for JapaneseCalendar<'a> {
+    fn name(&self) -> &str {
+        "Japanese"
+    }
+
+    fn to_ymd(&self, date: LocalDate) -> (i64, i8, i8) {
+        Gregorian.to_ymd(date)
+    }
+
+    fn from_ymd(&self, year: i64, month: i8, day: i8) -> Result<LocalDate, Error> {
+        Gregorian.from_ymd(year, month, day)
+    }
+
+    fn days_in_month(&self, year: i64, month: i8) -> i8 {
+        Gregorian.days_in_month(year, month)
+    }
+
+    fn era_year(&self, date: LocalDate) -> Option<EraYear> {
+        self.era_of(date).map(|(era, year)| EraYear { name: era.name, native_name: era.native_name, year: year })
+    }
+}
+
+/// Returns the date with the given fields, which are known to be valid.
+const fn date(year: i64, month: Month, day: i8) -> LocalDate {
+    match LocalDate::ymd(year, month, day) {
+        Ok(date) => date,
+        Err(_)   => panic!("invalid era start"),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    #[test]
+    fn eras() {
+        let calendar = JapaneseCalendar::MODERN;
+        let era_of = |year, month, day| calendar.era_of(date(year, month, day)).map(|(era, year)| (era.name, year));
+
+        assert_eq!(era_of(1868, Month::October, 22), None);
+        assert_eq!(era_of(1868, Month::October, 23), Some(("Meiji", 1)));
+        assert_eq!(era_of(1912, Month::July, 29), Some(("Meiji", 45)));
+        assert_eq!(era_of(1912, Month::July, 30), Some(("Taishō", 1)));
+        assert_eq!(era_of(1989, Month::January, 7), Some(("Shōwa", 64)));
+        assert_eq!(era_of(1989, Month::January, 8), Some(("Heisei", 1)));
+        assert_eq!(era_of(2019, Month::April, 30), Some(("Heisei", 31)));
+        assert_eq!(era_of(2019, Month::May, 1), Some(("Reiwa", 1)));
+        assert_eq!(era_of(2024, Month::January, 1), Some(("Reiwa", 6)));
+    }
+
+    #[test]
+    fn dates_in_eras() {
+        let calendar = JapaneseCalendar::MODERN;
+        let reiwa = calendar.era_named("Reiwa").unwrap();
+        assert_eq!(calendar.era_named("令和"), Some(reiwa));
+        assert_eq!(calendar.era_named("Edo"), None);
+
+        assert_eq!(calendar.date(reiwa, 6, 2, 29), Ok(date(2024, Month::February, 29)));
+        assert_eq!(calendar.date(reiwa, 1, 4, 30), Err(Error::YearOutOfRange { given: 1 }));
+        assert_eq!(calendar.date(reiwa, 5, 2, 29), Err(Error::DayOutOfRange { given: 29, max: 28 }));
+    }
+
+    #[test]
+    fn era_years() {
+        let calendar = JapaneseCalendar::MODERN;
+        assert_eq!(calendar.era_year(date(2024, Month::June, 1)), Some(EraYear { name: "Reiwa", native_name: "令和", year: 6 }));
+        assert_eq!(calendar.era_year(date(1800, Month::June, 1)), None);
+        assert_eq!(calendar.to_ymd(date(2024, Month::June, 1)), (2024, 6, 1));
+    }
+}
@@ -1,14 +1,25 @@
 //! ISO-8601 date and time calculations, which use years, months, days,
 //! hours, minutes, and seconds.
 
+pub mod calendar;
 pub mod datetime;
+pub mod easter;
+pub mod fiscal;
+#[cfg(feature = "std")]
 pub mod format;
+#[cfg(feature = "hebrew")]
+pub mod hebrew;
+pub mod holiday;
+#[cfg(feature = "islamic")]
+pub mod islamic;
 pub mod iter;
+pub mod japanese;
 pub mod offset;
 pub mod parse;
+pub mod partial;
 pub mod zone;
 
-pub use self::datetime::{LocalDate, LocalTime, LocalDateTime, Weekday, Month};
+pub use self::datetime::{LocalDate, LocalTime, LocalDateTime, Weekday, Weekend, WeekRule, Month, Era};
 
 /// The **date piece** trait is used for date and time values that have
 /// date components of years, months, and days.
@@ -35,11 +46,64 @@ pub trait DatePiece {
     /// This is the same as the last two digits of the year.
     fn year_of_century(&self) -> i64 { self.year() % 100 }
 
+    /// The era this date is in, which is BC, or BCE, for the year 0 and
+    /// the years before it.
+    fn era(&self) -> Era { Era::of_year(self.year()) }
+
+    /// The year within the era, which is the same as the year for dates
+    /// from the year 1 onwards, while the year 0 is 1 BC, -1 is 2 BC, and
+    /// so on.
+    fn year_of_era(&self) -> i64 { Era::year_of_era(self.year()) }
+
     /// The year number, relative to the year 2000.
     /// Internally, many routines use years relative the year 2000,
     /// rather than the year 0 (well, 1 BCE).
     fn years_from_2000(&self) -> i64 { self.year() - 2000 }
 
+    /// Whether this date falls on a Saturday or a Sunday.
+    fn is_weekend(&self) -> bool { self.is_weekend_in(Weekend::SATURDAY_SUNDAY) }
+
+    /// Whether this date falls on any day from Monday to Friday.
+    fn is_weekday(&self) -> bool { !self.is_weekend() }
+
+    /// Whether this date falls on one of the days of the given weekend.
+    fn is_weekend_in(&self, weekend: Weekend) -> bool { weekend.contains(self.weekday()) }
+
+    /// Whether this date falls outside the given weekend.
+    fn is_weekday_in(&self, weekend: Weekend) -> bool { !self.is_weekend_in(weekend) }
+
+    /// The number of the ISO 8601 week this date is in, from 1 to 53.
+    ///
+    /// Weeks start on Monday, and the first week of a year is the one with
+    /// its first Thursday in it, so the first few days of January can be
+    /// in the last week of the year before, and the last few days of
+    /// December can be in week 1 of the year after. Use `iso_week_year`
+    /// to find out which year the week is numbered in.
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month, DatePiece};
+    ///
+    /// let date = LocalDate::ymd(2021, Month::January, 3).unwrap();
+    /// assert_eq!((date.iso_week_year(), date.iso_week()), (2020, 53));
+    ///
+    /// let date = LocalDate::ymd(2024, Month::December, 30).unwrap();
+    /// assert_eq!((date.iso_week_year(), date.iso_week()), (2025, 1));
+    /// ```
+    fn iso_week(&self) -> i8 { self.week_in(WeekRule::ISO) }
+
+    /// The year that this date’s ISO 8601 week is numbered in, which is
+    /// the year its Thursday is in. This is the same as `year` except for
+    /// a few days around New Year.
+    fn iso_week_year(&self) -> i64 { self.week_year_in(WeekRule::ISO) }
+
+    /// The number of the week this date is in, using the given way of
+    /// numbering weeks, from 1 to 53.
+    fn week_in(&self, rule: WeekRule) -> i8 { rule.week_date(self).1 }
+
+    /// The year that this date’s week is numbered in, using the given way
+    /// of numbering weeks.
+    fn week_year_in(&self, rule: WeekRule) -> i64 { rule.week_date(self).0 }
+
     // I’d ideally like to include “century” here, but there’s some
     // discrepancy over what the result should be: the Gregorian
     // calendar calls the span from 2000 to 2099 the “21st Century”, but
@@ -64,5 +128,13 @@ pub trait TimePiece {
     fn second(&self) -> i8;
 
     /// The millisecond of the second.
-    fn millisecond(&self) -> i16;
+    /// This is derived from the nanosecond, rounding down.
+    fn millisecond(&self) -> i16 { (self.nanosecond() / 1_000_000) as i16 }
+
+    /// The microsecond of the second.
+    /// This is derived from the nanosecond, rounding down.
+    fn microsecond(&self) -> i32 { self.nanosecond() / 1_000 }
+
+    /// The nanosecond of the second, from 0 to 999,999,999.
+    fn nanosecond(&self) -> i32;
 }
\ No newline at end of file
@@ -1,15 +1,19 @@
 //! Datetimes with a fixed UTC offset.
 
+use std::cmp::Ordering;
 use std::error::Error as ErrorTrait;
 use std::fmt;
 
 use duration::Duration;
 use cal::{DatePiece, TimePiece};
-use cal::datetime::{LocalDateTime, Month, Weekday, Error as DateTimeError};
-use util::RangeExt;
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday, Error as DateTimeError};
+use cal::zone::{TimeZone, ZonedDateTime};
+use instant::Instant;
 
 
-#[derive(PartialEq, Debug, Copy, Clone)]
+/// An offset from UTC. Offsets are ordered by how far ahead of UTC they
+/// are, with UTC itself coming before an offset of zero seconds.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
 pub struct Offset {
     offset_seconds: Option<i32>,
 }
@@ -26,8 +30,8 @@ impl Offset {
         Offset { offset_seconds: None }
     }
 
-    pub fn of_seconds(seconds: i32) -> Result<Offset, Error> {
-        if seconds.is_within(-86400..86401) {
+    pub const fn of_seconds(seconds: i32) -> Result<Offset, Error> {
+        if seconds >= -86400 && seconds <= 86400 {
             Ok(Offset { offset_seconds: Some(seconds) })
         }
         else {
@@ -49,7 +53,7 @@ impl Offset {
         else {
             let hours = hours as i32;
             let minutes = minutes as i32;
-            Offset::of_seconds(hours * 24 + minutes * 60)
+            Offset::of_seconds(hours * 3600 + minutes * 60)
         }
     }
 
@@ -59,9 +63,49 @@ impl Offset {
             offset: self.clone(),
         }
     }
+
+    /// Pairs a time of day with this offset.
+    pub fn transform_time(&self, time: LocalTime) -> OffsetTime {
+        OffsetTime {
+            time: time,
+            offset: self.clone(),
+        }
+    }
+
+    /// Returns the number of seconds this offset is ahead of UTC, which is
+    /// zero for UTC itself.
+    pub(crate) fn seconds(&self) -> i32 {
+        self.offset_seconds.unwrap_or(0)
+    }
+}
+
+
+impl fmt::Display for Offset {
+
+    /// Formats this offset in the ISO 8601 format: `Z` for UTC, or the
+    /// hours and minutes ahead of or behind it, such as `+05:30`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.offset_seconds {
+            None => f.write_str("Z"),
+            Some(s) => {
+                let sign = if s < 0 { '-' } else { '+' };
+                let s = s.abs();
+                try!(write!(f, "{}{:02}:{:02}", sign, s / 3600, s / 60 % 60));
+
+                if s % 60 != 0 {
+                    try!(write!(f, ":{:02}", s % 60));
+                }
+
+                Ok(())
+            }
+        }
+    }
 }
 
 
+/// An error that occurs when creating an offset, or a date-time with an
+/// offset, from values that don’t make sense.
+#[non_exhaustive]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum Error {
     OutOfRange,
@@ -84,7 +128,7 @@ impl ErrorTrait for Error {
         }
     }
 
-    fn cause(&self) -> Option<&ErrorTrait> {
+    fn source(&self) -> Option<&(ErrorTrait + 'static)> {
         if let Error::Date(ref e) = *self {
             Some(e)
         }
@@ -94,13 +138,166 @@ impl ErrorTrait for Error {
     }
 }
 
+impl From<DateTimeError> for Error {
+    fn from(error: DateTimeError) -> Error {
+        Error::Date(error)
+    }
+}
 
-#[derive(PartialEq, Debug, Copy, Clone)]
+
+/// A local date-time paired with its offset from UTC.
+///
+/// Offset date-times are ordered by the instant they represent, no matter
+/// which offsets they’re in, so `12:00+01:00` comes before `11:30Z`. Two
+/// that represent the same instant in different offsets aren’t equal, so
+/// they’re ordered by their offsets to keep the ordering consistent with
+/// `==`.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
 pub struct OffsetDateTime {
     local: LocalDateTime,
     offset: Offset,
 }
 
+impl OffsetDateTime {
+
+    /// Returns the number of seconds since the Unix epoch and the
+    /// nanosecond of the second at which this date-time happens in UTC,
+    /// without any chance of overflowing.
+    fn utc_key(&self) -> (i128, i32) {
+        let seconds = self.local.to_instant().seconds() as i128 - self.offset.seconds() as i128;
+        (seconds, self.local.nanosecond())
+    }
+
+    /// Returns the local date-time, as it was given to `transform_date`.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDateTime, Offset};
+    ///
+    /// let local = LocalDateTime::at(1_000_000_000);
+    /// let datetime = Offset::of_hours_and_minutes(5, 30).unwrap().transform_date(local);
+    /// assert_eq!(datetime.local(), local);
+    /// ```
+    pub fn local(&self) -> LocalDateTime {
+        self.local
+    }
+
+    /// Returns the local date-time, as it was given to `transform_date`.
+    /// This is the same as `local`.
+    pub fn to_local(&self) -> LocalDateTime {
+        self.local
+    }
+
+    /// Returns the offset from UTC.
+    pub fn offset(&self) -> Offset {
+        self.offset
+    }
+
+    /// Returns the instant this date-time represents, which is the local
+    /// date-time moved back by the offset.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{Instant, OffsetDateTime};
+    ///
+    /// let datetime: OffsetDateTime = "2001-09-09T07:16:40+05:30".parse().unwrap();
+    /// assert_eq!(datetime.to_instant(), Instant::at(1_000_000_000));
+    /// ```
+    pub fn to_instant(&self) -> Instant {
+        self.local.to_instant() - Duration::of(self.offset.seconds() as i64)
+    }
+
+    /// Returns the same instant in the given time zone, using whichever
+    /// offset the zone has in effect at that point.
+    pub fn in_zone<'a>(&self, zone: &TimeZone<'a>) -> ZonedDateTime<'a> {
+        zone.zone_utc(self.local - Duration::of(self.offset.seconds() as i64))
+    }
+}
+
+/// A time of day paired with its offset from UTC, such as
+/// `10:15:30+01:00`, without a date. This is the same as SQL’s
+/// `TIME WITH TIME ZONE`.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, LocalTime, Month, OffsetTime};
+///
+/// let opening: OffsetTime = "09:00:00+01:00".parse().unwrap();
+/// assert_eq!(opening.to_utc(), LocalTime::hms(8, 0, 0).unwrap());
+///
+/// let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+/// assert_eq!(opening.at_date(date).to_string(), "2015-06-26T09:00:00+01:00");
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct OffsetTime {
+    time: LocalTime,
+    offset: Offset,
+}
+
+impl OffsetTime {
+
+    /// Returns the local time, as it was given to `transform_time`.
+    pub fn time(&self) -> LocalTime {
+        self.time
+    }
+
+    /// Returns the offset from UTC.
+    pub fn offset(&self) -> Offset {
+        self.offset
+    }
+
+    /// Returns the date-time at this time on the given date, with the same
+    /// offset.
+    ///
+    /// ### Panics
+    ///
+    /// Panics in the same cases as `LocalDateTime::new`.
+    pub fn at_date(&self, date: LocalDate) -> OffsetDateTime {
+        self.offset.transform_date(LocalDateTime::new(date, self.time))
+    }
+
+    /// Returns the time of day in UTC at this time, wrapping around
+    /// midnight if the offset moves it into a different day.
+    pub fn to_utc(&self) -> LocalTime {
+        let seconds = (self.time.to_seconds() - self.offset.seconds() as i64).rem_euclid(86400);
+        LocalTime::from_seconds_and_nanoseconds_since_midnight(seconds, self.time.nanosecond())
+    }
+}
+
+impl fmt::Display for OffsetTime {
+
+    /// Formats this time in the ISO 8601 format, with its offset, such as
+    /// `10:15:30+01:00`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}{}", self.time, self.offset)
+    }
+}
+
+impl fmt::Display for OffsetDateTime {
+
+    /// Formats this date-time in the ISO 8601 format, with its offset,
+    /// such as `2015-06-26T14:30:00+01:00`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}{}", self.local, self.offset)
+    }
+}
+
+impl PartialOrd for OffsetDateTime {
+    fn partial_cmp(&self, other: &OffsetDateTime) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OffsetDateTime {
+    fn cmp(&self, other: &OffsetDateTime) -> Ordering {
+        self.utc_key().cmp(&other.utc_key())
+            .then(self.offset.cmp(&other.offset))
+    }
+}
+
 impl DatePiece for OffsetDateTime {
     fn year(&self) -> i64 {
         self.offset.adjust(self.local).year()
@@ -136,8 +333,8 @@ impl TimePiece for OffsetDateTime {
         self.offset.adjust(self.local).second()
     }
 
-    fn millisecond(&self) -> i16 {
-        self.offset.adjust(self.local).millisecond()
+    fn nanosecond(&self) -> i32 {
+        self.offset.adjust(self.local).nanosecond()
     }
 }
 
@@ -145,6 +342,7 @@ impl TimePiece for OffsetDateTime {
 #[cfg(test)]
 mod test {
     use super::Offset;
+    use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month};
 
     #[test]
     fn fixed_seconds() {
@@ -180,4 +378,102 @@ mod test {
     fn fixed_hm_signs_zero() {
         assert!(Offset::of_hours_and_minutes(4, 0).is_ok());
     }
+
+    #[test]
+    fn fixed_hm_seconds() {
+        assert_eq!(Offset::of_hours_and_minutes(5, 30), Offset::of_seconds(5 * 3600 + 30 * 60));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Offset::utc().to_string(), "Z");
+        assert_eq!(Offset::of_hours_and_minutes(5, 30).unwrap().to_string(), "+05:30");
+        assert_eq!(Offset::of_hours_and_minutes(-3, -45).unwrap().to_string(), "-03:45");
+        assert_eq!(Offset::of_seconds(0).unwrap().to_string(), "+00:00");
+        assert_eq!(Offset::of_seconds(3661).unwrap().to_string(), "+01:01:01");
+    }
+
+    #[test]
+    fn display_datetime() {
+        let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+        let time = LocalTime::hms(14, 30, 0).unwrap();
+        let offset = Offset::of_hours_and_minutes(1, 0).unwrap();
+        let datetime = offset.transform_date(LocalDateTime::new(date, time));
+        assert_eq!(datetime.to_string(), "2015-06-26T14:30:00+01:00");
+    }
+
+    #[test]
+    fn ordered_by_instant() {
+        let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+        let noon_in_paris = Offset::of_hours_and_minutes(1, 0).unwrap()
+                                   .transform_date(LocalDateTime::new(date, LocalTime::hms(12, 0, 0).unwrap()));
+        let half_eleven_utc = Offset::utc()
+                                     .transform_date(LocalDateTime::new(date, LocalTime::hms(11, 30, 0).unwrap()));
+        assert!(noon_in_paris < half_eleven_utc);
+    }
+
+    #[test]
+    fn same_instant_ordered_by_offset() {
+        let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+        let utc = Offset::utc().transform_date(LocalDateTime::new(date, LocalTime::hms(11, 0, 0).unwrap()));
+        let zero = Offset::of_seconds(0).unwrap().transform_date(LocalDateTime::new(date, LocalTime::hms(11, 0, 0).unwrap()));
+        let paris = Offset::of_hours_and_minutes(1, 0).unwrap().transform_date(LocalDateTime::new(date, LocalTime::hms(12, 0, 0).unwrap()));
+
+        let mut datetimes = vec![ paris, zero, utc ];
+        datetimes.sort();
+        assert_eq!(datetimes, vec![ utc, zero, paris ]);
+    }
+
+    #[test]
+    fn extremes_do_not_overflow() {
+        let first = Offset::of_seconds(86400).unwrap().transform_date(LocalDateTime::at(i64::min_value()));
+        let last = Offset::of_seconds(-86400).unwrap().transform_date(LocalDateTime::at(i64::max_value()));
+        assert!(first < last);
+    }
+
+    #[test]
+    fn accessors() {
+        let local = LocalDateTime::new(LocalDate::ymd(2015, Month::June, 26).unwrap(), LocalTime::hms(12, 0, 0).unwrap());
+        let offset = Offset::of_hours_and_minutes(-3, -30).unwrap();
+        let datetime = offset.transform_date(local);
+        assert_eq!(datetime.local(), local);
+        assert_eq!(datetime.to_local(), local);
+        assert_eq!(datetime.offset(), offset);
+        assert_eq!(datetime.to_instant(), LocalDateTime::new(local.date(), LocalTime::hms(15, 30, 0).unwrap()).to_instant());
+        assert_eq!(Offset::utc().transform_date(local).to_instant(), local.to_instant());
+    }
+
+    #[test]
+    fn offset_times() {
+        let time = Offset::of_hours_and_minutes(-3, -30).unwrap().transform_time(LocalTime::hms(22, 0, 0).unwrap());
+        assert_eq!(time.to_string(), "22:00:00-03:30");
+        assert_eq!(time.to_utc(), LocalTime::hms(1, 30, 0).unwrap());
+        assert_eq!("22:00:00-03:30".parse(), Ok(time));
+
+        let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+        assert_eq!(time.at_date(date).local(), LocalDateTime::new(date, time.time()));
+        assert_eq!(time.at_date(date).offset(), time.offset());
+    }
+
+    #[test]
+    fn offset_times_keep_their_nanoseconds() {
+        let time = Offset::of_hours_and_minutes(1, 0).unwrap().transform_time(LocalTime::hms_ns(0, 30, 0, 5).unwrap());
+        assert_eq!(time.to_utc(), LocalTime::hms_ns(23, 30, 0, 5).unwrap());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("Z".parse().ok(), Some(Offset::utc()));
+        assert_eq!("+05:30".parse().ok(), Offset::of_hours_and_minutes(5, 30).ok());
+        assert_eq!("-0800".parse().ok(), Offset::of_hours_and_minutes(-8, 0).ok());
+        assert_eq!("+01".parse().ok(), Offset::of_hours_and_minutes(1, 0).ok());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("05:30".parse::<Offset>().is_err());
+        assert!("+5:30".parse::<Offset>().is_err());
+        assert!("+05:3".parse::<Offset>().is_err());
+        assert!("+05:60".parse::<Offset>().is_err());
+    }
 }
@@ -1,101 +1,495 @@
+#[cfg(feature = "std")]
+use std::convert::TryFrom;
 use std::error::Error as ErrorTrait;
 use std::fmt;
 use std::str::FromStr;
 
-use iso8601;
+#[cfg(feature = "std")]
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use cal::datetime::{Month, Error as DateTimeError};
+use cal::iter::{Year, YearMonth, MonthDay, Week};
+use cal::offset::{Offset, Error as OffsetError};
 
-use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday, Error as DateTimeError};
-use cal::offset::{Offset, OffsetDateTime, Error as OffsetError};
+// The full date and time formats are parsed by the `iso8601` crate, which
+// needs the standard library.
+#[cfg(feature = "std")]
+use iso8601;
+#[cfg(feature = "std")]
+use cal::TimePiece;
+#[cfg(feature = "std")]
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Weekday};
+#[cfg(feature = "std")]
+use cal::offset::{OffsetDateTime, OffsetTime};
+#[cfg(feature = "std")]
+use cal::partial::PartialDateTime;
+#[cfg(feature = "std")]
+use instant::Instant;
 
 
+#[cfg(feature = "std")]
 impl FromStr for LocalDate {
     type Err = Error<DateTimeError>;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match iso8601::date(input) {
-            Ok(fields)  => fields_to_date(fields).map_err(Error::Date),
+        let (year, input) = expanded_year(input);
+        match iso8601::date(&input) {
+            Ok(fields)  => local_date(fields, year).map_err(Error::Date),
             Err(e)      => Err(Error::Parse(e)),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl FromStr for LocalTime {
     type Err = Error<DateTimeError>;
 
     fn from_str(input: &str) -> Result<LocalTime, Self::Err> {
+        let nanosecond = try!(fraction(input));
         match iso8601::time(input) {
-            Ok(fields)  => fields_to_time(fields).map_err(Error::Date),
+            Ok(fields)  => local_time(fields, nanosecond).map_err(Error::Date),
             Err(e)      => Err(Error::Parse(e)),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl FromStr for LocalDateTime {
     type Err = Error<DateTimeError>;
 
     fn from_str(input: &str) -> Result<LocalDateTime, Self::Err> {
-        let fields = match iso8601::datetime(input) {
+        let nanosecond = try!(fraction(input));
+        let (year, input) = expanded_year(input);
+        match iso8601::datetime(&input) {
+            Ok(fields)  => local_datetime(fields, year, nanosecond).map_err(Error::Date),
+            Err(e)      => Err(Error::Parse(e)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for OffsetDateTime {
+    type Err = Error<OffsetError>;
+
+    fn from_str(input: &str) -> Result<OffsetDateTime, Self::Err> {
+        let nanosecond = try!(fraction(input));
+        let (year, input) = expanded_year(input);
+        match iso8601::datetime(&input) {
+            Ok(fields)  => offset_datetime(fields, year, nanosecond).map_err(Error::Date),
+            Err(e)      => Err(Error::Parse(e)),
+        }
+    }
+}
+
+/// Returns the number of nanoseconds in the fraction of a second at the
+/// end of an ISO 8601 time, or 0 if it doesn’t have one.
+///
+/// The `iso8601` crate reads all the digits after the decimal point into
+/// its `millisecond` field as one number, so it can’t tell `.5` from
+/// `.500000`, and it panics if there are too many of them to fit. The
+/// fraction gets read from the input itself instead, and more than nine
+/// digits, which is more precision than a `LocalTime` has, is an error.
+#[cfg(feature = "std")]
+fn fraction<E: ErrorTrait>(input: &str) -> Result<i32, Error<E>> {
+    let digits = match input.find('.') {
+        Some(index)  => &input[index + 1 ..],
+        None         => return Ok(0),
+    };
+
+    let length = digits.find(|c: char| !c.is_digit(10)).unwrap_or(digits.len());
+    if length == 0 || length > 9 {
+        return Err(Error::Parse(format!("Parser Error: {}", input)));
+    }
+
+    let nanosecond: i32 = digits[.. length].parse().unwrap();
+    Ok(nanosecond * 10_i32.pow(9 - length as u32))
+}
+
+/// Takes a year with more than four digits off the front of an ISO 8601
+/// date, returning it along with the input with a four-digit year in its
+/// place, or `None` and the input unchanged if its year is shorter.
+///
+/// The `iso8601` crate only reads four digits of year into an `i32`, but
+/// ISO 8601 allows more when the year has a sign in front of it, and a
+/// `LocalDate` can have years that don’t fit in an `i32` anyway. Only the
+/// extended format, with a `-` after the year, is read this way: in the
+/// basic format, there’s no telling where the year ends.
+#[cfg(feature = "std")]
+fn expanded_year(input: &str) -> (Option<i64>, Cow<str>) {
+    if !input.starts_with(|c: char| c == '+' || c == '-') {
+        return (None, Cow::Borrowed(input));
+    }
+
+    let length = 1 + input[1..].find(|c: char| !c.is_digit(10)).unwrap_or(input.len() - 1);
+    if length <= 5 || !input[length ..].starts_with('-') {
+        return (None, Cow::Borrowed(input));
+    }
+
+    // A year with too many digits to fit in an `i64` is well past the end
+    // of what a `LocalDate` can hold, so it gets rejected as out of range
+    // rather than as bad input.
+    let year = input[.. length].parse().unwrap_or(if input.starts_with('-') { i64::min_value() } else { i64::max_value() });
+    (Some(year), Cow::Owned(format!("0000{}", &input[length ..])))
+}
+
+/// Converts the fields of a date parsed by the `iso8601` crate, using the
+/// given year instead of its `year` field if there is one.
+#[cfg(feature = "std")]
+fn local_date(fields: iso8601::Date, year: Option<i64>) -> Result<LocalDate, DateTimeError> {
+    match fields {
+        iso8601::Date::YMD { year: four_digits, month, day } => {
+            let month_variant = try!(Month::from_one(month as i8));
+            LocalDate::ymd(year.unwrap_or(four_digits as i64), month_variant, day as i8)
+        }
+
+        iso8601::Date::Week { year: four_digits, ww, d } => {
+            let weekday_variant = try!(Weekday::from_one(d as i8));
+            LocalDate::ywd(year.unwrap_or(four_digits as i64), ww as i64, weekday_variant)
+        }
+
+        iso8601::Date::Ordinal { year: four_digits, ddd } => {
+            LocalDate::yd(year.unwrap_or(four_digits as i64), ddd as i64)
+        }
+    }
+}
+
+/// Converts the fields of a time parsed by the `iso8601` crate, using the
+/// given nanosecond instead of its `millisecond` field. ISO 8601 allows a
+/// leap second at the end of an hour, so this does too.
+#[cfg(feature = "std")]
+fn local_time(fields: iso8601::Time, nanosecond: i32) -> Result<LocalTime, DateTimeError> {
+    LocalTime::hms_ns_leap(fields.hour as i8, fields.minute as i8, fields.second as i8, nanosecond)
+}
+
+/// Converts the fields of a date-time parsed by the `iso8601` crate, using
+/// the given year and nanosecond instead of its `year` and `millisecond`
+/// fields.
+#[cfg(feature = "std")]
+fn local_datetime(fields: iso8601::DateTime, year: Option<i64>, nanosecond: i32) -> Result<LocalDateTime, DateTimeError> {
+    let date = try!(local_date(fields.date, year));
+    let time = try!(local_time(fields.time, nanosecond));
+    Ok(LocalDateTime::new(date, time))
+}
+
+/// Converts the fields of a date-time parsed by the `iso8601` crate, along
+/// with its UTC offset, using the given year and nanosecond instead of its
+/// `year` and `millisecond` fields.
+#[cfg(feature = "std")]
+fn offset_datetime(fields: iso8601::DateTime, year: Option<i64>, nanosecond: i32) -> Result<OffsetDateTime, OffsetError> {
+    let local  = try!(local_datetime(fields, year, nanosecond).map_err(OffsetError::Date));
+    let offset = try!(Offset::of_hours_and_minutes(fields.time.tz_offset_hours as i8, fields.time.tz_offset_minutes as i8));
+    Ok(offset.transform_date(local))
+}
+
+
+#[cfg(feature = "std")]
+impl TryFrom<iso8601::Date> for LocalDate {
+    type Error = DateTimeError;
+
+    /// Converts a date that has been parsed by the `iso8601` crate, in any
+    /// of its calendar, week, or ordinal forms.
+    fn try_from(fields: iso8601::Date) -> Result<LocalDate, DateTimeError> {
+        local_date(fields, None)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<iso8601::Time> for LocalTime {
+    type Error = DateTimeError;
+
+    /// Converts a time that has been parsed by the `iso8601` crate,
+    /// taking its `millisecond` field to be a number of milliseconds. Its
+    /// UTC offset, if it has one, is ignored.
+    fn try_from(fields: iso8601::Time) -> Result<LocalTime, DateTimeError> {
+        let nanosecond = try!(i32::try_from(fields.millisecond).ok()
+                                  .and_then(|ms| ms.checked_mul(1_000_000))
+                                  .ok_or(DateTimeError::OutOfRange));
+
+        local_time(fields, nanosecond)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<iso8601::DateTime> for LocalDateTime {
+    type Error = DateTimeError;
+
+    /// Converts a date-time that has been parsed by the `iso8601` crate.
+    /// Its UTC offset, if it has one, is ignored.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// extern crate datetime;
+    /// extern crate iso8601;
+    ///
+    /// use std::convert::TryFrom;
+    /// use datetime::{LocalDateTime, DatePiece};
+    ///
+    /// fn main() {
+    ///     let fields = iso8601::datetime("2015-06-26T14:30:00").unwrap();
+    ///     let datetime = LocalDateTime::try_from(fields).unwrap();
+    ///     assert_eq!(datetime.year(), 2015);
+    /// }
+    /// ```
+    fn try_from(fields: iso8601::DateTime) -> Result<LocalDateTime, DateTimeError> {
+        let time = try!(LocalTime::try_from(fields.time));
+        local_datetime(fields, None, time.nanosecond())
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<iso8601::DateTime> for OffsetDateTime {
+    type Error = OffsetError;
+
+    /// Converts a date-time that has been parsed by the `iso8601` crate,
+    /// along with its UTC offset.
+    fn try_from(fields: iso8601::DateTime) -> Result<OffsetDateTime, OffsetError> {
+        let time = try!(LocalTime::try_from(fields.time).map_err(OffsetError::Date));
+        offset_datetime(fields, None, time.nanosecond())
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for OffsetTime {
+    type Err = Error<OffsetError>;
+
+    /// Parses an ISO 8601 time with an offset, such as `10:15:30+01:00`.
+    /// Times without an offset are taken to be in UTC.
+    fn from_str(input: &str) -> Result<OffsetTime, Self::Err> {
+        let nanosecond = try!(fraction(input));
+        let fields = match iso8601::time(input) {
             Ok(fields)  => fields,
             Err(e)      => return Err(Error::Parse(e)),
         };
 
-        let date = try!(fields_to_date(fields.date).map_err(Error::Date));
-        let time = try!(fields_to_time(fields.time).map_err(Error::Date));
-        Ok(LocalDateTime::new(date, time))
+        let time = try!(local_time(fields, nanosecond).map_err(|e| Error::Date(OffsetError::Date(e))));
+        let offset = try!(Offset::of_hours_and_minutes(fields.tz_offset_hours as i8, fields.tz_offset_minutes as i8));
+        Ok(offset.transform_time(time))
     }
 }
 
-impl FromStr for OffsetDateTime {
+#[cfg(feature = "std")]
+impl FromStr for Instant {
     type Err = Error<OffsetError>;
 
-    fn from_str(input: &str) -> Result<OffsetDateTime, Self::Err> {
-        let fields = match iso8601::datetime(input) {
+    /// Parses an ISO 8601 date-time with an offset, such as
+    /// `2001-09-09T01:46:40Z`, into the instant it refers to. Date-times
+    /// without an offset are taken to be in UTC.
+    fn from_str(input: &str) -> Result<Instant, Self::Err> {
+        let nanosecond = try!(fraction(input));
+        let (year, input) = expanded_year(input);
+        let fields = match iso8601::datetime(&input) {
             Ok(fields)  => fields,
             Err(e)      => return Err(Error::Parse(e)),
         };
 
-        let date   = try!(fields_to_date(fields.date).map_err(|e| Error::Date(OffsetError::Date(e))));
-        let time   = try!(fields_to_time(fields.time).map_err(|e| Error::Date(OffsetError::Date(e))));
-        let offset = try!(Offset::of_hours_and_minutes(fields.time.tz_offset_hours as i8, fields.time.tz_offset_minutes as i8).map_err(Error::Date));
-        Ok(offset.transform_date(LocalDateTime::new(date, time)))
+        let datetime = try!(offset_datetime(fields, year, nanosecond).map_err(Error::Date));
+        Ok(datetime.to_instant())
     }
 }
 
+impl FromStr for Offset {
+    type Err = Error<OffsetError>;
+
+    /// Parses an ISO 8601 offset: either `Z` for UTC, or a sign followed by
+    /// hours and optional minutes, such as `+05:30`, `-0800`, or `+01`.
+    fn from_str(input: &str) -> Result<Offset, Self::Err> {
+        if input == "Z" {
+            return Ok(Offset::utc());
+        }
+
+        let (sign, rest) = match input.chars().next() {
+            Some('+') => ( 1, &input[1..]),
+            Some('-') => (-1, &input[1..]),
+            _         => return Err(Error::Parse(format!("Parser Error: {}", input))),
+        };
 
-fn fields_to_date(fields: iso8601::Date) -> Result<LocalDate, DateTimeError> {
-    if let iso8601::Date::YMD { year, month, day } = fields {
-        let month_variant = try!(Month::from_one(month as i8));
-        LocalDate::ymd(year as i64, month_variant, day as i8)
+        let digits: String = rest.chars().filter(|&c| c != ':').collect();
+        let valid = (digits.len() == 2 || digits.len() == 4)
+                 && digits.chars().all(|c| c.is_digit(10))
+                 && (rest.len() == digits.len() || rest.find(':') == Some(2) && rest.len() == 5);
+
+        if !valid {
+            return Err(Error::Parse(format!("Parser Error: {}", input)));
+        }
+
+        let hours: i8   = digits[0..2].parse().unwrap();
+        let minutes: i8 = if digits.len() == 4 { digits[2..4].parse().unwrap() } else { 0 };
+        Offset::of_hours_and_minutes(sign * hours, sign * minutes).map_err(Error::Date)
     }
-    else if let iso8601::Date::Week { year, ww, d } = fields {
-        let weekday_variant = try!(Weekday::from_one(d as i8));
-        LocalDate::ywd(year as i64, ww as i64, weekday_variant)
+}
+
+
+impl FromStr for Year {
+    type Err = Error<DateTimeError>;
+
+    /// Parses an ISO 8601 year of at least four digits, such as `2024`.
+    /// Years before 1 BCE have a minus sign in front of them, like they do
+    /// in dates, and years after 9999 may have a plus sign.
+    fn from_str(input: &str) -> Result<Year, Self::Err> {
+        let digits = input.trim_start_matches(|c| c == '-' || c == '+');
+        if input.len() - digits.len() > 1 || digits.len() < 4 || !digits.chars().all(|c| c.is_digit(10)) {
+            return Err(Error::Parse(format!("Parser Error: {}", input)));
+        }
+
+        match input.parse() {
+            Ok(year) => Ok(Year(year)),
+            Err(_)   => Err(Error::Date(DateTimeError::OutOfRange)),
+        }
+    }
+}
+
+impl FromStr for YearMonth {
+    type Err = Error<DateTimeError>;
+
+    /// Parses an ISO 8601 year and month, such as `2024-07`, with the year
+    /// written the same way as it is on its own.
+    fn from_str(input: &str) -> Result<YearMonth, Self::Err> {
+        let bad_input = || Error::Parse(format!("Parser Error: {}", input));
+
+        let (year, month) = match input.rfind('-') {
+            Some(index) if index > 0 => (&input[.. index], &input[index + 1 ..]),
+            _                        => return Err(bad_input()),
+        };
+
+        if month.len() != 2 || !month.chars().all(|c| c.is_digit(10)) {
+            return Err(bad_input());
+        }
+
+        let year: Year = try!(year.parse().map_err(|e| match e {
+            Error::Parse(_) => bad_input(),
+            other           => other,
+        }));
+
+        let month = try!(Month::from_one(month.parse().unwrap()));
+        Ok(year.month(month))
     }
-    else if let iso8601::Date::Ordinal { year, ddd } = fields {
-        LocalDate::yd(year as i64, ddd as i64)
+}
+
+
+impl FromStr for Week {
+    type Err = Error<DateTimeError>;
+
+    /// Parses an ISO 8601 week, such as `2024-W05`, or `2024W05` in the
+    /// basic format, with the year written the same way as it is on its
+    /// own.
+    fn from_str(input: &str) -> Result<Week, Self::Err> {
+        let bad_input = || Error::Parse(format!("Parser Error: {}", input));
+
+        let (year, week) = match input.rfind('W') {
+            Some(index) => (input[.. index].trim_end_matches('-'), &input[index + 1 ..]),
+            None        => return Err(bad_input()),
+        };
+
+        if year.len() + 2 < input.len() - week.len() || week.len() != 2 || !week.chars().all(|c| c.is_digit(10)) {
+            return Err(bad_input());
+        }
+
+        let year: Year = try!(year.parse().map_err(|e| match e {
+            Error::Parse(_) => bad_input(),
+            other           => other,
+        }));
+
+        Week::new(year.0, week.parse().unwrap()).map_err(Error::Date)
     }
-    else {
-        unreachable!()  // should be unnecessary??
+}
+
+
+impl FromStr for MonthDay {
+    type Err = Error<DateTimeError>;
+
+    /// Parses an ISO 8601 month and day without a year, such as
+    /// `--02-29`.
+    fn from_str(input: &str) -> Result<MonthDay, Self::Err> {
+        let bytes = input.as_bytes();
+        let valid = bytes.len() == 7 && &bytes[0..2] == b"--" && bytes[4] == b'-'
+                 && bytes[2..4].iter().chain(&bytes[5..7]).all(|b| b.is_ascii_digit());
+
+        if !valid {
+            return Err(Error::Parse(format!("Parser Error: {}", input)));
+        }
+
+        let month = try!(Month::from_one(input[2..4].parse().unwrap()));
+        MonthDay::new(month, input[5..7].parse().unwrap()).map_err(Error::Date)
     }
 }
 
-fn fields_to_time(fields: iso8601::Time) -> Result<LocalTime, DateTimeError> {
-    let h  = fields.hour as i8;
-    let m  = fields.minute as i8;
-    let s  = fields.second as i8;
-    let ms = fields.millisecond as i16;
 
-    LocalTime::hms_ms(h, m, s, ms)
+#[cfg(feature = "std")]
+impl FromStr for PartialDateTime {
+    type Err = Error<OffsetError>;
+
+    /// Parses an ISO 8601 date-time that may be missing its less precise
+    /// parts: a year such as `2024`, a year and month such as `2024-07`, a
+    /// month and day such as `--07-04`, or a full date, optionally followed
+    /// by a time and then an offset.
+    fn from_str(input: &str) -> Result<PartialDateTime, Self::Err> {
+        fn from_date_error(error: Error<DateTimeError>) -> Error<OffsetError> {
+            match error {
+                Error::Date(e)  => Error::Date(OffsetError::Date(e)),
+                Error::Parse(s) => Error::Parse(s),
+            }
+        }
+
+        let (date, time) = match input.find('T') {
+            Some(index) => (&input[.. index], Some(&input[index + 1 ..])),
+            None        => (input, None),
+        };
+
+        let dashes = date.trim_start_matches(|c| c == '-' || c == '+').matches('-').count();
+        let mut partial = if date.starts_with("--") {
+            PartialDateTime::from(try!(date.parse::<MonthDay>().map_err(from_date_error)))
+        }
+        else if dashes == 0 {
+            PartialDateTime::from(try!(date.parse::<Year>().map_err(from_date_error)))
+        }
+        else if dashes == 1 {
+            // This is either a year and month, or a year and day of the
+            // year, such as `2024-183`, which the date parser handles.
+            match date.parse::<YearMonth>() {
+                Ok(ym)               => PartialDateTime::from(ym),
+                Err(Error::Parse(_)) => PartialDateTime::from(try!(date.parse::<LocalDate>().map_err(from_date_error))),
+                Err(e)               => return Err(from_date_error(e)),
+            }
+        }
+        else {
+            PartialDateTime::from(try!(date.parse::<LocalDate>().map_err(from_date_error)))
+        };
+
+        if let Some(time) = time {
+            if !partial.has_date() {
+                return Err(Error::Parse(format!("Parser Error: {}", input)));
+            }
+
+            let (time, offset) = match time.find(|c| c == 'Z' || c == '+' || c == '-') {
+                Some(index) => (&time[.. index], Some(&time[index ..])),
+                None        => (time, None),
+            };
+
+            partial.time = Some(try!(time.parse::<LocalTime>().map_err(from_date_error)));
+            partial.offset = match offset {
+                Some("Z")    => Some(Offset::utc()),
+                Some(offset) => Some(try!(offset.parse())),
+                None         => None,
+            };
+        }
+
+        Ok(partial)
+    }
 }
 
 
+/// An error that occurs when parsing a string: either it isn’t in the
+/// ISO 8601 format at all, or it is, but the value it describes is invalid.
+#[non_exhaustive]
 #[derive(PartialEq, Debug, Clone)]
 pub enum Error<E: ErrorTrait> {
     Date(E),
     Parse(String),
 }
 
-impl<E: ErrorTrait> fmt::Display for Error<E> {
+impl<E: ErrorTrait + 'static> fmt::Display for Error<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             Error::Date(ref error)   => write!(f, "{}: {}", self.description(), error),
@@ -104,7 +498,7 @@ impl<E: ErrorTrait> fmt::Display for Error<E> {
     }
 }
 
-impl<E: ErrorTrait> ErrorTrait for Error<E> {
+impl<E: ErrorTrait + 'static> ErrorTrait for Error<E> {
     fn description(&self) -> &str {
         match *self {
             Error::Date(_)     => "parsing resulted in an invalid date",
@@ -112,10 +506,48 @@ impl<E: ErrorTrait> ErrorTrait for Error<E> {
         }
     }
 
-    fn cause(&self) -> Option<&ErrorTrait> {
+    fn source(&self) -> Option<&(ErrorTrait + 'static)> {
         match *self {
             Error::Date(ref error)   => Some(error),
             Error::Parse(_)          => None,
         }
     }
 }
+
+impl<E: ErrorTrait> From<E> for Error<E> {
+    fn from(error: E) -> Error<E> {
+        Error::Date(error)
+    }
+}
+
+
+/// The reason a string couldn’t be parsed at all, as opposed to being in
+/// the ISO 8601 format but describing an invalid value. This is what the
+/// `Parse` variant of the crate-wide error holds.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SyntaxError {
+    message: String,
+}
+
+impl SyntaxError {
+
+    /// Returns the message from the parser saying what it couldn’t read.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<String> for SyntaxError {
+    fn from(message: String) -> SyntaxError {
+        SyntaxError { message: message }
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl ErrorTrait for SyntaxError {
+}
@@ -0,0 +1,191 @@
+//! Date-times where only some of the fields are known.
+
+use cal::{DatePiece, TimePiece};
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Error as DateTimeError};
+use cal::iter::{Year, YearMonth, MonthDay};
+use cal::offset::{Offset, OffsetDateTime, Error as OffsetError};
+
+
+/// A date-time that may be missing some of its fields, such as one parsed
+/// from `2024-07`, which has a year and a month but no day or time.
+///
+/// Each field is `None` if it wasn’t known. Calling `resolve` fills in
+/// the missing fields from a complete date-time, so code that has to deal
+/// with incomplete data can leave the choice of defaults to whoever knows
+/// what they should be.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{LocalDate, LocalDateTime, LocalTime, Month, PartialDateTime};
+///
+/// let partial: PartialDateTime = "2024-07".parse().unwrap();
+/// assert_eq!(partial.day, None);
+///
+/// let defaults = LocalDateTime::new(LocalDate::ymd(2000, Month::January, 1).unwrap(), LocalTime::NOON);
+/// assert_eq!(partial.resolve_local(defaults).unwrap().to_string(), "2024-07-01T12:00:00");
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Default, Copy, Clone)]
+pub struct PartialDateTime {
+
+    /// The year, if it’s known.
+    pub year: Option<i64>,
+
+    /// The month, if it’s known.
+    pub month: Option<Month>,
+
+    /// The day of the month, if it’s known.
+    pub day: Option<i8>,
+
+    /// The time of day, if it’s known.
+    pub time: Option<LocalTime>,
+
+    /// The offset from UTC, if it’s known.
+    pub offset: Option<Offset>,
+}
+
+impl PartialDateTime {
+
+    /// Returns whether every field is known, so resolving this won’t use
+    /// any of the defaults.
+    pub fn is_complete(&self) -> bool {
+        self.year.is_some() && self.month.is_some() && self.day.is_some()
+            && self.time.is_some() && self.offset.is_some()
+    }
+
+    /// Returns whether all of the date fields are known.
+    pub fn has_date(&self) -> bool {
+        self.year.is_some() && self.month.is_some() && self.day.is_some()
+    }
+
+    /// Fills in any missing date and time fields from the given date-time,
+    /// ignoring the offset, and returns an error if the result isn’t a
+    /// valid date-time.
+    pub fn resolve_local(&self, defaults: LocalDateTime) -> Result<LocalDateTime, DateTimeError> {
+        LocalDateTime::builder()
+            .year(self.year.unwrap_or(defaults.year()))
+            .month(self.month.unwrap_or(defaults.month()))
+            .day(self.day.unwrap_or(defaults.day()))
+            .hour(self.time.unwrap_or(defaults.time()).hour())
+            .minute(self.time.unwrap_or(defaults.time()).minute())
+            .second(self.time.unwrap_or(defaults.time()).second())
+            .nanosecond(self.time.unwrap_or(defaults.time()).nanosecond())
+            .build()
+    }
+
+    /// Fills in any missing fields, including the offset, from the given
+    /// date-time, and returns an error if the result isn’t a valid
+    /// date-time.
+    pub fn resolve(&self, defaults: OffsetDateTime) -> Result<OffsetDateTime, OffsetError> {
+        let local = try!(self.resolve_local(defaults.local()));
+        Ok(self.offset.unwrap_or(defaults.offset()).transform_date(local))
+    }
+}
+
+impl From<Year> for PartialDateTime {
+    fn from(year: Year) -> PartialDateTime {
+        PartialDateTime { year: Some(year.0), .. PartialDateTime::default() }
+    }
+}
+
+impl From<YearMonth> for PartialDateTime {
+    fn from(ym: YearMonth) -> PartialDateTime {
+        PartialDateTime { year: Some(ym.year()), month: Some(ym.month()), .. PartialDateTime::default() }
+    }
+}
+
+impl From<MonthDay> for PartialDateTime {
+    fn from(md: MonthDay) -> PartialDateTime {
+        PartialDateTime { month: Some(md.month()), day: Some(md.day()), .. PartialDateTime::default() }
+    }
+}
+
+impl From<LocalDate> for PartialDateTime {
+    fn from(date: LocalDate) -> PartialDateTime {
+        PartialDateTime { year: Some(date.year()), month: Some(date.month()), day: Some(date.day()), .. PartialDateTime::default() }
+    }
+}
+
+impl From<LocalTime> for PartialDateTime {
+    fn from(time: LocalTime) -> PartialDateTime {
+        PartialDateTime { time: Some(time), .. PartialDateTime::default() }
+    }
+}
+
+impl From<LocalDateTime> for PartialDateTime {
+    fn from(datetime: LocalDateTime) -> PartialDateTime {
+        PartialDateTime { time: Some(datetime.time()), .. PartialDateTime::from(datetime.date()) }
+    }
+}
+
+impl From<OffsetDateTime> for PartialDateTime {
+    fn from(datetime: OffsetDateTime) -> PartialDateTime {
+        PartialDateTime { offset: Some(datetime.offset()), .. PartialDateTime::from(datetime.local()) }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    use cal::parse::Error as ParseError;
+
+    fn defaults() -> OffsetDateTime {
+        let local = LocalDateTime::new(LocalDate::ymd(2000, Month::January, 1).unwrap(), LocalTime::hms(12, 0, 0).unwrap());
+        Offset::utc().transform_date(local)
+    }
+
+    #[test]
+    fn nothing_known() {
+        let partial = PartialDateTime::default();
+        assert!(!partial.has_date());
+        assert_eq!(partial.resolve(defaults()), Ok(defaults()));
+    }
+
+    #[test]
+    fn everything_known() {
+        let local = LocalDateTime::new(LocalDate::ymd(2015, Month::June, 26).unwrap(), LocalTime::hms(9, 30, 0).unwrap());
+        let datetime = Offset::of_hours_and_minutes(1, 0).unwrap().transform_date(local);
+        let partial = PartialDateTime::from(datetime);
+        assert!(partial.is_complete());
+        assert_eq!(partial.resolve(defaults()), Ok(datetime));
+    }
+
+    #[test]
+    fn invalid_combination() {
+        let partial = PartialDateTime { month: Some(Month::February), day: Some(30), .. PartialDateTime::default() };
+        assert_eq!(partial.resolve_local(defaults().local()), Err(DateTimeError::DayOutOfRange { given: 30, max: 29 }));
+    }
+
+    #[test]
+    fn parse_reduced_precision() {
+        assert_eq!("2024".parse(), Ok(PartialDateTime::from(Year(2024))));
+        assert_eq!("2024-07".parse(), Ok(PartialDateTime::from(YearMonth::new(2024, Month::July))));
+        assert_eq!("--12-25".parse(), Ok(PartialDateTime::from(MonthDay::new(Month::December, 25).unwrap())));
+        assert_eq!("2024-07-01".parse(), Ok(PartialDateTime::from(LocalDate::ymd(2024, Month::July, 1).unwrap())));
+        assert_eq!("2024-183".parse(), Ok(PartialDateTime::from(LocalDate::ymd(2024, Month::July, 1).unwrap())));
+    }
+
+    #[test]
+    fn parse_times_and_offsets() {
+        let partial: PartialDateTime = "2024-07-01T10:15".parse().unwrap();
+        assert_eq!(partial.time, Some(LocalTime::hm(10, 15).unwrap()));
+        assert_eq!(partial.offset, None);
+
+        let partial: PartialDateTime = "2024-07-01T10:15:00-08:00".parse().unwrap();
+        assert_eq!(partial.offset, Some(Offset::of_hours_and_minutes(-8, 0).unwrap()));
+
+        let partial: PartialDateTime = "2024-07-01T10:15:00Z".parse().unwrap();
+        assert_eq!(partial.offset, Some(Offset::utc()));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("July".parse::<PartialDateTime>().is_err());
+        assert!("2024-07-01T".parse::<PartialDateTime>().is_err());
+        match "2024-13".parse::<PartialDateTime>() {
+            Err(ParseError::Date(_)) => {},
+            other => panic!("expected an invalid date, got {:?}", other),
+        }
+    }
+}
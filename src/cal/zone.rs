@@ -2,7 +2,8 @@
 
 use duration::Duration;
 use instant::Instant;
-use cal::{LocalDateTime, DatePiece, TimePiece, Month, Weekday};
+use cal::{LocalDate, LocalTime, LocalDateTime, DatePiece, TimePiece, Month, Weekday};
+use cal::offset::{Offset, OffsetDateTime};
 use util::RangeExt;
 
 
@@ -18,6 +19,18 @@ pub struct TimeZone<'a> {
     pub fixed_timespans: FixedTimespanSet<'a>,
 }
 
+impl TimeZone<'static> {
+
+    /// The UTC time zone, which has a single timespan with no offset.
+    pub const UTC: TimeZone<'static> = TimeZone {
+        name: "UTC",
+        fixed_timespans: FixedTimespanSet {
+            first: FixedTimespan { offset: 0, is_dst: false, name: "UTC" },
+            rest: &[],
+        },
+    };
+}
+
 impl<'a> TimeZone<'a> {
 
     /// Returns the total offset from UTC, in seconds, that this time zone
@@ -52,6 +65,17 @@ impl<'a> TimeZone<'a> {
         datetime + Duration::of(self.offset(datetime))
     }
 
+    /// Converts a local datetime in UTC to a zoned datetime that uses this
+    /// time zone, keeping track of the offset in effect at that instant.
+    pub(crate) fn zone_utc(&self, datetime: LocalDateTime) -> ZonedDateTime<'a> {
+        let offset = self.offset(datetime);
+        ZonedDateTime {
+            adjusted: datetime + Duration::of(offset),
+            current_offset: offset,
+            time_zone: self.clone(),
+        }
+    }
+
     /// Converts a local datetime that is *already* informally in this time
     /// zone into a zoned datetime that actually uses this time zone.
     ///
@@ -84,8 +108,6 @@ impl<'a> TimeZone<'a> {
             assert!(timespans.current.offset != previous_zone.offset,
                     "Offsets cannot be equal! Is this a non-transition transition?");
 
-            println!("unix timestamp {:?}, previous time {:?}", unix_timestamp, previous_transition_time);
-
             // Test whether this timestamp is in the *overlap* after the
             // current timespan starts but before the previous one ends.
             if previous_zone.offset > timespans.current.offset
@@ -109,9 +131,6 @@ impl<'a> TimeZone<'a> {
             assert!(timespans.current.offset != next_zone.offset,
                 "Offsets cannot be equal! Is this a non-transition transition?");
 
-            println!("unix timestamp {:?}, next time {:?}", unix_timestamp, next_transition_time);
-            println!("offset 1 {:?}, offset 2 {:?}", next_zone.offset, timespans.current.offset);
-
             // Test whether this timestamp is in the *overlap* after the
             // next timespan starts but before the current one ends.
             if timespans.current.offset > next_zone.offset
@@ -132,6 +151,70 @@ impl<'a> TimeZone<'a> {
 
         LocalTimes::Precise(zonify(timespans.current.offset))
     }
+
+    /// Returns the first instant after the given one at which a wall clock
+    /// in this time zone shows the given time, such as for working out
+    /// when a job that runs at the same time every day should next run.
+    ///
+    /// On days when the clocks change, the time may happen twice, or not
+    /// at all. If it happens twice, only the earlier one counts, so a daily
+    /// job runs once. If it gets skipped, the time is moved forwards by the
+    /// length of the gap, so a job at 02:30 on a day when the clocks jump
+    /// from 02:00 to 03:00 runs at 03:30. This can push it into the next
+    /// day, when the gap is just before midnight.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the next occurrence would be outside the range of dates
+    /// that can be represented.
+    pub fn next_occurrence_of(&self, time: LocalTime, after: Instant) -> Instant {
+        // Start from the day before, as a gap can push that day’s
+        // occurrence past midnight and into the day the given instant is on.
+        let today = self.to_zoned(LocalDateTime::from_instant(after)).date();
+        let mut date = LocalDate::checked_from_julian_day(today.julian_day() - 1).unwrap_or(today);
+
+        loop {
+            let instant = self.earliest_instant_at(LocalDateTime::new(date, time));
+            if instant > after {
+                return instant;
+            }
+
+            date = LocalDate::from_julian_day(date.julian_day() + 1);
+        }
+    }
+
+    /// Returns the earliest instant at which a wall clock in this time zone
+    /// shows the given local time, or if it gets skipped, the instant it
+    /// would have been had the clocks not changed.
+    fn earliest_instant_at(&self, local: LocalDateTime) -> Instant {
+        let unix_timestamp = local.to_instant().seconds();
+        let timespans = &self.fixed_timespans;
+
+        // Taking a timespan’s offset away from the local time gives the
+        // instant that a clock would show it at, which only counts if that
+        // instant is in the timespan. The timespans are in order, so the
+        // first one that counts gives the earliest instant.
+        for index in 0 .. timespans.rest.len() + 1 {
+            let (start, timespan, end) = timespans.bounds(index);
+            let instant = unix_timestamp - timespan.offset;
+
+            if start.map_or(true, |start| instant > start) && end.map_or(true, |end| instant <= end) {
+                return local.to_instant() - Duration::of(timespan.offset);
+            }
+        }
+
+        // Otherwise, the local time is in the space between two timespans:
+        // it would have been after the earlier one ends, but it would have
+        // been before the later one starts.
+        for &(transition_time, ref next) in timespans.rest {
+            let previous = timespans.find(transition_time);
+            if unix_timestamp - previous.offset > transition_time && unix_timestamp - next.offset <= transition_time {
+                return local.to_instant() - Duration::of(previous.offset);
+            }
+        }
+
+        unreachable!("local time is neither in a timespan nor between two")
+    }
 }
 
 
@@ -173,6 +256,21 @@ pub struct FixedTimespan<'a> {
 }
 
 impl<'a> FixedTimespanSet<'a> {
+
+    /// Returns the timespan with the given index, counting the first one as
+    /// 0, along with the transition times that it starts after and ends
+    /// at, or `None` if it goes on forever in that direction.
+    fn bounds(&self, index: usize) -> (Option<i64>, &FixedTimespan, Option<i64>) {
+        let end = self.rest.get(index).map(|&(time, _)| time);
+        if index == 0 {
+            (None, &self.first, end)
+        }
+        else {
+            let (start, ref timespan) = self.rest[index - 1];
+            (Some(start), timespan, end)
+        }
+    }
+
     fn find(&self, time: i64) -> &FixedTimespan {
         match self.rest.iter().take_while(|t| t.0 < time).last() {
             None     => &self.first,
@@ -281,9 +379,45 @@ pub struct ZonedDateTime<'a> {
 }
 
 impl<'a> ZonedDateTime<'a> {
+
+    /// Returns the current date and time in the given time zone.
+    #[cfg(feature = "std")]
+    pub fn now(zone: &TimeZone<'a>) -> ZonedDateTime<'a> {
+        zone.zone_utc(LocalDateTime::now())
+    }
+
     pub fn to_instant(&self) -> Instant {
         (self.adjusted - Duration::of(self.current_offset)).to_instant()
     }
+
+    /// Returns this datetime with the offset that’s in effect for it fixed
+    /// in place, dropping the rest of the time zone’s transitions. The
+    /// result has the same local time, and represents the same instant.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the time zone’s offset is a day or more, which never
+    /// happens with offsets from the zoneinfo database.
+    pub fn to_offset_datetime(&self) -> OffsetDateTime {
+        let offset = Offset::of_seconds(self.current_offset as i32).expect("offset out of range");
+        offset.transform_date(self.adjusted)
+    }
+
+    /// Returns the amount of time that actually passes between this
+    /// datetime and the given one, which is negative if the other one is
+    /// earlier. This is measured on the instant timeline, so a day that
+    /// crosses into daylight-saving time is only 23 hours long.
+    pub fn duration_until(&self, other: &ZonedDateTime) -> Duration {
+        other.to_instant() - self.to_instant()
+    }
+
+    /// Returns the difference between the local times of this datetime and
+    /// the given one, as they’d be read off a wall clock, which is negative
+    /// if the other one is earlier. Changes in the zone’s offset are
+    /// ignored, so noon to noon is always 24 hours.
+    pub fn calendar_difference(&self, other: &ZonedDateTime) -> Duration {
+        other.adjusted - self.adjusted
+    }
 }
 
 impl<'a> DatePiece for ZonedDateTime<'a> {
@@ -298,7 +432,7 @@ impl<'a> TimePiece for ZonedDateTime<'a> {
     fn hour(&self) -> i8 { self.adjusted.hour() }
     fn minute(&self) -> i8 { self.adjusted.minute() }
     fn second(&self) -> i8 { self.adjusted.second() }
-    fn millisecond(&self) -> i16 { self.adjusted.millisecond() }
+    fn nanosecond(&self) -> i32 { self.adjusted.nanosecond() }
 }
 
 
@@ -541,6 +675,70 @@ mod test {
         assert_eq!(instant, zoned_date.to_instant());
     }
 
+    #[test]
+    fn to_offset_datetime() {
+        let test_date = LocalDateTime::new(
+            LocalDate::ymd(2010, Month::June, 9).unwrap(),
+            LocalTime::hms(15, 15, 0).unwrap(),
+        );
+
+        let zoned_date = TEST_ZONESET.convert_local(test_date).unwrap_precise();
+        let offset_date = zoned_date.to_offset_datetime();
+        assert_eq!(offset_date.local(), test_date);
+        assert_eq!(offset_date.offset(), Offset::of_hours_and_minutes(1, 0).unwrap());
+        assert_eq!(offset_date.to_instant(), zoned_date.to_instant());
+    }
+
+    #[test]
+    fn offset_datetime_in_zone() {
+        let summer = LocalDateTime::new(
+            LocalDate::ymd(2010, Month::June, 9).unwrap(),
+            LocalTime::hms(9, 15, 0).unwrap(),
+        );
+
+        let winter = LocalDateTime::new(
+            LocalDate::ymd(2010, Month::December, 9).unwrap(),
+            LocalTime::hms(9, 15, 0).unwrap(),
+        );
+
+        let new_york = Offset::of_hours_and_minutes(-5, 0).unwrap();
+
+        let zoned_summer = new_york.transform_date(summer).in_zone(&TEST_ZONESET);
+        assert_eq!(zoned_summer.hour(), 15);
+        assert_eq!(zoned_summer.to_instant(), new_york.transform_date(summer).to_instant());
+
+        let zoned_winter = new_york.transform_date(winter).in_zone(&TEST_ZONESET);
+        assert_eq!(zoned_winter.hour(), 14);
+        assert_eq!(zoned_winter.to_offset_datetime().offset(), Offset::of_seconds(0).unwrap());
+    }
+
+    #[test]
+    fn differences_across_transition() {
+        let before = TEST_ZONESET.convert_local(LocalDateTime::new(
+            LocalDate::ymd(2010, Month::March, 27).unwrap(),
+            LocalTime::hms(12, 0, 0).unwrap(),
+        )).unwrap_precise();
+
+        let after = TEST_ZONESET.convert_local(LocalDateTime::new(
+            LocalDate::ymd(2010, Month::March, 28).unwrap(),
+            LocalTime::hms(12, 0, 0).unwrap(),
+        )).unwrap_precise();
+
+        assert_eq!(before.duration_until(&after), Duration::of(23 * 3600));
+        assert_eq!(before.calendar_difference(&after), Duration::of(24 * 3600));
+        assert_eq!(after.duration_until(&before), Duration::of(-23 * 3600));
+        assert_eq!(after.calendar_difference(&before), Duration::of(-24 * 3600));
+    }
+
+    #[test]
+    fn now() {
+        let zoned = ZonedDateTime::now(&TEST_ZONESET);
+        let offset = zoned.to_offset_datetime().offset();
+        assert!(offset == Offset::of_seconds(0).unwrap() || offset == Offset::of_seconds(3600).unwrap());
+        assert!(LocalDateTime::now_in(&TEST_ZONESET) >= zoned.to_offset_datetime().local());
+        assert!(LocalDate::today() <= LocalDateTime::now().date());
+    }
+
     #[test]
     fn ambiguity() {
         let test_date = LocalDateTime::new(
@@ -566,4 +764,95 @@ mod test {
         assert!(converted.is_impossible(),
             "Local time {:?} should be impossible", converted);
     }
+
+    mod next_occurrence {
+        use super::*;
+
+        fn instant(year: i64, month: Month, day: i8, hour: i8, minute: i8) -> Instant {
+            LocalDateTime::new(LocalDate::ymd(year, month, day).unwrap(), LocalTime::hm(hour, minute).unwrap()).to_instant()
+        }
+
+        #[test]
+        fn later_today() {
+            let after = instant(2010, Month::June, 9, 6, 0);
+            let time = LocalTime::hm(9, 0).unwrap();
+            assert_eq!(TEST_ZONESET.next_occurrence_of(time, after), instant(2010, Month::June, 9, 8, 0));
+        }
+
+        #[test]
+        fn tomorrow() {
+            let after = instant(2010, Month::June, 9, 8, 0);
+            let time = LocalTime::hm(9, 0).unwrap();
+            assert_eq!(TEST_ZONESET.next_occurrence_of(time, after), instant(2010, Month::June, 10, 8, 0));
+        }
+
+        #[test]
+        fn date_changes_in_zone() {
+            // 23:30 UTC is already the next day in the zone.
+            let after = instant(2010, Month::June, 9, 23, 30);
+            let time = LocalTime::hm(0, 45).unwrap();
+            assert_eq!(TEST_ZONESET.next_occurrence_of(time, after), instant(2010, Month::June, 9, 23, 45));
+        }
+
+        #[test]
+        fn skipped_by_spring_forward() {
+            // The clocks went from 01:00 to 02:00 on the 28th of March.
+            let time = LocalTime::hm(1, 30).unwrap();
+            let after = instant(2010, Month::March, 27, 12, 0);
+            let next = TEST_ZONESET.next_occurrence_of(time, after);
+            assert_eq!(next, instant(2010, Month::March, 28, 1, 30));
+            assert_eq!(TEST_ZONESET.to_zoned(LocalDateTime::from_instant(next)).time(), LocalTime::hm(2, 30).unwrap());
+
+            assert_eq!(TEST_ZONESET.next_occurrence_of(time, next), instant(2010, Month::March, 29, 0, 30));
+        }
+
+        #[test]
+        fn repeated_by_fall_back() {
+            // The clocks went from 02:00 back to 01:00 on the 31st of October.
+            let time = LocalTime::hm(1, 30).unwrap();
+            let after = instant(2010, Month::October, 30, 12, 0);
+            let next = TEST_ZONESET.next_occurrence_of(time, after);
+            assert_eq!(next, instant(2010, Month::October, 31, 0, 30));
+
+            assert_eq!(TEST_ZONESET.next_occurrence_of(time, next), instant(2010, Month::November, 1, 1, 30));
+        }
+
+        #[test]
+        fn two_transitions_in_a_day() {
+            // The clocks went from 10:00 to 11:00, then from 17:00 back
+            // to 16:00, on the 1st of January.
+            const ZONE: TimeZone<'static> = TimeZone {
+                name: "Test Zone",
+                fixed_timespans: FixedTimespanSet {
+                    first: FixedTimespan { offset: 0, is_dst: false, name: "ZONE_A" },
+                    rest: &[
+                        (1577872800, FixedTimespan { offset: 3600, is_dst: true,  name: "ZONE_B" }),
+                        (1577894400, FixedTimespan { offset: 0,    is_dst: false, name: "ZONE_A" }),
+                    ],
+                },
+            };
+
+            let after = instant(2020, Month::January, 1, 0, 0);
+            assert_eq!(ZONE.next_occurrence_of(LocalTime::hm(10, 30).unwrap(), after), instant(2020, Month::January, 1, 10, 30));
+            assert_eq!(ZONE.next_occurrence_of(LocalTime::hm(16, 30).unwrap(), after), instant(2020, Month::January, 1, 15, 30));
+        }
+
+        #[test]
+        fn pushed_past_midnight() {
+            // The clocks went from 23:00 to midnight on the 1st of February,
+            // so 23:30 that day happens at what’s 00:30 on the 2nd.
+            const ZONE: TimeZone<'static> = TimeZone {
+                name: "Test Zone",
+                fixed_timespans: FixedTimespanSet {
+                    first: FixedTimespan { offset: 0, is_dst: false, name: "ZONE_A" },
+                    rest: &[
+                        (1580598000, FixedTimespan { offset: 3600, is_dst: true, name: "ZONE_B" }),
+                    ],
+                },
+            };
+
+            let after = instant(2020, Month::February, 1, 23, 10);
+            assert_eq!(ZONE.next_occurrence_of(LocalTime::hm(23, 30).unwrap(), after), instant(2020, Month::February, 1, 23, 30));
+        }
+    }
 }
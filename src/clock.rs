@@ -0,0 +1,115 @@
+//! Clocks that can be swapped out, so code that asks for the time can be
+//! tested.
+
+use cal::datetime::LocalDateTime;
+use cal::zone::{TimeZone, ZonedDateTime};
+use instant::Instant;
+
+
+/// A source of the current time, and of the time zone it should be shown
+/// in.
+///
+/// Code that calls `Instant::now()` directly gets a different answer each
+/// time it runs, which makes it hard to test. Code that takes a `&dyn Clock`
+/// instead can be given a `SystemClock` in production, and a `MockClock`
+/// from the `testing` module in its tests.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{Clock, Instant, TimeZone};
+/// use datetime::testing::MockClock;
+///
+/// fn greeting(clock: &dyn Clock) -> &'static str {
+///     use datetime::TimePiece;
+///     if clock.now_local().hour() < 12 { "Good morning" } else { "Good afternoon" }
+/// }
+///
+/// let clock = MockClock::new(Instant::at(1_000_000_000), TimeZone::UTC);
+/// assert_eq!(greeting(&clock), "Good morning");
+/// ```
+pub trait Clock {
+
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Returns the time zone that local times should be given in.
+    fn timezone(&self) -> TimeZone;
+
+    /// Returns the current date and time as shown on a wall clock in this
+    /// clock’s time zone.
+    fn now_local(&self) -> LocalDateTime {
+        self.timezone().to_zoned(LocalDateTime::from_instant(self.now()))
+    }
+
+    /// Returns the current date and time in this clock’s time zone,
+    /// keeping track of the offset in effect.
+    fn now_zoned(&self) -> ZonedDateTime {
+        self.timezone().zone_utc(LocalDateTime::from_instant(self.now()))
+    }
+}
+
+
+/// A clock that reads the computer’s current time.
+///
+/// This library doesn’t come with a copy of the zoneinfo database, so it
+/// can’t work out the system’s time zone by itself: the clock has to be
+/// given one, and uses UTC by default. The zone’s name can be found with
+/// `sys_timezone`.
+#[cfg(feature = "std")]
+#[derive(PartialEq, Debug, Clone)]
+pub struct SystemClock<'a> {
+    time_zone: TimeZone<'a>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> SystemClock<'a> {
+
+    /// Creates a new clock that gives local times in the given time zone.
+    pub fn new(time_zone: TimeZone<'a>) -> SystemClock<'a> {
+        SystemClock { time_zone: time_zone }
+    }
+}
+
+#[cfg(feature = "std")]
+impl SystemClock<'static> {
+
+    /// Creates a new clock that gives local times in UTC.
+    pub fn utc() -> SystemClock<'static> {
+        SystemClock::new(TimeZone::UTC)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock<'static> {
+    fn default() -> SystemClock<'static> {
+        SystemClock::utc()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Clock for SystemClock<'a> {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn timezone(&self) -> TimeZone {
+        self.time_zone.clone()
+    }
+}
+
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    pub use super::*;
+
+    #[test]
+    fn system() {
+        let clock = SystemClock::default();
+        assert_eq!(clock.timezone(), TimeZone::UTC);
+
+        let before = Instant::now();
+        let now = clock.now();
+        assert!(before <= now && now <= Instant::now());
+    }
+}
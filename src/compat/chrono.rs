@@ -0,0 +1,180 @@
+//! Conversions to and from the `chrono` crate’s types.
+//!
+//! Converting from chrono always succeeds. Converting to chrono can fail,
+//! because chrono’s years only go up to about 262,000 (where this library’s
+//! go the whole way up to `i64`), and because it has no way to represent
+//! the 24:00 that ends a day. Chrono’s leap seconds, which it stores as
+//! nanosecond values over a billion in the 59th second, become the 60th
+//! second, and vice versa. Leap seconds anywhere other than the end of an
+//! hour, where this library doesn’t allow them, get clamped to the last
+//! nanosecond of the second before.
+
+use std::convert::TryFrom;
+
+use chrono::{NaiveDate, NaiveTime, NaiveDateTime, DateTime, FixedOffset, Datelike, Timelike};
+use chrono::TimeZone as ChronoTimeZone;
+
+use cal::{DatePiece, TimePiece};
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Error};
+use cal::offset::{Offset, OffsetDateTime};
+
+
+impl From<NaiveDate> for LocalDate {
+    fn from(date: NaiveDate) -> LocalDate {
+        let month = Month::from_one(date.month() as i8).unwrap();
+        LocalDate::ymd(date.year() as i64, month, date.day() as i8).unwrap()
+    }
+}
+
+impl TryFrom<LocalDate> for NaiveDate {
+    type Error = Error;
+
+    fn try_from(date: LocalDate) -> Result<NaiveDate, Error> {
+        if date.year() < i32::min_value() as i64 || date.year() > i32::max_value() as i64 {
+            return Err(Error::YearOutOfRange { given: date.year() });
+        }
+
+        NaiveDate::from_ymd_opt(date.year() as i32, date.month() as u32, date.day() as u32)
+                  .ok_or(Error::YearOutOfRange { given: date.year() })
+    }
+}
+
+impl From<NaiveTime> for LocalTime {
+    fn from(time: NaiveTime) -> LocalTime {
+        if time.nanosecond() >= 1_000_000_000 && time.minute() == 59 && time.second() == 59 {
+            LocalTime::hms_ns_leap(time.hour() as i8, 59, 60, (time.nanosecond() - 1_000_000_000) as i32).unwrap()
+        }
+        else if time.nanosecond() >= 1_000_000_000 {
+            LocalTime::hms_ns(time.hour() as i8, time.minute() as i8, time.second() as i8, 999_999_999).unwrap()
+        }
+        else {
+            LocalTime::hms_ns(time.hour() as i8, time.minute() as i8, time.second() as i8, time.nanosecond() as i32).unwrap()
+        }
+    }
+}
+
+impl TryFrom<LocalTime> for NaiveTime {
+    type Error = Error;
+
+    fn try_from(time: LocalTime) -> Result<NaiveTime, Error> {
+        if time.is_leap_second() {
+            return NaiveTime::from_hms_nano_opt(time.hour() as u32, time.minute() as u32, 59, time.nanosecond() as u32 + 1_000_000_000)
+                             .ok_or(Error::HourOutOfRange { given: time.hour() });
+        }
+
+        NaiveTime::from_hms_nano_opt(time.hour() as u32, time.minute() as u32, time.second() as u32, time.nanosecond() as u32)
+                  .ok_or(Error::HourOutOfRange { given: time.hour() })
+    }
+}
+
+impl From<NaiveDateTime> for LocalDateTime {
+    fn from(datetime: NaiveDateTime) -> LocalDateTime {
+        LocalDateTime::new(datetime.date().into(), datetime.time().into())
+    }
+}
+
+impl TryFrom<LocalDateTime> for NaiveDateTime {
+    type Error = Error;
+
+    fn try_from(datetime: LocalDateTime) -> Result<NaiveDateTime, Error> {
+        let date = try!(NaiveDate::try_from(datetime.date()));
+        let time = try!(NaiveTime::try_from(datetime.time()));
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+impl From<DateTime<FixedOffset>> for OffsetDateTime {
+    fn from(datetime: DateTime<FixedOffset>) -> OffsetDateTime {
+        let offset = Offset::of_seconds(datetime.offset().local_minus_utc()).unwrap();
+        offset.transform_date(datetime.naive_local().into())
+    }
+}
+
+impl TryFrom<OffsetDateTime> for DateTime<FixedOffset> {
+    type Error = Error;
+
+    fn try_from(datetime: OffsetDateTime) -> Result<DateTime<FixedOffset>, Error> {
+        let local = try!(NaiveDateTime::try_from(datetime.local()));
+        let offset = try!(FixedOffset::east_opt(datetime.offset().seconds()).ok_or(Error::OutOfRange));
+
+        // A fixed offset always maps a local time to exactly one instant.
+        Ok(offset.from_local_datetime(&local).unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    fn moon_landing() -> LocalDateTime {
+        LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms_ms(20, 17, 40, 500).unwrap())
+    }
+
+    fn chrono_moon_landing() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(1969, 7, 20).unwrap().and_hms_nano_opt(20, 17, 40, 500_000_000).unwrap()
+    }
+
+    #[test]
+    fn from_naive() {
+        assert_eq!(LocalDateTime::from(chrono_moon_landing()), moon_landing())
+    }
+
+    #[test]
+    fn to_naive() {
+        assert_eq!(NaiveDateTime::try_from(moon_landing()), Ok(chrono_moon_landing()))
+    }
+
+    #[test]
+    fn year_out_of_range() {
+        let date = LocalDate::ymd(1_000_000, Month::January, 1).unwrap();
+        assert_eq!(NaiveDate::try_from(date), Err(Error::YearOutOfRange { given: 1_000_000 }))
+    }
+
+    #[test]
+    fn end_of_day_out_of_range() {
+        let time = LocalTime::hms(24, 0, 0).unwrap();
+        assert_eq!(NaiveTime::try_from(time), Err(Error::HourOutOfRange { given: 24 }))
+    }
+
+    #[test]
+    fn leap_second() {
+        let time = NaiveTime::from_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap();
+        assert_eq!(LocalTime::from(time), LocalTime::hms_ns_leap(23, 59, 60, 500_000_000).unwrap())
+    }
+
+    #[test]
+    fn leap_second_mid_hour() {
+        let time = NaiveTime::from_hms_nano_opt(12, 30, 59, 1_500_000_000).unwrap();
+        assert_eq!(LocalTime::from(time), LocalTime::hms_ns(12, 30, 59, 999_999_999).unwrap())
+    }
+
+    #[test]
+    fn leap_second_to_chrono() {
+        let time = LocalTime::hms_ns_leap(23, 59, 60, 500_000_000).unwrap();
+        assert_eq!(NaiveTime::try_from(time), Ok(NaiveTime::from_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap()))
+    }
+
+    #[test]
+    fn from_fixed_offset() {
+        let offset = FixedOffset::east_opt(-4 * 3600).unwrap();
+        let datetime = OffsetDateTime::from(offset.from_local_datetime(&chrono_moon_landing()).unwrap());
+        assert_eq!(datetime.to_string(), "1969-07-20T20:17:40.500-04:00")
+    }
+
+    #[test]
+    fn offset_round_trip() {
+        let datetime = Offset::of_hours_and_minutes(5, 30).unwrap().transform_date(moon_landing());
+        let chrono = DateTime::<FixedOffset>::try_from(datetime).unwrap();
+        assert_eq!(chrono.offset().local_minus_utc(), 5 * 3600 + 30 * 60);
+        assert_eq!(OffsetDateTime::from(chrono), datetime);
+    }
+
+    #[test]
+    fn utc_round_trip() {
+        let datetime = Offset::utc().transform_date(moon_landing());
+        let chrono = DateTime::<FixedOffset>::try_from(datetime).unwrap();
+        assert_eq!(chrono.offset().local_minus_utc(), 0);
+        assert_eq!(chrono.naive_local(), chrono_moon_landing());
+    }
+}
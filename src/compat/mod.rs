@@ -0,0 +1,23 @@
+//! Conversions to and from the types of other date and time libraries,
+//! each enabled by a cargo feature of the same name.
+
+#[cfg(feature = "chrono")]
+mod chrono;
+
+#[cfg(feature = "time")]
+mod time;
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+
+#[cfg(feature = "prost")]
+mod prost;
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+
+#[cfg(feature = "rand")]
+mod rand;
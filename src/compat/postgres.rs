@@ -0,0 +1,314 @@
+//! Conversions to and from PostgreSQL’s date and time types, for use with
+//! the `postgres` crate.
+//!
+//! - `LocalDate` maps to `DATE`, stored as a number of days since the 1st
+//!   of January, 2000;
+//! - `LocalTime` maps to `TIME`, stored as a number of microseconds since
+//!   midnight;
+//! - `LocalDateTime` maps to `TIMESTAMP`, stored as a number of
+//!   microseconds since midnight at the start of 2000;
+//! - `OffsetDateTime` maps to `TIMESTAMPTZ`, stored in the same way, but in
+//!   UTC. PostgreSQL doesn’t keep the offset, so values read back out of
+//!   the database are always in UTC.
+//!
+//! PostgreSQL only has microsecond precision, so any nanoseconds get
+//! rounded down when storing values. Its special `infinity` and
+//! `-infinity` values cannot be read, and produce an error.
+
+use std::error::Error as ErrorTrait;
+use std::fmt;
+
+use bytes::BytesMut;
+use postgres_types::{FromSql, ToSql, Type, IsNull};
+
+use cal::TimePiece;
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime};
+use cal::offset::{Offset, OffsetDateTime};
+use duration::Duration;
+
+
+/// The Unix timestamp of PostgreSQL’s epoch, 2000-01-01T00:00:00.
+const POSTGRES_EPOCH: i64 = 946684800;
+
+/// The Julian day of PostgreSQL’s epoch.
+const POSTGRES_EPOCH_JULIAN_DAY: i64 = 2_451_545;
+
+/// The number of microseconds in a day.
+const MICROSECONDS_IN_DAY: i64 = 86400 * 1_000_000;
+
+type BoxedError = Box<dyn ErrorTrait + Sync + Send>;
+
+
+/// An error that occurs when a value read out of PostgreSQL can’t be
+/// represented by this library’s types.
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Error {
+
+    /// The value was `infinity` or `-infinity`.
+    Infinity,
+
+    /// The value was not the right length for its type.
+    InvalidLength,
+
+    /// The value was outside of the range of its type.
+    OutOfRange,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl ErrorTrait for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Infinity       => "infinite dates cannot be represented",
+            Error::InvalidLength  => "invalid length for date or time value",
+            Error::OutOfRange     => "date or time value out of range",
+        }
+    }
+}
+
+
+fn read_i32(raw: &[u8]) -> Result<i32, BoxedError> {
+    if raw.len() != 4 {
+        return Err(Box::new(Error::InvalidLength));
+    }
+
+    Ok(i32::from_be_bytes([ raw[0], raw[1], raw[2], raw[3] ]))
+}
+
+fn read_i64(raw: &[u8]) -> Result<i64, BoxedError> {
+    if raw.len() != 8 {
+        return Err(Box::new(Error::InvalidLength));
+    }
+
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(raw);
+    Ok(i64::from_be_bytes(bytes))
+}
+
+/// Converts a number of microseconds since the PostgreSQL epoch into a
+/// local date-time.
+fn from_timestamp(microseconds: i64) -> Result<LocalDateTime, BoxedError> {
+    if microseconds == i64::max_value() || microseconds == i64::min_value() {
+        return Err(Box::new(Error::Infinity));
+    }
+
+    let seconds = microseconds.div_euclid(1_000_000) + POSTGRES_EPOCH;
+    let nanoseconds = microseconds.rem_euclid(1_000_000) as i32 * 1000;
+    Ok(LocalDateTime::at_ns(seconds, nanoseconds))
+}
+
+/// Converts a local date-time into a number of microseconds since the
+/// PostgreSQL epoch.
+fn to_timestamp(datetime: LocalDateTime) -> Result<i64, BoxedError> {
+    let microseconds = (datetime.time().nanosecond() / 1000) as i64;
+
+    datetime.to_instant().seconds().checked_sub(POSTGRES_EPOCH)
+           .and_then(|s| s.checked_mul(1_000_000))
+           .and_then(|s| s.checked_add(microseconds))
+           .ok_or_else(|| Box::new(Error::OutOfRange) as BoxedError)
+}
+
+
+impl<'a> FromSql<'a> for LocalDate {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<LocalDate, BoxedError> {
+        let days = try!(read_i32(raw));
+        if days == i32::max_value() || days == i32::min_value() {
+            return Err(Box::new(Error::Infinity));
+        }
+
+        Ok(LocalDate::from_julian_day(POSTGRES_EPOCH_JULIAN_DAY + days as i64))
+    }
+
+    accepts!(DATE);
+}
+
+impl ToSql for LocalDate {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        // Going through the Julian day rather than midnight’s timestamp
+        // works for the first and last dates too, whose midnights are
+        // outside the range of a timestamp.
+        let days = self.julian_day() - POSTGRES_EPOCH_JULIAN_DAY;
+
+        if days >= i32::max_value() as i64 || days <= i32::min_value() as i64 {
+            return Err(Box::new(Error::OutOfRange));
+        }
+
+        out.extend_from_slice(&(days as i32).to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(DATE);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for LocalTime {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<LocalTime, BoxedError> {
+        let microseconds = try!(read_i64(raw));
+
+        // PostgreSQL allows 24:00:00 as a time, just as this library does.
+        if microseconds < 0 || microseconds > MICROSECONDS_IN_DAY {
+            return Err(Box::new(Error::OutOfRange));
+        }
+
+        let seconds = microseconds / 1_000_000;
+        let nanoseconds = (microseconds % 1_000_000) as i32 * 1000;
+        Ok(LocalTime::from_seconds_and_nanoseconds_since_midnight(seconds, nanoseconds))
+    }
+
+    accepts!(TIME);
+}
+
+impl ToSql for LocalTime {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        let microseconds = self.to_seconds() * 1_000_000 + (self.nanosecond() / 1000) as i64;
+        out.extend_from_slice(&microseconds.to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIME);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for LocalDateTime {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<LocalDateTime, BoxedError> {
+        from_timestamp(try!(read_i64(raw)))
+    }
+
+    accepts!(TIMESTAMP);
+}
+
+impl ToSql for LocalDateTime {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        out.extend_from_slice(&try!(to_timestamp(*self)).to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMP);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for OffsetDateTime {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<OffsetDateTime, BoxedError> {
+        let utc = try!(from_timestamp(try!(read_i64(raw))));
+        Ok(Offset::utc().transform_date(utc))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
+impl ToSql for OffsetDateTime {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        let utc = try!(self.local().checked_sub(Duration::of(self.offset().seconds() as i64))
+                                   .ok_or_else(|| Box::new(Error::OutOfRange) as BoxedError));
+        out.extend_from_slice(&try!(to_timestamp(utc)).to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMPTZ);
+    to_sql_checked!();
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::Month;
+
+    /// Writes a value in PostgreSQL’s binary format, then reads it back.
+    fn round_trip<T>(value: T, ty: Type) -> (Vec<u8>, T)
+    where T: ToSql + for<'a> FromSql<'a> {
+        let mut out = BytesMut::new();
+        let _ = value.to_sql_checked(&ty, &mut out).unwrap();
+        let read = T::from_sql(&ty, &out).unwrap();
+        (out.to_vec(), read)
+    }
+
+    #[test]
+    fn date() {
+        let date = LocalDate::ymd(2000, Month::January, 2).unwrap();
+        assert_eq!(round_trip(date, Type::DATE), (vec![ 0, 0, 0, 1 ], date));
+    }
+
+    #[test]
+    fn date_before_epoch() {
+        let date = LocalDate::ymd(1999, Month::December, 31).unwrap();
+        assert_eq!(round_trip(date, Type::DATE), (vec![ 255, 255, 255, 255 ], date));
+    }
+
+    #[test]
+    fn time() {
+        let time = LocalTime::hms_ns(0, 0, 1, 500_000).unwrap();
+        assert_eq!(round_trip(time, Type::TIME), (1_000_500i64.to_be_bytes().to_vec(), time));
+    }
+
+    #[test]
+    fn end_of_day() {
+        let time = LocalTime::hms(24, 0, 0).unwrap();
+        assert_eq!(round_trip(time, Type::TIME).1, time);
+    }
+
+    #[test]
+    fn timestamp() {
+        let datetime = LocalDateTime::new(LocalDate::ymd(2000, Month::January, 1).unwrap(), LocalTime::hms_ns(0, 0, 2, 250_000).unwrap());
+        assert_eq!(round_trip(datetime, Type::TIMESTAMP), (2_000_250i64.to_be_bytes().to_vec(), datetime));
+    }
+
+    #[test]
+    fn timestamp_truncates_nanoseconds() {
+        let datetime = LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms_ns(20, 17, 40, 123_456_789).unwrap());
+        let expected = LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms_ns(20, 17, 40, 123_456_000).unwrap());
+        assert_eq!(round_trip(datetime, Type::TIMESTAMP).1, expected);
+    }
+
+    #[test]
+    fn timestamptz_is_read_back_in_utc() {
+        let local = LocalDateTime::new(LocalDate::ymd(2000, Month::January, 1).unwrap(), LocalTime::hms(5, 30, 0).unwrap());
+        let datetime = Offset::of_hours_and_minutes(5, 30).unwrap().transform_date(local);
+        let (bytes, read) = round_trip(datetime, Type::TIMESTAMPTZ);
+        assert_eq!(bytes, vec![ 0; 8 ]);
+        assert_eq!(read.to_string(), "2000-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn ends_of_the_range() {
+        let mut out = BytesMut::new();
+        assert!(LocalDate::MIN.to_sql_checked(&Type::DATE, &mut out).is_err());
+        assert!(LocalDate::MAX.to_sql_checked(&Type::DATE, &mut out).is_err());
+        assert!(LocalDateTime::MIN.to_sql_checked(&Type::TIMESTAMP, &mut out).is_err());
+        assert!(LocalDateTime::MAX.to_sql_checked(&Type::TIMESTAMP, &mut out).is_err());
+
+        let datetime = Offset::of_hours_and_minutes(5, 30).unwrap().transform_date(LocalDateTime::MIN);
+        assert!(datetime.to_sql_checked(&Type::TIMESTAMPTZ, &mut out).is_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn furthest_dates() {
+        let first = LocalDate::from_julian_day(POSTGRES_EPOCH_JULIAN_DAY + i32::min_value() as i64 + 1);
+        let last = LocalDate::from_julian_day(POSTGRES_EPOCH_JULIAN_DAY + i32::max_value() as i64 - 1);
+        assert_eq!(round_trip(first, Type::DATE).1, first);
+        assert_eq!(round_trip(last, Type::DATE).1, last);
+    }
+
+    #[test]
+    fn infinity() {
+        assert!(LocalDate::from_sql(&Type::DATE, &i32::max_value().to_be_bytes()).is_err());
+        assert!(LocalDateTime::from_sql(&Type::TIMESTAMP, &i64::min_value().to_be_bytes()).is_err());
+    }
+
+    #[test]
+    fn wrong_type() {
+        let mut out = BytesMut::new();
+        assert!(LocalDate::ymd(2000, Month::January, 1).unwrap().to_sql_checked(&Type::TIMESTAMP, &mut out).is_err());
+        assert!(!<LocalTime as ToSql>::accepts(&Type::DATE));
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert!(LocalTime::from_sql(&Type::TIME, &[ 0, 0, 0, 0 ]).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Conversions to and from the protobuf well-known types `Timestamp` and
+//! `Duration`, as generated by the `prost-types` crate for gRPC APIs.
+//!
+//! Both protobuf types are a pair of seconds and nanoseconds, the same
+//! precision as this library’s types. Protobuf requires a timestamp’s
+//! nanoseconds to be between 0 and 999,999,999: any values outside that
+//! range are clamped into it.
+
+use std::convert::TryFrom;
+
+use prost_types::{Timestamp, Duration as ProtoDuration};
+
+use duration::{Duration, Error as DurationError};
+use instant::Instant;
+
+
+impl From<Timestamp> for Instant {
+    fn from(timestamp: Timestamp) -> Instant {
+        let nanos = if timestamp.nanos < 0 { 0 } else if timestamp.nanos > 999_999_999 { 999_999_999 } else { timestamp.nanos };
+        Instant::at_ns(timestamp.seconds, nanos)
+    }
+}
+
+impl From<Instant> for Timestamp {
+    fn from(instant: Instant) -> Timestamp {
+        Timestamp {
+            seconds: instant.seconds(),
+            nanos:   instant.nanoseconds(),
+        }
+    }
+}
+
+impl TryFrom<ProtoDuration> for Duration {
+    type Error = DurationError;
+
+    fn try_from(duration: ProtoDuration) -> Result<Duration, DurationError> {
+        let total_ns = duration.seconds as i128 * 1_000_000_000 + duration.nanos as i128;
+        let seconds = total_ns.div_euclid(1_000_000_000);
+
+        if seconds < i64::min_value() as i128 || seconds > i64::max_value() as i128 {
+            Err(DurationError::OutOfRange)
+        }
+        else {
+            Ok(Duration::of_ns(seconds as i64, total_ns.rem_euclid(1_000_000_000) as i32))
+        }
+    }
+}
+
+impl From<Duration> for ProtoDuration {
+
+    /// Converts a duration into protobuf’s representation, where the
+    /// seconds and nanoseconds always have the same sign.
+    fn from(duration: Duration) -> ProtoDuration {
+        ProtoDuration { seconds: duration.whole_seconds(), nanos: duration.subsec_nanoseconds() }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    #[test]
+    fn timestamp_round_trip() {
+        let instant = Instant::at_ms(1_600_000_000, 250);
+        let timestamp = Timestamp::from(instant);
+        assert_eq!(timestamp, Timestamp { seconds: 1_600_000_000, nanos: 250_000_000 });
+        assert_eq!(Instant::from(timestamp), instant);
+    }
+
+    #[test]
+    fn timestamp_before_epoch() {
+        let timestamp = Timestamp { seconds: -1, nanos: 999_999_999 };
+        assert_eq!(Instant::from(timestamp), Instant::at_ns(-1, 999_999_999));
+    }
+
+    #[test]
+    fn timestamp_nanos_clamped() {
+        assert_eq!(Instant::from(Timestamp { seconds: 10, nanos: -5 }), Instant::at(10));
+        assert_eq!(Instant::from(Timestamp { seconds: 10, nanos: 2_000_000_000 }), Instant::at_ns(10, 999_999_999));
+    }
+
+    #[test]
+    fn duration_round_trip() {
+        let duration = Duration::of_ms(90, 500);
+        let proto = ProtoDuration::from(duration);
+        assert_eq!(proto, ProtoDuration { seconds: 90, nanos: 500_000_000 });
+        assert_eq!(Duration::try_from(proto), Ok(duration));
+    }
+
+    #[test]
+    fn negative_duration() {
+        let duration = Duration::of_ms(-2, 500);
+        let proto = ProtoDuration::from(duration);
+        assert_eq!(proto, ProtoDuration { seconds: -1, nanos: -500_000_000 });
+        assert_eq!(Duration::try_from(proto), Ok(duration));
+    }
+
+    #[test]
+    fn duration_nanoseconds() {
+        let proto = ProtoDuration { seconds: -1, nanos: -999_999 };
+        assert_eq!(Duration::try_from(proto.clone()), Ok(Duration::of_ns(-2, 999_000_001)));
+        assert_eq!(ProtoDuration::from(Duration::of_ns(-2, 999_000_001)), proto);
+    }
+
+    #[test]
+    fn duration_out_of_range() {
+        let proto = ProtoDuration { seconds: i64::min_value(), nanos: -1_000_000 };
+        assert_eq!(Duration::try_from(proto), Err(DurationError::OutOfRange));
+    }
+}
@@ -0,0 +1,168 @@
+//! Implementations of `quickcheck::Arbitrary`, so that code using this
+//! library can be property-tested.
+//!
+//! Every generated value is valid. Dates, date-times, and instants can be
+//! anywhere in the range that can be represented, including the extended
+//! years that need a sign in front of them in ISO 8601; times can be
+//! anything up to the last nanosecond of the day; and offsets are a whole
+//! number of minutes between -23:59 and +23:59. Durations can be anything
+//! at all, so adding them to other values may overflow.
+
+use quickcheck::{Arbitrary, Gen};
+
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday};
+use cal::offset::{Offset, OffsetDateTime};
+use duration::Duration;
+use instant::Instant;
+
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sunday, Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday, Weekday::Friday, Weekday::Saturday,
+];
+
+/// Returns a number between `low` and `high`, inclusive.
+fn in_range(g: &mut Gen, low: i64, high: i64) -> i64 {
+    // The span of the whole range of `i64` doesn’t fit in an `i64`, or in a
+    // `u64` once the 1 is added, so that case takes any number at all.
+    let span = high.wrapping_sub(low) as u64;
+    let offset = match span.checked_add(1) {
+        Some(span)  => u64::arbitrary(g) % span,
+        None        => u64::arbitrary(g),
+    };
+
+    low.wrapping_add(offset as i64)
+}
+
+impl Arbitrary for Month {
+    fn arbitrary(g: &mut Gen) -> Month {
+        *g.choose(&Month::ALL).unwrap()
+    }
+}
+
+impl Arbitrary for Weekday {
+    fn arbitrary(g: &mut Gen) -> Weekday {
+        *g.choose(&WEEKDAYS).unwrap()
+    }
+}
+
+impl Arbitrary for LocalDate {
+    fn arbitrary(g: &mut Gen) -> LocalDate {
+        let first = LocalDate::MIN.julian_day();
+        let last = LocalDate::MAX.julian_day();
+        LocalDate::from_julian_day(in_range(g, first, last))
+    }
+}
+
+impl Arbitrary for LocalTime {
+    fn arbitrary(g: &mut Gen) -> LocalTime {
+        let seconds = in_range(g, 0, 86399);
+        let nanoseconds = in_range(g, 0, 999_999_999);
+        LocalTime::from_seconds_and_nanoseconds_since_midnight(seconds, nanoseconds as i32)
+    }
+}
+
+impl Arbitrary for LocalDateTime {
+    fn arbitrary(g: &mut Gen) -> LocalDateTime {
+        LocalDateTime::from_instant(Instant::arbitrary(g))
+    }
+}
+
+impl Arbitrary for Offset {
+    fn arbitrary(g: &mut Gen) -> Offset {
+        let minutes = in_range(g, -(24 * 60 - 1), 24 * 60 - 1);
+        if minutes == 0 && bool::arbitrary(g) {
+            Offset::utc()
+        }
+        else {
+            Offset::of_seconds(minutes as i32 * 60).unwrap()
+        }
+    }
+}
+
+impl Arbitrary for OffsetDateTime {
+    fn arbitrary(g: &mut Gen) -> OffsetDateTime {
+        let local = LocalDateTime::arbitrary(g);
+        let offset = Offset::arbitrary(g);
+
+        // At the very ends of the range, moving the date-time back by the
+        // offset could take its instant out of range, so it stays in UTC.
+        match local.to_instant().checked_sub(Duration::of(offset.seconds() as i64)) {
+            Some(_)  => offset.transform_date(local),
+            None     => Offset::utc().transform_date(local),
+        }
+    }
+}
+
+impl Arbitrary for Instant {
+    fn arbitrary(g: &mut Gen) -> Instant {
+        Instant::at_ns(i64::arbitrary(g), in_range(g, 0, 999_999_999) as i32)
+    }
+}
+
+impl Arbitrary for Duration {
+    fn arbitrary(g: &mut Gen) -> Duration {
+        Duration::of_ns(i64::arbitrary(g), in_range(g, 0, 999_999_999) as i32)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::{DatePiece, TimePiece};
+
+    fn generate<T: Arbitrary>() -> Vec<T> {
+        let mut g = Gen::new(100);
+        (0 .. 1000).map(|_| T::arbitrary(&mut g)).collect()
+    }
+
+    #[test]
+    fn dates_are_valid() {
+        for date in generate::<LocalDate>() {
+            assert_eq!(LocalDate::ymd(date.year(), date.month(), date.day()), Ok(date));
+        }
+    }
+
+    #[test]
+    fn dates_can_be_parsed() {
+        for date in generate::<LocalDate>() {
+            assert_eq!(date.to_string().parse(), Ok(date));
+        }
+    }
+
+    #[test]
+    fn times_are_valid() {
+        for time in generate::<LocalTime>() {
+            assert_eq!(LocalTime::hms_ns(time.hour(), time.minute(), time.second(), time.nanosecond()), Ok(time));
+        }
+    }
+
+    #[test]
+    fn instants_are_valid() {
+        for instant in generate::<Instant>() {
+            assert!(instant.nanoseconds() >= 0 && instant.nanoseconds() <= 999_999_999);
+        }
+    }
+
+    #[test]
+    fn offset_datetimes_have_instants() {
+        for datetime in generate::<OffsetDateTime>() {
+            let utc = datetime.local() - Duration::of(datetime.offset().seconds() as i64);
+            assert_eq!(LocalDateTime::from_instant(datetime.to_instant()), utc);
+        }
+    }
+
+    #[test]
+    fn offset_datetimes_can_be_parsed() {
+        for datetime in generate::<OffsetDateTime>() {
+            let parsed: OffsetDateTime = datetime.to_string().parse().unwrap();
+            assert_eq!(parsed.to_string(), datetime.to_string());
+        }
+    }
+
+    #[test]
+    fn every_month_is_generated() {
+        let months = generate::<Month>();
+        assert!(Month::all().all(|month| months.contains(&month)));
+    }
+}
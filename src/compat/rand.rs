@@ -0,0 +1,106 @@
+//! Uniform sampling of dates and date-times with `rand`, so that test data
+//! and simulations can pick them from a range:
+//!
+//! ```rust,ignore
+//! let date: LocalDate = rng.gen_range(start .. end);
+//! ```
+//!
+//! Dates are sampled by day, and date-times by nanosecond.
+
+use rand::Rng;
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+
+use cal::datetime::{LocalDate, LocalDateTime};
+
+
+/// A sampler that picks days uniformly from a range of dates.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformLocalDate(UniformInt<i64>);
+
+impl SampleUniform for LocalDate {
+    type Sampler = UniformLocalDate;
+}
+
+impl UniformSampler for UniformLocalDate {
+    type X = LocalDate;
+
+    fn new<B1, B2>(low: B1, high: B2) -> UniformLocalDate
+    where B1: SampleBorrow<LocalDate> + Sized, B2: SampleBorrow<LocalDate> + Sized {
+        UniformLocalDate(UniformInt::<i64>::new(low.borrow().julian_day(), high.borrow().julian_day()))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> UniformLocalDate
+    where B1: SampleBorrow<LocalDate> + Sized, B2: SampleBorrow<LocalDate> + Sized {
+        UniformLocalDate(UniformInt::<i64>::new_inclusive(low.borrow().julian_day(), high.borrow().julian_day()))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> LocalDate {
+        LocalDate::from_julian_day(self.0.sample(rng))
+    }
+}
+
+
+/// A sampler that picks nanoseconds uniformly from a range of date-times.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformLocalDateTime(UniformInt<i128>);
+
+impl SampleUniform for LocalDateTime {
+    type Sampler = UniformLocalDateTime;
+}
+
+impl UniformSampler for UniformLocalDateTime {
+    type X = LocalDateTime;
+
+    fn new<B1, B2>(low: B1, high: B2) -> UniformLocalDateTime
+    where B1: SampleBorrow<LocalDateTime> + Sized, B2: SampleBorrow<LocalDateTime> + Sized {
+        UniformLocalDateTime(UniformInt::<i128>::new(low.borrow().to_unix_nanos(), high.borrow().to_unix_nanos()))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> UniformLocalDateTime
+    where B1: SampleBorrow<LocalDateTime> + Sized, B2: SampleBorrow<LocalDateTime> + Sized {
+        UniformLocalDateTime(UniformInt::<i128>::new_inclusive(low.borrow().to_unix_nanos(), high.borrow().to_unix_nanos()))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> LocalDateTime {
+        LocalDateTime::from_unix_nanos(self.0.sample(rng))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::{LocalTime, Month};
+    pub use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn dates_in_range() {
+        let mut rng = StepRng::new(0, 0x9E37_79B9_7F4A_7C15);
+        let start = LocalDate::ymd(2016, Month::February, 1).unwrap();
+        let end = LocalDate::ymd(2016, Month::March, 1).unwrap();
+
+        for _ in 0 .. 1000 {
+            let date = rng.gen_range(start .. end);
+            assert!(date >= start && date < end);
+        }
+    }
+
+    #[test]
+    fn single_day() {
+        let mut rng = StepRng::new(12345, 67890);
+        let date = LocalDate::ymd(2016, Month::February, 29).unwrap();
+        assert_eq!(rng.gen_range(date ..= date), date);
+    }
+
+    #[test]
+    fn datetimes_in_range() {
+        let mut rng = StepRng::new(0, 0x9E37_79B9_7F4A_7C15);
+        let start = LocalDateTime::new(LocalDate::ymd(1969, Month::December, 31).unwrap(), LocalTime::hm(23, 0).unwrap());
+        let end = LocalDateTime::new(LocalDate::ymd(1970, Month::January, 1).unwrap(), LocalTime::hm(1, 0).unwrap());
+
+        for _ in 0 .. 1000 {
+            let datetime = rng.gen_range(start ..= end);
+            assert!(datetime >= start && datetime <= end);
+        }
+    }
+}
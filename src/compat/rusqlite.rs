@@ -0,0 +1,238 @@
+//! Conversions to and from SQLite values, for use with the `rusqlite` crate.
+//!
+//! SQLite has no dedicated date or time types. Instead, its date and time
+//! functions understand three conventions, and this module follows them:
+//!
+//! - values are always *written* as ISO 8601 text, such as
+//!   `2016-02-29T13:45:00.500`, as this is the only format that keeps the
+//!   local time and UTC offset intact;
+//! - `TEXT` values are read as ISO 8601, with either a `T` or a space
+//!   between the date and the time, as produced by SQLite’s own
+//!   `datetime()` function;
+//! - `REAL` values are read as Julian day numbers, the fractional number of
+//!   days since noon in Greenwich on the 24th of November, 4714 BC;
+//! - `INTEGER` values are read as Unix timestamps, the number of seconds
+//!   since midnight at the start of 1970.
+//!
+//! Numbers can only be read into the types that they make sense for: a
+//! `LocalTime` can only be read from text. Numeric values always refer to
+//! UTC, so an `OffsetDateTime` read from one has an offset of zero.
+
+use std::error::Error as ErrorTrait;
+use std::str::FromStr;
+
+use rusqlite;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime};
+use cal::offset::{Offset, OffsetDateTime};
+use instant::Instant;
+
+
+/// Reads a text value as a string, replacing the space that SQLite puts
+/// between the date and the time with the `T` that ISO 8601 expects.
+fn read_text(value: ValueRef) -> FromSqlResult<String> {
+    let text = try!(value.as_str());
+
+    if text.len() > 10 && text.as_bytes()[10] == b' ' {
+        Ok(format!("{}T{}", &text[..10], &text[11..]))
+    }
+    else {
+        Ok(text.to_owned())
+    }
+}
+
+/// Parses a text value into one of this library’s types.
+fn parse_text<T>(value: ValueRef) -> FromSqlResult<T>
+where T: FromStr, T::Err: ErrorTrait + Send + Sync + 'static {
+    let text = try!(read_text(value));
+    text.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+}
+
+/// Reads a numeric value as a date-time in UTC, or fails if the value is
+/// not a number.
+fn read_number(value: ValueRef) -> FromSqlResult<LocalDateTime> {
+    match value {
+        ValueRef::Real(julian_date)  => LocalDateTime::from_julian_date(julian_date).map_err(|_| FromSqlError::OutOfRange(julian_date as i64)),
+        ValueRef::Integer(seconds)   => Ok(LocalDateTime::at(seconds)),
+        _                            => Err(FromSqlError::InvalidType),
+    }
+}
+
+
+impl FromSql for LocalDate {
+    fn column_result(value: ValueRef) -> FromSqlResult<LocalDate> {
+        match value {
+            ValueRef::Text(_)  => parse_text(value),
+            _                  => read_number(value).map(|datetime| datetime.date()),
+        }
+    }
+}
+
+impl ToSql for LocalDate {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for LocalTime {
+    fn column_result(value: ValueRef) -> FromSqlResult<LocalTime> {
+        match value {
+            ValueRef::Text(_)  => parse_text(value),
+            _                  => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl ToSql for LocalTime {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for LocalDateTime {
+    fn column_result(value: ValueRef) -> FromSqlResult<LocalDateTime> {
+        match value {
+            ValueRef::Text(_)  => parse_text(value),
+            _                  => read_number(value),
+        }
+    }
+}
+
+impl ToSql for LocalDateTime {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for OffsetDateTime {
+    fn column_result(value: ValueRef) -> FromSqlResult<OffsetDateTime> {
+        match value {
+            ValueRef::Text(_)  => parse_text(value),
+            _                  => read_number(value).map(|utc| Offset::utc().transform_date(utc)),
+        }
+    }
+}
+
+impl ToSql for OffsetDateTime {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for Instant {
+    fn column_result(value: ValueRef) -> FromSqlResult<Instant> {
+        match value {
+            ValueRef::Text(_)  => parse_text(value),
+            _                  => read_number(value).map(|utc| utc.to_instant()),
+        }
+    }
+}
+
+impl ToSql for Instant {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::Month;
+    pub use rusqlite::types::Value;
+
+    fn text(value: &str) -> Value {
+        Value::Text(value.to_owned())
+    }
+
+    fn read<T: FromSql>(value: Value) -> FromSqlResult<T> {
+        T::column_result(ValueRef::from(&value))
+    }
+
+    fn moon_landing() -> LocalDateTime {
+        LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms_ms(20, 17, 40, 500).unwrap())
+    }
+
+    #[test]
+    fn write_text() {
+        let datetime = moon_landing();
+        assert_eq!(datetime.to_sql().unwrap(), ToSqlOutput::Owned(text("1969-07-20T20:17:40.500")));
+    }
+
+    #[test]
+    fn write_offset() {
+        let datetime = Offset::of_hours_and_minutes(-4, 0).unwrap().transform_date(moon_landing());
+        assert_eq!(datetime.to_sql().unwrap(), ToSqlOutput::Owned(text("1969-07-20T20:17:40.500-04:00")));
+    }
+
+    #[test]
+    fn read_text() {
+        assert_eq!(read::<LocalDateTime>(text("1969-07-20T20:17:40.500")).unwrap(), moon_landing());
+    }
+
+    #[test]
+    fn read_sqlite_text() {
+        assert_eq!(read::<LocalDateTime>(text("1969-07-20 20:17:40.500")).unwrap(), moon_landing());
+    }
+
+    #[test]
+    fn read_date_and_time() {
+        assert_eq!(read::<LocalDate>(text("2016-02-29")).unwrap(), LocalDate::ymd(2016, Month::February, 29).unwrap());
+        assert_eq!(read::<LocalTime>(text("13:45:00")).unwrap(), LocalTime::hms(13, 45, 0).unwrap());
+    }
+
+    #[test]
+    fn read_julian_day() {
+        let datetime = read::<LocalDateTime>(Value::Real(2457447.0)).unwrap();
+        assert_eq!(datetime.to_string(), "2016-02-28T12:00:00");
+    }
+
+    #[test]
+    fn read_julian_day_date() {
+        let date = read::<LocalDate>(Value::Real(2457446.75)).unwrap();
+        assert_eq!(date, LocalDate::ymd(2016, Month::February, 28).unwrap());
+    }
+
+    #[test]
+    fn read_unix_time() {
+        assert_eq!(read::<Instant>(Value::Integer(1456664400)).unwrap(), Instant::at(1456664400));
+    }
+
+    #[test]
+    fn numbers_are_utc() {
+        let datetime = read::<OffsetDateTime>(Value::Integer(0)).unwrap();
+        assert_eq!(datetime.to_string(), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn instant_round_trip() {
+        let instant = Instant::at_ms(1456664400, 250);
+        let output = instant.to_sql().unwrap();
+        assert_eq!(output, ToSqlOutput::Owned(text("2016-02-28T13:00:00.250Z")));
+
+        if let ToSqlOutput::Owned(value) = output {
+            assert_eq!(read::<Instant>(value).unwrap(), instant);
+        }
+    }
+
+    #[test]
+    fn time_from_number() {
+        assert!(read::<LocalTime>(Value::Real(2457448.0)).is_err());
+    }
+
+    #[test]
+    fn invalid_text() {
+        assert!(read::<LocalDate>(text("not a date")).is_err());
+    }
+
+    #[test]
+    fn julian_day_out_of_range() {
+        assert!(read::<LocalDateTime>(Value::Real(::std::f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn blob() {
+        assert!(read::<LocalDateTime>(Value::Blob(vec![ 1, 2, 3 ])).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Conversions to and from the `time` crate’s types.
+//!
+//! Most conversions can fail in one direction or the other: the time crate
+//! only supports years between -9999 and 9999, has no way to represent the
+//! 24:00 that ends a day or a leap second, and allows UTC offsets of up to 26 hours.
+
+use std::convert::TryFrom;
+
+use time;
+
+use cal::{DatePiece, TimePiece};
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Error};
+use cal::offset::{Offset, OffsetDateTime, Error as OffsetError};
+use duration::{Duration, Error as DurationError};
+
+
+impl From<time::Date> for LocalDate {
+    fn from(date: time::Date) -> LocalDate {
+        let month = Month::from_one(u8::from(date.month()) as i8).unwrap();
+        LocalDate::ymd(date.year() as i64, month, date.day() as i8).unwrap()
+    }
+}
+
+impl TryFrom<LocalDate> for time::Date {
+    type Error = Error;
+
+    fn try_from(date: LocalDate) -> Result<time::Date, Error> {
+        if date.year() < i32::min_value() as i64 || date.year() > i32::max_value() as i64 {
+            return Err(Error::YearOutOfRange { given: date.year() });
+        }
+
+        let month = time::Month::try_from(date.month() as u8).unwrap();
+        time::Date::from_calendar_date(date.year() as i32, month, date.day() as u8)
+                   .map_err(|_| Error::YearOutOfRange { given: date.year() })
+    }
+}
+
+impl From<time::Time> for LocalTime {
+    fn from(time: time::Time) -> LocalTime {
+        LocalTime::hms_ns(time.hour() as i8, time.minute() as i8, time.second() as i8, time.nanosecond() as i32).unwrap()
+    }
+}
+
+impl TryFrom<LocalTime> for time::Time {
+    type Error = Error;
+
+    fn try_from(time: LocalTime) -> Result<time::Time, Error> {
+        time::Time::from_hms_nano(time.hour() as u8, time.minute() as u8, time.second() as u8, time.nanosecond() as u32)
+                   .map_err(|_| if time.is_leap_second() { Error::SecondOutOfRange { given: 60 } }
+                                else { Error::HourOutOfRange { given: time.hour() } })
+    }
+}
+
+impl From<time::PrimitiveDateTime> for LocalDateTime {
+    fn from(datetime: time::PrimitiveDateTime) -> LocalDateTime {
+        LocalDateTime::new(datetime.date().into(), datetime.time().into())
+    }
+}
+
+impl TryFrom<LocalDateTime> for time::PrimitiveDateTime {
+    type Error = Error;
+
+    fn try_from(datetime: LocalDateTime) -> Result<time::PrimitiveDateTime, Error> {
+        let date = try!(time::Date::try_from(datetime.date()));
+        let time = try!(time::Time::try_from(datetime.time()));
+        Ok(time::PrimitiveDateTime::new(date, time))
+    }
+}
+
+impl TryFrom<time::OffsetDateTime> for OffsetDateTime {
+    type Error = OffsetError;
+
+    fn try_from(datetime: time::OffsetDateTime) -> Result<OffsetDateTime, OffsetError> {
+        let offset = try!(Offset::of_seconds(datetime.offset().whole_seconds()));
+        let local = LocalDateTime::new(datetime.date().into(), datetime.time().into());
+        Ok(offset.transform_date(local))
+    }
+}
+
+impl TryFrom<OffsetDateTime> for time::OffsetDateTime {
+    type Error = OffsetError;
+
+    fn try_from(datetime: OffsetDateTime) -> Result<time::OffsetDateTime, OffsetError> {
+        let local = try!(time::PrimitiveDateTime::try_from(datetime.local()).map_err(OffsetError::Date));
+        let offset = try!(time::UtcOffset::from_whole_seconds(datetime.offset().seconds()).map_err(|_| OffsetError::OutOfRange));
+        Ok(local.assume_offset(offset))
+    }
+}
+
+impl TryFrom<time::Duration> for Duration {
+    type Error = DurationError;
+
+    fn try_from(duration: time::Duration) -> Result<Duration, DurationError> {
+        let total_ns = duration.whole_seconds() as i128 * 1_000_000_000 + duration.subsec_nanoseconds() as i128;
+        let seconds = total_ns.div_euclid(1_000_000_000);
+
+        if seconds < i64::min_value() as i128 {
+            Err(DurationError::OutOfRange)
+        }
+        else {
+            Ok(Duration::of_ns(seconds as i64, total_ns.rem_euclid(1_000_000_000) as i32))
+        }
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> time::Duration {
+        let (seconds, nanoseconds) = duration.lengths_ns();
+        time::Duration::new(seconds, nanoseconds)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+
+    fn moon_landing() -> LocalDateTime {
+        LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms_ms(20, 17, 40, 500).unwrap())
+    }
+
+    fn time_moon_landing() -> time::PrimitiveDateTime {
+        time::Date::from_calendar_date(1969, time::Month::July, 20).unwrap().with_hms_nano(20, 17, 40, 500_000_000).unwrap()
+    }
+
+    #[test]
+    fn from_date() {
+        let date = time::Date::from_calendar_date(2016, time::Month::February, 29).unwrap();
+        assert_eq!(LocalDate::from(date), LocalDate::ymd(2016, Month::February, 29).unwrap())
+    }
+
+    #[test]
+    fn datetime_round_trip() {
+        assert_eq!(LocalDateTime::from(time_moon_landing()), moon_landing());
+        assert_eq!(time::PrimitiveDateTime::try_from(moon_landing()), Ok(time_moon_landing()));
+    }
+
+    #[test]
+    fn year_out_of_range() {
+        let date = LocalDate::ymd(10_000, Month::January, 1).unwrap();
+        assert_eq!(time::Date::try_from(date), Err(Error::YearOutOfRange { given: 10_000 }))
+    }
+
+    #[test]
+    fn end_of_day_out_of_range() {
+        assert_eq!(time::Time::try_from(LocalTime::hms(24, 0, 0).unwrap()), Err(Error::HourOutOfRange { given: 24 }))
+    }
+
+    #[test]
+    fn leap_second_out_of_range() {
+        assert_eq!(time::Time::try_from(LocalTime::hms_leap(23, 59, 60).unwrap()), Err(Error::SecondOutOfRange { given: 60 }))
+    }
+
+    #[test]
+    fn offset_round_trip() {
+        let datetime = Offset::of_hours_and_minutes(-9, -30).unwrap().transform_date(moon_landing());
+        let converted = time::OffsetDateTime::try_from(datetime).unwrap();
+        assert_eq!(converted.offset().whole_seconds(), -(9 * 3600 + 30 * 60));
+        assert_eq!(OffsetDateTime::try_from(converted), Ok(datetime));
+    }
+
+    #[test]
+    fn offset_out_of_range() {
+        let offset = time::UtcOffset::from_whole_seconds(25 * 3600).unwrap();
+        let datetime = time_moon_landing().assume_offset(offset);
+        assert_eq!(OffsetDateTime::try_from(datetime), Err(OffsetError::OutOfRange))
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(time::Duration::from(Duration::of_ms(-2, 500)), time::Duration::new(-1, -500_000_000));
+        assert_eq!(Duration::try_from(time::Duration::new(-1, -500_000_000)), Ok(Duration::of_ms(-2, 500)));
+    }
+
+    #[test]
+    fn duration_nanoseconds() {
+        assert_eq!(Duration::try_from(time::Duration::new(1, 999_999)), Ok(Duration::of_ns(1, 999_999)));
+        assert_eq!(Duration::try_from(time::Duration::new(-1, -999_999)), Ok(Duration::of_ns(-2, 999_000_001)));
+        assert_eq!(time::Duration::from(Duration::of_ns(-2, 999_000_001)), time::Duration::new(-1, -999_999));
+    }
+
+    #[test]
+    fn duration_out_of_range() {
+        let duration = time::Duration::new(i64::min_value(), -1_000_000);
+        assert_eq!(Duration::try_from(duration), Err(DurationError::OutOfRange))
+    }
+}
@@ -0,0 +1,145 @@
+//! Conversions to and from .NET ticks.
+//!
+//! .NET’s `DateTime.Ticks` counts the number of 100-nanosecond intervals
+//! since midnight at the start of the 1st of January in the year 1, in the
+//! proleptic Gregorian calendar. The same count shows up in SQL Server’s
+//! `datetime2` values and in data serialised by C# services.
+//!
+//! A .NET `DateTime` can only hold dates between the years 1 and 9999, so
+//! converting anything outside that range produces `None`. Ticks have no
+//! time zone, so whether they refer to UTC or local time depends on where
+//! they came from.
+
+use cal::TimePiece;
+use cal::datetime::LocalDateTime;
+use instant::Instant;
+
+
+/// The Unix timestamp of the start of the year 1, 0001-01-01T00:00:00.
+const TICKS_EPOCH: i64 = -62135596800;
+
+/// The number of ticks in a second.
+const TICKS_PER_SECOND: i64 = 10_000_000;
+
+/// The largest number of ticks that .NET allows, the last tick of
+/// 9999-12-31.
+const MAX_TICKS: i64 = 3155378975999999999;
+
+
+/// A number of **ticks** is a point in time, measured as the number of
+/// 100-nanosecond intervals since the start of the year 1.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct Ticks {
+    ticks: i64,
+}
+
+impl Ticks {
+
+    /// Creates a new tick count, returning `None` if it’s outside the range
+    /// that .NET supports.
+    pub fn new(ticks: i64) -> Option<Ticks> {
+        if ticks < 0 || ticks > MAX_TICKS {
+            None
+        }
+        else {
+            Some(Ticks { ticks: ticks })
+        }
+    }
+
+    /// Converts a date-time to ticks, keeping its full 100-nanosecond
+    /// precision.
+    ///
+    /// ```rust
+    /// use datetime::LocalDateTime;
+    /// use datetime::dotnet::Ticks;
+    ///
+    /// let ticks = Ticks::from_datetime(LocalDateTime::at(0)).unwrap();
+    /// assert_eq!(ticks.ticks(), 621355968000000000);
+    /// ```
+    pub fn from_datetime(datetime: LocalDateTime) -> Option<Ticks> {
+        let seconds = datetime.to_instant().seconds();
+        Ticks::from_seconds(seconds, datetime.time().nanosecond() as i64 / 100)
+    }
+
+    /// Converts a UTC instant to ticks.
+    pub fn from_instant(instant: Instant) -> Option<Ticks> {
+        Ticks::from_seconds(instant.seconds(), instant.nanoseconds() as i64 / 100)
+    }
+
+    fn from_seconds(unix_seconds: i64, ticks: i64) -> Option<Ticks> {
+        unix_seconds.checked_sub(TICKS_EPOCH)
+                    .and_then(|s| s.checked_mul(TICKS_PER_SECOND))
+                    .and_then(|t| t.checked_add(ticks))
+                    .and_then(Ticks::new)
+    }
+
+    /// Converts these ticks to a date-time.
+    pub fn to_datetime(&self) -> LocalDateTime {
+        let (seconds, ticks) = self.split();
+        LocalDateTime::at_ns(seconds, ticks as i32 * 100)
+    }
+
+    /// Converts these ticks to an instant, treating them as UTC.
+    pub fn to_instant(&self) -> Instant {
+        let (seconds, ticks) = self.split();
+        Instant::at_ns(seconds, ticks as i32 * 100)
+    }
+
+    /// Splits these ticks into a Unix timestamp and the number of ticks so
+    /// far this second.
+    fn split(&self) -> (i64, i64) {
+        let seconds = self.ticks / TICKS_PER_SECOND + TICKS_EPOCH;
+        (seconds, self.ticks % TICKS_PER_SECOND)
+    }
+
+    /// Returns the number of 100-nanosecond intervals since the start of
+    /// the year 1.
+    pub fn ticks(&self) -> i64 {
+        self.ticks
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::{LocalDate, LocalTime, Month};
+
+    #[test]
+    fn min_value() {
+        let ticks = Ticks::new(0).unwrap();
+        assert_eq!(ticks.to_datetime().to_string(), "0001-01-01T00:00:00");
+    }
+
+    #[test]
+    fn max_value() {
+        let ticks = Ticks::new(MAX_TICKS).unwrap();
+        let datetime = LocalDateTime::new(LocalDate::ymd(9999, Month::December, 31).unwrap(), LocalTime::hms_ns(23, 59, 59, 999_999_900).unwrap());
+        assert_eq!(ticks.to_datetime(), datetime);
+        assert_eq!(Ticks::from_datetime(datetime), Some(ticks));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(Ticks::new(-1), None);
+        assert_eq!(Ticks::new(MAX_TICKS + 1), None);
+        assert_eq!(Ticks::from_instant(Instant::at(TICKS_EPOCH - 1)), None);
+        assert_eq!(Ticks::from_datetime(LocalDateTime::new(LocalDate::ymd(10_000, Month::January, 1).unwrap(), LocalTime::midnight())), None);
+    }
+
+    #[test]
+    fn unix_epoch() {
+        let ticks = Ticks::from_instant(Instant::at_ms(0, 250)).unwrap();
+        assert_eq!(ticks.ticks(), 621355968002500000);
+        assert_eq!(ticks.to_instant(), Instant::at_ms(0, 250));
+    }
+
+    #[test]
+    fn precision() {
+        let datetime = LocalDateTime::at_ns(1_234_567_890, 123_456_700);
+        assert_eq!(Ticks::from_datetime(datetime).unwrap().to_datetime(), datetime);
+
+        let instant = Instant::at_ns(1_234_567_890, 123_456_700);
+        assert_eq!(Ticks::from_instant(instant).unwrap().to_instant(), instant);
+    }
+}
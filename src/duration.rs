@@ -1,39 +1,141 @@
 //! Lengths of time on the timeline.
 
+use std::convert::TryFrom;
+use std::error::Error as ErrorTrait;
+use std::fmt;
+use std::iter::Peekable;
 use std::ops::{Add, Sub, Mul};
+use std::str::{CharIndices, FromStr};
+use std::time::Duration as StdDuration;
 
 
 /// A **duration** is a length of time on the timeline, irrespective of
-/// time zone or calendar format, with millisecond precision.
-#[derive(Clone, PartialEq, Eq, Debug, Copy)]
+/// time zone or calendar format, with nanosecond precision.
+///
+/// Durations are ordered by their length, with negative durations coming
+/// before zero. As the nanoseconds portion is always positive, comparing
+/// the seconds first and then the nanoseconds gives the right answer.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy)]
 pub struct Duration {
     seconds: i64,
-    milliseconds: i16,
+    nanoseconds: i32,
 }
 
 impl Duration {
 
+    /// A duration of one nanosecond.
+    pub const NANOSECOND: Duration = Duration { seconds: 0, nanoseconds: 1 };
+
+    /// A duration of one microsecond.
+    pub const MICROSECOND: Duration = Duration { seconds: 0, nanoseconds: 1_000 };
+
+    /// A duration of one millisecond.
+    pub const MILLISECOND: Duration = Duration { seconds: 0, nanoseconds: 1_000_000 };
+
+    /// A duration of one second.
+    pub const SECOND: Duration = Duration { seconds: 1, nanoseconds: 0 };
+
+    /// A duration of one minute, or sixty seconds.
+    pub const MINUTE: Duration = Duration { seconds: 60, nanoseconds: 0 };
+
+    /// A duration of one hour, or sixty minutes.
+    pub const HOUR: Duration = Duration { seconds: 3600, nanoseconds: 0 };
+
+    /// A duration of one day, taken to be exactly twenty-four hours long.
+    /// Days on the calendar can be longer or shorter than this when the
+    /// clocks change, which this library’s durations know nothing about.
+    pub const DAY: Duration = Duration { seconds: 86400, nanoseconds: 0 };
+
+    /// A duration of one week, or seven twenty-four-hour days.
+    pub const WEEK: Duration = Duration { seconds: 7 * 86400, nanoseconds: 0 };
+
     /// Create a new zero-length duration.
-    pub fn zero() -> Duration {
-        Duration { seconds: 0, milliseconds: 0 }
+    pub const fn zero() -> Duration {
+        Duration { seconds: 0, nanoseconds: 0 }
     }
 
     /// Create a new duration that’s the given number of seconds long.
-    pub fn of(seconds: i64) -> Duration {
-        Duration { seconds: seconds, milliseconds: 0 }
+    pub const fn of(seconds: i64) -> Duration {
+        Duration { seconds: seconds, nanoseconds: 0 }
     }
 
     /// Create a new duration that’s the given number of seconds and
     /// milliseconds long.
-    pub fn of_ms(seconds: i64, milliseconds: i16) -> Duration {
-        assert!(milliseconds >= 0 && milliseconds <= 999);  // TODO: replace assert with returning Result
-        Duration { seconds: seconds, milliseconds: milliseconds }
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of milliseconds is not between 0 and 999. Use
+    /// `try_of_ms` to handle that case instead.
+    pub const fn of_ms(seconds: i64, milliseconds: i16) -> Duration {
+        match Duration::try_of_ms(seconds, milliseconds) {
+            Ok(duration) => duration,
+            Err(_)       => panic!("milliseconds out of range"),
+        }
+    }
+
+    /// Create a new duration that’s the given number of seconds and
+    /// milliseconds long, returning an error if the number of milliseconds
+    /// is not between 0 and 999.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{Duration, DurationError};
+    ///
+    /// const POLL_INTERVAL: Duration = match Duration::try_of_ms(0, 250) {
+    ///     Ok(duration) => duration,
+    ///     Err(_)       => panic!("invalid poll interval"),
+    /// };
+    ///
+    /// assert_eq!(POLL_INTERVAL, 250 * Duration::MILLISECOND);
+    /// assert_eq!(Duration::try_of_ms(0, 1000), Err(DurationError::OutOfRange));
+    /// ```
+    pub const fn try_of_ms(seconds: i64, milliseconds: i16) -> Result<Duration, Error> {
+        if milliseconds < 0 || milliseconds > 999 {
+            Err(Error::OutOfRange)
+        }
+        else {
+            Ok(Duration { seconds: seconds, nanoseconds: milliseconds as i32 * 1_000_000 })
+        }
+    }
+
+    /// Create a new duration that’s the given number of seconds and
+    /// nanoseconds long.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of nanoseconds is not between 0 and
+    /// 999,999,999. Use `try_of_ns` to handle that case instead.
+    pub const fn of_ns(seconds: i64, nanoseconds: i32) -> Duration {
+        match Duration::try_of_ns(seconds, nanoseconds) {
+            Ok(duration) => duration,
+            Err(_)       => panic!("nanoseconds out of range"),
+        }
+    }
+
+    /// Create a new duration that’s the given number of seconds and
+    /// nanoseconds long, returning an error if the number of nanoseconds
+    /// is not between 0 and 999,999,999.
+    pub const fn try_of_ns(seconds: i64, nanoseconds: i32) -> Result<Duration, Error> {
+        if nanoseconds < 0 || nanoseconds > 999_999_999 {
+            Err(Error::OutOfRange)
+        }
+        else {
+            Ok(Duration { seconds: seconds, nanoseconds: nanoseconds })
+        }
     }
 
     /// Return the seconds and milliseconds portions of the duration as
-    /// a 2-element tuple.
+    /// a 2-element tuple. Any nanoseconds beyond the millisecond are
+    /// discarded.
     pub fn lengths(&self) -> (i64, i16) {
-        (self.seconds, self.milliseconds)
+        (self.seconds, (self.nanoseconds / 1_000_000) as i16)
+    }
+
+    /// Return the seconds and nanoseconds portions of the duration as
+    /// a 2-element tuple.
+    pub fn lengths_ns(&self) -> (i64, i32) {
+        (self.seconds, self.nanoseconds)
     }
 
     // I’ve done it like this instead of having separate seconds() and
@@ -41,18 +143,143 @@ impl Duration {
     // people will think that milliseconds() returns the *total* length
     // in milliseconds, rather than just this particular portion. This
     // way, it’s clear that there are two separate values being returned.
+    //
+    // The accessors below have the same problem solved with their names:
+    // the `whole_` ones return the total length, and the `subsec_` one
+    // returns only the part left over.
+
+    /// Returns the total length of this duration in seconds, including the
+    /// fractional part.
+    pub fn as_seconds_f64(&self) -> f64 {
+        self.seconds as f64 + self.nanoseconds as f64 / 1_000_000_000.0
+    }
+
+    /// Returns the total length of this duration in nanoseconds.
+    pub fn whole_nanoseconds(&self) -> i128 {
+        self.seconds as i128 * 1_000_000_000 + self.nanoseconds as i128
+    }
+
+    /// Returns the number of whole milliseconds in this duration, rounding
+    /// towards zero.
+    pub fn whole_milliseconds(&self) -> i128 {
+        self.whole_nanoseconds() / 1_000_000
+    }
+
+    /// Returns the number of whole seconds in this duration, rounding
+    /// towards zero, so that minus one and a half seconds is minus one.
+    pub fn whole_seconds(&self) -> i64 {
+        if self.seconds < 0 && self.nanoseconds > 0 {
+            self.seconds + 1
+        }
+        else {
+            self.seconds
+        }
+    }
+
+    /// Returns the number of whole minutes in this duration, rounding
+    /// towards zero.
+    pub fn whole_minutes(&self) -> i64 {
+        self.whole_seconds() / 60
+    }
+
+    /// Returns the number of whole hours in this duration, rounding
+    /// towards zero.
+    pub fn whole_hours(&self) -> i64 {
+        self.whole_seconds() / 3600
+    }
+
+    /// Returns the number of whole twenty-four-hour days in this duration,
+    /// rounding towards zero.
+    pub fn whole_days(&self) -> i64 {
+        self.whole_seconds() / 86400
+    }
+
+    /// Returns the number of whole weeks in this duration, rounding
+    /// towards zero.
+    pub fn whole_weeks(&self) -> i64 {
+        self.whole_seconds() / (7 * 86400)
+    }
+
+    /// Returns the number of milliseconds left over after the whole seconds
+    /// in this duration. This has the same sign as the duration, so adding
+    /// it to `whole_seconds` gives the total length.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::Duration;
+    ///
+    /// let duration = Duration::of_ms(-2, 500);  // minus one and a half seconds
+    /// assert_eq!(duration.whole_seconds(), -1);
+    /// assert_eq!(duration.subsec_milliseconds(), -500);
+    /// ```
+    pub fn subsec_milliseconds(&self) -> i16 {
+        (self.subsec_nanoseconds() / 1_000_000) as i16
+    }
+
+    /// Returns the number of nanoseconds left over after the whole seconds
+    /// in this duration, with the same sign as the duration.
+    pub fn subsec_nanoseconds(&self) -> i32 {
+        if self.seconds < 0 && self.nanoseconds > 0 {
+            self.nanoseconds - 1_000_000_000
+        }
+        else {
+            self.nanoseconds
+        }
+    }
+
+    /// Adds two durations together, returning `None` if the result would
+    /// be too long to represent.
+    pub fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        let ns = self.nanoseconds + rhs.nanoseconds;
+        let (carry, ns) = if ns >= 1_000_000_000 { (1, ns - 1_000_000_000) } else { (0, ns) };
+        let seconds = self.seconds.checked_add(rhs.seconds)?.checked_add(carry)?;
+        Some(Duration { seconds: seconds, nanoseconds: ns })
+    }
+
+    /// Subtracts one duration from another, returning `None` if the result
+    /// would be too long to represent.
+    pub fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        let ns = self.nanoseconds - rhs.nanoseconds;
+        let (borrow, ns) = if ns < 0 { (1, ns + 1_000_000_000) } else { (0, ns) };
+        let seconds = self.seconds.checked_sub(rhs.seconds)?.checked_sub(borrow)?;
+        Some(Duration { seconds: seconds, nanoseconds: ns })
+    }
+
+    /// Multiplies a duration by a number, returning `None` if the result
+    /// would be too long to represent.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::Duration;
+    ///
+    /// assert_eq!(Duration::HOUR.checked_mul(3), Some(Duration::of(10800)));
+    /// assert_eq!(Duration::WEEK.checked_mul(i64::max_value()), None);
+    /// ```
+    pub fn checked_mul(self, amount: i64) -> Option<Duration> {
+        let total = self.whole_nanoseconds().checked_mul(amount as i128)?;
+        let seconds = total.div_euclid(1_000_000_000);
+
+        if seconds > i64::max_value() as i128 || seconds < i64::min_value() as i128 {
+            None
+        }
+        else {
+            Some(Duration { seconds: seconds as i64, nanoseconds: total.rem_euclid(1_000_000_000) as i32 })
+        }
+    }
 }
 
 impl Add<Duration> for Duration {
     type Output = Duration;
 
     fn add(self, rhs: Duration) -> Duration {
-        let ms = self.milliseconds + rhs.milliseconds;
-        if ms >= 1000 {
-            Duration::of_ms(self.seconds + rhs.seconds + 1, ms - 1000)
+        let ns = self.nanoseconds + rhs.nanoseconds;
+        if ns >= 1_000_000_000 {
+            Duration::of_ns(self.seconds + rhs.seconds + 1, ns - 1_000_000_000)
         }
         else {
-            Duration::of_ms(self.seconds + rhs.seconds, ms)
+            Duration::of_ns(self.seconds + rhs.seconds, ns)
         }
     }
 }
@@ -61,12 +288,12 @@ impl Sub<Duration> for Duration {
     type Output = Duration;
 
     fn sub(self, rhs: Duration) -> Duration {
-        let ms = self.milliseconds - rhs.milliseconds;
-        if ms < 0 {
-            Duration::of_ms(self.seconds - rhs.seconds - 1, ms + 1000)
+        let ns = self.nanoseconds - rhs.nanoseconds;
+        if ns < 0 {
+            Duration::of_ns(self.seconds - rhs.seconds - 1, ns + 1_000_000_000)
         }
         else {
-            Duration::of_ms(self.seconds - rhs.seconds, ms)
+            Duration::of_ns(self.seconds - rhs.seconds, ns)
         }
     }
 }
@@ -75,11 +302,450 @@ impl Mul<i64> for Duration {
     type Output = Duration;
 
     fn mul(self, amount: i64) -> Duration {
-        let ms = self.milliseconds as i64 * amount;
-        Duration::of_ms(self.seconds * amount + ms / 1000, (ms % 1000) as i16)
+        // The nanoseconds are always positive, so a negative amount needs
+        // to borrow from the seconds rather than leave them negative.
+        let ns = self.nanoseconds as i128 * amount as i128;
+        let carry = ns.div_euclid(1_000_000_000) as i64;
+        Duration::of_ns(self.seconds * amount + carry, ns.rem_euclid(1_000_000_000) as i32)
     }
 }
 
+impl fmt::Display for Duration {
+
+    /// Formats this duration in the ISO 8601 format, such as
+    /// `PT1H30M15.250S`. Only hours, minutes, and seconds are used, so long
+    /// durations have a large number of hours rather than any days, unless
+    /// the alternate flag is given, in which case every 24 hours becomes a
+    /// day, such as `P1DT2H`. Negative durations are prefixed with a minus
+    /// sign. The fraction of a second is written with three, six, or nine
+    /// digits, whichever is the fewest that keep all of it.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::Duration;
+    ///
+    /// assert_eq!(Duration::of_ms(5415, 250).to_string(), "PT1H30M15.250S");
+    /// assert_eq!(Duration::of(60).to_string(), "PT1M");
+    /// assert_eq!(Duration::zero().to_string(), "PT0S");
+    /// assert_eq!(format!("{:#}", Duration::of(26 * 3600)), "P1DT2H");
+    /// assert_eq!(format!("{:#}", Duration::of(86400)), "P1D");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let total_ns = self.whole_nanoseconds();
+        if total_ns < 0 {
+            try!(f.write_str("-"));
+        }
+
+        let total_ns = total_ns.abs();
+        let total_seconds = total_ns / 1_000_000_000;
+        let mut hours = total_seconds / 3600;
+        let minutes = total_seconds / 60 % 60;
+        let seconds = total_seconds % 60;
+        let nanoseconds = total_ns % 1_000_000_000;
+
+        if f.alternate() && hours >= 24 {
+            try!(write!(f, "P{}D", hours / 24));
+            hours %= 24;
+
+            if total_ns % (86400 * 1_000_000_000) == 0 {
+                return Ok(());
+            }
+
+            try!(f.write_str("T"));
+        }
+        else {
+            try!(f.write_str("PT"));
+        }
+
+        if hours > 0 {
+            try!(write!(f, "{}H", hours));
+        }
+
+        if minutes > 0 {
+            try!(write!(f, "{}M", minutes));
+        }
+
+        if nanoseconds % 1_000_000 == 0 && nanoseconds > 0 {
+            try!(write!(f, "{}.{:03}S", seconds, nanoseconds / 1_000_000));
+        }
+        else if nanoseconds % 1_000 == 0 && nanoseconds > 0 {
+            try!(write!(f, "{}.{:06}S", seconds, nanoseconds / 1_000));
+        }
+        else if nanoseconds > 0 {
+            try!(write!(f, "{}.{:09}S", seconds, nanoseconds));
+        }
+        else if seconds > 0 || total_ns == 0 {
+            try!(write!(f, "{}S", seconds));
+        }
+
+        Ok(())
+    }
+}
+
+impl Mul<Duration> for i64 {
+    type Output = Duration;
+
+    /// Multiplies a duration by a number, so that lengths of time can be
+    /// written as `3 * Duration::HOUR`.
+    fn mul(self, duration: Duration) -> Duration {
+        duration * self
+    }
+}
+
+impl TryFrom<StdDuration> for Duration {
+    type Error = Error;
+
+    /// Converts a standard library duration into a duration. This fails if
+    /// the number of seconds is too large to fit in an `i64`.
+    fn try_from(duration: StdDuration) -> Result<Duration, Error> {
+        if duration.as_secs() > i64::max_value() as u64 {
+            return Err(Error::OutOfRange);
+        }
+
+        Ok(Duration::of_ns(duration.as_secs() as i64, duration.subsec_nanos() as i32))
+    }
+}
+
+impl TryFrom<Duration> for StdDuration {
+    type Error = Error;
+
+    /// Converts a duration into a standard library duration, for use with
+    /// functions such as `std::thread::sleep`. This fails if the duration
+    /// is negative, as standard library durations cannot be.
+    fn try_from(duration: Duration) -> Result<StdDuration, Error> {
+        if duration.seconds < 0 {
+            Err(Error::Negative)
+        }
+        else {
+            Ok(StdDuration::new(duration.seconds as u64, duration.nanoseconds as u32))
+        }
+    }
+}
+
+
+/// An error that occurs when converting between this library’s durations
+/// and the standard library’s.
+#[non_exhaustive]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Error {
+
+    /// The duration was negative, which the standard library cannot handle.
+    Negative,
+
+    /// The duration was too long to be represented.
+    OutOfRange,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl ErrorTrait for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Negative    => "duration is negative",
+            Error::OutOfRange  => "duration out of range",
+        }
+    }
+}
+
+
+impl FromStr for Duration {
+    type Err = ParseError;
+
+    /// Parses a human-readable duration, such as `1h30m`, `90s`, or
+    /// `2d 4h`, in the style of the `humantime` crate.
+    ///
+    /// The input is a list of whole numbers, each followed by a unit, with
+    /// optional whitespace between the parts. The lengths of all the parts
+    /// get added together. The recognised units are:
+    ///
+    /// - `ns`, `nsec`: nanoseconds
+    /// - `us`, `usec`: microseconds
+    /// - `ms`, `msec`: milliseconds
+    /// - `s`, `sec`, `second`, `seconds`
+    /// - `m`, `min`, `minute`, `minutes`
+    /// - `h`, `hr`, `hour`, `hours`
+    /// - `d`, `day`, `days`
+    /// - `w`, `week`, `weeks`
+    /// - `M`, `month`, `months`: 30.44 days
+    /// - `y`, `year`, `years`: 365.25 days
+    ///
+    /// Strings beginning with `P` (or `-P`) are instead parsed as ISO 8601
+    /// durations, such as `PT1H30M15.250S`, which is the format durations
+    /// are displayed in. These can have week, day, hour, minute, and second
+    /// components, and the last of these can have a decimal fraction. Year
+    /// and month components are rejected, as their length varies.
+    ///
+    /// Fractions of a nanosecond, which can only come from a fractional
+    /// ISO 8601 component, get rounded down.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::Duration;
+    ///
+    /// assert_eq!("1h30m".parse(), Ok(Duration::of(5400)));
+    /// assert_eq!("2d 4h".parse(), Ok(Duration::of(187200)));
+    /// assert_eq!("1s 500ms".parse(), Ok(Duration::of_ms(1, 500)));
+    /// assert_eq!("PT1H30M15.250S".parse(), Ok(Duration::of_ms(5415, 250)));
+    ///
+    /// assert!("90".parse::<Duration>().is_err());
+    /// ```
+    fn from_str(input: &str) -> Result<Duration, ParseError> {
+        let trimmed = input.trim_start();
+        if trimmed.starts_with('P') || trimmed.starts_with("-P") {
+            return parse_iso(input, input.len() - trimmed.len());
+        }
+
+        let mut total_nanos: i128 = 0;
+        let mut chars = input.char_indices().peekable();
+        let mut any_parts = false;
+
+        loop {
+            skip_whitespace(&mut chars);
+
+            let mut number: u64 = match chars.next() {
+                None => break,
+                Some((_, c)) if c.is_digit(10) => c.to_digit(10).unwrap() as u64,
+                Some((pos, c)) => return Err(ParseError::InvalidCharacter { c: c, pos: pos }),
+            };
+
+            while let Some(&(_, c)) = chars.peek() {
+                match c.to_digit(10) {
+                    Some(digit) => {
+                        number = try!(number.checked_mul(10)
+                                            .and_then(|n| n.checked_add(digit as u64))
+                                            .ok_or(ParseError::OutOfRange));
+                        let _ = chars.next();
+                    }
+                    None => break,
+                }
+            }
+
+            // Units can be separated from their numbers by whitespace.
+            skip_whitespace(&mut chars);
+
+            let start = match chars.peek() {
+                Some(&(pos, c)) if c.is_alphabetic() => pos,
+                Some(&(pos, _)) => return Err(ParseError::MissingUnit { pos: pos }),
+                None            => return Err(ParseError::MissingUnit { pos: input.len() }),
+            };
+
+            let mut end = input.len();
+            while let Some(&(pos, c)) = chars.peek() {
+                if !c.is_alphabetic() { end = pos; break; }
+                let _ = chars.next();
+            }
+
+            let unit_nanos = try!(unit_in_nanoseconds(&input[start .. end])
+                                     .ok_or(ParseError::UnknownUnit { start: start, end: end }));
+
+            total_nanos += number as i128 * unit_nanos;
+            if total_nanos / 1_000_000_000 > i64::max_value() as i128 {
+                return Err(ParseError::OutOfRange);
+            }
+
+            any_parts = true;
+        }
+
+        if any_parts {
+            Ok(from_total_nanoseconds(total_nanos))
+        }
+        else {
+            Err(ParseError::Empty)
+        }
+    }
+}
+
+/// Parses an ISO 8601 duration, such as `PT1H30M` or `-P2DT0.5S`, starting
+/// at the given position in the input string.
+fn parse_iso(input: &str, start: usize) -> Result<Duration, ParseError> {
+    let mut chars = input[start ..].trim_end().char_indices().peekable();
+    let mut total_nanos: i128 = 0;
+    let mut any_parts = false;
+    let mut in_time = false;
+    let mut last_rank = None;
+
+    let negative = match chars.peek() {
+        Some(&(_, '-')) => { let _ = chars.next(); true },
+        _ => false,
+    };
+
+    // The caller has already checked that the ‘P’ is there.
+    let _ = chars.next();
+
+    while let Some((pos, c)) = chars.next() {
+        let pos = pos + start;
+
+        if c == 'T' && !in_time {
+            in_time = true;
+            continue;
+        }
+
+        let mut number: u64 = match c.to_digit(10) {
+            Some(digit) => digit as u64,
+            None => return Err(ParseError::InvalidCharacter { c: c, pos: pos }),
+        };
+
+        while let Some(&(_, c)) = chars.peek() {
+            match c.to_digit(10) {
+                Some(digit) => {
+                    number = try!(number.checked_mul(10)
+                                        .and_then(|n| n.checked_add(digit as u64))
+                                        .ok_or(ParseError::OutOfRange));
+                    let _ = chars.next();
+                }
+                None => break,
+            }
+        }
+
+        // Fractions can use either a full stop or a comma, and only the
+        // first nine digits are significant.
+        let mut fraction: i128 = 0;
+        let mut fraction_scale: i128 = 1;
+        let mut has_fraction = false;
+        if let Some(&(_, '.')) | Some(&(_, ',')) = chars.peek() {
+            let _ = chars.next();
+            has_fraction = true;
+
+            while let Some(&(_, c)) = chars.peek() {
+                match c.to_digit(10) {
+                    Some(digit) => {
+                        if fraction_scale < 1_000_000_000 {
+                            fraction = fraction * 10 + digit as i128;
+                            fraction_scale *= 10;
+                        }
+                        let _ = chars.next();
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        let (pos, designator) = match chars.next() {
+            Some((p, c)) => (p + start, c),
+            None         => return Err(ParseError::MissingUnit { pos: input.len() }),
+        };
+
+        let (rank, unit_nanos) = match (in_time, designator) {
+            (false, 'W') => (0,  7 * 86400 * 1_000_000_000),
+            (false, 'D') => (1,      86400 * 1_000_000_000),
+            (true,  'H') => (2,       3600 * 1_000_000_000),
+            (true,  'M') => (3,         60 * 1_000_000_000),
+            (true,  'S') => (4,              1_000_000_000),
+            _ => return Err(ParseError::UnknownUnit { start: pos, end: pos + designator.len_utf8() }),
+        };
+
+        // Components have to come in order, and only the last one can have
+        // a fraction.
+        if last_rank.map_or(false, |r| r >= rank) {
+            return Err(ParseError::UnknownUnit { start: pos, end: pos + 1 });
+        }
+        last_rank = if has_fraction { Some(5) } else { Some(rank) };
+
+        total_nanos += number as i128 * unit_nanos + fraction * unit_nanos / fraction_scale;
+        if total_nanos / 1_000_000_000 > i64::max_value() as i128 {
+            return Err(ParseError::OutOfRange);
+        }
+
+        any_parts = true;
+    }
+
+    if !any_parts {
+        Err(ParseError::Empty)
+    }
+    else if negative {
+        Ok(from_total_nanoseconds(-total_nanos))
+    }
+    else {
+        Ok(from_total_nanoseconds(total_nanos))
+    }
+}
+
+/// Converts a total number of nanoseconds into a duration.
+fn from_total_nanoseconds(nanos: i128) -> Duration {
+    let seconds = nanos.div_euclid(1_000_000_000) as i64;
+    let nanoseconds = nanos.rem_euclid(1_000_000_000) as i32;
+    Duration::of_ns(seconds, nanoseconds)
+}
+
+/// Advances the iterator past any whitespace characters.
+fn skip_whitespace(chars: &mut Peekable<CharIndices>) {
+    while let Some(&(_, c)) = chars.peek() {
+        if !c.is_whitespace() { break; }
+        let _ = chars.next();
+    }
+}
+
+/// Returns the length of the unit with the given name, in nanoseconds, or
+/// `None` if it isn’t a unit that can be parsed.
+fn unit_in_nanoseconds(unit: &str) -> Option<i128> {
+    Some(match unit {
+        "ns" | "nsec"                                          =>                          1,
+        "us" | "usec"                                          =>                      1_000,
+        "ms" | "msec"                                          =>                  1_000_000,
+        "s"  | "sec"   | "second" | "seconds"                  =>              1_000_000_000,
+        "m"  | "min"   | "mins"   | "minute"  | "minutes"      =>         60 * 1_000_000_000,
+        "h"  | "hr"    | "hrs"    | "hour"    | "hours"        =>       3600 * 1_000_000_000,
+        "d"  | "day"   | "days"                                =>      86400 * 1_000_000_000,
+        "w"  | "week"  | "weeks"                               =>  7 * 86400 * 1_000_000_000,
+        "M"  | "month" | "months"                              =>    2630016 * 1_000_000_000,
+        "y"  | "year"  | "years"                               =>   31557600 * 1_000_000_000,
+        _ => return None,
+    })
+}
+
+
+/// An error that occurs when parsing a human-readable duration.
+#[non_exhaustive]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ParseError {
+
+    /// The input string was empty, or only contained whitespace.
+    Empty,
+
+    /// A character was found where a number was expected.
+    InvalidCharacter { c: char, pos: usize },
+
+    /// A number was not followed by a unit.
+    MissingUnit { pos: usize },
+
+    /// A unit, found between the `start` and `end` positions in the input
+    /// string, was not recognised.
+    UnknownUnit { start: usize, end: usize },
+
+    /// The duration was too long to be represented.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ParseError::InvalidCharacter { c, pos } => write!(f, "{} ({:?} at position {})", self.description(), c, pos),
+            ParseError::MissingUnit { pos }         => write!(f, "{} (at position {})", self.description(), pos),
+            ParseError::UnknownUnit { start, end }  => write!(f, "{} (at positions {} to {})", self.description(), start, end),
+            _                                       => write!(f, "{}", self.description()),
+        }
+    }
+}
+
+impl ErrorTrait for ParseError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseError::Empty                   => "empty duration",
+            ParseError::InvalidCharacter { .. } => "invalid character in duration",
+            ParseError::MissingUnit { .. }      => "missing unit in duration",
+            ParseError::UnknownUnit { .. }      => "unknown unit in duration",
+            ParseError::OutOfRange              => "duration out of range",
+        }
+    }
+}
+
+
 #[cfg(test)]
 mod test {
     pub use super::Duration;
@@ -145,4 +811,387 @@ mod test {
             assert_eq!(Duration::of(1), Duration::of_ms(0, 500) * 2)
         }
     }
+
+    mod checked {
+        use super::*;
+
+        #[test]
+        fn add() {
+            assert_eq!(Duration::of_ms(0, 750).checked_add(Duration::of_ms(0, 750)), Some(Duration::of_ms(1, 500)))
+        }
+
+        #[test]
+        fn add_overflow() {
+            assert_eq!(Duration::of(i64::max_value()).checked_add(Duration::of(1)), None);
+            assert_eq!(Duration::of_ms(i64::max_value(), 500).checked_add(Duration::of_ms(0, 500)), None);
+        }
+
+        #[test]
+        fn sub() {
+            assert_eq!(Duration::of_ms(1, 500).checked_sub(Duration::of_ms(0, 750)), Some(Duration::of_ms(0, 750)))
+        }
+
+        #[test]
+        fn sub_overflow() {
+            assert_eq!(Duration::of(i64::min_value()).checked_sub(Duration::of_ms(0, 1)), None)
+        }
+
+        #[test]
+        fn mul() {
+            assert_eq!(Duration::of_ms(0, 500).checked_mul(3), Some(Duration::of_ms(1, 500)));
+            assert_eq!(Duration::of_ms(1, 500).checked_mul(-1), Some(Duration::of_ms(-2, 500)));
+        }
+
+        #[test]
+        fn mul_overflow() {
+            assert_eq!(Duration::of(i64::max_value() / 2 + 1).checked_mul(2), None)
+        }
+    }
+
+    mod constants {
+        use super::*;
+
+        #[test]
+        fn lengths() {
+            assert_eq!(Duration::NANOSECOND, Duration::of_ns(0, 1));
+            assert_eq!(Duration::MICROSECOND, 1000 * Duration::NANOSECOND);
+            assert_eq!(Duration::MILLISECOND, Duration::of_ms(0, 1));
+            assert_eq!(Duration::MINUTE, Duration::of(60));
+            assert_eq!(Duration::HOUR, 60 * Duration::MINUTE);
+            assert_eq!(Duration::DAY, 24 * Duration::HOUR);
+            assert_eq!(Duration::WEEK, 7 * Duration::DAY);
+        }
+
+        #[test]
+        fn scaled() {
+            assert_eq!(3 * Duration::HOUR + 15 * Duration::MINUTE, Duration::of(11700));
+            assert_eq!(Duration::SECOND * 2 + 250 * Duration::MILLISECOND, Duration::of_ms(2, 250));
+            assert_eq!(-1 * Duration::MILLISECOND, Duration::of_ms(-1, 999));
+            assert_eq!(Duration::of_ms(0, 500) * -1, Duration::of_ms(-1, 500));
+            assert_eq!(-3 * Duration::of_ms(1, 250), Duration::of_ms(-4, 250));
+            assert_eq!(-2 * Duration::HOUR, Duration::of(-7200));
+            assert_eq!(-3 * Duration::NANOSECOND, Duration::of_ns(-1, 999_999_997));
+        }
+
+        #[test]
+        fn const_evaluable() {
+            const TIMEOUT: Duration = Duration::MINUTE;
+            assert_eq!(TIMEOUT.lengths(), (60, 0));
+
+            const NONE: Duration = Duration::zero();
+            const QUARTER: Duration = Duration::of_ms(0, 250);
+            const TICK: Duration = Duration::of_ns(0, 100);
+            assert_eq!(NONE.lengths(), (0, 0));
+            assert_eq!(QUARTER.lengths(), (0, 250));
+            assert_eq!(TICK.lengths_ns(), (0, 100));
+        }
+
+        #[test]
+        fn out_of_range() {
+            use super::super::Error;
+
+            assert_eq!(Duration::try_of_ms(1, 500), Ok(Duration::of_ms(1, 500)));
+            assert_eq!(Duration::try_of_ms(1, -1), Err(Error::OutOfRange));
+            assert_eq!(Duration::try_of_ms(1, 1000), Err(Error::OutOfRange));
+            assert_eq!(Duration::try_of_ns(1, 1_000_000_000), Err(Error::OutOfRange));
+        }
+
+        #[test]
+        #[should_panic]
+        fn of_ms_out_of_range() {
+            let _ = Duration::of_ms(0, 5000);
+        }
+    }
+
+    mod accessors {
+        use super::*;
+
+        #[test]
+        fn seconds_f64() {
+            assert_eq!(Duration::of_ms(90, 250).as_seconds_f64(), 90.25);
+            assert_eq!(Duration::of_ms(-2, 500).as_seconds_f64(), -1.5);
+        }
+
+        #[test]
+        fn whole_milliseconds() {
+            assert_eq!(Duration::of_ms(90, 250).whole_milliseconds(), 90_250);
+            assert_eq!(Duration::of_ms(-2, 500).whole_milliseconds(), -1_500);
+        }
+
+        #[test]
+        fn whole_units() {
+            let duration = Duration::of(9 * 86400 + 5 * 3600 + 7 * 60 + 3);
+            assert_eq!(duration.whole_weeks(), 1);
+            assert_eq!(duration.whole_days(), 9);
+            assert_eq!(duration.whole_hours(), 9 * 24 + 5);
+            assert_eq!(duration.whole_minutes(), (9 * 24 + 5) * 60 + 7);
+            assert_eq!(duration.whole_seconds(), 796023);
+        }
+
+        #[test]
+        fn negative_rounds_towards_zero() {
+            let duration = Duration::of_ms(-91, 500);
+            assert_eq!(duration.whole_seconds(), -90);
+            assert_eq!(duration.whole_minutes(), -1);
+            assert_eq!(duration.subsec_milliseconds(), -500);
+        }
+
+        #[test]
+        fn subsec_milliseconds() {
+            assert_eq!(Duration::of_ms(90, 250).subsec_milliseconds(), 250);
+            assert_eq!(Duration::of(-3).subsec_milliseconds(), 0);
+        }
+
+        #[test]
+        fn nanoseconds() {
+            let duration = Duration::of_ns(-2, 499_999_999);  // minus 1.500000001 seconds
+            assert_eq!(duration.whole_nanoseconds(), -1_500_000_001);
+            assert_eq!(duration.whole_milliseconds(), -1_500);
+            assert_eq!(duration.subsec_nanoseconds(), -500_000_001);
+            assert_eq!(duration.subsec_milliseconds(), -500);
+            assert_eq!(duration.lengths_ns(), (-2, 499_999_999));
+        }
+    }
+
+    mod ordering {
+        use super::*;
+        use std::collections::{BTreeMap, HashSet};
+
+        #[test]
+        fn milliseconds() {
+            assert!(Duration::of_ms(1, 250) < Duration::of_ms(1, 500))
+        }
+
+        #[test]
+        fn negative() {
+            assert!(Duration::of_ms(-1, 500) < Duration::zero());
+            assert!(Duration::of_ms(-2, 999) < Duration::of_ms(-1, 0));
+        }
+
+        #[test]
+        fn sorting() {
+            let mut durations = vec![ Duration::of(5), Duration::of_ms(-1, 500), Duration::of_ms(0, 1), Duration::zero() ];
+            durations.sort();
+            assert_eq!(durations, vec![ Duration::of_ms(-1, 500), Duration::zero(), Duration::of_ms(0, 1), Duration::of(5) ]);
+        }
+
+        #[test]
+        fn map_keys() {
+            let mut map = BTreeMap::new();
+            let _ = map.insert(Duration::of(60), "minute");
+            let _ = map.insert(Duration::of(1), "second");
+            assert_eq!(map.values().collect::<Vec<_>>(), vec![ &"second", &"minute" ]);
+        }
+
+        #[test]
+        fn deduplicate() {
+            let set: HashSet<Duration> = vec![ Duration::of(1), Duration::of_ms(1, 0), Duration::of_ms(1, 1) ].into_iter().collect();
+            assert_eq!(set.len(), 2);
+        }
+    }
+
+    mod parsing {
+        use super::*;
+        use super::super::ParseError;
+
+        #[test]
+        fn seconds() {
+            assert_eq!("90s".parse(), Ok(Duration::of(90)))
+        }
+
+        #[test]
+        fn hours_and_minutes() {
+            assert_eq!("1h30m".parse(), Ok(Duration::of(5400)))
+        }
+
+        #[test]
+        fn with_spaces() {
+            assert_eq!("  2d 4h ".parse(), Ok(Duration::of(2 * 86400 + 4 * 3600)))
+        }
+
+        #[test]
+        fn long_units() {
+            assert_eq!("1 week 2 days 3 hours 4 minutes 5 seconds".parse(), Ok(Duration::of(788645)))
+        }
+
+        #[test]
+        fn milliseconds() {
+            assert_eq!("2s 250ms".parse(), Ok(Duration::of_ms(2, 250)))
+        }
+
+        #[test]
+        fn sub_milliseconds() {
+            assert_eq!("1500us 999999ns".parse(), Ok(Duration::of_ns(0, 2_499_999)))
+        }
+
+        #[test]
+        fn months_and_years() {
+            assert_eq!("1M".parse(), Ok(Duration::of(2630016)));
+            assert_eq!("1y".parse(), Ok(Duration::of(31557600)));
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!("   ".parse::<Duration>(), Err(ParseError::Empty))
+        }
+
+        #[test]
+        fn missing_unit() {
+            assert_eq!("1h30".parse::<Duration>(), Err(ParseError::MissingUnit { pos: 4 }))
+        }
+
+        #[test]
+        fn unknown_unit() {
+            assert_eq!("5 fortnights".parse::<Duration>(), Err(ParseError::UnknownUnit { start: 2, end: 12 }))
+        }
+
+        #[test]
+        fn invalid_character() {
+            assert_eq!("-5s".parse::<Duration>(), Err(ParseError::InvalidCharacter { c: '-', pos: 0 }))
+        }
+
+        #[test]
+        fn overflow() {
+            assert_eq!("99999999999999999999s".parse::<Duration>(), Err(ParseError::OutOfRange));
+            assert_eq!("9999999999999999y".parse::<Duration>(), Err(ParseError::OutOfRange));
+        }
+    }
+
+    mod iso {
+        use super::*;
+        use super::super::ParseError;
+
+        #[test]
+        fn display() {
+            assert_eq!(Duration::of_ms(5415, 250).to_string(), "PT1H30M15.250S")
+        }
+
+        #[test]
+        fn display_zero() {
+            assert_eq!(Duration::zero().to_string(), "PT0S")
+        }
+
+        #[test]
+        fn display_skips_zero_components() {
+            assert_eq!(Duration::of(3600).to_string(), "PT1H");
+            assert_eq!(Duration::of(3605).to_string(), "PT1H5S");
+            assert_eq!(Duration::of_ms(0, 5).to_string(), "PT0.005S");
+            assert_eq!(Duration::of_ns(0, 5_000).to_string(), "PT0.000005S");
+            assert_eq!(Duration::of_ns(1, 5).to_string(), "PT1.000000005S");
+        }
+
+        #[test]
+        fn display_long() {
+            assert_eq!(Duration::of(3 * 86400).to_string(), "PT72H")
+        }
+
+        #[test]
+        fn display_days() {
+            assert_eq!(format!("{:#}", Duration::of(3 * 86400)), "P3D");
+            assert_eq!(format!("{:#}", Duration::of_ms(86400, 500)), "P1DT0.500S");
+            assert_eq!(format!("{:#}", Duration::of(-(86400 + 5400))), "-P1DT1H30M");
+            assert_eq!(format!("{:#}", Duration::of(3600)), "PT1H");
+            assert_eq!(format!("{:#}", Duration::of(90061)).parse(), Ok(Duration::of(90061)));
+        }
+
+        #[test]
+        fn display_negative() {
+            assert_eq!(Duration::of_ms(-1, 500).to_string(), "-PT0.500S");
+            assert_eq!(Duration::of(-5400).to_string(), "-PT1H30M");
+        }
+
+        #[test]
+        fn parse() {
+            assert_eq!("PT1H30M15.250S".parse(), Ok(Duration::of_ms(5415, 250)))
+        }
+
+        #[test]
+        fn parse_days_and_weeks() {
+            assert_eq!("P1W".parse(), Ok(Duration::of(604800)));
+            assert_eq!("P2DT3H".parse(), Ok(Duration::of(2 * 86400 + 3 * 3600)));
+        }
+
+        #[test]
+        fn parse_fraction() {
+            assert_eq!("PT0,5S".parse(), Ok(Duration::of_ms(0, 500)));
+            assert_eq!("PT1.5H".parse(), Ok(Duration::of(5400)));
+            assert_eq!("PT0.0009999S".parse(), Ok(Duration::of_ns(0, 999_900)));
+            assert_eq!("PT0.0000000019S".parse(), Ok(Duration::of_ns(0, 1)));
+        }
+
+        #[test]
+        fn parse_negative() {
+            assert_eq!("-PT0.5S".parse(), Ok(Duration::of_ms(-1, 500)))
+        }
+
+        #[test]
+        fn round_trip() {
+            for &(s, ns) in &[ (0, 0), (5415, 250_000_000), (-1, 500_000_000), (86400, 1), (-90061, 0), (-1, 999_999) ] {
+                let duration = Duration::of_ns(s, ns);
+                assert_eq!(duration.to_string().parse(), Ok(duration));
+            }
+        }
+
+        #[test]
+        fn months_are_rejected() {
+            assert_eq!("P1M".parse::<Duration>(), Err(ParseError::UnknownUnit { start: 2, end: 3 }))
+        }
+
+        #[test]
+        fn out_of_order() {
+            assert_eq!("PT1S1M".parse::<Duration>(), Err(ParseError::UnknownUnit { start: 5, end: 6 }))
+        }
+
+        #[test]
+        fn fraction_not_last() {
+            assert!("PT1.5H30M".parse::<Duration>().is_err())
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!("PT".parse::<Duration>(), Err(ParseError::Empty))
+        }
+
+        #[test]
+        fn missing_designator() {
+            assert_eq!("PT5".parse::<Duration>(), Err(ParseError::MissingUnit { pos: 3 }))
+        }
+    }
+
+    mod std_conversions {
+        use super::*;
+        use super::super::Error;
+        use std::convert::TryFrom;
+        use std::time::Duration as StdDuration;
+
+        #[test]
+        fn from_std() {
+            let std = StdDuration::new(90, 250_999_999);
+            assert_eq!(Duration::try_from(std), Ok(Duration::of_ns(90, 250_999_999)))
+        }
+
+        #[test]
+        fn from_std_too_long() {
+            let std = StdDuration::new(u64::max_value(), 0);
+            assert_eq!(Duration::try_from(std), Err(Error::OutOfRange))
+        }
+
+        #[test]
+        fn to_std() {
+            let std = StdDuration::try_from(Duration::of_ms(90, 250));
+            assert_eq!(std, Ok(StdDuration::from_millis(90_250)))
+        }
+
+        #[test]
+        fn to_std_nanoseconds() {
+            let std = StdDuration::try_from(Duration::of_ns(0, 1));
+            assert_eq!(std, Ok(StdDuration::new(0, 1)))
+        }
+
+        #[test]
+        fn to_std_negative() {
+            let std = StdDuration::try_from(Duration::of_ms(-1, 500));
+            assert_eq!(std, Err(Error::Negative))
+        }
+    }
 }
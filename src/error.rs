@@ -0,0 +1,211 @@
+//! An error type that covers every error this library can return.
+//!
+//! Each module has its own error type, which says exactly what went wrong
+//! in that module. All of them convert into this type with `From`, so a
+//! function that does several different things with dates and times can
+//! use `?` on each of them and return a single error type. The module’s
+//! error is kept as the `source` of this one, and the message for this one
+//! only says which kind of thing went wrong, so printing the whole chain
+//! doesn’t repeat anything.
+
+use std::error::Error as ErrorTrait;
+use std::fmt;
+
+use cal::datetime::Error as DateTimeError;
+#[cfg(feature = "std")]
+use cal::format::FormatError;
+use cal::offset::Error as OffsetError;
+use cal::parse::{Error as ParseError, SyntaxError};
+use duration::{Error as DurationError, ParseError as DurationParseError};
+use excel::Error as ExcelError;
+
+
+/// Any error that this library can return.
+///
+/// More kinds of error may be added in the future, so matching on this
+/// needs a wildcard arm.
+#[non_exhaustive]
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+
+    /// A date or time was constructed from fields that don’t make sense.
+    DateTime(DateTimeError),
+
+    /// A UTC offset, or a date-time with an offset, was invalid.
+    Offset(OffsetError),
+
+    /// A string could not be parsed as an ISO 8601 value.
+    Parse(SyntaxError),
+
+    /// A duration could not be converted to or from the standard library’s.
+    Duration(DurationError),
+
+    /// A string could not be parsed as a human-readable duration.
+    DurationParse(DurationParseError),
+
+    /// A date format string was invalid.
+    #[cfg(feature = "std")]
+    Format(FormatError),
+
+    /// A spreadsheet serial date could not be converted.
+    Excel(ExcelError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let layer = match *self {
+            Error::DateTime(_)      => "invalid date or time",
+            Error::Offset(_)        => "invalid UTC offset",
+            Error::Parse(_)         => "invalid ISO 8601 string",
+            Error::Duration(_)      => "duration out of range",
+            Error::DurationParse(_) => "invalid duration string",
+            #[cfg(feature = "std")]
+            Error::Format(_)        => "invalid date format",
+            Error::Excel(_)         => "invalid spreadsheet date",
+        };
+
+        write!(f, "{}", layer)
+    }
+}
+
+impl ErrorTrait for Error {
+    fn source(&self) -> Option<&(ErrorTrait + 'static)> {
+        match *self {
+            Error::DateTime(ref e)      => Some(e),
+            Error::Offset(ref e)        => Some(e),
+            Error::Parse(ref e)         => Some(e),
+            Error::Duration(ref e)      => Some(e),
+            Error::DurationParse(ref e) => Some(e),
+            #[cfg(feature = "std")]
+            Error::Format(ref e)        => Some(e),
+            Error::Excel(ref e)         => Some(e),
+        }
+    }
+}
+
+impl From<DateTimeError> for Error {
+    fn from(error: DateTimeError) -> Error {
+        Error::DateTime(error)
+    }
+}
+
+impl From<OffsetError> for Error {
+    fn from(error: OffsetError) -> Error {
+        Error::Offset(error)
+    }
+}
+
+impl<E: ErrorTrait> From<ParseError<E>> for Error where Error: From<E> {
+
+    /// Converts a parse error into either the error for the date that was
+    /// parsed but turned out to be invalid, or the message saying why it
+    /// couldn’t be parsed at all.
+    fn from(error: ParseError<E>) -> Error {
+        match error {
+            ParseError::Date(e)       => Error::from(e),
+            ParseError::Parse(string) => Error::Parse(SyntaxError::from(string)),
+        }
+    }
+}
+
+impl From<DurationError> for Error {
+    fn from(error: DurationError) -> Error {
+        Error::Duration(error)
+    }
+}
+
+impl From<DurationParseError> for Error {
+    fn from(error: DurationParseError) -> Error {
+        Error::DurationParse(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FormatError> for Error {
+    fn from(error: FormatError) -> Error {
+        Error::Format(error)
+    }
+}
+
+impl From<ExcelError> for Error {
+    fn from(error: ExcelError) -> Error {
+        Error::Excel(error)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::error::Error as ErrorTrait;
+    use cal::datetime::{LocalDate, LocalTime, Month};
+    use cal::offset::Offset;
+    use duration::Duration;
+
+    fn parse_and_check(input: &str) -> Result<LocalDate, Error> {
+        let date: LocalDate = input.parse()?;
+        let _ = LocalTime::hms(12, 0, 0)?;
+        let _ = Offset::of_hours_and_minutes(1, 0)?;
+        let _ = "1h30m".parse::<Duration>()?;
+        Ok(date)
+    }
+
+    #[test]
+    fn question_mark() {
+        assert_eq!(parse_and_check("2015-06-26"), LocalDate::ymd(2015, Month::June, 26).map_err(Error::from));
+    }
+
+    #[test]
+    fn invalid_date_keeps_its_field() {
+        assert_eq!(parse_and_check("2015-02-29"), Err(Error::DateTime(DateTimeError::DayOutOfRange { given: 29, max: 28 })));
+    }
+
+    #[test]
+    fn unparseable_string() {
+        match parse_and_check("the moon") {
+            Err(Error::Parse(_)) => {},
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_errors_from_offsets() {
+        let error = Error::from("+25:00".parse::<Offset>().unwrap_err());
+        assert_eq!(error, Error::Offset(OffsetError::OutOfRange));
+    }
+
+    #[test]
+    fn source() {
+        let error = Error::from(DateTimeError::HourOutOfRange { given: 25 });
+        assert_eq!(error.to_string(), "invalid date or time");
+        assert_eq!(error.source().unwrap().to_string(), "hour out of range (25)");
+        assert!(error.source().unwrap().source().is_none());
+    }
+
+    #[test]
+    fn parse_error_source() {
+        let error = parse_and_check("the moon").unwrap_err();
+        assert_eq!(error.to_string(), "invalid ISO 8601 string");
+
+        let source = error.source().expect("parse errors have a source");
+        assert_eq!(source.to_string(), "Parser Error: the moon");
+        match error {
+            Error::Parse(ref e) => assert_eq!(e.message(), "Parser Error: the moon"),
+            other               => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn chain_does_not_repeat() {
+        let error = Error::from("1h30x".parse::<Duration>().unwrap_err());
+        let mut messages = vec![ error.to_string() ];
+        let mut source = error.source();
+        while let Some(e) = source {
+            messages.push(e.to_string());
+            source = e.source();
+        }
+
+        assert_eq!(messages.len(), 2);
+        assert_ne!(messages[0], messages[1]);
+    }
+}
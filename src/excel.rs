@@ -0,0 +1,248 @@
+//! Conversions to and from spreadsheet serial dates.
+//!
+//! Excel, Lotus 1-2-3, and the spreadsheets that copy them store dates as
+//! **serial numbers**: the number of days since a fixed day zero, with the
+//! time of day as the fractional part. There are two systems in use:
+//!
+//! - The **1900** system, used by default on Windows, counts the 1st of
+//!   January, 1900 as day 1. Lotus 1-2-3 wrongly treated 1900 as a leap
+//!   year, and Excel kept the bug for compatibility, so day 60 is the
+//!   nonexistent 29th of February, 1900, and every day after it is one
+//!   higher than it should be. Converting day 60 produces an error.
+//! - The **1904** system, used by older versions of Excel for the Mac,
+//!   counts the 1st of January, 1904 as day 0.
+//!
+//! Spreadsheets have no notion of time zones, so these conversions work
+//! with local dates and times. Negative serial numbers are not allowed.
+
+use std::error::Error as ErrorTrait;
+use std::fmt;
+
+use cal::TimePiece;
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime};
+use util::round;
+
+
+/// The number of milliseconds in a day.
+const MILLISECONDS_IN_DAY: i64 = 86400 * 1000;
+
+/// The serial number of the 29th of February, 1900, which never happened.
+const FICTITIOUS_LEAP_DAY: i64 = 60;
+
+
+/// One of the two systems that spreadsheets use to count days.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum DateSystem {
+
+    /// The 1900 system, where day 1 is the 1st of January, 1900, including
+    /// the extra leap day from Lotus 1-2-3.
+    Excel1900,
+
+    /// The 1904 system, where day 0 is the 1st of January, 1904.
+    Excel1904,
+}
+
+impl DateSystem {
+
+    /// Returns the number of days between the Unix epoch and day zero of
+    /// this system.
+    fn day_zero(&self) -> i64 {
+        match *self {
+            DateSystem::Excel1900 => -25568,  // 1899-12-31
+            DateSystem::Excel1904 => -24107,  // 1904-01-01
+        }
+    }
+
+    /// Converts a serial number of a day into a number of days since the
+    /// Unix epoch.
+    fn unix_days(&self, serial: i64) -> Result<i64, Error> {
+        if serial < 0 {
+            return Err(Error::OutOfRange);
+        }
+
+        match *self {
+            DateSystem::Excel1900 if serial == FICTITIOUS_LEAP_DAY => Err(Error::NonexistentDate),
+            DateSystem::Excel1900 if serial > FICTITIOUS_LEAP_DAY  => Ok(self.day_zero() + serial - 1),
+            _                                                      => Ok(self.day_zero() + serial),
+        }
+    }
+
+    /// Converts a number of days since the Unix epoch into the serial
+    /// number of that day.
+    fn serial_of_unix_days(&self, days: i64) -> Result<i64, Error> {
+        let serial = days - self.day_zero();
+
+        if serial < 0 {
+            Err(Error::OutOfRange)
+        }
+        else if *self == DateSystem::Excel1900 && serial >= FICTITIOUS_LEAP_DAY {
+            Ok(serial + 1)
+        }
+        else {
+            Ok(serial)
+        }
+    }
+
+    /// Returns the date with the given serial number.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, Month};
+    /// use datetime::excel::DateSystem;
+    ///
+    /// let date = DateSystem::Excel1900.date(42430).unwrap();
+    /// assert_eq!(date, LocalDate::ymd(2016, Month::March, 1).unwrap());
+    /// ```
+    pub fn date(&self, serial: i64) -> Result<LocalDate, Error> {
+        let days = try!(self.unix_days(serial));
+        let seconds = try!(days.checked_mul(86400).ok_or(Error::OutOfRange));
+        Ok(LocalDateTime::at(seconds).date())
+    }
+
+    /// Returns the date-time with the given serial number, to the nearest
+    /// millisecond.
+    pub fn datetime(&self, serial: f64) -> Result<LocalDateTime, Error> {
+        let milliseconds = round(serial * MILLISECONDS_IN_DAY as f64);
+
+        if !milliseconds.is_finite() || milliseconds.abs() >= i64::max_value() as f64 {
+            return Err(Error::OutOfRange);
+        }
+
+        let milliseconds = milliseconds as i64;
+        let date = try!(self.date(milliseconds.div_euclid(MILLISECONDS_IN_DAY)));
+        let time = milliseconds.rem_euclid(MILLISECONDS_IN_DAY);
+        let time = LocalTime::from_seconds_and_milliseconds_since_midnight(time / 1000, (time % 1000) as i16);
+        Ok(LocalDateTime::new(date, time))
+    }
+
+    /// Returns the serial number of the given date.
+    pub fn date_serial(&self, date: LocalDate) -> Result<i64, Error> {
+        let seconds = LocalDateTime::new(date, LocalTime::midnight()).to_instant().seconds();
+        self.serial_of_unix_days(seconds / 86400)
+    }
+
+    /// Returns the serial number of the given date-time, with the time of
+    /// day as its fractional part.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+    /// use datetime::excel::DateSystem;
+    ///
+    /// let date = LocalDate::ymd(1904, Month::January, 2).unwrap();
+    /// let datetime = LocalDateTime::new(date, LocalTime::hm(18, 0).unwrap());
+    /// assert_eq!(DateSystem::Excel1904.datetime_serial(datetime), Ok(1.75));
+    /// ```
+    pub fn datetime_serial(&self, datetime: LocalDateTime) -> Result<f64, Error> {
+        let days = try!(self.date_serial(datetime.date()));
+        let time = datetime.time();
+        let seconds = time.to_seconds() as f64 + time.nanosecond() as f64 / 1_000_000_000.0;
+        Ok(days as f64 + seconds / 86400.0)
+    }
+}
+
+
+/// An error that occurs when converting to or from a serial number.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Error {
+
+    /// The date is before day zero of the date system, or the serial
+    /// number is too large to be represented.
+    OutOfRange,
+
+    /// The serial number refers to the 29th of February, 1900, which only
+    /// exists in the 1900 system.
+    NonexistentDate,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl ErrorTrait for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::OutOfRange       => "serial date out of range",
+            Error::NonexistentDate  => "serial date refers to the 29th of February, 1900",
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::Month;
+
+    #[test]
+    fn first_day() {
+        assert_eq!(DateSystem::Excel1900.date(1), Ok(LocalDate::ymd(1900, Month::January, 1).unwrap()));
+        assert_eq!(DateSystem::Excel1904.date(0), Ok(LocalDate::ymd(1904, Month::January, 1).unwrap()));
+    }
+
+    #[test]
+    fn day_zero() {
+        assert_eq!(DateSystem::Excel1900.date(0), Ok(LocalDate::ymd(1899, Month::December, 31).unwrap()));
+        assert_eq!(DateSystem::Excel1900.date_serial(LocalDate::ymd(1899, Month::December, 31).unwrap()), Ok(0));
+    }
+
+    #[test]
+    fn around_the_leap_year_bug() {
+        assert_eq!(DateSystem::Excel1900.date(59), Ok(LocalDate::ymd(1900, Month::February, 28).unwrap()));
+        assert_eq!(DateSystem::Excel1900.date(60), Err(Error::NonexistentDate));
+        assert_eq!(DateSystem::Excel1900.date(61), Ok(LocalDate::ymd(1900, Month::March, 1).unwrap()));
+        assert_eq!(DateSystem::Excel1900.date_serial(LocalDate::ymd(1900, Month::February, 28).unwrap()), Ok(59));
+        assert_eq!(DateSystem::Excel1900.date_serial(LocalDate::ymd(1900, Month::March, 1).unwrap()), Ok(61));
+    }
+
+    #[test]
+    fn systems_differ() {
+        let date = LocalDate::ymd(2016, Month::February, 29).unwrap();
+        assert_eq!(DateSystem::Excel1900.date_serial(date), Ok(42429));
+        assert_eq!(DateSystem::Excel1904.date_serial(date), Ok(42429 - 1462));
+    }
+
+    #[test]
+    fn datetime() {
+        let datetime = DateSystem::Excel1900.datetime(42429.5).unwrap();
+        assert_eq!(datetime, LocalDateTime::new(LocalDate::ymd(2016, Month::February, 29).unwrap(), LocalTime::hm(12, 0).unwrap()));
+        assert_eq!(DateSystem::Excel1900.datetime_serial(datetime), Ok(42429.5));
+    }
+
+    #[test]
+    fn time_only() {
+        let datetime = DateSystem::Excel1904.datetime(0.25).unwrap();
+        assert_eq!(datetime.time(), LocalTime::hm(6, 0).unwrap());
+    }
+
+    #[test]
+    fn datetime_round_trip() {
+        let datetime = LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms_ms(20, 17, 40, 500).unwrap());
+        let serial = DateSystem::Excel1900.datetime_serial(datetime).unwrap();
+        assert_eq!(DateSystem::Excel1900.datetime(serial), Ok(datetime));
+    }
+
+    #[test]
+    fn before_day_zero() {
+        assert_eq!(DateSystem::Excel1904.date_serial(LocalDate::ymd(1903, Month::December, 31).unwrap()), Err(Error::OutOfRange));
+        assert_eq!(DateSystem::Excel1900.date(-1), Err(Error::OutOfRange));
+        assert_eq!(DateSystem::Excel1900.datetime(-0.5), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn too_far_in_the_future() {
+        assert_eq!(DateSystem::Excel1900.date(i64::max_value()), Err(Error::OutOfRange));
+        assert_eq!(DateSystem::Excel1904.datetime(1e15), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn not_a_number() {
+        use std::f64;
+        assert_eq!(DateSystem::Excel1900.datetime(f64::NAN), Err(Error::OutOfRange));
+    }
+}
@@ -0,0 +1,171 @@
+//! Conversions to and from Windows `FILETIME` values.
+//!
+//! A `FILETIME` counts the number of 100-nanosecond intervals since
+//! midnight UTC on the 1st of January, 1601, the start of the Gregorian
+//! calendar’s first 400-year cycle to follow its adoption. The same count
+//! is used by NTFS timestamps, Windows event logs, and Active Directory
+//! attributes such as `lastLogonTimestamp`.
+//!
+//! Windows stores these as unsigned 64-bit numbers, so instants before 1601
+//! cannot be represented, and converting one produces `None`.
+
+use cal::TimePiece;
+use cal::datetime::LocalDateTime;
+use instant::Instant;
+
+
+/// The Unix timestamp of the FILETIME epoch, 1601-01-01T00:00:00 UTC.
+const FILETIME_EPOCH: i64 = -11644473600;
+
+/// The number of 100-nanosecond intervals in a second.
+const INTERVALS_PER_SECOND: i64 = 10_000_000;
+
+
+/// A **file time** is an exact point on the timeline, measured as the
+/// number of 100-nanosecond intervals since the start of 1601.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct FileTime {
+    intervals: u64,
+}
+
+impl FileTime {
+
+    /// Creates a new file time set to the given number of 100-nanosecond
+    /// intervals since the FILETIME epoch.
+    pub fn new(intervals: u64) -> FileTime {
+        FileTime { intervals: intervals }
+    }
+
+    /// Creates a new file time from the two halves of a Windows `FILETIME`
+    /// struct, `dwLowDateTime` and `dwHighDateTime`.
+    pub fn from_parts(low: u32, high: u32) -> FileTime {
+        FileTime::new((high as u64) << 32 | low as u64)
+    }
+
+    /// Converts a UTC instant to a file time, returning `None` if the
+    /// instant is before 1601 or too far in the future to fit.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    /// use datetime::filetime::FileTime;
+    ///
+    /// let filetime = FileTime::from_instant(Instant::at(0)).unwrap();
+    /// assert_eq!(filetime.intervals(), 116444736000000000);
+    /// ```
+    pub fn from_instant(instant: Instant) -> Option<FileTime> {
+        FileTime::from_seconds(instant.seconds(), instant.nanoseconds() as i64 / 100)
+    }
+
+    /// Converts a date-time to a file time, treating it as though it were
+    /// in UTC.
+    pub fn from_datetime(datetime: LocalDateTime) -> Option<FileTime> {
+        let seconds = datetime.to_instant().seconds();
+        FileTime::from_seconds(seconds, datetime.time().nanosecond() as i64 / 100)
+    }
+
+    fn from_seconds(unix_seconds: i64, intervals: i64) -> Option<FileTime> {
+        let seconds = unix_seconds.checked_sub(FILETIME_EPOCH)?;
+        if seconds < 0 {
+            return None;
+        }
+
+        (seconds as u64).checked_mul(INTERVALS_PER_SECOND as u64)
+                        .and_then(|i| i.checked_add(intervals as u64))
+                        .map(FileTime::new)
+    }
+
+    /// Converts this file time to a UTC instant.
+    pub fn to_instant(&self) -> Instant {
+        let (seconds, intervals) = self.split();
+        Instant::at_ns(seconds, intervals as i32 * 100)
+    }
+
+    /// Converts this file time to a date-time in UTC.
+    pub fn to_datetime(&self) -> LocalDateTime {
+        let (seconds, intervals) = self.split();
+        LocalDateTime::at_ns(seconds, intervals as i32 * 100)
+    }
+
+    /// Splits this file time into a Unix timestamp and the number of
+    /// intervals so far this second.
+    fn split(&self) -> (i64, i64) {
+        let seconds = (self.intervals / INTERVALS_PER_SECOND as u64) as i64;
+        let intervals = (self.intervals % INTERVALS_PER_SECOND as u64) as i64;
+        (seconds + FILETIME_EPOCH, intervals)
+    }
+
+    /// Returns the number of 100-nanosecond intervals since the FILETIME
+    /// epoch.
+    pub fn intervals(&self) -> u64 {
+        self.intervals
+    }
+
+    /// Returns the low 32 bits of this file time, as stored in a Windows
+    /// `FILETIME` struct’s `dwLowDateTime` field.
+    pub fn low(&self) -> u32 {
+        self.intervals as u32
+    }
+
+    /// Returns the high 32 bits of this file time, as stored in a Windows
+    /// `FILETIME` struct’s `dwHighDateTime` field.
+    pub fn high(&self) -> u32 {
+        (self.intervals >> 32) as u32
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::{LocalDate, LocalTime, Month};
+
+    #[test]
+    fn epoch() {
+        let filetime = FileTime::new(0);
+        assert_eq!(filetime.to_instant(), Instant::at(FILETIME_EPOCH));
+        assert_eq!(filetime.to_datetime().to_string(), "1601-01-01T00:00:00");
+    }
+
+    #[test]
+    fn unix_epoch() {
+        assert_eq!(FileTime::from_instant(Instant::at(0)), Some(FileTime::new(116444736000000000)));
+    }
+
+    #[test]
+    fn milliseconds() {
+        let filetime = FileTime::from_instant(Instant::at_ms(0, 250)).unwrap();
+        assert_eq!(filetime.intervals(), 116444736002500000);
+        assert_eq!(filetime.to_instant(), Instant::at_ms(0, 250));
+    }
+
+    #[test]
+    fn instant_precision() {
+        let instant = Instant::at_ns(1_234_567_890, 123_456_700);
+        assert_eq!(FileTime::from_instant(instant).unwrap().to_instant(), instant);
+    }
+
+    #[test]
+    fn datetime_precision() {
+        let datetime = LocalDateTime::new(LocalDate::ymd(2009, Month::July, 25).unwrap(), LocalTime::hms_ns(23, 59, 59, 123_456_700).unwrap());
+        let filetime = FileTime::from_datetime(datetime).unwrap();
+        assert_eq!(filetime.intervals(), 128930399991234567);
+        assert_eq!(filetime.to_datetime(), datetime);
+    }
+
+    #[test]
+    fn before_epoch() {
+        assert_eq!(FileTime::from_instant(Instant::at_ms(FILETIME_EPOCH - 1, 999)), None);
+    }
+
+    #[test]
+    fn too_late() {
+        assert_eq!(FileTime::from_instant(Instant::at(i64::max_value())), None);
+    }
+
+    #[test]
+    fn parts() {
+        let filetime = FileTime::from_parts(0xD53E8000, 0x019DB1DE);
+        assert_eq!(filetime.intervals(), 116444736000000000);
+        assert_eq!((filetime.low(), filetime.high()), (0xD53E8000, 0x019DB1DE));
+    }
+}
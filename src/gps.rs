@@ -0,0 +1,171 @@
+//! Conversions between UTC and GPS time.
+//!
+//! GPS time started at midnight UTC on the 6th of January 1980, and has
+//! been counting seconds continuously since then, without leap seconds.
+//! This puts it a fixed 19 seconds behind TAI, and an ever-increasing
+//! number of seconds ahead of UTC. Receivers usually express it as a week
+//! number and the number of seconds into that week, with weeks beginning
+//! at midnight between Saturday and Sunday.
+
+use duration::Duration;
+use instant::Instant;
+use tai::{LeapSecondTable, LEAP_SECONDS};
+
+
+/// The Unix timestamp of the GPS epoch, 1980-01-06T00:00:00 UTC.
+const GPS_EPOCH: i64 = 315964800;
+
+/// The number of seconds GPS time is behind TAI. This never changes.
+const TAI_MINUS_GPS: i64 = 19;
+
+/// The number of seconds in a GPS week.
+const SECONDS_IN_WEEK: i64 = 7 * 86400;
+
+
+/// A **GPS time** is an exact point on the timeline, measured as the number
+/// of seconds since the GPS epoch on the GPS time scale, with millisecond
+/// precision.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct GpsTime {
+    seconds: i64,
+    milliseconds: i16,
+}
+
+impl GpsTime {
+
+    /// Creates a new GPS time set to the given number of continuous GPS
+    /// seconds, and zero milliseconds.
+    pub fn at(seconds: i64) -> GpsTime {
+        GpsTime::at_ms(seconds, 0)
+    }
+
+    /// Creates a new GPS time set to the given number of continuous GPS
+    /// seconds, along with the number of milliseconds so far this second.
+    pub fn at_ms(seconds: i64, milliseconds: i16) -> GpsTime {
+        GpsTime { seconds: seconds, milliseconds: milliseconds }
+    }
+
+    /// Creates a new GPS time from a week number, counting from the GPS
+    /// epoch, and the length of time into that week.
+    ///
+    /// Week numbers here are *continuous*: the number broadcast by
+    /// satellites rolls over every 1024 weeks, and needs to be adjusted
+    /// before being used here.
+    pub fn from_week(week: i64, time_of_week: Duration) -> GpsTime {
+        let (seconds, milliseconds) = time_of_week.lengths();
+        GpsTime::at_ms(week * SECONDS_IN_WEEK + seconds, milliseconds)
+    }
+
+    /// Converts a UTC instant to GPS time, using the bundled leap second
+    /// table.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    /// use datetime::gps::GpsTime;
+    ///
+    /// let gps = GpsTime::from_utc(Instant::at(315964800));
+    /// assert_eq!(gps.seconds(), 0);
+    /// ```
+    pub fn from_utc(utc: Instant) -> GpsTime {
+        GpsTime::from_utc_with(utc, &LEAP_SECONDS)
+    }
+
+    /// Converts a UTC instant to GPS time, using the given leap second
+    /// table.
+    pub fn from_utc_with(utc: Instant, table: &LeapSecondTable) -> GpsTime {
+        let tai = table.utc_to_tai(utc);
+        GpsTime::at_ms(tai.seconds() - TAI_MINUS_GPS - GPS_EPOCH, tai.milliseconds())
+    }
+
+    /// Converts this GPS time to a UTC instant, using the bundled leap
+    /// second table.
+    pub fn to_utc(&self) -> Instant {
+        self.to_utc_with(&LEAP_SECONDS)
+    }
+
+    /// Converts this GPS time to a UTC instant, using the given leap second
+    /// table.
+    pub fn to_utc_with(&self, table: &LeapSecondTable) -> Instant {
+        let tai = Instant::at_ms(self.seconds + TAI_MINUS_GPS + GPS_EPOCH, self.milliseconds);
+        table.tai_to_utc(tai)
+    }
+
+    /// Returns the number of continuous GPS seconds since the GPS epoch.
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Returns the number of milliseconds so far this second.
+    pub fn milliseconds(&self) -> i16 {
+        self.milliseconds
+    }
+
+    /// Returns the continuous week number of this GPS time, counting from
+    /// the GPS epoch.
+    pub fn week(&self) -> i64 {
+        div_floor(self.seconds, SECONDS_IN_WEEK)
+    }
+
+    /// Returns the length of time since the start of this GPS time’s week.
+    pub fn time_of_week(&self) -> Duration {
+        let seconds = self.seconds - self.week() * SECONDS_IN_WEEK;
+        Duration::of_ms(seconds, self.milliseconds)
+    }
+}
+
+/// Divides, rounding towards negative infinity rather than towards zero,
+/// so that times before the epoch end up in negative weeks.
+fn div_floor(number: i64, divisor: i64) -> i64 {
+    let quotient = number / divisor;
+    if number % divisor < 0 { quotient - 1 } else { quotient }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use instant::Instant;
+    pub use duration::Duration;
+
+    #[test]
+    fn epoch() {
+        let gps = GpsTime::from_utc(Instant::at(315964800));
+        assert_eq!(gps, GpsTime::at(0));
+        assert_eq!(gps.week(), 0);
+        assert_eq!(gps.time_of_week(), Duration::zero());
+    }
+
+    #[test]
+    fn eighteen_seconds_ahead() {
+        // 2017-01-01T00:00:00 UTC, after the 18th leap second since 1980.
+        let gps = GpsTime::from_utc(Instant::at_ms(1483228800, 500));
+        assert_eq!(gps, GpsTime::at_ms(1483228800 - 315964800 + 18, 500));
+    }
+
+    #[test]
+    fn week_number() {
+        // 2017-01-01 was the Sunday at the start of GPS week 1930.
+        let gps = GpsTime::from_utc(Instant::at(1483228800));
+        assert_eq!(gps.week(), 1930);
+        assert_eq!(gps.time_of_week(), Duration::of(18));
+    }
+
+    #[test]
+    fn from_week() {
+        let gps = GpsTime::from_week(1930, Duration::of_ms(18, 250));
+        assert_eq!(gps.to_utc(), Instant::at_ms(1483228800, 250));
+    }
+
+    #[test]
+    fn before_epoch() {
+        let gps = GpsTime::at(-1);
+        assert_eq!(gps.week(), -1);
+        assert_eq!(gps.time_of_week(), Duration::of(SECONDS_IN_WEEK - 1));
+    }
+
+    #[test]
+    fn round_trip() {
+        let utc = Instant::at_ms(1_600_000_000, 123);
+        assert_eq!(GpsTime::from_utc(utc).to_utc(), utc);
+    }
+}
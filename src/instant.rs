@@ -1,44 +1,174 @@
 //! Exact points on a timeline.
 
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::{Add, Sub};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 
-use system::sys_time;
+use cal::datetime::LocalDateTime;
+#[cfg(feature = "std")]
+use system::{sys_time, split_system_time, join_system_time};
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+use system::{ClockSource, sys_clock_time};
+use util::{join_bytes, split_bytes};
 use duration::Duration;
 
 
 /// An **instant** is an exact point on the timeline, irrespective of time
-/// zone or calendar format, with millisecond precision.
+/// zone or calendar format, with nanosecond precision.
 ///
 /// Internally, this is represented by a 64-bit integer of seconds, and a
-/// 16-bit integer of milliseconds. This means that it will overflow (and thus
+/// 32-bit integer of nanoseconds. This means that it will overflow (and thus
 /// be unsuitable for) instants past GMT 15:30:08, Sunday 4th December,
-/// 292,277,026,596 (yes, that’s a year)
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// 292,277,026,596 (yes, that’s a year). Adding or subtracting a duration
+/// past that point panics; use `checked_add` and `checked_sub` to get
+/// `None` instead. Local date-times have exactly the same range.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Instant {
     seconds: i64,
-    milliseconds: i16,
+    nanoseconds: i32,
 }
 
 impl Instant {
 
+    /// The earliest instant that can be represented, the first nanosecond
+    /// of `-292277022657-01-27T08:29:52Z`.
+    pub const MIN: Instant = Instant { seconds: i64::min_value(), nanoseconds: 0 };
+
+    /// The latest instant that can be represented, the last nanosecond of
+    /// `+292277026596-12-04T15:30:07Z`.
+    pub const MAX: Instant = Instant { seconds: i64::max_value(), nanoseconds: 999_999_999 };
+
     /// Creates a new Instant set to the number of seconds since the Unix
     /// epoch, and zero milliseconds.
-    pub fn at(seconds: i64) -> Instant {
-        Instant::at_ms(seconds, 0)
+    pub const fn at(seconds: i64) -> Instant {
+        Instant { seconds: seconds, nanoseconds: 0 }
     }
 
     /// Creates a new Instant set to the number of seconds since the
     /// Unix epoch, along with the number of milliseconds so far this
     /// second.
-    pub fn at_ms(seconds: i64, milliseconds: i16) -> Instant {
-        Instant { seconds: seconds, milliseconds: milliseconds }
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of milliseconds is not between 0 and 999. Use
+    /// `checked_at_ms` to handle that case instead.
+    pub const fn at_ms(seconds: i64, milliseconds: i16) -> Instant {
+        match Instant::checked_at_ms(seconds, milliseconds) {
+            Some(instant) => instant,
+            None          => panic!("milliseconds out of range"),
+        }
+    }
+
+    /// Creates a new Instant set to the number of seconds since the
+    /// Unix epoch, along with the number of milliseconds so far this
+    /// second, returning `None` if the number of milliseconds is not
+    /// between 0 and 999.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    ///
+    /// assert_eq!(Instant::checked_at_ms(1, 500), Some(Instant::at_ms(1, 500)));
+    /// assert_eq!(Instant::checked_at_ms(0, 5000), None);
+    /// ```
+    pub const fn checked_at_ms(seconds: i64, milliseconds: i16) -> Option<Instant> {
+        if milliseconds < 0 || milliseconds > 999 {
+            None
+        }
+        else {
+            Some(Instant { seconds: seconds, nanoseconds: milliseconds as i32 * 1_000_000 })
+        }
+    }
+
+    /// Creates a new Instant set to the number of seconds since the
+    /// Unix epoch, along with the number of nanoseconds so far this
+    /// second.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of nanoseconds is not between 0 and
+    /// 999,999,999. Use `checked_at_ns` to handle that case instead.
+    pub const fn at_ns(seconds: i64, nanoseconds: i32) -> Instant {
+        match Instant::checked_at_ns(seconds, nanoseconds) {
+            Some(instant) => instant,
+            None          => panic!("nanoseconds out of range"),
+        }
+    }
+
+    /// Creates a new Instant set to the number of seconds since the
+    /// Unix epoch, along with the number of nanoseconds so far this
+    /// second, returning `None` if the number of nanoseconds is not
+    /// between 0 and 999,999,999.
+    pub const fn checked_at_ns(seconds: i64, nanoseconds: i32) -> Option<Instant> {
+        if nanoseconds < 0 || nanoseconds > 999_999_999 {
+            None
+        }
+        else {
+            Some(Instant { seconds: seconds, nanoseconds: nanoseconds })
+        }
+    }
+
+    /// Creates a new Instant set to the number of milliseconds since the
+    /// Unix epoch, the format used by JavaScript’s `Date.now()`. Negative
+    /// values refer to instants before the epoch.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    ///
+    /// assert_eq!(Instant::from_unix_millis(1_500), Instant::at_ms(1, 500));
+    /// assert_eq!(Instant::from_unix_millis(-1), Instant::at_ms(-1, 999));
+    /// ```
+    pub fn from_unix_millis(milliseconds: i64) -> Instant {
+        Instant::at_ms(milliseconds.div_euclid(1000), milliseconds.rem_euclid(1000) as i16)
+    }
+
+    /// Creates a new Instant set to the number of nanoseconds since the
+    /// Unix epoch, as exchanged by many tracing and metrics systems.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of seconds does not fit in an `i64`. Use
+    /// `checked_from_nanos` to handle that case instead.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    ///
+    /// assert_eq!(Instant::from_nanos(1_500_999_999), Instant::at_ns(1, 500_999_999));
+    /// assert_eq!(Instant::from_nanos(-1), Instant::at_ns(-1, 999_999_999));
+    /// ```
+    pub fn from_nanos(nanoseconds: i128) -> Instant {
+        Instant::checked_from_nanos(nanoseconds).expect("nanosecond timestamp out of range")
+    }
+
+    /// Creates a new Instant set to the number of nanoseconds since the
+    /// Unix epoch, returning `None` if the number of seconds does not fit
+    /// in an `i64`.
+    pub fn checked_from_nanos(nanoseconds: i128) -> Option<Instant> {
+        let seconds = i64::try_from(nanoseconds.div_euclid(1_000_000_000)).ok()?;
+        Some(Instant::at_ns(seconds, nanoseconds.rem_euclid(1_000_000_000) as i32))
     }
 
-    /// Creates a new Instant set to the computer’s current time.
+    /// Creates a new Instant set to the computer’s current time, to as
+    /// fine a resolution as the system clock gives, which is well under a
+    /// millisecond everywhere but WebAssembly.
+    #[cfg(feature = "std")]
     pub fn now() -> Instant {
-        let (s, ms) = unsafe { sys_time() };
-        Instant { seconds: s, milliseconds: ms }
+        let (s, ns) = unsafe { sys_time() };
+        Instant::at_ns(s, ns)
+    }
+
+    /// Creates a new Instant set to the current time according to the
+    /// given clock, returning `None` if it isn’t one of the realtime
+    /// clocks, or the kernel doesn’t support it.
+    #[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+    pub fn now_from(source: ClockSource) -> Option<Instant> {
+        if !source.is_realtime() {
+            return None;
+        }
+
+        let (s, ns) = sys_clock_time(source)?;
+        Some(Instant::at_ns(s, ns))
     }
 
     /// Creates a new Instant set to the Unix epoch.
@@ -53,13 +183,145 @@ impl Instant {
 
     /// Returns the number of milliseconds at this instant
     pub fn milliseconds(&self) -> i16 {
-        self.milliseconds
+        (self.nanoseconds / 1_000_000) as i16
+    }
+
+    /// Returns the number of nanoseconds at this instant
+    pub fn nanoseconds(&self) -> i32 {
+        self.nanoseconds
+    }
+
+    /// Returns the number of milliseconds between the Unix epoch and this
+    /// instant, the format used by JavaScript’s `Date.now()`.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    ///
+    /// assert_eq!(Instant::at_ms(1, 500).to_unix_millis(), 1_500);
+    /// assert_eq!(Instant::at_ms(-1, 999).to_unix_millis(), -1);
+    /// ```
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the number of milliseconds does not fit in an `i64`. Use
+    /// `checked_to_unix_millis` to handle that case instead.
+    pub fn to_unix_millis(&self) -> i64 {
+        self.checked_to_unix_millis().expect("millisecond timestamp out of range")
+    }
+
+    /// Returns the number of milliseconds between the Unix epoch and this
+    /// instant, or `None` if it does not fit in an `i64`.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    ///
+    /// assert_eq!(Instant::at_ms(1, 500).checked_to_unix_millis(), Some(1_500));
+    /// assert_eq!(Instant::at(i64::max_value()).checked_to_unix_millis(), None);
+    /// ```
+    pub fn checked_to_unix_millis(&self) -> Option<i64> {
+        self.seconds.checked_mul(1000)?.checked_add(self.milliseconds() as i64)
+    }
+
+    /// Returns the number of nanoseconds between the Unix epoch and this
+    /// instant.
+    pub fn to_nanos(&self) -> i128 {
+        self.seconds as i128 * 1_000_000_000 + self.nanoseconds as i128
+    }
+
+    /// Returns this instant in a fixed-width binary encoding: the number of
+    /// seconds since the Unix epoch as a big-endian `i64`, followed by the
+    /// number of nanoseconds so far that second as a big-endian `u32`. This
+    /// is the same encoding that `LocalDateTime` uses.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    ///
+    /// assert_eq!(Instant::at_ms(1, 500).to_bytes(), [0, 0, 0, 0, 0, 0, 0, 1, 29, 205, 101, 0]);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; 12] {
+        join_bytes(self.seconds, self.nanoseconds as u32)
+    }
+
+    /// Decodes an instant from the binary encoding produced by `to_bytes`.
+    /// Returns `None` if the number of nanoseconds is too large.
+    pub fn from_bytes(bytes: [u8; 12]) -> Option<Instant> {
+        let (seconds, nanoseconds) = split_bytes(bytes)?;
+        Some(Instant::at_ns(seconds, nanoseconds as i32))
+    }
+
+    /// Adds a duration to this instant, returning `None` if the number of
+    /// seconds would overflow.
+    pub fn checked_add(self, duration: Duration) -> Option<Instant> {
+        let (seconds, nanoseconds) = duration.lengths_ns();
+        let ns = self.nanoseconds + nanoseconds;
+        let (carry, ns) = if ns >= 1_000_000_000 { (1, ns - 1_000_000_000) } else { (0, ns) };
+        let seconds = self.seconds.checked_add(seconds)?.checked_add(carry)?;
+        Some(Instant::at_ns(seconds, ns))
+    }
+
+    /// Subtracts a duration from this instant, returning `None` if the
+    /// number of seconds would overflow.
+    pub fn checked_sub(self, duration: Duration) -> Option<Instant> {
+        let (seconds, nanoseconds) = duration.lengths_ns();
+        let ns = self.nanoseconds - nanoseconds;
+        let (borrow, ns) = if ns < 0 { (1, ns + 1_000_000_000) } else { (0, ns) };
+        let seconds = self.seconds.checked_sub(seconds)?.checked_sub(borrow)?;
+        Some(Instant::at_ns(seconds, ns))
+    }
+
+    /// Returns the length of time between the `earlier` instant and this
+    /// one. If `earlier` is actually *later* than this instant, the
+    /// duration is negative.
+    ///
+    /// ```rust
+    /// use datetime::{Instant, Duration};
+    ///
+    /// let start = Instant::at_ms(1000, 750);
+    /// let end = Instant::at_ms(1002, 250);
+    /// assert_eq!(end.duration_since(start), Duration::of_ms(1, 500));
+    /// ```
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        let ns = self.nanoseconds - earlier.nanoseconds;
+        if ns < 0 {
+            Duration::of_ns(self.seconds - earlier.seconds - 1, ns + 1_000_000_000)
+        }
+        else {
+            Duration::of_ns(self.seconds - earlier.seconds, ns)
+        }
+    }
+
+    /// Returns the length of time between this instant and the current
+    /// time, according to the computer’s clock.
+    ///
+    /// As the wall clock can be changed underneath the program, this is
+    /// not suitable for measuring how long something took: use a
+    /// `MonotonicInstant` for that instead.
+    #[cfg(feature = "std")]
+    pub fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(*self)
+    }
+}
+
+impl Default for Instant {
+
+    /// Returns the Unix epoch, `1970-01-01T00:00:00Z`.
+    fn default() -> Instant {
+        Instant::at(0)
     }
 }
 
 impl fmt::Debug for Instant {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Instant({}s/{}ms)", self.seconds, self.milliseconds)
+        write!(f, "Instant({}s/{}ns)", self.seconds, self.nanoseconds)
+    }
+}
+
+impl fmt::Display for Instant {
+
+    /// Formats this instant in the ISO 8601 format, as a date-time in UTC,
+    /// such as `2001-09-09T01:46:40Z`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}Z", LocalDateTime::from_instant(*self))
     }
 }
 
@@ -67,11 +329,7 @@ impl Add<Duration> for Instant {
     type Output = Instant;
 
     fn add(self, duration: Duration) -> Instant {
-        let (seconds, milliseconds) = duration.lengths();
-        Instant {
-            seconds: self.seconds + seconds,
-            milliseconds: self.milliseconds + milliseconds,
-        }
+        self.checked_add(duration).expect("instant out of range")
     }
 }
 
@@ -79,11 +337,37 @@ impl Sub<Duration> for Instant {
     type Output = Instant;
 
     fn sub(self, duration: Duration) -> Instant {
-        let (seconds, milliseconds) = duration.lengths();
-        Instant {
-            seconds: self.seconds - seconds,
-            milliseconds: self.milliseconds - milliseconds,
-        }
+        self.checked_sub(duration).expect("instant out of range")
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SystemTime> for Instant {
+
+    /// Converts a standard library `SystemTime` into an instant.
+    fn from(time: SystemTime) -> Instant {
+        let (seconds, nanoseconds) = split_system_time(time);
+        Instant::at_ns(seconds, nanoseconds)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Instant> for SystemTime {
+
+    /// Converts an instant into a standard library `SystemTime`.
+    ///
+    /// This panics if the instant is too far in the past or future to be
+    /// represented by the platform’s `SystemTime` type.
+    fn from(instant: Instant) -> SystemTime {
+        join_system_time(instant.seconds, instant.nanoseconds)
     }
 }
 
@@ -102,11 +386,112 @@ mod test {
         assert_eq!(Instant::at_ms(3, 333).milliseconds(), 333)
     }
 
+    #[test]
+    fn default() {
+        assert_eq!(Instant::default(), Instant::at(0))
+    }
+
     #[test]
     fn epoch() {
         assert_eq!(Instant::at_epoch().seconds(), 0)
     }
 
+    #[test]
+    fn display() {
+        assert_eq!(Instant::at_ms(1_000_000_000, 250).to_string(), "2001-09-09T01:46:40.250Z")
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("2001-09-09T01:46:40.250Z".parse(), Ok(Instant::at_ms(1_000_000_000, 250)));
+        assert_eq!("2001-09-09T03:16:40+01:30".parse(), Ok(Instant::at(1_000_000_000)));
+    }
+
+    #[test]
+    fn unix_millis() {
+        assert_eq!(Instant::from_unix_millis(1_000_000_000_250), Instant::at_ms(1_000_000_000, 250));
+        assert_eq!(Instant::at_ms(1_000_000_000, 250).to_unix_millis(), 1_000_000_000_250);
+    }
+
+    #[test]
+    fn unix_millis_before_epoch() {
+        assert_eq!(Instant::from_unix_millis(-1_500), Instant::at_ms(-2, 500));
+        assert_eq!(Instant::at_ms(-2, 500).to_unix_millis(), -1_500);
+    }
+
+    #[test]
+    fn unix_millis_out_of_range() {
+        assert_eq!(Instant::at(i64::max_value() / 1000).checked_to_unix_millis(), Some(i64::max_value() / 1000 * 1000));
+        assert_eq!(Instant::at(i64::max_value() / 1000 + 1).checked_to_unix_millis(), None);
+        assert_eq!(Instant::at(i64::min_value() / 1000 - 1).checked_to_unix_millis(), None);
+    }
+
+    #[test]
+    fn nanos() {
+        assert_eq!(Instant::from_nanos(1_000_000_000_250_000_000), Instant::at_ms(1_000_000_000, 250));
+        assert_eq!(Instant::at_ms(1_000_000_000, 250).to_nanos(), 1_000_000_000_250_000_000);
+    }
+
+    #[test]
+    fn nanos_before_epoch() {
+        assert_eq!(Instant::from_nanos(-1_500_000_001), Instant::at_ns(-2, 499_999_999));
+        assert_eq!(Instant::at_ms(-2, 500).to_nanos(), -1_500_000_000);
+    }
+
+    #[test]
+    fn nanos_round_trip() {
+        for &nanoseconds in &[ 1_700_000_000_123_456_789, -1, -1_500_000_001, 0 ] {
+            assert_eq!(Instant::from_nanos(nanoseconds).to_nanos(), nanoseconds);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn nanos_out_of_range() {
+        let _ = Instant::from_nanos(i128::max_value());
+    }
+
+    #[test]
+    fn checked_nanos() {
+        assert_eq!(Instant::checked_from_nanos(i128::max_value()), None);
+        assert_eq!(Instant::checked_from_nanos(Instant::MIN.to_nanos()), Some(Instant::MIN));
+        assert_eq!(Instant::checked_from_nanos(Instant::MAX.to_nanos()), Some(Instant::MAX));
+        assert_eq!(Instant::checked_from_nanos(Instant::MAX.to_nanos() + 1), None);
+    }
+
+    #[test]
+    fn sub_second_out_of_range() {
+        assert_eq!(Instant::checked_at_ms(0, 999), Some(Instant::at_ns(0, 999_000_000)));
+        assert_eq!(Instant::checked_at_ms(0, 5000), None);
+        assert_eq!(Instant::checked_at_ms(0, -1), None);
+        assert_eq!(Instant::checked_at_ns(0, 1_000_000_000), None);
+        assert_eq!(Instant::checked_at_ns(0, -1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn at_ms_out_of_range() {
+        let _ = Instant::at_ms(0, 5000);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let instant = Instant::at_ms(-2, 500);
+        assert_eq!(Instant::from_bytes(instant.to_bytes()), Some(instant));
+    }
+
+    #[test]
+    fn bytes_keep_nanoseconds() {
+        let bytes = [0, 0, 0, 0, 0, 0, 0, 1, 29, 205, 101, 1];
+        assert_eq!(Instant::from_bytes(bytes), Some(Instant::at_ns(1, 500_000_001)));
+        assert_eq!(Instant::at_ns(1, 500_000_001).to_bytes(), bytes);
+    }
+
+    #[test]
+    fn bytes_invalid_nanoseconds() {
+        assert_eq!(Instant::from_bytes([0, 0, 0, 0, 0, 0, 0, 0, 0x3B, 0x9A, 0xCA, 0x00]), None);
+    }
+
     #[test]
     fn sanity() {
         // Test that the system call has worked at all.
@@ -114,6 +499,53 @@ mod test {
         assert!(Instant::now().seconds() != 0)
     }
 
+    #[test]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn now_has_sub_millisecond_precision() {
+        // Any one reading could land on a whole millisecond, but it’s
+        // vanishingly unlikely that they all do.
+        assert!((0 .. 10).any(|_| Instant::now().nanoseconds() % 1_000_000 != 0))
+    }
+
+    mod system_time {
+        use super::*;
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        #[test]
+        fn from_system_time() {
+            let time = UNIX_EPOCH + Duration::new(1_234_567_890, 987_654_321);
+            assert_eq!(Instant::from(time), Instant::at_ns(1_234_567_890, 987_654_321))
+        }
+
+        #[test]
+        fn before_epoch() {
+            let time = UNIX_EPOCH - Duration::from_millis(1500);
+            assert_eq!(Instant::from(time), Instant::at_ms(-2, 500))
+        }
+
+        #[test]
+        fn round_trip() {
+            let instant = Instant::at_ns(-54_321, 123_456_789);
+            assert_eq!(Instant::from(SystemTime::from(instant)), instant)
+        }
+
+        #[test]
+        fn now() {
+            let now = Instant::from(SystemTime::now());
+            assert!(now.seconds() - Instant::now().seconds() <= 1)
+        }
+
+        #[test]
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        fn now_from_clock_source() {
+            use system::ClockSource;
+
+            let coarse = Instant::now_from(ClockSource::RealtimeCoarse).unwrap();
+            assert!((Instant::now().seconds() - coarse.seconds()).abs() <= 1);
+            assert_eq!(Instant::now_from(ClockSource::Boottime), None);
+        }
+    }
+
     mod duration_arithmetic {
         use super::*;
         use duration::Duration;
@@ -127,5 +559,60 @@ mod test {
         fn subtraction() {
             assert_eq!(Instant::at(20), Instant::at(50) - Duration::of(30))
         }
+
+        #[test]
+        fn addition_wrapping() {
+            assert_eq!(Instant::at_ms(11, 100), Instant::at_ms(3, 800) + Duration::of_ms(7, 300))
+        }
+
+        #[test]
+        fn subtraction_wrapping() {
+            assert_eq!(Instant::at_ms(19, 900), Instant::at_ms(50, 200) - Duration::of_ms(30, 300))
+        }
+
+        #[test]
+        fn checked_overflow() {
+            assert_eq!(Instant::at(i64::max_value()).checked_add(Duration::of(1)), None);
+            assert_eq!(Instant::at(i64::min_value()).checked_sub(Duration::of_ms(0, 1)), None);
+            assert_eq!(Instant::at_ms(3, 800).checked_add(Duration::of_ms(7, 300)), Some(Instant::at_ms(11, 100)));
+        }
+
+        #[test]
+        #[should_panic]
+        fn addition_overflow() {
+            let _ = Instant::at(i64::max_value()) + Duration::of(1);
+        }
+
+        #[test]
+        fn addition_nanoseconds() {
+            assert_eq!(Instant::at_ns(3, 999_999_999) + Duration::of_ns(0, 1), Instant::at(4));
+            assert_eq!(Instant::at(4) - Duration::of_ns(0, 1), Instant::at_ns(3, 999_999_999));
+        }
+
+        #[test]
+        fn difference() {
+            assert_eq!(Instant::at_ms(50, 200) - Instant::at_ms(20, 100), Duration::of_ms(30, 100))
+        }
+
+        #[test]
+        fn difference_wrapping() {
+            assert_eq!(Instant::at_ms(50, 100).duration_since(Instant::at_ms(20, 200)), Duration::of_ms(29, 900))
+        }
+
+        #[test]
+        fn difference_nanoseconds() {
+            assert_eq!(Instant::at_ns(1, 1) - Instant::at_ns(0, 999_999_999), Duration::of_ns(0, 2))
+        }
+
+        #[test]
+        fn negative_difference() {
+            assert_eq!(Instant::at(20) - Instant::at_ms(50, 500), Duration::of_ms(-31, 500))
+        }
+
+        #[test]
+        fn elapsed() {
+            let then = Instant::now() - Duration::of(10);
+            assert!(then.elapsed().lengths().0 >= 9)
+        }
     }
 }
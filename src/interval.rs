@@ -0,0 +1,694 @@
+//! Spans of time between two points on a timeline.
+
+use std::error::Error as ErrorTrait;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Add, Sub};
+use std::slice;
+use std::str::FromStr;
+
+use alloc::vec::Vec;
+
+use cal::parse::Error as ParseError;
+use duration::Duration;
+
+
+/// An **interval** is the span of time from one point on the timeline up
+/// to another, such as between two instants or two date-times.
+///
+/// Intervals are *half-open*: they include their start, but not their end.
+/// This means that two intervals where one ends exactly when the other
+/// starts don’t overlap, and that an interval whose start and end are the
+/// same is empty. An interval whose end comes before its start is empty,
+/// too.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{Instant, Interval, Duration};
+///
+/// let morning = Interval { start: Instant::at(0), end: Instant::at(3600) };
+/// let meeting = Interval { start: Instant::at(1800), end: Instant::at(5400) };
+///
+/// assert!(morning.overlaps(&meeting));
+/// assert_eq!(morning.intersection(&meeting), Some(Interval { start: Instant::at(1800), end: Instant::at(3600) }));
+/// assert_eq!(morning.union(&meeting).unwrap().duration(), Duration::of(5400));
+/// assert!(!morning.contains(Instant::at(3600)));
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
+pub struct Interval<T> {
+
+    /// The first point in this interval.
+    pub start: T,
+
+    /// The point just after the end of this interval, which isn’t part of
+    /// it.
+    pub end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+
+    /// Creates a new interval from its start and end.
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval { start: start, end: end }
+    }
+
+    /// Returns whether this interval contains no points at all, which is
+    /// the case when its end is not after its start.
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Returns whether the given point is within this interval: on or
+    /// after its start, and before its end.
+    pub fn contains(&self, point: T) -> bool {
+        self.start <= point && point < self.end
+    }
+
+    /// Returns whether the two intervals have any points in common.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.is_empty() && !other.is_empty()
+            && self.start < other.end && other.start < self.end
+    }
+
+    /// Returns whether one of the intervals starts exactly where the other
+    /// ends, so they touch without overlapping.
+    pub fn abuts(&self, other: &Interval<T>) -> bool {
+        !self.is_empty() && !other.is_empty()
+            && (self.end == other.start || other.end == self.start)
+    }
+
+    /// Returns the points that are in both intervals, or `None` if they
+    /// don’t overlap.
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(other) {
+            Some(Interval::new(self.start.max(other.start), self.end.min(other.end)))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Returns the interval covering both intervals, if they overlap or
+    /// abut, or `None` if there would be a gap between them. An empty
+    /// interval joins onto any other.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.is_empty() {
+            Some(*other)
+        }
+        else if other.is_empty() {
+            Some(*self)
+        }
+        else if self.overlaps(other) || self.abuts(other) {
+            Some(Interval::new(self.start.min(other.start), self.end.max(other.end)))
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl<T: Ord + Copy + Sub<T, Output=Duration>> Interval<T> {
+
+    /// Returns the length of this interval, which is zero if it’s empty.
+    pub fn duration(&self) -> Duration {
+        if self.is_empty() {
+            Duration::zero()
+        }
+        else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T: fmt::Display + Ord + Copy + Sub<T, Output=Duration>> fmt::Display for Interval<T> {
+
+    /// Formats this interval in the ISO 8601 format, as its start and end
+    /// separated by a slash, such as `2015-06-26T09:00:00Z/2015-06-26T17:00:00Z`.
+    /// With the alternate flag, the end is replaced by the interval’s
+    /// duration, written with days, such as `2015-06-26T09:00:00Z/PT8H`.
+    ///
+    /// ### Examples
+    ///
+    /// ```rust
+    /// use datetime::{Instant, Interval};
+    ///
+    /// let interval = Interval::new(Instant::at(0), Instant::at(93600));
+    /// assert_eq!(interval.to_string(), "1970-01-01T00:00:00Z/1970-01-02T02:00:00Z");
+    /// assert_eq!(format!("{:#}", interval), "1970-01-01T00:00:00Z/P1DT2H");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}/{:#}", self.start, self.duration())
+        }
+        else {
+            write!(f, "{}/{}", self.start, self.end)
+        }
+    }
+}
+
+impl<T, E> FromStr for Interval<T>
+where T: FromStr<Err=ParseError<E>> + Ord + Copy + Add<Duration, Output=T> + Sub<Duration, Output=T>,
+      E: ErrorTrait {
+    type Err = ParseError<E>;
+
+    /// Parses an ISO 8601 interval in any of the three forms that have a
+    /// start and end: `start/end`, `start/duration`, or `duration/end`,
+    /// where the duration is written like `P1DT2H`.
+    fn from_str(input: &str) -> Result<Interval<T>, Self::Err> {
+        let bad_input = || ParseError::Parse(format!("Parser Error: {}", input));
+
+        let mut parts = input.splitn(2, '/');
+        let (first, second) = match (parts.next(), parts.next()) {
+            (Some(first), Some(second)) => (first, second),
+            _                           => return Err(bad_input()),
+        };
+
+        let is_duration = |part: &str| part.starts_with('P');
+        let duration = |part: &str| part.parse::<Duration>().map_err(|e| ParseError::Parse(format!("{}: {}", e, part)));
+
+        if is_duration(first) && is_duration(second) {
+            Err(bad_input())
+        }
+        else if is_duration(second) {
+            let start: T = try!(first.parse());
+            Ok(Interval::new(start, start + try!(duration(second))))
+        }
+        else if is_duration(first) {
+            let end: T = try!(second.parse());
+            Ok(Interval::new(end - try!(duration(first)), end))
+        }
+        else {
+            Ok(Interval::new(try!(first.parse()), try!(second.parse())))
+        }
+    }
+}
+
+
+/// An interval that repeats back-to-back, such as every day for five days,
+/// written in ISO 8601 as `R5/2024-01-01T00:00:00Z/P1D`.
+///
+/// Each repetition starts when the one before it ends, and lasts as long
+/// as the first one. Iterating over this gives each repetition as an
+/// interval, and never finishes if the number of repetitions isn’t given.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{Instant, Interval, RepeatingInterval};
+///
+/// let daily: RepeatingInterval<Instant> = "R3/1970-01-01T00:00:00Z/P1D".parse().unwrap();
+/// let days: Vec<_> = daily.iter().collect();
+///
+/// assert_eq!(days.len(), 3);
+/// assert_eq!(days[2], Interval::new(Instant::at(2 * 86400), Instant::at(3 * 86400)));
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct RepeatingInterval<T> {
+
+    /// The first repetition.
+    pub first: Interval<T>,
+
+    /// The number of times the interval happens, including the first, or
+    /// `None` if it repeats forever.
+    pub repetitions: Option<u64>,
+}
+
+impl<T> RepeatingInterval<T>
+where T: Ord + Copy + Add<Duration, Output=T> + Sub<T, Output=Duration> {
+
+    /// Returns an iterator over each repetition of the interval.
+    pub fn iter(&self) -> Repetitions<T> {
+        Repetitions {
+            next: self.first.start,
+            duration: self.first.duration(),
+            remaining: self.repetitions,
+        }
+    }
+}
+
+impl<T> fmt::Display for RepeatingInterval<T>
+where T: fmt::Display + Ord + Copy + Sub<T, Output=Duration> {
+
+    /// Formats this repeating interval in the ISO 8601 format, such as
+    /// `R5/2024-01-01T00:00:00Z/P1D`, leaving out the number after the
+    /// `R` if it repeats forever.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.repetitions {
+            Some(n) => write!(f, "R{}/{:#}", n, self.first),
+            None    => write!(f, "R/{:#}", self.first),
+        }
+    }
+}
+
+impl<T, E> FromStr for RepeatingInterval<T>
+where T: FromStr<Err=ParseError<E>> + Ord + Copy + Add<Duration, Output=T> + Sub<Duration, Output=T>,
+      E: ErrorTrait {
+    type Err = ParseError<E>;
+
+    /// Parses an ISO 8601 repeating interval: an `R`, an optional number
+    /// of repetitions, a slash, and then an interval in any of the forms
+    /// that `Interval` can be parsed from.
+    fn from_str(input: &str) -> Result<RepeatingInterval<T>, Self::Err> {
+        let bad_input = || ParseError::Parse(format!("Parser Error: {}", input));
+
+        if !input.starts_with('R') {
+            return Err(bad_input());
+        }
+
+        let slash = try!(input.find('/').ok_or_else(bad_input));
+        let repetitions = match &input[1 .. slash] {
+            ""     => None,
+            digits => Some(try!(digits.parse::<u64>().map_err(|_| bad_input()))),
+        };
+
+        let first = try!(input[slash + 1 ..].parse());
+        Ok(RepeatingInterval { first: first, repetitions: repetitions })
+    }
+}
+
+/// An iterator over the repetitions of a `RepeatingInterval`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Repetitions<T> {
+    next: T,
+    duration: Duration,
+    remaining: Option<u64>,
+}
+
+impl<T: Copy + Add<Duration, Output=T>> Iterator for Repetitions<T> {
+    type Item = Interval<T>;
+
+    fn next(&mut self) -> Option<Interval<T>> {
+        match self.remaining {
+            Some(0)     => return None,
+            Some(ref mut n) => *n -= 1,
+            None        => {},
+        }
+
+        let start = self.next;
+        self.next = start + self.duration;
+        Some(Interval { start: start, end: self.next })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(n) => (n as usize, Some(n as usize)),
+            None    => (usize::max_value(), None),
+        }
+    }
+}
+
+
+/// A set of points on a timeline, stored as a list of intervals.
+///
+/// The intervals are kept sorted, and any that overlap or abut get merged
+/// together, so no two of them touch and there are never any empty ones.
+/// This makes it easy to answer questions such as when a room is free:
+/// add every booking to a set, then look for the gaps in it.
+///
+/// ### Examples
+///
+/// ```rust
+/// use datetime::{Instant, Interval, IntervalSet, Duration};
+///
+/// let mut busy = IntervalSet::new();
+/// busy.insert(Interval::new(Instant::at(0), Instant::at(100)));
+/// busy.insert(Interval::new(Instant::at(150), Instant::at(200)));
+/// busy.insert(Interval::new(Instant::at(100), Instant::at(120)));
+///
+/// let day = Interval::new(Instant::at(0), Instant::at(300));
+/// let free: Vec<_> = busy.gaps(day).iter().cloned().collect();
+/// assert_eq!(free, vec![ Interval::new(Instant::at(120), Instant::at(150)),
+///                        Interval::new(Instant::at(200), Instant::at(300)) ]);
+///
+/// let slot = busy.first_gap(day, Duration::of(60)).unwrap();
+/// assert_eq!(slot, Interval::new(Instant::at(200), Instant::at(260)));
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+
+    /// Creates a new set with nothing in it.
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: Vec::new() }
+    }
+
+    /// Returns the intervals in this set, in order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// Returns an iterator over the intervals in this set, in order.
+    pub fn iter(&self) -> slice::Iter<Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// Returns whether this set has nothing in it.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns whether the given point is in any of the intervals.
+    pub fn contains(&self, point: T) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(point))
+    }
+
+    /// Returns whether any part of the given interval is in this set.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intervals.iter().any(|interval| interval.overlaps(other))
+    }
+
+    /// Adds every point in the given interval to this set, merging it with
+    /// any intervals that it overlaps or abuts.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let mut merged = interval;
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+
+        for existing in self.intervals.drain(..) {
+            match merged.union(&existing) {
+                Some(union) => merged = union,
+                None        => intervals.push(existing),
+            }
+        }
+
+        let position = intervals.iter().position(|i| i.start > merged.start).unwrap_or(intervals.len());
+        intervals.insert(position, merged);
+        self.intervals = intervals;
+    }
+
+    /// Removes every point in the given interval from this set, splitting
+    /// any intervals that it falls in the middle of.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+
+        for existing in self.intervals.drain(..) {
+            if !existing.overlaps(&interval) {
+                intervals.push(existing);
+                continue;
+            }
+
+            let before = Interval::new(existing.start, interval.start);
+            let after = Interval::new(interval.end, existing.end);
+
+            if !before.is_empty() {
+                intervals.push(before);
+            }
+
+            if !after.is_empty() {
+                intervals.push(after);
+            }
+        }
+
+        self.intervals = intervals;
+    }
+
+    /// Returns the set of points that are in either set.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut set = self.clone();
+        for interval in &other.intervals {
+            set.insert(*interval);
+        }
+        set
+    }
+
+    /// Returns the set of points that are in this set but not the other
+    /// one.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut set = self.clone();
+        for interval in &other.intervals {
+            set.remove(*interval);
+        }
+        set
+    }
+
+    /// Returns the set of points that are in both sets.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.iter()
+            .flat_map(|a| other.iter().filter_map(move |b| a.intersection(b)))
+            .collect()
+    }
+
+    /// Returns the parts of the given interval that aren’t in this set.
+    pub fn gaps(&self, within: Interval<T>) -> IntervalSet<T> {
+        let mut gaps = IntervalSet::new();
+        gaps.insert(within);
+        for interval in &self.intervals {
+            gaps.remove(*interval);
+        }
+        gaps
+    }
+}
+
+impl<T: Ord + Copy + Add<Duration, Output=T> + Sub<T, Output=Duration>> IntervalSet<T> {
+
+    /// Returns the earliest interval of the given length that’s within
+    /// the given one but doesn’t overlap this set, or `None` if there
+    /// isn’t a gap that long.
+    pub fn first_gap(&self, within: Interval<T>, length: Duration) -> Option<Interval<T>> {
+        self.gaps(within).iter()
+            .find(|gap| gap.duration() >= length)
+            .map(|gap| Interval::new(gap.start, gap.start + length))
+    }
+}
+
+impl<T: Ord + Copy> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item=Interval<T>>>(intervals: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl<T: Ord + Copy> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        set.insert(interval);
+        set
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+    pub use instant::Instant;
+
+    fn at(seconds: i64) -> Instant {
+        Instant::at(seconds)
+    }
+
+    #[test]
+    fn half_open() {
+        let interval = Interval::new(at(10), at(20));
+        assert!(interval.contains(at(10)));
+        assert!(interval.contains(at(19)));
+        assert!(!interval.contains(at(20)));
+        assert!(!interval.contains(at(9)));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(Interval::new(at(10), at(10)).is_empty());
+        assert!(Interval::new(at(20), at(10)).is_empty());
+        assert!(!Interval::new(at(20), at(10)).contains(at(15)));
+        assert_eq!(Interval::new(at(20), at(10)).duration(), Duration::zero());
+    }
+
+    #[test]
+    fn abutting_intervals_do_not_overlap() {
+        let first = Interval::new(at(0), at(10));
+        let second = Interval::new(at(10), at(20));
+        assert!(!first.overlaps(&second));
+        assert!(first.abuts(&second));
+        assert_eq!(first.intersection(&second), None);
+        assert_eq!(first.union(&second), Some(Interval::new(at(0), at(20))));
+    }
+
+    #[test]
+    fn gaps_have_no_union() {
+        let first = Interval::new(at(0), at(10));
+        let second = Interval::new(at(11), at(20));
+        assert_eq!(first.union(&second), None);
+        assert_eq!(first.union(&Interval::new(at(50), at(50))), Some(first));
+    }
+
+    #[test]
+    fn nested() {
+        let outer = Interval::new(at(0), at(100));
+        let inner = Interval::new(at(25), at(75));
+        assert_eq!(outer.intersection(&inner), Some(inner));
+        assert_eq!(outer.union(&inner), Some(outer));
+    }
+
+    mod sets {
+        use super::*;
+
+        fn set(intervals: &[(i64, i64)]) -> IntervalSet<Instant> {
+            intervals.iter().map(|&(s, e)| Interval::new(at(s), at(e))).collect()
+        }
+
+        fn pairs(set: &IntervalSet<Instant>) -> Vec<(i64, i64)> {
+            set.iter().map(|i| (i.start.seconds(), i.end.seconds())).collect()
+        }
+
+        #[test]
+        fn inserting_merges_and_sorts() {
+            let set = set(&[ (50, 60), (0, 10), (10, 20), (55, 70), (30, 30) ]);
+            assert_eq!(pairs(&set), vec![ (0, 20), (50, 70) ]);
+        }
+
+        #[test]
+        fn inserting_bridges_a_gap() {
+            let mut set = set(&[ (0, 10), (20, 30), (40, 50) ]);
+            set.insert(Interval::new(at(5), at(45)));
+            assert_eq!(pairs(&set), vec![ (0, 50) ]);
+        }
+
+        #[test]
+        fn removing_splits() {
+            let mut set = set(&[ (0, 100) ]);
+            set.remove(Interval::new(at(40), at(60)));
+            assert_eq!(pairs(&set), vec![ (0, 40), (60, 100) ]);
+            set.remove(Interval::new(at(0), at(40)));
+            assert_eq!(pairs(&set), vec![ (60, 100) ]);
+            assert!(!set.contains(at(40)));
+            assert!(set.contains(at(60)));
+        }
+
+        #[test]
+        fn set_operations() {
+            let a = set(&[ (0, 10), (20, 30) ]);
+            let b = set(&[ (5, 25) ]);
+            assert_eq!(pairs(&a.union(&b)), vec![ (0, 30) ]);
+            assert_eq!(pairs(&a.difference(&b)), vec![ (0, 5), (25, 30) ]);
+            assert_eq!(pairs(&a.intersection(&b)), vec![ (5, 10), (20, 25) ]);
+        }
+
+        #[test]
+        fn gaps() {
+            let busy = set(&[ (10, 20), (30, 40) ]);
+            assert_eq!(pairs(&busy.gaps(Interval::new(at(0), at(50)))), vec![ (0, 10), (20, 30), (40, 50) ]);
+            assert_eq!(pairs(&busy.gaps(Interval::new(at(15), at(35)))), vec![ (20, 30) ]);
+            assert!(busy.gaps(Interval::new(at(10), at(20))).is_empty());
+        }
+
+        #[test]
+        fn first_gap() {
+            let busy = set(&[ (10, 20), (25, 40) ]);
+            let within = Interval::new(at(0), at(50));
+            assert_eq!(busy.first_gap(within, Duration::of(5)), Some(Interval::new(at(0), at(5))));
+            assert_eq!(busy.first_gap(within, Duration::of(10)), Some(Interval::new(at(0), at(10))));
+            assert_eq!(busy.first_gap(within, Duration::of(11)), None);
+        }
+    }
+
+    mod iso {
+        use super::*;
+
+        #[test]
+        fn round_trip_with_end() {
+            let interval = Interval::new(Instant::at_ms(0, 500), Instant::at(86400));
+            let string = interval.to_string();
+            assert_eq!(string, "1970-01-01T00:00:00.500Z/1970-01-02T00:00:00Z");
+            assert_eq!(string.parse(), Ok(interval));
+        }
+
+        #[test]
+        fn round_trip_with_duration() {
+            let interval = Interval::new(Instant::at(0), Instant::at(3 * 86400 + 7200));
+            let string = format!("{:#}", interval);
+            assert_eq!(string, "1970-01-01T00:00:00Z/P3DT2H");
+            assert_eq!(string.parse(), Ok(interval));
+        }
+
+        #[test]
+        fn duration_then_end() {
+            let interval: Interval<LocalDateTime> = "PT1H/2015-06-26T12:00:00".parse().unwrap();
+            let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+            assert_eq!(interval, Interval::new(date.at(LocalTime::hms(11, 0, 0).unwrap()), date.at(LocalTime::NOON)));
+        }
+
+        #[test]
+        fn invalid() {
+            assert!("1970-01-01T00:00:00Z".parse::<Interval<Instant>>().is_err());
+            assert!("PT1H/PT2H".parse::<Interval<Instant>>().is_err());
+            assert!("1970-01-01T00:00:00Z/P1Q".parse::<Interval<Instant>>().is_err());
+            assert!("1970-01-01T00:00:00Z/tomorrow".parse::<Interval<Instant>>().is_err());
+        }
+    }
+
+    mod repeating {
+        use super::*;
+
+        #[test]
+        fn counted() {
+            let repeating: RepeatingInterval<Instant> = "R5/2024-01-01T00:00Z/P1D".parse().unwrap();
+            assert_eq!(repeating.repetitions, Some(5));
+
+            let days: Vec<_> = repeating.iter().collect();
+            assert_eq!(days.len(), 5);
+            assert_eq!(days[0].start.to_string(), "2024-01-01T00:00:00Z");
+            assert_eq!(days[4].end.to_string(), "2024-01-06T00:00:00Z");
+            assert!(days.windows(2).all(|pair| pair[0].end == pair[1].start));
+        }
+
+        #[test]
+        fn forever() {
+            let repeating: RepeatingInterval<Instant> = "R/1970-01-01T00:00:00Z/1970-01-01T01:00:00Z".parse().unwrap();
+            assert_eq!(repeating.repetitions, None);
+            assert_eq!(repeating.iter().nth(1000).unwrap().start, Instant::at(1000 * 3600));
+        }
+
+        #[test]
+        fn zero_repetitions() {
+            let repeating: RepeatingInterval<Instant> = "R0/1970-01-01T00:00:00Z/PT1H".parse().unwrap();
+            assert_eq!(repeating.iter().next(), None);
+        }
+
+        #[test]
+        fn round_trip() {
+            let repeating = RepeatingInterval { first: Interval::new(Instant::at(0), Instant::at(90000)), repetitions: Some(3) };
+            assert_eq!(repeating.to_string(), "R3/1970-01-01T00:00:00Z/P1DT1H");
+            assert_eq!(repeating.to_string().parse(), Ok(repeating));
+
+            let forever = RepeatingInterval { repetitions: None, .. repeating };
+            assert_eq!(forever.to_string(), "R/1970-01-01T00:00:00Z/P1DT1H");
+            assert_eq!(forever.to_string().parse(), Ok(forever));
+        }
+
+        #[test]
+        fn invalid() {
+            assert!("5/1970-01-01T00:00:00Z/PT1H".parse::<RepeatingInterval<Instant>>().is_err());
+            assert!("Rx/1970-01-01T00:00:00Z/PT1H".parse::<RepeatingInterval<Instant>>().is_err());
+            assert!("R5".parse::<RepeatingInterval<Instant>>().is_err());
+        }
+    }
+
+    #[test]
+    fn datetimes() {
+        let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+        let interval = Interval::new(date.at(LocalTime::hms(9, 0, 0).unwrap()), date.at(LocalTime::hms_ms(17, 30, 0, 250).unwrap()));
+        assert_eq!(interval.duration(), Duration::of_ms(8 * 3600 + 30 * 60, 250));
+        assert!(interval.contains(date.at(LocalTime::NOON)));
+    }
+}
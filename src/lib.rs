@@ -10,29 +10,141 @@
 #![warn(unused_qualifications)]
 #![warn(unused_results)]
 
+// Without the `std` feature, only `core` and `alloc` are available. Naming
+// `core` as `std` lets the modules that only need the parts common to both
+// refer to them the same way in either case.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 extern crate locale;
+
+#[cfg(feature = "std")]
 extern crate libc;
-extern crate num;
+
+#[cfg(feature = "std")]
 extern crate pad;
+
+#[cfg(feature = "std")]
 extern crate iso8601;
 
+#[cfg(feature = "serde")]
+extern crate serde as serdelib;
+
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+#[cfg(feature = "time")]
+extern crate time;
+
+#[cfg(feature = "postgres")]
+extern crate bytes;
+
+#[cfg(feature = "postgres")]
+#[macro_use]
+extern crate postgres_types;
+
+#[cfg(feature = "rusqlite")]
+extern crate rusqlite;
+
+#[cfg(feature = "prost")]
+extern crate prost_types;
+
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+extern crate js_sys;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+extern crate wasm_bindgen;
+
+
+#[macro_use]
+mod macros;
+
+// The macros refer to `Result` through `$crate`, as a crate that uses them
+// might not link the standard library, or have a `std` of its own.
+#[doc(hidden)]
+pub use std::result::Result as __Result;
 
 mod cal;
 pub use cal::{DatePiece, TimePiece};
-pub use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday};
+pub use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday, WeekdaySet, Weekend, WeekRule, Era, EraNames, MonthNames, Error as DateTimeError};
+pub use cal::datetime::{is_leap_year, days_in_month, days_in_year, weeks_in_iso_year};
+pub use cal::datetime::{LocalDateTimeBuilder, Fields};
+pub use cal::calendar as calendar;
+pub use cal::easter as easter;
+pub use cal::fiscal::{FiscalCalendar, FiscalDate, WeekPattern};
+pub use cal::holiday::{self as holiday, HolidayCalendar};
+#[cfg(feature = "islamic")]
+pub use cal::islamic as islamic;
+#[cfg(feature = "std")]
 pub use cal::format as format;
+#[cfg(feature = "hebrew")]
+pub use cal::hebrew as hebrew;
 pub use cal::iter as iter;
-pub use cal::offset::{Offset, OffsetDateTime};
+pub use cal::japanese::{JapaneseCalendar, JapaneseEra};
+pub use cal::offset::{Offset, OffsetDateTime, OffsetTime, Error as OffsetError};
+pub use cal::parse::{Error as ParseError, SyntaxError};
+pub use cal::partial::PartialDateTime;
 pub use cal::zone::{TimeZone, ZonedDateTime};
 pub use cal::zone as zone;
 
 mod duration;
-pub use duration::Duration;
+pub use duration::{Duration, Error as DurationError, ParseError as DurationParseError};
+
+mod error;
+pub use error::Error;
 
 mod instant;
 pub use instant::Instant;
 
+mod interval;
+pub use interval::{Interval, IntervalSet, RepeatingInterval, Repetitions};
+
+mod timestamp;
+pub use timestamp::UnixTimestamp;
+
+mod clock;
+pub use clock::Clock;
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+
+#[cfg(feature = "std")]
+mod monotonic;
+#[cfg(feature = "std")]
+pub use monotonic::{MonotonicInstant, Anchor, Deadline, Stopwatch};
+
+#[cfg(feature = "std")]
 mod system;
-pub use system::sys_timezone;
+#[cfg(feature = "std")]
+pub use system::{sys_timezone, cached_sys_timezone, refresh_sys_timezone};
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+pub use system::ClockSource;
+
+pub mod tai;
+pub mod gps;
+pub mod filetime;
+pub mod ntp;
+pub mod excel;
+pub mod dotnet;
+pub mod arrow;
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod testing;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+mod compat;
 
 mod util;
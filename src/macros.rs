@@ -0,0 +1,163 @@
+//! Macros that create dates and times from literals, checking them at
+//! compile time.
+//!
+//! Each macro expands to a constant, so an invalid value, such as the 30th
+//! of February, stops the program from compiling instead of returning an
+//! error at run time.
+
+
+/// Creates a `LocalDate` from a literal in the ISO 8601 `year-month-day`
+/// format, checking it at compile time.
+///
+/// ### Examples
+///
+/// ```rust
+/// #[macro_use] extern crate datetime;
+/// use datetime::{LocalDate, Month};
+///
+/// fn main() {
+///     assert_eq!(date!(2024-07-07), LocalDate::ymd(2024, Month::July, 7).unwrap());
+///     assert_eq!(date!(-43-03-15), LocalDate::ymd(-43, Month::March, 15).unwrap());
+/// }
+/// ```
+///
+/// An invalid date fails to compile:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate datetime;
+///
+/// fn main() {
+///     let _ = date!(2024-02-30);
+/// }
+/// ```
+#[macro_export]
+macro_rules! date {
+    ($year:literal - $month:literal - $day:literal) => {{
+        const DATE: $crate::LocalDate = match $crate::Month::from_one($month) {
+            $crate::__Result::Ok(month) => match $crate::LocalDate::ymd($year, month, $day) {
+                $crate::__Result::Ok(date) => date,
+                $crate::__Result::Err(_)   => panic!(concat!("invalid date: ", stringify!($year-$month-$day))),
+            },
+            $crate::__Result::Err(_) => panic!(concat!("invalid month: ", stringify!($year-$month-$day))),
+        };
+        DATE
+    }};
+    (- $year:literal - $month:literal - $day:literal) => {{
+        const DATE: $crate::LocalDate = match $crate::Month::from_one($month) {
+            $crate::__Result::Ok(month) => match $crate::LocalDate::ymd(-$year, month, $day) {
+                $crate::__Result::Ok(date) => date,
+                $crate::__Result::Err(_)   => panic!(concat!("invalid date: -", stringify!($year-$month-$day))),
+            },
+            $crate::__Result::Err(_) => panic!(concat!("invalid month: -", stringify!($year-$month-$day))),
+        };
+        DATE
+    }};
+}
+
+/// Creates a `LocalTime` from a literal in the `hour:minute` or
+/// `hour:minute:second` format, checking it at compile time. Fractions of
+/// a second aren’t supported, as they can’t be told apart from a
+/// floating-point literal. As in ISO 8601, the second can be 60 at the
+/// end of an hour, for a leap second.
+///
+/// ### Examples
+///
+/// ```rust
+/// #[macro_use] extern crate datetime;
+/// use datetime::LocalTime;
+///
+/// fn main() {
+///     assert_eq!(time!(14:30), LocalTime::hm(14, 30).unwrap());
+///     assert_eq!(time!(23:59:60), LocalTime::hms_leap(23, 59, 60).unwrap());
+/// }
+/// ```
+///
+/// An invalid time fails to compile:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate datetime;
+///
+/// fn main() {
+///     let _ = time!(14:60);
+/// }
+/// ```
+#[macro_export]
+macro_rules! time {
+    ($hour:literal : $minute:literal) => {{
+        const TIME: $crate::LocalTime = match $crate::LocalTime::hm($hour, $minute) {
+            $crate::__Result::Ok(time) => time,
+            $crate::__Result::Err(_)   => panic!(concat!("invalid time: ", stringify!($hour:$minute))),
+        };
+        TIME
+    }};
+    ($hour:literal : $minute:literal : $second:literal) => {{
+        const TIME: $crate::LocalTime = match $crate::LocalTime::hms_leap($hour, $minute, $second) {
+            $crate::__Result::Ok(time) => time,
+            $crate::__Result::Err(_)   => panic!(concat!("invalid time: ", stringify!($hour:$minute:$second))),
+        };
+        TIME
+    }};
+}
+
+/// Creates a `LocalDateTime` from a date and a time, separated by a space,
+/// in the formats used by the `date!` and `time!` macros, checking it at
+/// compile time.
+///
+/// ### Examples
+///
+/// ```rust
+/// #[macro_use] extern crate datetime;
+/// use datetime::{LocalDateTime, LocalDate, LocalTime, Month};
+///
+/// fn main() {
+///     let date = LocalDate::ymd(1969, Month::July, 20).unwrap();
+///     let time = LocalTime::hms(20, 17, 40).unwrap();
+///     assert_eq!(datetime!(1969-07-20 20:17:40), LocalDateTime::new(date, time));
+/// }
+/// ```
+#[macro_export]
+macro_rules! datetime {
+    ($year:literal - $month:literal - $day:literal $($time:tt)+) => {{
+        const DATETIME: $crate::LocalDateTime = $crate::LocalDateTime::new($crate::date!($year-$month-$day), $crate::time!($($time)+));
+        DATETIME
+    }};
+    (- $year:literal - $month:literal - $day:literal $($time:tt)+) => {{
+        const DATETIME: $crate::LocalDateTime = $crate::LocalDateTime::new($crate::date!(-$year-$month-$day), $crate::time!($($time)+));
+        DATETIME
+    }};
+}
+
+
+#[cfg(test)]
+mod test {
+    use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+
+    #[test]
+    fn dates() {
+        assert_eq!(date!(2015-06-26), LocalDate::ymd(2015, Month::June, 26).unwrap());
+        assert_eq!(date!(2016-02-29), LocalDate::ymd(2016, Month::February, 29).unwrap());
+        assert_eq!(date!(0-01-01), LocalDate::ymd(0, Month::January, 1).unwrap());
+        assert_eq!(date!(-1-12-31), LocalDate::ymd(-1, Month::December, 31).unwrap());
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(time!(09:05), LocalTime::hm(9, 5).unwrap());
+        assert_eq!(time!(24:00), LocalTime::hm(24, 0).unwrap());
+        assert_eq!(time!(12:34:56), LocalTime::hms(12, 34, 56).unwrap());
+    }
+
+    #[test]
+    fn datetimes() {
+        let date = LocalDate::ymd(2015, Month::June, 26).unwrap();
+        assert_eq!(datetime!(2015-06-26 14:30), LocalDateTime::new(date, LocalTime::hm(14, 30).unwrap()));
+        assert_eq!(datetime!(2015-06-26 14:30:15), LocalDateTime::new(date, LocalTime::hms(14, 30, 15).unwrap()));
+        assert_eq!(datetime!(-43-03-15 12:00), LocalDateTime::new(LocalDate::ymd(-43, Month::March, 15).unwrap(), LocalTime::hm(12, 0).unwrap()));
+    }
+
+    #[test]
+    fn usable_in_constants() {
+        const LAUNCH: LocalDateTime = datetime!(2015-06-26 14:30);
+        assert_eq!(LAUNCH.date(), date!(2015-06-26));
+    }
+}
@@ -0,0 +1,649 @@
+//! Points on the system’s monotonic clock, for measuring elapsed time.
+
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::time::Duration as StdDuration;
+
+use cal::datetime::LocalDateTime;
+use duration::Duration;
+use instant::Instant;
+use system::sys_monotonic_time;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use system::{ClockSource, sys_clock_time};
+
+
+/// A **monotonic instant** is a reading of the system’s monotonic clock,
+/// which only ever moves forwards at a steady rate.
+///
+/// Unlike an `Instant`, this is *not* a point on the calendar timeline: the
+/// wall clock can be stepped forwards or backwards by the user or by NTP,
+/// but the monotonic clock is unaffected by these jumps, making it the
+/// right tool for measuring how long something took. The flip side is
+/// that its starting point is unspecified, so a monotonic instant can only
+/// be meaningfully compared with other monotonic instants taken while the
+/// computer has been running.
+///
+/// The clock used is `CLOCK_MONOTONIC` on Linux and other Unixes,
+/// `mach_absolute_time` on macOS and iOS, and `QueryPerformanceCounter` on
+/// Windows.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct MonotonicInstant {
+    seconds: i64,
+    nanoseconds: i32,
+}
+
+impl MonotonicInstant {
+
+    /// Creates a new monotonic instant set to the current reading of the
+    /// system’s monotonic clock.
+    pub fn now() -> MonotonicInstant {
+        let (s, ns) = unsafe { sys_monotonic_time() };
+        MonotonicInstant { seconds: s, nanoseconds: ns }
+    }
+
+    /// Creates a new monotonic instant set to the current reading of the
+    /// given clock, returning `None` if it’s one of the realtime clocks,
+    /// which can jump around, or the kernel doesn’t support it.
+    ///
+    /// Instants read from different clocks can’t be meaningfully compared
+    /// with each other.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn now_from(source: ClockSource) -> Option<MonotonicInstant> {
+        if source.is_realtime() {
+            return None;
+        }
+
+        let (s, ns) = sys_clock_time(source)?;
+        Some(MonotonicInstant { seconds: s, nanoseconds: ns })
+    }
+
+    /// Returns the length of time that has passed between the `earlier`
+    /// instant and this one. If `earlier` is actually *later* than this
+    /// instant, the duration is negative.
+    ///
+    /// ```rust
+    /// use datetime::MonotonicInstant;
+    ///
+    /// let start = MonotonicInstant::now();
+    /// let end = MonotonicInstant::now();
+    /// assert!(end.duration_since(start).lengths().0 >= 0);
+    /// ```
+    pub fn duration_since(&self, earlier: MonotonicInstant) -> Duration {
+        let mut seconds = self.seconds - earlier.seconds;
+        let mut nanoseconds = self.nanoseconds - earlier.nanoseconds;
+
+        if nanoseconds < 0 {
+            seconds -= 1;
+            nanoseconds += 1_000_000_000;
+        }
+
+        Duration::of_ns(seconds, nanoseconds)
+    }
+
+    /// Returns the length of time that has passed since this instant.
+    pub fn elapsed(&self) -> Duration {
+        MonotonicInstant::now().duration_since(*self)
+    }
+
+    /// Returns the instant the given length of time after this one, or
+    /// `None` if it would overflow.
+    pub fn checked_add(self, duration: Duration) -> Option<MonotonicInstant> {
+        let (seconds, nanoseconds) = duration.lengths_ns();
+        let nanoseconds = self.nanoseconds + nanoseconds;
+        let (carry, nanoseconds) = if nanoseconds >= 1_000_000_000 { (1, nanoseconds - 1_000_000_000) } else { (0, nanoseconds) };
+        let seconds = self.seconds.checked_add(seconds)?.checked_add(carry)?;
+        Some(MonotonicInstant { seconds: seconds, nanoseconds: nanoseconds })
+    }
+
+    /// Returns the instant the given length of time before this one, or
+    /// `None` if it would overflow.
+    pub fn checked_sub(self, duration: Duration) -> Option<MonotonicInstant> {
+        let (seconds, nanoseconds) = duration.lengths_ns();
+        let nanoseconds = self.nanoseconds - nanoseconds;
+        let (borrow, nanoseconds) = if nanoseconds < 0 { (1, nanoseconds + 1_000_000_000) } else { (0, nanoseconds) };
+        let seconds = self.seconds.checked_sub(seconds)?.checked_sub(borrow)?;
+        Some(MonotonicInstant { seconds: seconds, nanoseconds: nanoseconds })
+    }
+}
+
+impl fmt::Debug for MonotonicInstant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MonotonicInstant({}s/{}ns)", self.seconds, self.nanoseconds)
+    }
+}
+
+impl Add<Duration> for MonotonicInstant {
+    type Output = MonotonicInstant;
+
+    fn add(self, duration: Duration) -> MonotonicInstant {
+        self.checked_add(duration).expect("monotonic instant out of range")
+    }
+}
+
+impl Sub<Duration> for MonotonicInstant {
+    type Output = MonotonicInstant;
+
+    fn sub(self, duration: Duration) -> MonotonicInstant {
+        self.checked_sub(duration).expect("monotonic instant out of range")
+    }
+}
+
+impl Sub<MonotonicInstant> for MonotonicInstant {
+    type Output = Duration;
+
+    fn sub(self, earlier: MonotonicInstant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+
+/// A **stopwatch** measures how long something takes on the monotonic
+/// clock. It can be stopped and started again, only counting the time it
+/// spent running, and split into laps.
+///
+/// ```rust
+/// use datetime::Stopwatch;
+///
+/// let mut stopwatch = Stopwatch::start_new();
+/// let first = stopwatch.lap();
+/// let second = stopwatch.lap();
+/// let total = stopwatch.stop();
+/// assert!(total >= first + second);
+/// assert_eq!(stopwatch.laps(), &[ first, second ]);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Stopwatch {
+
+    /// The number of nanoseconds counted in the runs before this one.
+    previous_runs: i64,
+
+    /// When the current run started, or `None` if the stopwatch is stopped.
+    started: Option<MonotonicInstant>,
+
+    /// The number of nanoseconds counted when the last lap ended.
+    lap_started: i64,
+
+    laps: Vec<Duration>,
+}
+
+impl Stopwatch {
+
+    /// Creates a new stopwatch that is stopped, and has counted nothing.
+    pub fn new() -> Stopwatch {
+        Stopwatch::default()
+    }
+
+    /// Creates a new stopwatch, and starts it.
+    pub fn start_new() -> Stopwatch {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.start();
+        stopwatch
+    }
+
+    /// Starts this stopwatch counting from where it left off. Starting a
+    /// stopwatch that is already running does nothing.
+    pub fn start(&mut self) {
+        self.start_at(MonotonicInstant::now())
+    }
+
+    /// Stops this stopwatch, and returns how long it has been running for
+    /// in total. Stopping a stopwatch that isn’t running does nothing.
+    pub fn stop(&mut self) -> Duration {
+        self.stop_at(MonotonicInstant::now())
+    }
+
+    /// Returns whether this stopwatch is running.
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Returns how long this stopwatch has been running for in total.
+    pub fn elapsed(&self) -> Duration {
+        nanoseconds_to_duration(self.counted_at(MonotonicInstant::now()))
+    }
+
+    /// Ends the current lap, records it, and returns how long it was. The
+    /// first lap starts when the stopwatch is first started; each one
+    /// after it starts when the last one ended, and time while the
+    /// stopwatch is stopped doesn’t count towards either.
+    pub fn lap(&mut self) -> Duration {
+        self.lap_at(MonotonicInstant::now())
+    }
+
+    /// Returns the lengths of the laps recorded so far, in order.
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+
+    /// Stops this stopwatch, and sets it back to zero, discarding any laps.
+    pub fn reset(&mut self) {
+        *self = Stopwatch::new();
+    }
+
+    fn start_at(&mut self, now: MonotonicInstant) {
+        if self.started.is_none() {
+            self.started = Some(now);
+        }
+    }
+
+    fn stop_at(&mut self, now: MonotonicInstant) -> Duration {
+        self.previous_runs = self.counted_at(now);
+        self.started = None;
+        nanoseconds_to_duration(self.previous_runs)
+    }
+
+    fn lap_at(&mut self, now: MonotonicInstant) -> Duration {
+        let counted = self.counted_at(now);
+        let lap = nanoseconds_to_duration(counted - self.lap_started);
+        self.lap_started = counted;
+        self.laps.push(lap);
+        lap
+    }
+
+    /// Returns the number of nanoseconds counted up to the given instant.
+    fn counted_at(&self, now: MonotonicInstant) -> i64 {
+        match self.started {
+            Some(started) => self.previous_runs + (now.seconds - started.seconds) * 1_000_000_000
+                                                + (now.nanoseconds - started.nanoseconds) as i64,
+            None          => self.previous_runs,
+        }
+    }
+}
+
+/// Converts a number of nanoseconds into a duration.
+fn nanoseconds_to_duration(nanoseconds: i64) -> Duration {
+    let seconds = nanoseconds.div_euclid(1_000_000_000);
+    let nanoseconds = nanoseconds.rem_euclid(1_000_000_000);
+    Duration::of_ns(seconds, nanoseconds as i32)
+}
+
+
+/// A **deadline** is a point on the monotonic clock that something has to
+/// happen by, for working out how long is left to wait.
+///
+/// Because it uses the monotonic clock, a deadline stays the same length
+/// of time away if the wall clock is changed in the meantime, which is
+/// what timeouts want.
+///
+/// ```rust
+/// use datetime::{Deadline, Duration};
+///
+/// let deadline = Deadline::after(Duration::of(30));
+/// assert!(!deadline.is_expired());
+/// assert!(deadline.remaining() <= Duration::of(30));
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct Deadline(MonotonicInstant);
+
+impl Deadline {
+
+    /// Creates a new deadline at the given point on the monotonic clock.
+    pub fn at(instant: MonotonicInstant) -> Deadline {
+        Deadline(instant)
+    }
+
+    /// Creates a new deadline the given length of time from now. A
+    /// duration too long for the monotonic clock to reach gives a deadline
+    /// at the end of it, which never passes.
+    pub fn after(duration: Duration) -> Deadline {
+        let end = if duration < Duration::zero() { MonotonicInstant { seconds: i64::min_value(), nanoseconds: 0 } }
+                                                 else { MonotonicInstant { seconds: i64::max_value(), nanoseconds: 999_999_999 } };
+        Deadline(MonotonicInstant::now().checked_add(duration).unwrap_or(end))
+    }
+
+    /// Creates a new deadline at the given wall-clock instant, going by how
+    /// far away it is now. If the wall clock is changed afterwards, the
+    /// deadline doesn’t move with it.
+    pub fn at_instant(instant: Instant) -> Deadline {
+        Deadline(Anchor::now().to_monotonic(instant))
+    }
+
+    /// Returns the point on the monotonic clock that this deadline is at.
+    pub fn instant(&self) -> MonotonicInstant {
+        self.0
+    }
+
+    /// Returns whether this deadline has passed.
+    pub fn is_expired(&self) -> bool {
+        self.remaining_nanoseconds(MonotonicInstant::now()) == 0
+    }
+
+    /// Returns how long is left until this deadline, or zero if it has
+    /// passed.
+    pub fn remaining(&self) -> Duration {
+        let nanoseconds = self.remaining_nanoseconds(MonotonicInstant::now());
+        Duration::of_ns((nanoseconds / 1_000_000_000) as i64, (nanoseconds % 1_000_000_000) as i32)
+    }
+
+    /// Returns how long is left until this deadline as a standard library
+    /// duration, or zero if it has passed, to pass as a timeout to APIs
+    /// that take one, such as `TcpStream::set_read_timeout`.
+    pub fn remaining_std(&self) -> StdDuration {
+        let nanoseconds = self.remaining_nanoseconds(MonotonicInstant::now());
+        StdDuration::new((nanoseconds / 1_000_000_000) as u64, (nanoseconds % 1_000_000_000) as u32)
+    }
+
+    /// Returns the number of nanoseconds left at the given instant, which
+    /// is never negative, and never more than the longest `Duration`.
+    ///
+    /// The difference between two instants can be more seconds than fit in
+    /// an `i64`, so this is worked out in an `i128`.
+    fn remaining_nanoseconds(&self, now: MonotonicInstant) -> i128 {
+        let longest = i64::max_value() as i128 * 1_000_000_000 + 999_999_999;
+        let seconds = self.0.seconds as i128 - now.seconds as i128;
+        let nanoseconds = seconds * 1_000_000_000 + (self.0.nanoseconds - now.nanoseconds) as i128;
+        nanoseconds.max(0).min(longest)
+    }
+}
+
+
+/// An **anchor** pairs a reading of the monotonic clock with the wall
+/// clock’s time at the same moment, so that later monotonic readings can be
+/// turned into the instants they happened at.
+///
+/// Reading the wall clock each time is no good for long-running
+/// measurements: NTP or the user can step it at any time, which can make
+/// events appear to happen out of order, or a few seconds apart when they
+/// were simultaneous. Taking an anchor once at startup, then timestamping
+/// everything with the monotonic clock, keeps every event in the same
+/// frame of reference, at the cost of drifting from the wall clock if it
+/// gets corrected later on.
+///
+/// ```rust
+/// use datetime::{Anchor, MonotonicInstant};
+///
+/// let anchor = Anchor::now();
+/// let event = MonotonicInstant::now();
+/// assert!(anchor.to_instant(event) >= anchor.wall());
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Anchor {
+    monotonic: MonotonicInstant,
+    wall: Instant,
+}
+
+impl Anchor {
+
+    /// Creates a new anchor from the current readings of the monotonic
+    /// clock and the wall clock.
+    pub fn now() -> Anchor {
+        Anchor::new(MonotonicInstant::now(), Instant::now())
+    }
+
+    /// Creates a new anchor from a monotonic reading and the wall clock’s
+    /// time at the same moment.
+    pub fn new(monotonic: MonotonicInstant, wall: Instant) -> Anchor {
+        Anchor { monotonic: monotonic, wall: wall }
+    }
+
+    /// Returns the monotonic reading this anchor was taken at.
+    pub fn monotonic(&self) -> MonotonicInstant {
+        self.monotonic
+    }
+
+    /// Returns the wall clock’s time when this anchor was taken.
+    pub fn wall(&self) -> Instant {
+        self.wall
+    }
+
+    /// Returns the instant that the given monotonic reading was taken at,
+    /// which may be before this anchor.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the result would overflow.
+    pub fn to_instant(&self, reading: MonotonicInstant) -> Instant {
+        self.wall + reading.duration_since(self.monotonic)
+    }
+
+    /// Returns the date-time in UTC that the given monotonic reading was
+    /// taken at.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the result would overflow.
+    pub fn to_datetime(&self, reading: MonotonicInstant) -> LocalDateTime {
+        LocalDateTime::from_instant(self.to_instant(reading))
+    }
+
+    /// Returns the monotonic reading that would be taken at the given
+    /// instant, such as to work out how long to wait until then.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the result would overflow.
+    pub fn to_monotonic(&self, instant: Instant) -> MonotonicInstant {
+        self.monotonic + (instant - self.wall)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::{MonotonicInstant, Anchor, Deadline, Stopwatch};
+    use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month};
+    use duration::Duration;
+    use instant::Instant;
+
+    fn at(seconds: i64, nanoseconds: i32) -> MonotonicInstant {
+        MonotonicInstant { seconds: seconds, nanoseconds: nanoseconds }
+    }
+
+    #[test]
+    fn never_goes_backwards() {
+        let first = MonotonicInstant::now();
+        let second = MonotonicInstant::now();
+        assert!(second >= first)
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn clock_sources() {
+        use system::ClockSource;
+
+        let first = MonotonicInstant::now_from(ClockSource::Boottime).unwrap();
+        let second = MonotonicInstant::now_from(ClockSource::Boottime).unwrap();
+        assert!(second >= first);
+        assert!(MonotonicInstant::now_from(ClockSource::MonotonicCoarse).is_some());
+        assert_eq!(MonotonicInstant::now_from(ClockSource::Realtime), None);
+    }
+
+    #[test]
+    fn elapsed() {
+        let start = MonotonicInstant::now();
+        assert!(start.elapsed().lengths().0 >= 0)
+    }
+
+    mod duration_arithmetic {
+        use super::*;
+
+        #[test]
+        fn difference() {
+            assert_eq!(at(10, 750_000_000) - at(8, 250_000_000), Duration::of_ms(2, 500))
+        }
+
+        #[test]
+        fn difference_wrapping() {
+            assert_eq!(at(10, 250_000_000).duration_since(at(8, 750_000_000)), Duration::of_ms(1, 500))
+        }
+
+        #[test]
+        fn difference_nanoseconds() {
+            assert_eq!(at(1, 1_999_999).duration_since(at(1, 0)), Duration::of_ns(0, 1_999_999))
+        }
+
+        #[test]
+        fn negative_difference() {
+            assert_eq!(at(8, 0).duration_since(at(10, 500_000_000)), Duration::of_ms(-3, 500))
+        }
+
+        #[test]
+        fn addition() {
+            assert_eq!(at(3, 800_000_000) + Duration::of_ms(1, 300), at(5, 100_000_000))
+        }
+
+        #[test]
+        fn checked_arithmetic() {
+            assert_eq!(at(1, 500_000_000).checked_add(Duration::of_ms(0, 700)), Some(at(2, 200_000_000)));
+            assert_eq!(at(1, 500_000_000).checked_sub(Duration::of_ms(0, 700)), Some(at(0, 800_000_000)));
+            assert_eq!(at(i64::max_value(), 500_000_000).checked_add(Duration::of_ms(0, 700)), None);
+            assert_eq!(at(i64::min_value(), 500_000_000).checked_sub(Duration::of_ms(0, 700)), None);
+            assert_eq!(at(0, 0).checked_add(Duration::of(i64::max_value())), Some(at(i64::max_value(), 0)));
+        }
+
+        #[test]
+        fn subtraction() {
+            assert_eq!(at(3, 200_000_000) - Duration::of_ms(1, 300), at(1, 900_000_000))
+        }
+    }
+    mod anchoring {
+        use super::*;
+
+        fn anchor() -> Anchor {
+            Anchor::new(at(100, 500_000_000), Instant::at(1_000_000_000))
+        }
+
+        #[test]
+        fn later_readings() {
+            assert_eq!(anchor().to_instant(at(100, 500_000_000)), Instant::at(1_000_000_000));
+            assert_eq!(anchor().to_instant(at(190, 750_000_000)), Instant::at_ms(1_000_000_090, 250));
+        }
+
+        #[test]
+        fn earlier_readings() {
+            assert_eq!(anchor().to_instant(at(99, 0)), Instant::at_ms(999_999_998, 500));
+        }
+
+        #[test]
+        fn datetimes() {
+            let datetime = LocalDateTime::new(LocalDate::ymd(2001, Month::September, 9).unwrap(), LocalTime::hms(1, 47, 40).unwrap());
+            assert_eq!(anchor().to_datetime(at(160, 500_000_000)), datetime);
+        }
+
+        #[test]
+        fn back_to_monotonic() {
+            assert_eq!(anchor().to_monotonic(Instant::at_ms(1_000_000_090, 250)), at(190, 750_000_000));
+            assert_eq!(anchor().to_monotonic(Instant::at(999_999_999)), at(99, 500_000_000));
+        }
+
+        #[test]
+        fn now() {
+            let anchor = Anchor::now();
+            let later = MonotonicInstant::now();
+            assert!(anchor.to_instant(later) >= anchor.wall());
+        }
+    }
+    mod stopwatch {
+        use super::*;
+
+        #[test]
+        fn starts_stopped() {
+            let stopwatch = Stopwatch::new();
+            assert!(!stopwatch.is_running());
+            assert_eq!(stopwatch.elapsed(), Duration::zero());
+        }
+
+        #[test]
+        fn only_counts_while_running() {
+            let mut stopwatch = Stopwatch::new();
+            stopwatch.start_at(at(10, 0));
+            assert_eq!(stopwatch.stop_at(at(12, 500_000_000)), Duration::of_ms(2, 500));
+
+            stopwatch.start_at(at(20, 0));
+            stopwatch.start_at(at(21, 0));
+            assert_eq!(stopwatch.stop_at(at(21, 250_000_000)), Duration::of_ms(3, 750));
+            assert_eq!(stopwatch.stop_at(at(30, 0)), Duration::of_ms(3, 750));
+            assert_eq!(stopwatch.elapsed(), Duration::of_ms(3, 750));
+        }
+
+        #[test]
+        fn sub_millisecond_runs_add_up() {
+            let mut stopwatch = Stopwatch::new();
+            for second in 0 .. 4 {
+                stopwatch.start_at(at(second, 0));
+                let _ = stopwatch.stop_at(at(second, 600_000));
+            }
+            assert_eq!(stopwatch.elapsed(), Duration::of_ns(0, 2_400_000));
+        }
+
+        #[test]
+        fn laps() {
+            let mut stopwatch = Stopwatch::new();
+            stopwatch.start_at(at(0, 0));
+            assert_eq!(stopwatch.lap_at(at(1, 0)), Duration::of(1));
+
+            let _ = stopwatch.stop_at(at(2, 0));
+            stopwatch.start_at(at(10, 0));
+            assert_eq!(stopwatch.lap_at(at(10, 500_000_000)), Duration::of_ms(1, 500));
+            assert_eq!(stopwatch.laps(), &[ Duration::of(1), Duration::of_ms(1, 500) ]);
+
+            stopwatch.reset();
+            assert!(stopwatch.laps().is_empty());
+            assert!(!stopwatch.is_running());
+        }
+
+        #[test]
+        fn real_clock() {
+            let mut stopwatch = Stopwatch::start_new();
+            assert!(stopwatch.is_running());
+            let lap = stopwatch.lap();
+            assert!(stopwatch.stop() >= lap);
+        }
+    }
+    mod deadlines {
+        use super::*;
+        use std::time::Duration as StdDuration;
+
+        #[test]
+        fn remaining() {
+            let deadline = Deadline::at(at(10, 250_000_000));
+            assert_eq!(deadline.remaining_nanoseconds(at(8, 500_000_000)), 1_750_000_000);
+            assert_eq!(deadline.remaining_nanoseconds(at(10, 250_000_000)), 0);
+            assert_eq!(deadline.remaining_nanoseconds(at(11, 0)), 0);
+        }
+
+        #[test]
+        fn far_apart() {
+            let deadline = Deadline::at(at(i64::max_value(), 999_999_999));
+            assert_eq!(deadline.remaining_nanoseconds(at(i64::min_value(), 0)), i64::max_value() as i128 * 1_000_000_000 + 999_999_999);
+            assert_eq!(Deadline::at(at(i64::min_value(), 0)).remaining_nanoseconds(at(i64::max_value(), 0)), 0);
+        }
+
+        #[test]
+        fn far_future() {
+            let deadline = Deadline::after(Duration::of(20_000_000_000));
+            assert!(!deadline.is_expired());
+            assert!(deadline.remaining() > Duration::of(19_999_999_000));
+            assert!(deadline.remaining_std() > StdDuration::from_secs(19_999_999_000));
+
+            let deadline = Deadline::after(Duration::of(i64::max_value()));
+            assert!(!deadline.is_expired());
+            assert_eq!(deadline.instant(), at(i64::max_value(), 999_999_999));
+            assert!(Deadline::after(Duration::of(i64::min_value())).is_expired());
+        }
+
+        #[test]
+        fn after() {
+            let deadline = Deadline::after(Duration::of(60));
+            assert!(!deadline.is_expired());
+            assert!(deadline.remaining() <= Duration::of(60));
+            assert!(deadline.remaining_std() <= StdDuration::from_secs(60));
+            assert!(deadline.remaining_std() > StdDuration::from_secs(50));
+        }
+
+        #[test]
+        fn expired() {
+            let deadline = Deadline::after(Duration::of(-1));
+            assert!(deadline.is_expired());
+            assert_eq!(deadline.remaining(), Duration::zero());
+            assert_eq!(deadline.remaining_std(), StdDuration::new(0, 0));
+        }
+
+        #[test]
+        fn at_instant() {
+            let deadline = Deadline::at_instant(Instant::now() + Duration::of(60));
+            assert!(deadline.remaining() > Duration::of(50));
+            assert!(Deadline::at_instant(Instant::at(0)).is_expired());
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Conversions to and from NTP timestamps.
+//!
+//! An NTP timestamp is a 64-bit number: the top 32 bits count the seconds
+//! since midnight UTC on the 1st of January, 1900, and the bottom 32 bits
+//! are a binary fraction of a second. Like Unix time, it does not count
+//! leap seconds.
+//!
+//! Thirty-two bits of seconds only last about 136 years, so the count wraps
+//! around at 06:28:16 UTC on the 7th of February, 2036, which starts NTP
+//! **era** 1. A timestamp on its own doesn’t say which era it’s in, so
+//! converting one to an instant needs the era to be worked out somehow:
+//!
+//! - `to_instant_in_era` uses a known era number;
+//! - `to_instant_near` picks whichever era puts the result within 68 years
+//!   of another instant, such as the current time;
+//! - `to_instant` follows RFC 4330, and treats timestamps with the top bit
+//!   set as being in era 0 (1968 to 2036), and the rest as being in era 1
+//!   (2036 to 2104).
+
+use instant::Instant;
+
+
+/// The Unix timestamp of the NTP epoch, 1900-01-01T00:00:00 UTC.
+const NTP_EPOCH: i64 = -2208988800;
+
+/// The number of seconds in an NTP era.
+const SECONDS_IN_ERA: i64 = 1 << 32;
+
+
+/// An **NTP timestamp** is a point on the timeline within an NTP era,
+/// measured in seconds and fractions of a second since the start of that
+/// era.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct NtpTimestamp {
+    seconds: u32,
+    fraction: u32,
+}
+
+impl NtpTimestamp {
+
+    /// Creates a new NTP timestamp from a number of seconds since the start
+    /// of its era, and a fraction of a second in units of 2⁻³² seconds.
+    pub fn new(seconds: u32, fraction: u32) -> NtpTimestamp {
+        NtpTimestamp { seconds: seconds, fraction: fraction }
+    }
+
+    /// Creates a new NTP timestamp from its 64-bit representation, as sent
+    /// over the wire.
+    pub fn from_bits(bits: u64) -> NtpTimestamp {
+        NtpTimestamp::new((bits >> 32) as u32, bits as u32)
+    }
+
+    /// Converts a UTC instant to an NTP timestamp, discarding its era.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    /// use datetime::ntp::NtpTimestamp;
+    ///
+    /// let ntp = NtpTimestamp::from_instant(Instant::at_ms(0, 500));
+    /// assert_eq!(ntp, NtpTimestamp::new(2208988800, 1 << 31));
+    /// ```
+    pub fn from_instant(instant: Instant) -> NtpTimestamp {
+        // Subtracting the epoch can overflow for instants near the end of
+        // time, but wrapping around 2⁶⁴ doesn’t change the remainder when
+        // dividing by 2³², so the seconds into the era are still right.
+        let seconds = instant.seconds().wrapping_sub(NTP_EPOCH).rem_euclid(SECONDS_IN_ERA);
+
+        // Round the fraction up, so that converting it back to milliseconds by
+        // rounding down gives the same number again.
+        let fraction = (((instant.milliseconds() as u64) << 32) + 999) / 1000;
+
+        NtpTimestamp::new(seconds as u32, fraction as u32)
+    }
+
+    /// Returns the number of the NTP era that the given instant is in. Era
+    /// 0 began in 1900, era 1 begins in 2036, and instants before 1900 are
+    /// in negative eras.
+    pub fn era_of(instant: Instant) -> i64 {
+        (instant.seconds() as i128 - NTP_EPOCH as i128).div_euclid(SECONDS_IN_ERA as i128) as i64
+    }
+
+    /// Converts this timestamp to a UTC instant, assuming that it’s in the
+    /// given era.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the instant is outside the range that can be represented,
+    /// which only happens for eras further than two billion or so from
+    /// this one. Use `checked_to_instant_in_era` to handle that case
+    /// instead.
+    pub fn to_instant_in_era(&self, era: i64) -> Instant {
+        self.checked_to_instant_in_era(era).expect("instant out of range")
+    }
+
+    /// Converts this timestamp to a UTC instant, assuming that it’s in the
+    /// given era, or returns `None` if the instant is outside the range
+    /// that can be represented.
+    pub fn checked_to_instant_in_era(&self, era: i64) -> Option<Instant> {
+        let seconds = NTP_EPOCH as i128 + era as i128 * SECONDS_IN_ERA as i128 + self.seconds as i128;
+        if seconds < i64::min_value() as i128 || seconds > i64::max_value() as i128 {
+            None
+        }
+        else {
+            Some(Instant::at_ms(seconds as i64, self.milliseconds()))
+        }
+    }
+
+    /// Converts this timestamp to a UTC instant, choosing the era that puts
+    /// it closest to the given instant. Near the ends of the range that can
+    /// be represented, the closest era that is in range gets used instead.
+    ///
+    /// ```rust
+    /// use datetime::Instant;
+    /// use datetime::ntp::NtpTimestamp;
+    ///
+    /// let ntp = NtpTimestamp::new(1000, 0);
+    /// let instant = ntp.to_instant_near(Instant::at(2085978496));
+    /// assert_eq!(NtpTimestamp::era_of(instant), 1);
+    /// ```
+    pub fn to_instant_near(&self, pivot: Instant) -> Instant {
+        let pivot_seconds = NtpTimestamp::from_instant(pivot).seconds;
+        let difference = self.seconds.wrapping_sub(pivot_seconds) as i32;
+
+        let seconds = pivot.seconds() as i128 + difference as i128;
+        let seconds = if seconds > i64::max_value() as i128 { seconds - SECONDS_IN_ERA as i128 }
+                 else if seconds < i64::min_value() as i128 { seconds + SECONDS_IN_ERA as i128 }
+                 else { seconds };
+
+        Instant::at_ms(seconds as i64, self.milliseconds())
+    }
+
+    /// Converts this timestamp to a UTC instant, between 1968 and 2104,
+    /// using the top bit of the seconds to decide on the era.
+    pub fn to_instant(&self) -> Instant {
+        let era = if self.seconds & 0x8000_0000 != 0 { 0 } else { 1 };
+        self.to_instant_in_era(era)
+    }
+
+    /// Returns the number of seconds since the start of this timestamp’s
+    /// era.
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    /// Returns the fraction of a second, in units of 2⁻³² seconds.
+    pub fn fraction(&self) -> u32 {
+        self.fraction
+    }
+
+    /// Returns the fraction of a second as a number of milliseconds,
+    /// rounded down.
+    pub fn milliseconds(&self) -> i16 {
+        ((self.fraction as u64 * 1000) >> 32) as i16
+    }
+
+    /// Returns the 64-bit representation of this timestamp, as sent over
+    /// the wire.
+    pub fn to_bits(&self) -> u64 {
+        (self.seconds as u64) << 32 | self.fraction as u64
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use instant::Instant;
+
+    /// The Unix timestamp of the start of NTP era 1.
+    const ERA_1: i64 = 2085978496;
+
+    #[test]
+    fn unix_epoch() {
+        let ntp = NtpTimestamp::from_instant(Instant::at(0));
+        assert_eq!(ntp, NtpTimestamp::new(2208988800, 0));
+        assert_eq!(ntp.to_instant(), Instant::at(0));
+    }
+
+    #[test]
+    fn bits() {
+        let ntp = NtpTimestamp::from_bits(0x83AA7E80_80000000);
+        assert_eq!(ntp, NtpTimestamp::new(2208988800, 0x80000000));
+        assert_eq!(ntp.to_bits(), 0x83AA7E80_80000000);
+    }
+
+    #[test]
+    fn milliseconds_round_trip() {
+        for ms in 0 .. 1000 {
+            let instant = Instant::at_ms(1_500_000_000, ms);
+            assert_eq!(NtpTimestamp::from_instant(instant).to_instant(), instant);
+        }
+    }
+
+    #[test]
+    fn era_rollover() {
+        let ntp = NtpTimestamp::from_instant(Instant::at(ERA_1));
+        assert_eq!(ntp, NtpTimestamp::new(0, 0));
+        assert_eq!(ntp.to_instant(), Instant::at(ERA_1));
+    }
+
+    #[test]
+    fn eras() {
+        assert_eq!(NtpTimestamp::era_of(Instant::at(NTP_EPOCH)), 0);
+        assert_eq!(NtpTimestamp::era_of(Instant::at(ERA_1 - 1)), 0);
+        assert_eq!(NtpTimestamp::era_of(Instant::at(ERA_1)), 1);
+        assert_eq!(NtpTimestamp::era_of(Instant::at(NTP_EPOCH - 1)), -1);
+    }
+
+    #[test]
+    fn in_era() {
+        let ntp = NtpTimestamp::new(10, 0);
+        assert_eq!(ntp.to_instant_in_era(0), Instant::at(NTP_EPOCH + 10));
+        assert_eq!(ntp.to_instant_in_era(1), Instant::at(ERA_1 + 10));
+    }
+
+    #[test]
+    fn near_pivot_across_rollover() {
+        let before = NtpTimestamp::from_instant(Instant::at(ERA_1 - 5));
+        let after = NtpTimestamp::from_instant(Instant::at(ERA_1 + 5));
+        assert_eq!(before.to_instant_near(Instant::at(ERA_1 + 5)), Instant::at(ERA_1 - 5));
+        assert_eq!(after.to_instant_near(Instant::at(ERA_1 - 5)), Instant::at(ERA_1 + 5));
+    }
+
+    #[test]
+    fn ends_of_the_range() {
+        for &instant in &[ Instant::at(i64::max_value()), Instant::at(i64::min_value()) ] {
+            let ntp = NtpTimestamp::from_instant(instant);
+            let era = NtpTimestamp::era_of(instant);
+            assert_eq!(ntp.to_instant_in_era(era), instant);
+            assert_eq!(ntp.to_instant_near(instant), instant);
+        }
+
+        assert_eq!(NtpTimestamp::era_of(Instant::at(i64::max_value())), ((i64::max_value() as i128 - NTP_EPOCH as i128) >> 32) as i64);
+        assert_eq!(NtpTimestamp::from_instant(Instant::at(i64::max_value())).seconds(), (i64::max_value() as i128 - NTP_EPOCH as i128) as u32);
+    }
+
+    #[test]
+    fn near_the_ends_of_the_range() {
+        let ntp = NtpTimestamp::from_instant(Instant::at(i64::max_value() - 10));
+        let pivot = Instant::at(i64::max_value() - 5);
+        assert_eq!(NtpTimestamp::new(ntp.seconds().wrapping_add(20), 0).to_instant_near(pivot), Instant::at(i64::max_value() - SECONDS_IN_ERA + 10));
+        assert_eq!(ntp.to_instant_near(pivot), Instant::at(i64::max_value() - 10));
+    }
+
+    #[test]
+    fn eras_out_of_range() {
+        let ntp = NtpTimestamp::new(0, 0);
+        assert_eq!(ntp.checked_to_instant_in_era(i64::max_value()), None);
+        assert_eq!(ntp.checked_to_instant_in_era(i64::min_value()), None);
+        assert_eq!(ntp.checked_to_instant_in_era(1), Some(Instant::at(ERA_1)));
+    }
+
+    #[test]
+    fn before_1968_is_next_era() {
+        let ntp = NtpTimestamp::from_instant(Instant::at(-100_000_000));
+        assert_eq!(ntp.to_instant(), Instant::at(-100_000_000 + SECONDS_IN_ERA));
+        assert_eq!(ntp.to_instant_in_era(0), Instant::at(-100_000_000));
+    }
+}
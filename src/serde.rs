@@ -0,0 +1,671 @@
+//! Serialization and deserialization with Serde, enabled by the `serde`
+//! feature.
+//!
+//! Every value type gets serialized as a string in its ISO 8601 format, as
+//! produced by its `Display` implementation and read back with its
+//! `FromStr` one: dates become `"2015-06-26"`, instants become
+//! `"2015-06-26T14:30:00Z"`, durations become `"PT1H30M"`, and so on.
+//! Months and weekdays become their English names, such as `"June"`.
+//!
+//! Local date-times and instants are the exception: for formats that are
+//! not human-readable, such as bincode, they get serialized as a tuple of a
+//! number of seconds since the Unix epoch and a number of nanoseconds,
+//! which is twelve bytes in bincode. These are the same two numbers that
+//! their `to_bytes` methods encode.
+//!
+//! Zoned date-times are not included, as they borrow their time zone
+//! rather than owning it; convert them to an offset date-time first.
+//!
+//! For talking to APIs that use integer Unix timestamps instead, the
+//! `ts_seconds`, `ts_milliseconds`, and `ts_nanoseconds` modules can be
+//! used with Serde’s `with` attribute on an `Instant` field:
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "datetime::serde::ts_seconds")]
+//!     created_at: Instant,
+//! }
+//! ```
+//!
+//! For CBOR, as used by COSE and CWT tokens, the `cbor_tag0` and
+//! `cbor_tag1` modules produce the contents of the two standard date-time
+//! tags: tag 0 holds an RFC 3339 string, and tag 1 holds a number of
+//! seconds since the epoch, which may be fractional. The tag itself is
+//! written by the CBOR library, such as with `ciborium::tag::Required`.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serdelib::{Serialize, Serializer, Deserialize, Deserializer};
+use serdelib::ser::SerializeTuple;
+use serdelib::de::{self, Visitor, SeqAccess, Unexpected};
+
+use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday};
+use cal::offset::{Offset, OffsetDateTime};
+use duration::Duration;
+use instant::Instant;
+use util::{join_bytes, split_bytes};
+
+
+/// A visitor that deserializes a string into any type that can be parsed
+/// from one, turning the parse error into a Serde error.
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+where T: FromStr, T::Err: fmt::Display {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        value.parse().map_err(|e| E::custom(format_args!("invalid {} {:?}: {}", self.expecting, value, e)))
+    }
+}
+
+/// Implements `Serialize` and `Deserialize` for a type using its `Display`
+/// and `FromStr` implementations.
+macro_rules! iso_string {
+    ($type:ty, $expecting:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$type, D::Error> {
+                deserializer.deserialize_str(FromStrVisitor { expecting: $expecting, marker: PhantomData })
+            }
+        }
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` for a type that has a binary
+/// encoding as well as a string one. Formats that are not human-readable,
+/// such as bincode, get a tuple of the seconds since the Unix epoch and the
+/// nanoseconds so far that second; every other format gets the string.
+macro_rules! iso_string_or_binary {
+    ($type:ty, $expecting:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    return serializer.collect_str(self);
+                }
+
+                let (seconds, nanoseconds) = split_bytes(self.to_bytes()).unwrap();
+                let mut tuple = try!(serializer.serialize_tuple(2));
+                try!(tuple.serialize_element(&seconds));
+                try!(tuple.serialize_element(&nanoseconds));
+                tuple.end()
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$type, D::Error> {
+                struct BinaryVisitor;
+
+                impl<'de> Visitor<'de> for BinaryVisitor {
+                    type Value = $type;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("a pair of seconds and nanoseconds")
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$type, A::Error> {
+                        let seconds: i64 = try!(try!(seq.next_element()).ok_or_else(|| de::Error::invalid_length(0, &self)));
+                        let nanoseconds: u32 = try!(try!(seq.next_element()).ok_or_else(|| de::Error::invalid_length(1, &self)));
+                        <$type>::from_bytes(join_bytes(seconds, nanoseconds))
+                            .ok_or_else(|| de::Error::invalid_value(Unexpected::Unsigned(nanoseconds as u64), &"fewer than a billion nanoseconds"))
+                    }
+                }
+
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(FromStrVisitor { expecting: $expecting, marker: PhantomData })
+                }
+                else {
+                    deserializer.deserialize_tuple(2, BinaryVisitor)
+                }
+            }
+        }
+    };
+}
+
+iso_string!(LocalDate,      "an ISO 8601 date");
+iso_string!(LocalTime,      "an ISO 8601 time");
+iso_string!(Offset,         "an ISO 8601 UTC offset");
+iso_string!(OffsetDateTime, "an ISO 8601 date-time with an offset");
+iso_string!(Duration,       "an ISO 8601 duration");
+
+iso_string_or_binary!(LocalDateTime, "an ISO 8601 date-time");
+iso_string_or_binary!(Instant,       "an ISO 8601 date-time in UTC");
+
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March",     "April",   "May",      "June",
+    "July",    "August",   "September", "October", "November", "December",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday",
+];
+
+impl Serialize for Month {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(MONTH_NAMES[self.months_from_january()])
+    }
+}
+
+impl<'de> Deserialize<'de> for Month {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Month, D::Error> {
+        struct MonthVisitor;
+
+        impl<'de> Visitor<'de> for MonthVisitor {
+            type Value = Month;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the English name of a month")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Month, E> {
+                match MONTH_NAMES.iter().position(|&name| name == value) {
+                    Some(index) => Ok(Month::from_zero(index as i8).unwrap()),
+                    None        => Err(E::invalid_value(Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(MonthVisitor)
+    }
+}
+
+impl Serialize for Weekday {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(WEEKDAY_NAMES[*self as usize])
+    }
+}
+
+impl<'de> Deserialize<'de> for Weekday {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Weekday, D::Error> {
+        struct WeekdayVisitor;
+
+        impl<'de> Visitor<'de> for WeekdayVisitor {
+            type Value = Weekday;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the English name of a day of the week")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Weekday, E> {
+                match WEEKDAY_NAMES.iter().position(|&name| name == value) {
+                    Some(index) => Ok(Weekday::from_zero(index as i8).unwrap()),
+                    None        => Err(E::invalid_value(Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(WeekdayVisitor)
+    }
+}
+
+
+/// Converts an instant into a whole number of units since the Unix epoch,
+/// where there are the given number of units in a second, rounding down.
+/// Returns `None` if the number would be too big to fit in an `i64`.
+fn to_timestamp(instant: &Instant, units_per_second: i64) -> Option<i64> {
+    let sub_second = instant.nanoseconds() as i64 * units_per_second / 1_000_000_000;
+    instant.seconds().checked_mul(units_per_second).and_then(|units| units.checked_add(sub_second))
+}
+
+/// Converts a number of units since the Unix epoch back into an instant.
+fn from_timestamp(timestamp: i64, units_per_second: i64) -> Instant {
+    let seconds = timestamp.div_euclid(units_per_second);
+    let nanoseconds = timestamp.rem_euclid(units_per_second) * 1_000_000_000 / units_per_second;
+    Instant::at_ns(seconds, nanoseconds as i32)
+}
+
+fn serialize_timestamp<S: Serializer>(instant: &Instant, units_per_second: i64, serializer: S) -> Result<S::Ok, S::Error> {
+    use serdelib::ser::Error;
+
+    match to_timestamp(instant, units_per_second) {
+        Some(timestamp) => serializer.serialize_i64(timestamp),
+        None            => Err(S::Error::custom(format_args!("instant {} is too far from the epoch to be a timestamp", instant))),
+    }
+}
+
+/// A visitor that deserializes an integer timestamp into an instant.
+struct TimestampVisitor {
+    units_per_second: i64,
+    expecting: &'static str,
+}
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = Instant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Instant, E> {
+        Ok(from_timestamp(value, self.units_per_second))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Instant, E> {
+        if value > i64::max_value() as u64 {
+            Err(E::invalid_value(Unexpected::Unsigned(value), &self))
+        }
+        else {
+            Ok(from_timestamp(value as i64, self.units_per_second))
+        }
+    }
+}
+
+/// Serializes instants as a whole number of seconds since the Unix epoch,
+/// discarding any milliseconds.
+pub mod ts_seconds {
+    use serdelib::{Serializer, Deserializer};
+    use instant::Instant;
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_timestamp(instant, 1, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        deserializer.deserialize_i64(super::TimestampVisitor { units_per_second: 1, expecting: "a Unix timestamp in seconds" })
+    }
+}
+
+/// Serializes instants as a whole number of milliseconds since the Unix
+/// epoch, which is the format used by JavaScript.
+pub mod ts_milliseconds {
+    use serdelib::{Serializer, Deserializer};
+    use instant::Instant;
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_timestamp(instant, 1_000, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        deserializer.deserialize_i64(super::TimestampVisitor { units_per_second: 1_000, expecting: "a Unix timestamp in milliseconds" })
+    }
+}
+
+/// Serializes instants as a whole number of nanoseconds since the Unix
+/// epoch. Only instants within about 292 years of 1970 can be serialized.
+pub mod ts_nanoseconds {
+    use serdelib::{Serializer, Deserializer};
+    use instant::Instant;
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_timestamp(instant, 1_000_000_000, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        deserializer.deserialize_i64(super::TimestampVisitor { units_per_second: 1_000_000_000, expecting: "a Unix timestamp in nanoseconds" })
+    }
+}
+
+
+/// Serializes instants as the contents of CBOR tag 0: an RFC 3339 string
+/// in UTC, such as `"2013-03-21T20:04:00Z"`.
+pub mod cbor_tag0 {
+    use std::marker::PhantomData;
+
+    use serdelib::{Serializer, Deserializer};
+    use instant::Instant;
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(instant)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        deserializer.deserialize_str(super::FromStrVisitor { expecting: "an RFC 3339 date-time", marker: PhantomData })
+    }
+}
+
+/// Serializes instants as the contents of CBOR tag 1: a number of seconds
+/// since the Unix epoch. Whole seconds are written as integers, and
+/// anything else as a floating-point number; both are accepted when
+/// deserializing, with floats rounded to the nearest millisecond.
+pub mod cbor_tag1 {
+    use std::fmt;
+
+    use serdelib::{Serializer, Deserializer};
+    use serdelib::de::{self, Visitor, Unexpected};
+    use instant::Instant;
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        if instant.nanoseconds() == 0 {
+            serializer.serialize_i64(instant.seconds())
+        }
+        else {
+            serializer.serialize_f64(instant.seconds() as f64 + instant.nanoseconds() as f64 / 1_000_000_000.0)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        deserializer.deserialize_any(EpochVisitor)
+    }
+
+    struct EpochVisitor;
+
+    impl<'de> Visitor<'de> for EpochVisitor {
+        type Value = Instant;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a number of seconds since the Unix epoch")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Instant, E> {
+            Ok(Instant::at(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Instant, E> {
+            if value > i64::max_value() as u64 {
+                Err(E::invalid_value(Unexpected::Unsigned(value), &self))
+            }
+            else {
+                Ok(Instant::at(value as i64))
+            }
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<Instant, E> {
+            let milliseconds = (value * 1000.0).round();
+
+            if !milliseconds.is_finite() || milliseconds.abs() >= i64::max_value() as f64 {
+                Err(E::invalid_value(Unexpected::Float(value), &self))
+            }
+            else {
+                Ok(Instant::from_unix_millis(milliseconds as i64))
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    extern crate serde_json;
+
+    pub use cal::datetime::{LocalDate, LocalTime, LocalDateTime, Month, Weekday};
+    pub use cal::offset::{Offset, OffsetDateTime};
+    pub use duration::Duration;
+    pub use instant::Instant;
+    pub use self::serde_json::{to_string, from_str};
+
+    mod serialize {
+        use super::*;
+
+        #[test]
+        fn date() {
+            let date = LocalDate::ymd(1969, Month::July, 20).unwrap();
+            assert_eq!(to_string(&date).unwrap(), r#""1969-07-20""#);
+        }
+
+        #[test]
+        fn time() {
+            let time = LocalTime::hms_ms(20, 17, 40, 500).unwrap();
+            assert_eq!(to_string(&time).unwrap(), r#""20:17:40.500""#);
+        }
+
+        #[test]
+        fn datetime() {
+            let datetime = LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms(20, 17, 40).unwrap());
+            assert_eq!(to_string(&datetime).unwrap(), r#""1969-07-20T20:17:40""#);
+        }
+
+        #[test]
+        fn offset_datetime() {
+            let datetime = LocalDateTime::new(LocalDate::ymd(1969, Month::July, 20).unwrap(), LocalTime::hms(20, 17, 40).unwrap());
+            let datetime = Offset::of_hours_and_minutes(-5, 0).unwrap().transform_date(datetime);
+            assert_eq!(to_string(&datetime).unwrap(), r#""1969-07-20T20:17:40-05:00""#);
+        }
+
+        #[test]
+        fn instant() {
+            assert_eq!(to_string(&Instant::at(1_000_000_000)).unwrap(), r#""2001-09-09T01:46:40Z""#);
+        }
+
+        #[test]
+        fn duration() {
+            assert_eq!(to_string(&Duration::of(5400)).unwrap(), r#""PT1H30M""#);
+        }
+
+        #[test]
+        fn month_and_weekday() {
+            assert_eq!(to_string(&Month::July).unwrap(), r#""July""#);
+            assert_eq!(to_string(&Weekday::Sunday).unwrap(), r#""Sunday""#);
+        }
+    }
+
+    mod deserialize {
+        use super::*;
+
+        #[test]
+        fn date() {
+            assert_eq!(from_str::<LocalDate>(r#""1969-07-20""#).unwrap(), LocalDate::ymd(1969, Month::July, 20).unwrap());
+        }
+
+        #[test]
+        fn offset_datetime() {
+            let datetime: OffsetDateTime = from_str(r#""1969-07-20T20:17:40-05:00""#).unwrap();
+            assert_eq!(datetime.to_string(), "1969-07-20T20:17:40-05:00");
+        }
+
+        #[test]
+        fn instant() {
+            assert_eq!(from_str::<Instant>(r#""2001-09-09T03:46:40+02:00""#).unwrap(), Instant::at(1_000_000_000));
+        }
+
+        #[test]
+        fn duration() {
+            assert_eq!(from_str::<Duration>(r#""PT0.250S""#).unwrap(), Duration::of_ms(0, 250));
+        }
+
+        #[test]
+        fn month_and_weekday() {
+            assert_eq!(from_str::<Month>(r#""December""#).unwrap(), Month::December);
+            assert_eq!(from_str::<Weekday>(r#""Saturday""#).unwrap(), Weekday::Saturday);
+        }
+
+        #[test]
+        fn short_fraction() {
+            let time = LocalTime::hms_ms(20, 17, 40, 500).unwrap();
+            assert_eq!(from_str::<LocalTime>(r#""20:17:40.5""#).unwrap(), time);
+        }
+
+        #[test]
+        fn too_precise() {
+            assert!(from_str::<LocalTime>(r#""20:17:40.1234567890""#).is_err());
+        }
+
+        #[test]
+        fn invalid_date() {
+            assert!(from_str::<LocalDate>(r#""1969-02-30""#).is_err());
+        }
+
+        #[test]
+        fn invalid_month() {
+            assert!(from_str::<Month>(r#""Smarch""#).is_err());
+        }
+
+        #[test]
+        fn not_a_string() {
+            assert!(from_str::<Instant>("1000000000").is_err());
+        }
+    }
+
+    mod round_trip {
+        use super::*;
+
+        #[test]
+        fn every_type() {
+            let date = LocalDate::ymd(2015, Month::December, 31).unwrap();
+            let time = LocalTime::hms_ms(23, 59, 59, 999).unwrap();
+            let datetime = LocalDateTime::new(date, time);
+
+            assert_eq!(from_str::<LocalDate>(&to_string(&date).unwrap()).unwrap(), date);
+            assert_eq!(from_str::<LocalTime>(&to_string(&time).unwrap()).unwrap(), time);
+            assert_eq!(from_str::<LocalDateTime>(&to_string(&datetime).unwrap()).unwrap(), datetime);
+            assert_eq!(from_str::<Instant>(&to_string(&Instant::at_ms(-1, 5)).unwrap()).unwrap(), Instant::at_ms(-1, 5));
+            assert_eq!(from_str::<Duration>(&to_string(&Duration::of_ms(-3, 1)).unwrap()).unwrap(), Duration::of_ms(-3, 1));
+        }
+
+        #[test]
+        fn nanoseconds() {
+            let datetime = LocalDateTime::at_ns(1_000_000_000, 123_456_789);
+            assert_eq!(to_string(&datetime).unwrap(), r#""2001-09-09T01:46:40.123456789""#);
+            assert_eq!(from_str::<LocalDateTime>(&to_string(&datetime).unwrap()).unwrap(), datetime);
+
+            let time = LocalTime::hms_ns(1, 46, 40, 123_456_000).unwrap();
+            assert_eq!(to_string(&time).unwrap(), r#""01:46:40.123456""#);
+            assert_eq!(from_str::<LocalTime>(&to_string(&time).unwrap()).unwrap(), time);
+        }
+
+        #[test]
+        fn offsets() {
+            for offset in &[ Offset::utc(), Offset::of_hours_and_minutes(5, 30).unwrap(), Offset::of_hours_and_minutes(-9, -30).unwrap() ] {
+                assert_eq!(from_str::<Offset>(&to_string(offset).unwrap()).unwrap(), *offset);
+            }
+        }
+    }
+
+    mod timestamps {
+        use super::*;
+        use super::serde_json::{Serializer, Deserializer};
+        use serde::{ts_seconds, ts_milliseconds, ts_nanoseconds};
+
+        pub fn serialized<F>(serialize: F) -> String
+        where F: FnOnce(&mut Serializer<Vec<u8>>) -> Result<(), serde_json::Error> {
+            let mut serializer = Serializer::new(Vec::new());
+            serialize(&mut serializer).unwrap();
+            String::from_utf8(serializer.into_inner()).unwrap()
+        }
+
+        #[test]
+        fn seconds() {
+            let instant = Instant::at_ms(1_000_000_000, 999);
+            assert_eq!(serialized(|s| ts_seconds::serialize(&instant, s)), "1000000000");
+            assert_eq!(ts_seconds::deserialize(&mut Deserializer::from_str("1000000000")).unwrap(), Instant::at(1_000_000_000));
+        }
+
+        #[test]
+        fn milliseconds() {
+            let instant = Instant::at_ms(1_000_000_000, 250);
+            assert_eq!(serialized(|s| ts_milliseconds::serialize(&instant, s)), "1000000000250");
+            assert_eq!(ts_milliseconds::deserialize(&mut Deserializer::from_str("1000000000250")).unwrap(), instant);
+        }
+
+        #[test]
+        fn nanoseconds() {
+            let instant = Instant::at_ns(1_000_000_000, 250_999_999);
+            assert_eq!(serialized(|s| ts_nanoseconds::serialize(&instant, s)), "1000000000250999999");
+            assert_eq!(ts_nanoseconds::deserialize(&mut Deserializer::from_str("1000000000250999999")).unwrap(), instant);
+            assert_eq!(serialized(|s| ts_milliseconds::serialize(&instant, s)), "1000000000250");
+        }
+
+        #[test]
+        fn before_epoch() {
+            let instant = Instant::at_ms(-2, 500);
+            assert_eq!(serialized(|s| ts_milliseconds::serialize(&instant, s)), "-1500");
+            assert_eq!(ts_milliseconds::deserialize(&mut Deserializer::from_str("-1500")).unwrap(), instant);
+            assert_eq!(ts_nanoseconds::deserialize(&mut Deserializer::from_str("-1")).unwrap(), Instant::at_ns(-1, 999_999_999));
+        }
+
+        #[test]
+        fn nanoseconds_overflow() {
+            let instant = Instant::at(10_000_000_000);
+            let mut serializer = Serializer::new(Vec::new());
+            assert!(ts_nanoseconds::serialize(&instant, &mut serializer).is_err());
+        }
+
+        #[test]
+        fn not_a_number() {
+            assert!(ts_seconds::deserialize(&mut Deserializer::from_str(r#""1000""#)).is_err());
+        }
+    }
+
+    mod cbor {
+        use super::*;
+        use super::timestamps::serialized;
+        use super::serde_json::Deserializer;
+        use serde::{cbor_tag0, cbor_tag1};
+
+        #[test]
+        fn tag0() {
+            let instant = Instant::at(1363896240);
+            assert_eq!(serialized(|s| cbor_tag0::serialize(&instant, s)), r#""2013-03-21T20:04:00Z""#);
+            assert_eq!(cbor_tag0::deserialize(&mut Deserializer::from_str(r#""2013-03-21T20:04:00Z""#)).unwrap(), instant);
+        }
+
+        #[test]
+        fn tag0_with_offset() {
+            let instant = cbor_tag0::deserialize(&mut Deserializer::from_str(r#""2013-03-21T22:04:00+02:00""#)).unwrap();
+            assert_eq!(instant, Instant::at(1363896240));
+        }
+
+        #[test]
+        fn tag1_integer() {
+            let instant = Instant::at(1363896240);
+            assert_eq!(serialized(|s| cbor_tag1::serialize(&instant, s)), "1363896240");
+            assert_eq!(cbor_tag1::deserialize(&mut Deserializer::from_str("1363896240")).unwrap(), instant);
+        }
+
+        #[test]
+        fn tag1_float() {
+            let instant = Instant::at_ms(1363896240, 500);
+            assert_eq!(serialized(|s| cbor_tag1::serialize(&instant, s)), "1363896240.5");
+            assert_eq!(cbor_tag1::deserialize(&mut Deserializer::from_str("1363896240.5")).unwrap(), instant);
+        }
+
+        #[test]
+        fn tag1_before_epoch() {
+            assert_eq!(cbor_tag1::deserialize(&mut Deserializer::from_str("-1.25")).unwrap(), Instant::at_ms(-2, 750));
+        }
+
+        #[test]
+        fn tag1_not_a_number() {
+            assert!(cbor_tag1::deserialize(&mut Deserializer::from_str(r#""1000""#)).is_err());
+        }
+    }
+
+    mod binary {
+        extern crate bincode;
+
+        use super::*;
+        use self::bincode::{serialize, deserialize};
+
+        #[test]
+        fn datetime_is_twelve_bytes() {
+            let datetime = LocalDateTime::at_ns(1_234_567_890, 123_456_789);
+            let bytes = serialize(&datetime).unwrap();
+            assert_eq!(bytes.len(), 12);
+            assert_eq!(deserialize::<LocalDateTime>(&bytes).unwrap(), datetime);
+        }
+
+        #[test]
+        fn instant_round_trip() {
+            let instant = Instant::at_ms(-2, 500);
+            let bytes = serialize(&instant).unwrap();
+            assert_eq!(bytes, [254, 255, 255, 255, 255, 255, 255, 255, 0, 101, 205, 29]);
+            assert_eq!(deserialize::<Instant>(&bytes).unwrap(), instant);
+        }
+
+        #[test]
+        fn invalid_nanoseconds() {
+            let bytes = [0, 0, 0, 0, 0, 0, 0, 0, 0, 202, 154, 59];
+            assert!(deserialize::<Instant>(&bytes).is_err());
+        }
+
+        #[test]
+        fn strings_stay_human_readable() {
+            assert_eq!(to_string(&Instant::at(0)).unwrap(), r#""1970-01-01T00:00:00Z""#);
+        }
+    }
+}
@@ -1,8 +1,12 @@
 //! System-dependent functions, or anything that this library is unable to
 //! do without help from the OS.
 
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm")))]
 use std::ffi::OsStr;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm")))]
 use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+use std::time::{Duration as StdDuration, SystemTime, UNIX_EPOCH};
 
 extern crate libc;
 
@@ -12,36 +16,280 @@ extern {
     fn gettimeofday(tp: *mut libc::timeval, tzp: *mut libc::timezone) -> libc::c_int;
 }
 
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+#[repr(C)]
+struct MachTimebaseInfo {
+    numer: u32,
+    denom: u32,
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+extern {
+    fn mach_absolute_time() -> u64;
+    fn mach_timebase_info(info: *mut MachTimebaseInfo) -> libc::c_int;
+}
+
 #[cfg(all(unix, not(target_os = "macos"), not(target_os = "ios")))]
 extern {
     fn clock_gettime(clk_id: libc::c_int, tp: *mut libc::timespec) -> libc::c_int;
 }
 
+#[cfg(windows)]
+#[repr(C)]
+struct FileTime {
+    low_date_time: u32,
+    high_date_time: u32,
+}
+
+#[cfg(windows)]
+extern "system" {
+    fn GetSystemTimePreciseAsFileTime(time: *mut FileTime);
+    fn QueryPerformanceCounter(count: *mut i64) -> i32;
+    fn QueryPerformanceFrequency(frequency: *mut i64) -> i32;
+}
+
 
 /// Returns the system’s current time, as a tuple of seconds elapsed since
-/// the Unix epoch, and the millisecond of the second.
+/// the Unix epoch, and the nanosecond of the second.
 #[cfg(any(target_os = "macos", target_os = "ios"))]
-pub unsafe fn sys_time() -> (i64, i16) {
+pub unsafe fn sys_time() -> (i64, i32) {
     use std::ptr::null_mut;
 
     let mut tv = libc::timeval { tv_sec: 0, tv_usec: 0 };
     let _ = gettimeofday(&mut tv, null_mut());
-    (tv.tv_sec, (tv.tv_usec / 1000) as i16)
+    (tv.tv_sec, tv.tv_usec as i32 * 1000)
+}
+
+/// Returns the system’s current time, as a tuple of seconds elapsed since
+/// the Unix epoch, and the nanosecond of the second.
+///
+/// This uses `GetSystemTimePreciseAsFileTime`, which has a resolution of
+/// well under a millisecond, rather than `GetSystemTimeAsFileTime`, which
+/// only gets updated at every clock tick (usually every 15ms or so). It is
+/// only available from Windows 8 onwards.
+#[cfg(windows)]
+pub unsafe fn sys_time() -> (i64, i32) {
+    let mut ft = FileTime { low_date_time: 0, high_date_time: 0 };
+    GetSystemTimePreciseAsFileTime(&mut ft);
+
+    // FILETIMEs count 100-nanosecond intervals since the 1st of January,
+    // 1601, which is 11,644,473,600 seconds before the Unix epoch.
+    let intervals = ((ft.high_date_time as u64) << 32 | ft.low_date_time as u64) as i64;
+    let seconds = intervals / 10_000_000 - 11_644_473_600;
+    let nanoseconds = (intervals % 10_000_000) as i32 * 100;
+    (seconds, nanoseconds)
 }
 
 /// Returns the system’s current time, as a tuple of seconds elapsed since
-/// the Unix epoch, and the millisecond of the second.
-#[cfg(not(any(target_os = "macos", target_os = "ios", windows)))]
-pub unsafe fn sys_time() -> (i64, i16) {
+/// the Unix epoch, and the nanosecond of the second.
+#[cfg(not(any(target_os = "macos", target_os = "ios", windows, all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))))]
+pub unsafe fn sys_time() -> (i64, i32) {
+    let mut tv = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    let _ = clock_gettime(libc::CLOCK_REALTIME, &mut tv);
+    (tv.tv_sec, tv.tv_nsec as i32)
+}
+
+/// Returns the system’s current time, as a tuple of seconds elapsed since
+/// the Unix epoch, and the nanosecond of the second.
+///
+/// WebAssembly has no system calls of its own, so this asks JavaScript’s
+/// `Date.now()`, which only has millisecond precision.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+pub unsafe fn sys_time() -> (i64, i32) {
+    split_milliseconds(js_sys::Date::now())
+}
+
+
+/// Returns the current reading of the system’s monotonic clock, as a tuple
+/// of seconds and the nanosecond of the second. The starting point of this
+/// clock is unspecified (it’s usually the time the system booted), so the
+/// values are only useful when compared against each other.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub unsafe fn sys_monotonic_time() -> (i64, i32) {
+    let mut info = MachTimebaseInfo { numer: 0, denom: 0 };
+    let _ = mach_timebase_info(&mut info);
+
+    let nanos = mach_absolute_time() as u128 * info.numer as u128 / info.denom as u128;
+    ((nanos / 1_000_000_000) as i64, (nanos % 1_000_000_000) as i32)
+}
+
+/// Returns the current reading of the system’s monotonic clock, as a tuple
+/// of seconds and the nanosecond of the second. The starting point of this
+/// clock is unspecified (it’s usually the time the system booted), so the
+/// values are only useful when compared against each other.
+#[cfg(windows)]
+pub unsafe fn sys_monotonic_time() -> (i64, i32) {
+    let mut frequency = 0;
+    let mut count = 0;
+    let _ = QueryPerformanceFrequency(&mut frequency);
+    let _ = QueryPerformanceCounter(&mut count);
+
+    let nanos = (count % frequency) as i128 * 1_000_000_000 / frequency as i128;
+    (count / frequency, nanos as i32)
+}
+
+/// Returns the current reading of the system’s monotonic clock, as a tuple
+/// of seconds and the nanosecond of the second. The starting point of this
+/// clock is unspecified (it’s usually the time the system booted), so the
+/// values are only useful when compared against each other.
+#[cfg(not(any(target_os = "macos", target_os = "ios", windows, all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))))]
+pub unsafe fn sys_monotonic_time() -> (i64, i32) {
+    let mut tv = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    let _ = clock_gettime(libc::CLOCK_MONOTONIC, &mut tv);
+    (tv.tv_sec, tv.tv_nsec as i32)
+}
+
+/// Returns the current reading of the system’s monotonic clock, as a tuple
+/// of seconds and the nanosecond of the second. The starting point of this
+/// clock is unspecified (it’s usually the time the page or process
+/// started), so the values are only useful when compared against each
+/// other.
+///
+/// This uses JavaScript’s `performance.now()`, falling back to
+/// `Date.now()`, which is *not* monotonic, in environments without it.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+pub unsafe fn sys_monotonic_time() -> (i64, i32) {
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let performance = Reflect::get(&js_sys::global(), &JsValue::from_str("performance")).unwrap_or(JsValue::UNDEFINED);
+    let now = Reflect::get(&performance, &JsValue::from_str("now")).ok()
+                      .and_then(|now| now.dyn_into::<Function>().ok());
+
+    match now.and_then(|now| now.call0(&performance).ok()).and_then(|ms| ms.as_f64()) {
+        Some(milliseconds) => split_milliseconds(milliseconds),
+        None               => split_milliseconds(js_sys::Date::now()),
+    }
+}
+
+/// Splits a number of milliseconds, as JavaScript counts time, into a tuple
+/// of seconds and the nanosecond of the second.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+fn split_milliseconds(milliseconds: f64) -> (i64, i32) {
+    let seconds = (milliseconds / 1000.0).floor();
+    let nanoseconds = ((milliseconds - seconds * 1000.0) * 1_000_000.0) as i32;
+    (seconds as i64, nanoseconds.min(999_999_999))
+}
+
+
+/// One of the clocks that Linux can be asked for the time, for the
+/// constructors that take one, such as `MonotonicInstant::now_from`.
+///
+/// The **realtime** clocks tell the time of day, so they give `Instant`s
+/// and date-times; the rest count from an unspecified point, so they give
+/// `MonotonicInstant`s. **Coarse** clocks are much quicker to read, but are
+/// only updated every clock tick, usually every few milliseconds.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum ClockSource {
+
+    /// The system-wide wall clock, `CLOCK_REALTIME`, which is what `now()`
+    /// uses. It can jump forwards or backwards if the system time is set.
+    Realtime,
+
+    /// A faster, lower-resolution version of the wall clock,
+    /// `CLOCK_REALTIME_COARSE`.
+    RealtimeCoarse,
+
+    /// The clock that `MonotonicInstant::now()` uses, `CLOCK_MONOTONIC`,
+    /// which only ever moves forwards, and stops while the system is
+    /// suspended.
+    Monotonic,
+
+    /// A faster, lower-resolution version of the monotonic clock,
+    /// `CLOCK_MONOTONIC_COARSE`.
+    MonotonicCoarse,
+
+    /// The monotonic clock, but one that keeps counting while the system is
+    /// suspended, `CLOCK_BOOTTIME`. This is the one to schedule against
+    /// when a job should run after a length of real time, even if the
+    /// machine was asleep for some of it.
+    Boottime,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl ClockSource {
+
+    /// Returns whether this clock tells the time of day, rather than
+    /// counting from an unspecified point.
+    pub fn is_realtime(self) -> bool {
+        match self {
+            ClockSource::Realtime | ClockSource::RealtimeCoarse => true,
+            _                                                   => false,
+        }
+    }
+
+    fn id(self) -> libc::clockid_t {
+        match self {
+            ClockSource::Realtime         => libc::CLOCK_REALTIME,
+            ClockSource::RealtimeCoarse   => libc::CLOCK_REALTIME_COARSE,
+            ClockSource::Monotonic        => libc::CLOCK_MONOTONIC,
+            ClockSource::MonotonicCoarse  => libc::CLOCK_MONOTONIC_COARSE,
+            ClockSource::Boottime         => libc::CLOCK_BOOTTIME,
+        }
+    }
+}
+
+/// Returns the current reading of the given clock, as a tuple of seconds
+/// and the nanosecond of the second, or `None` if the kernel doesn’t
+/// support that clock.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn sys_clock_time(source: ClockSource) -> Option<(i64, i32)> {
     let mut tv = libc::timespec { tv_sec: 0, tv_nsec: 0 };
-    clock_gettime(libc::CLOCK_REALTIME, &mut tv);
-    (tv.tv_sec, (tv.tv_nsec / 1000) as i16)
+    match unsafe { clock_gettime(source.id(), &mut tv) } {
+        0 => Some((tv.tv_sec, tv.tv_nsec as i32)),
+        _ => None,
+    }
+}
+
+
+/// Splits a standard library `SystemTime` into a tuple of seconds elapsed
+/// since the Unix epoch, and the nanosecond of the second. Times before the
+/// epoch have a negative number of seconds, but the nanosecond is always
+/// positive, counting forwards from the start of the second.
+pub fn split_system_time(time: SystemTime) -> (i64, i32) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => (after.as_secs() as i64, after.subsec_nanos() as i32),
+        Err(e) => {
+            let before = e.duration();
+            let seconds = -(before.as_secs() as i64);
+            let nanoseconds = before.subsec_nanos() as i32;
+
+            if nanoseconds == 0 {
+                (seconds, 0)
+            }
+            else {
+                (seconds - 1, 1_000_000_000 - nanoseconds)
+            }
+        }
+    }
+}
+
+/// Creates a standard library `SystemTime` from a number of seconds since
+/// the Unix epoch and the nanosecond of the second. This is the reverse of
+/// `split_system_time`.
+///
+/// This panics if the time cannot be represented by the platform’s
+/// `SystemTime` type.
+pub fn join_system_time(seconds: i64, nanoseconds: i32) -> SystemTime {
+    let fraction = StdDuration::new(0, nanoseconds as u32);
+
+    if seconds >= 0 {
+        UNIX_EPOCH + StdDuration::new(seconds as u64, 0) + fraction
+    }
+    else {
+        UNIX_EPOCH - StdDuration::new(seconds.unsigned_abs(), 0) + fraction
+    }
 }
 
 
 /// Attempts to determine the system’s current time zone. There’s no
 /// guaranteed way to do this, so this function returns `None` if no
 /// timezone could be found.
+///
+/// This reads the filesystem every time it’s called; use
+/// `cached_sys_timezone` in code that runs often.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm")))]
 pub fn sys_timezone() -> Option<String> {
     use std::fs::read_link;
 
@@ -59,8 +307,51 @@ pub fn sys_timezone() -> Option<String> {
     None
 }
 
+/// Attempts to determine the system’s current time zone. There’s no
+/// guaranteed way to do this, so this function returns `None` if no
+/// timezone could be found.
+///
+/// WebAssembly has no filesystem to look at, so this asks JavaScript for the
+/// zone used by `Intl.DateTimeFormat`, which is the browser’s or Node’s.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+pub fn sys_timezone() -> Option<String> {
+    use js_sys::{Array, Object, Reflect};
+    use js_sys::Intl::DateTimeFormat;
+    use wasm_bindgen::JsValue;
+
+    let options = DateTimeFormat::new(&Array::new(), &Object::new()).resolved_options();
+    let timezone = Reflect::get(&options, &JsValue::from_str("timeZone")).ok()?;
+    timezone.as_string().filter(|tz| !tz.is_empty())
+}
+
+/// The result of the last call to `sys_timezone` made through
+/// `refresh_sys_timezone`, or `None` if it’s never been called.
+static CACHED_TIMEZONE: RwLock<Option<Option<String>>> = RwLock::new(None);
+
+/// Returns the system’s time zone, in the same way as `sys_timezone`, but
+/// only looks it up the first time it’s called: after that, the same
+/// answer is returned without touching the filesystem, until
+/// `refresh_sys_timezone` is called.
+pub fn cached_sys_timezone() -> Option<String> {
+    if let Some(ref timezone) = *CACHED_TIMEZONE.read().unwrap_or_else(PoisonError::into_inner) {
+        return timezone.clone();
+    }
+
+    refresh_sys_timezone()
+}
+
+/// Looks up the system’s time zone again, replacing the answer that
+/// `cached_sys_timezone` returns, and returns it. Call this after the
+/// system’s time zone has been changed.
+pub fn refresh_sys_timezone() -> Option<String> {
+    let timezone = sys_timezone();
+    *CACHED_TIMEZONE.write().unwrap_or_else(PoisonError::into_inner) = Some(timezone.clone());
+    timezone
+}
+
 /// Given a path, returns whether a valid zoneinfo timezone name can be
 /// detected at the end of that path.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm")))]
 fn extract_timezone(path: &Path) -> Option<String> {
     let mut bits = Vec::new();
 
@@ -77,6 +368,7 @@ fn extract_timezone(path: &Path) -> Option<String> {
 /// Returns whether the input string could be used as a component of a
 /// zoneinfo timezone name, which in this case is whether its first
 /// character is a capital letter.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm")))]
 fn is_tz_component(component: &OsStr) -> bool {
     if let Some(component_str) = component.to_str() {
         let first_char = component_str.chars().next().unwrap();
@@ -90,14 +382,72 @@ fn is_tz_component(component: &OsStr) -> bool {
 
 #[cfg(test)]
 mod test {
-    use super::{sys_time, extract_timezone};
+    use super::{sys_time, sys_monotonic_time, extract_timezone};
+    use super::{sys_timezone, cached_sys_timezone, refresh_sys_timezone};
+    use super::{split_system_time, join_system_time};
     use std::path::Path;
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn sanity_check() {
         assert!((0, 0) != unsafe { sys_time() })
     }
 
+    #[test]
+    fn nanosecond_range() {
+        let (_, nanos) = unsafe { sys_time() };
+        assert!(nanos >= 0 && nanos < 1_000_000_000)
+    }
+
+    #[test]
+    fn monotonic_sanity_check() {
+        let (_, nanos) = unsafe { sys_monotonic_time() };
+        assert!(nanos >= 0 && nanos < 1_000_000_000)
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn clock_sources() {
+        use super::{ClockSource, sys_clock_time};
+
+        for &source in &[ ClockSource::Realtime, ClockSource::RealtimeCoarse, ClockSource::Monotonic,
+                          ClockSource::MonotonicCoarse, ClockSource::Boottime ] {
+            let (_, nanos) = sys_clock_time(source).unwrap();
+            assert!(nanos >= 0 && nanos < 1_000_000_000, "{:?}", source);
+        }
+
+        // The boot-time clock counts time spent suspended, so it can never
+        // be behind the monotonic one.
+        let monotonic = sys_clock_time(ClockSource::Monotonic).unwrap();
+        let boottime = sys_clock_time(ClockSource::Boottime).unwrap();
+        assert!(boottime >= monotonic);
+    }
+
+    #[test]
+    fn split_after_epoch() {
+        let time = UNIX_EPOCH + Duration::new(1_234_567_890, 123_456_789);
+        assert_eq!(split_system_time(time), (1_234_567_890, 123_456_789));
+    }
+
+    #[test]
+    fn split_before_epoch() {
+        let time = UNIX_EPOCH - Duration::new(10, 250_000_000);
+        assert_eq!(split_system_time(time), (-11, 750_000_000));
+    }
+
+    #[test]
+    fn split_before_epoch_exact() {
+        let time = UNIX_EPOCH - Duration::new(10, 0);
+        assert_eq!(split_system_time(time), (-10, 0));
+    }
+
+    #[test]
+    fn join_round_trip() {
+        for &(s, ns) in &[ (0, 0), (1_234_567_890, 5), (-11, 750_000_000), (-1, 0) ] {
+            assert_eq!(split_system_time(join_system_time(s, ns)), (s, ns));
+        }
+    }
+
     #[test]
     fn two() {
         let timezone = extract_timezone(Path::new("/usr/share/zoneinfo/Europe/London"));
@@ -109,4 +459,11 @@ mod test {
         let timezone = extract_timezone(Path::new("/usr/share/zoneinfo/CST6CDT"));
         assert_eq!(timezone, Some("CST6CDT".to_string()));
     }
+
+    #[test]
+    fn cached_timezone() {
+        assert_eq!(cached_sys_timezone(), sys_timezone());
+        assert_eq!(refresh_sys_timezone(), sys_timezone());
+        assert_eq!(cached_sys_timezone(), sys_timezone());
+    }
 }
@@ -0,0 +1,198 @@
+//! Conversions between UTC and International Atomic Time (TAI).
+//!
+//! TAI is a continuous time scale: every second on it is exactly one SI
+//! second long, and it never jumps. UTC is kept within a second of the
+//! Earth’s rotation by occasionally inserting *leap seconds*, which means
+//! that the difference between the two grows by one each time this
+//! happens. This library’s instants ignore leap seconds, so converting to
+//! TAI is a matter of adding the number of seconds that have been inserted
+//! up until that point, which is looked up in a leap second table.
+//!
+//! TAI instants are represented as an `Instant` counting seconds since
+//! 1970 on the TAI time scale, in the same way as Linux’s `CLOCK_TAI`: they
+//! are the corresponding UTC Unix timestamp plus the TAI−UTC offset in
+//! effect at the time.
+
+use duration::Duration;
+use instant::Instant;
+
+
+/// A **leap second table** is a list of the points at which the offset
+/// between TAI and UTC changed.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LeapSecondTable<'a> {
+
+    /// The changes to the offset, as a slice of tuples, each containing:
+    ///
+    /// 1. The instant the new offset takes effect, stored as a UTC Unix
+    ///    timestamp. This is the instant immediately *after* the leap
+    ///    second, which is midnight at the start of a month.
+    /// 2. The total offset of TAI ahead of UTC from then on, in seconds.
+    ///
+    /// The entries must be sorted by their instants. Times before the first
+    /// entry are assumed to have the first entry’s offset.
+    pub entries: &'a [ (i64, i64) ],
+}
+
+/// The leap seconds that have been announced by the International Earth
+/// Rotation and Reference Systems Service, up to the one at the end of
+/// 2016. Until 1972, UTC was not kept in step with TAI using whole leap
+/// seconds, so the offset was fixed at 10 seconds when they were
+/// introduced.
+pub const LEAP_SECONDS: LeapSecondTable<'static> = LeapSecondTable {
+    entries: &[
+        (  63072000, 10),  // 1972-01-01
+        (  78796800, 11),  // 1972-07-01
+        (  94694400, 12),  // 1973-01-01
+        ( 126230400, 13),  // 1974-01-01
+        ( 157766400, 14),  // 1975-01-01
+        ( 189302400, 15),  // 1976-01-01
+        ( 220924800, 16),  // 1977-01-01
+        ( 252460800, 17),  // 1978-01-01
+        ( 283996800, 18),  // 1979-01-01
+        ( 315532800, 19),  // 1980-01-01
+        ( 362793600, 20),  // 1981-07-01
+        ( 394329600, 21),  // 1982-07-01
+        ( 425865600, 22),  // 1983-07-01
+        ( 489024000, 23),  // 1985-07-01
+        ( 567993600, 24),  // 1988-01-01
+        ( 631152000, 25),  // 1990-01-01
+        ( 662688000, 26),  // 1991-01-01
+        ( 709948800, 27),  // 1992-07-01
+        ( 741484800, 28),  // 1993-07-01
+        ( 773020800, 29),  // 1994-07-01
+        ( 820454400, 30),  // 1996-01-01
+        ( 867715200, 31),  // 1997-07-01
+        ( 915148800, 32),  // 1999-01-01
+        (1136073600, 33),  // 2006-01-01
+        (1230768000, 34),  // 2009-01-01
+        (1341100800, 35),  // 2012-07-01
+        (1435708800, 36),  // 2015-07-01
+        (1483228800, 37),  // 2017-01-01
+    ],
+};
+
+impl<'a> LeapSecondTable<'a> {
+
+    /// Returns the number of seconds that TAI is ahead of UTC at the given
+    /// UTC instant.
+    pub fn offset_at_utc(&self, utc: Instant) -> i64 {
+        let first = self.entries.first().map(|e| e.1).unwrap_or(0);
+
+        match self.entries.iter().take_while(|e| e.0 <= utc.seconds()).last() {
+            Some(entry) => entry.1,
+            None        => first,
+        }
+    }
+
+    /// Returns the number of seconds that TAI is ahead of UTC at the given
+    /// TAI instant.
+    ///
+    /// On the TAI time scale, each new offset comes into effect one second
+    /// *earlier* than the UTC time it’s listed at plus the offset itself,
+    /// as that second is the leap second.
+    pub fn offset_at_tai(&self, tai: Instant) -> i64 {
+        let first = self.entries.first().map(|e| e.1).unwrap_or(0);
+
+        match self.entries.iter().take_while(|e| e.0 + e.1 - 1 <= tai.seconds()).last() {
+            Some(entry) => entry.1,
+            None        => first,
+        }
+    }
+
+    /// Converts an instant in UTC to the corresponding instant in TAI.
+    pub fn utc_to_tai(&self, utc: Instant) -> Instant {
+        utc + Duration::of(self.offset_at_utc(utc))
+    }
+
+    /// Converts an instant in TAI to the corresponding instant in UTC.
+    ///
+    /// As UTC instants ignore leap seconds, a TAI instant that falls
+    /// *during* a leap second is converted to the second before it, in the
+    /// same way that the system clock repeats 23:59:59. This means this is
+    /// the inverse of `utc_to_tai` for every other instant.
+    pub fn tai_to_utc(&self, tai: Instant) -> Instant {
+        tai - Duration::of(self.offset_at_tai(tai))
+    }
+}
+
+
+/// Converts an instant in UTC to the corresponding instant in TAI, using
+/// the bundled leap second table.
+///
+/// ```rust
+/// use datetime::Instant;
+/// use datetime::tai;
+///
+/// let utc = Instant::at(1_500_000_000);  // July 2017
+/// assert_eq!(tai::utc_to_tai(utc), Instant::at(1_500_000_037));
+/// ```
+pub fn utc_to_tai(utc: Instant) -> Instant {
+    LEAP_SECONDS.utc_to_tai(utc)
+}
+
+/// Converts an instant in TAI to the corresponding instant in UTC, using
+/// the bundled leap second table.
+pub fn tai_to_utc(tai: Instant) -> Instant {
+    LEAP_SECONDS.tai_to_utc(tai)
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use instant::Instant;
+
+    /// The instant the leap second at the end of 2016 finished.
+    const NEW_YEAR_2017: i64 = 1483228800;
+
+    #[test]
+    fn before_table() {
+        assert_eq!(LEAP_SECONDS.offset_at_utc(Instant::at(0)), 10)
+    }
+
+    #[test]
+    fn after_table() {
+        assert_eq!(LEAP_SECONDS.offset_at_utc(Instant::at(1_600_000_000)), 37)
+    }
+
+    #[test]
+    fn either_side_of_a_leap_second() {
+        assert_eq!(LEAP_SECONDS.offset_at_utc(Instant::at(NEW_YEAR_2017 - 1)), 36);
+        assert_eq!(LEAP_SECONDS.offset_at_utc(Instant::at(NEW_YEAR_2017)), 37);
+    }
+
+    #[test]
+    fn keeps_milliseconds() {
+        assert_eq!(utc_to_tai(Instant::at_ms(NEW_YEAR_2017, 250)), Instant::at_ms(NEW_YEAR_2017 + 37, 250))
+    }
+
+    #[test]
+    fn during_a_leap_second() {
+        // 2016-12-31T23:59:60 UTC, which UTC instants can’t express.
+        let leap_second = Instant::at(NEW_YEAR_2017 + 36);
+        assert_eq!(tai_to_utc(leap_second), Instant::at(NEW_YEAR_2017 - 1))
+    }
+
+    #[test]
+    fn round_trips() {
+        for &seconds in &[ -1_000_000, 0, 78796799, 78796800, NEW_YEAR_2017 - 1, NEW_YEAR_2017, 1_600_000_000 ] {
+            let utc = Instant::at(seconds);
+            assert_eq!(tai_to_utc(utc_to_tai(utc)), utc, "round-tripping {}", seconds);
+        }
+    }
+
+    #[test]
+    fn custom_table() {
+        let table = LeapSecondTable { entries: &[ (100, 1), (200, 2) ] };
+        assert_eq!(table.utc_to_tai(Instant::at(150)), Instant::at(151));
+        assert_eq!(table.tai_to_utc(Instant::at(201)), Instant::at(199));
+        assert_eq!(table.tai_to_utc(Instant::at(202)), Instant::at(200));
+    }
+
+    #[test]
+    fn empty_table() {
+        let table = LeapSecondTable { entries: &[] };
+        assert_eq!(table.utc_to_tai(Instant::at(150)), Instant::at(150));
+    }
+}
@@ -0,0 +1,220 @@
+//! Helpers for testing code that uses this library.
+//!
+//! Code that takes a `&dyn Clock` rather than asking the system for the
+//! time can be handed a `MockClock` in its tests, which only moves when
+//! it’s told to.
+//!
+//! ```rust
+//! use datetime::{Clock, Duration, Instant, TimeZone};
+//! use datetime::testing::MockClock;
+//!
+//! let clock = MockClock::new(Instant::at(0), TimeZone::UTC);
+//! clock.advance(Duration::of(90));
+//! assert_eq!(clock.now(), Instant::at(90));
+//! assert_eq!(clock.now_local().to_string(), "1970-01-01T00:01:30");
+//! ```
+
+use std::sync::{Mutex, MutexGuard};
+
+use clock::Clock;
+use cal::zone::TimeZone;
+use duration::Duration;
+use instant::Instant;
+use monotonic::MonotonicInstant;
+
+
+/// A clock whose time is set by the test using it.
+///
+/// It starts out **frozen**, so that every reading returns the same
+/// instant until it is set or advanced. It can also be **resumed**, after
+/// which it ticks along at the same rate as the system clock, starting from
+/// wherever it was, which suits tests for timeouts that still need real
+/// time to pass; freezing it again stops it at its current reading.
+///
+/// The clock can be moved through a shared reference, so the code under
+/// test can hold on to it while the test moves it, even from another
+/// thread.
+#[derive(Debug)]
+pub struct MockClock<'a> {
+    state: Mutex<State>,
+    time_zone: TimeZone<'a>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+
+    /// The clock reads this instant until it’s moved.
+    Frozen(Instant),
+
+    /// The clock read the instant at the given point on the monotonic
+    /// clock, and has moved on by however long has elapsed since.
+    Running(Instant, MonotonicInstant),
+}
+
+impl<'a> MockClock<'a> {
+
+    /// Creates a new frozen clock that reads the given instant, and gives
+    /// local times in the given time zone.
+    pub fn new(instant: Instant, time_zone: TimeZone<'a>) -> MockClock<'a> {
+        MockClock { state: Mutex::new(State::Frozen(instant)), time_zone: time_zone }
+    }
+
+    /// Locks this clock’s state. The state is always left whole, so a
+    /// panic in another thread while it was locked doesn’t matter.
+    fn state(&self) -> MutexGuard<State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Moves this clock to the given instant, which may be before the one
+    /// it was at. A running clock carries on running from there.
+    pub fn set(&self, instant: Instant) {
+        let mut state = self.state();
+        *state = match *state {
+            State::Frozen(_)     => State::Frozen(instant),
+            State::Running(_, _) => State::Running(instant, MonotonicInstant::now()),
+        };
+    }
+
+    /// Moves this clock on by the given duration, or back if it’s negative.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the new instant would overflow.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state();
+        *state = match *state {
+            State::Frozen(instant)         => State::Frozen(instant + duration),
+            State::Running(instant, since) => State::Running(instant + duration, since),
+        };
+    }
+
+    /// Stops this clock at its current reading.
+    pub fn freeze(&self) {
+        let mut state = self.state();
+        *state = State::Frozen(state.reading());
+    }
+
+    /// Starts this clock ticking forwards from its current reading.
+    /// Resuming a clock that is already running does nothing.
+    pub fn resume(&self) {
+        let mut state = self.state();
+        if let State::Frozen(instant) = *state {
+            *state = State::Running(instant, MonotonicInstant::now());
+        }
+    }
+
+    /// Returns whether this clock is frozen.
+    pub fn is_frozen(&self) -> bool {
+        match *self.state() {
+            State::Frozen(_)     => true,
+            State::Running(_, _) => false,
+        }
+    }
+}
+
+impl State {
+
+    /// Returns the instant a clock in this state reads right now.
+    fn reading(&self) -> Instant {
+        match *self {
+            State::Frozen(instant)         => instant,
+            State::Running(instant, since) => instant + since.elapsed(),
+        }
+    }
+}
+
+impl<'a> Clone for MockClock<'a> {
+    fn clone(&self) -> MockClock<'a> {
+        MockClock { state: Mutex::new(*self.state()), time_zone: self.time_zone.clone() }
+    }
+}
+
+impl<'a> Clock for MockClock<'a> {
+    fn now(&self) -> Instant {
+        self.state().reading()
+    }
+
+    fn timezone(&self) -> TimeZone {
+        self.time_zone.clone()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::{LocalDateTime, LocalDate, LocalTime, Month};
+    pub use cal::zone::{FixedTimespanSet, FixedTimespan};
+
+    const ZONE: TimeZone<'static> = TimeZone {
+        name: "Test/Zone",
+        fixed_timespans: FixedTimespanSet {
+            first: FixedTimespan { offset: 3600, is_dst: false, name: "ZONE_A" },
+            rest: &[
+                (1000, FixedTimespan { offset: 7200, is_dst: true, name: "ZONE_B" }),
+            ],
+        },
+    };
+
+    fn local(clock: &dyn Clock) -> LocalDateTime {
+        clock.now_local()
+    }
+
+    #[test]
+    fn frozen() {
+        let clock = MockClock::new(Instant::at(0), ZONE);
+        assert!(clock.is_frozen());
+        assert_eq!(local(&clock), LocalDateTime::new(LocalDate::ymd(1970, Month::January, 1).unwrap(), LocalTime::hms(1, 0, 0).unwrap()));
+
+        clock.advance(Duration::of(1000));
+        assert_eq!(clock.now(), Instant::at(1000));
+        assert_eq!(clock.now_zoned().to_instant(), Instant::at(1000));
+
+        clock.set(Instant::at(1001));
+        assert_eq!(local(&clock), LocalDateTime::new(LocalDate::ymd(1970, Month::January, 1).unwrap(), LocalTime::hms(2, 16, 41).unwrap()));
+
+        clock.advance(Duration::of(-1));
+        assert_eq!(clock.now(), Instant::at(1000));
+    }
+
+    #[test]
+    fn running() {
+        let clock = MockClock::new(Instant::at(0), TimeZone::UTC);
+        clock.resume();
+        assert!(!clock.is_frozen());
+
+        clock.advance(Duration::of(3600));
+        let reading = clock.now();
+        assert!(reading >= Instant::at(3600) && reading < Instant::at(3660));
+
+        clock.freeze();
+        let frozen = clock.now();
+        assert!(frozen >= reading);
+        assert_eq!(clock.now(), frozen);
+    }
+
+    #[test]
+    fn set_while_running() {
+        let clock = MockClock::new(Instant::at(0), TimeZone::UTC);
+        clock.resume();
+        clock.set(Instant::at(-500));
+        clock.freeze();
+        assert!(clock.now() >= Instant::at(-500) && clock.now() < Instant::at(-440));
+    }
+
+    #[test]
+    fn across_threads() {
+        use std::thread;
+
+        let clock = MockClock::new(Instant::at(0), TimeZone::UTC);
+        let shared = &clock;
+        thread::scope(|scope| {
+            for _ in 0 .. 4 {
+                let _ = scope.spawn(move || shared.advance(Duration::of(10)));
+            }
+        });
+
+        assert_eq!(clock.now(), Instant::at(40));
+        assert_eq!(clock.clone().now(), Instant::at(40));
+    }
+}
@@ -0,0 +1,180 @@
+//! Whole-second Unix timestamps.
+
+use std::fmt;
+use std::ops::{Add, Sub};
+
+use cal::datetime::LocalDateTime;
+use duration::Duration;
+use instant::Instant;
+
+
+/// A **Unix timestamp** is the number of whole seconds since midnight UTC
+/// on the 1st of January, 1970, not counting leap seconds.
+///
+/// This is the same number that most APIs, databases, and log formats use,
+/// so it can be passed straight through as an `i64`. It formats as that
+/// number, or as an ISO 8601 date-time in UTC with the alternate flag:
+///
+/// ```rust
+/// use datetime::UnixTimestamp;
+///
+/// let timestamp = UnixTimestamp(1_000_000_000);
+/// assert_eq!(format!("{}", timestamp), "1000000000");
+/// assert_eq!(format!("{:#}", timestamp), "2001-09-09T01:46:40Z");
+/// ```
+#[repr(transparent)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Clone, Copy)]
+pub struct UnixTimestamp(pub i64);
+
+impl UnixTimestamp {
+
+    /// Returns the timestamp of the current second, according to the
+    /// system clock.
+    #[cfg(feature = "std")]
+    pub fn now() -> UnixTimestamp {
+        UnixTimestamp::from(Instant::now())
+    }
+
+    /// Returns the number of seconds since the Unix epoch.
+    pub fn seconds(&self) -> i64 {
+        self.0
+    }
+
+    /// Adds a duration to this timestamp, returning `None` if the result
+    /// would overflow. Any fraction of a second in the duration rounds the
+    /// result down to the second before.
+    pub fn checked_add(self, duration: Duration) -> Option<UnixTimestamp> {
+        Instant::from(self).checked_add(duration).map(UnixTimestamp::from)
+    }
+
+    /// Subtracts a duration from this timestamp, returning `None` if the
+    /// result would overflow. Any fraction of a second in the duration
+    /// rounds the result down to the second before.
+    pub fn checked_sub(self, duration: Duration) -> Option<UnixTimestamp> {
+        Instant::from(self).checked_sub(duration).map(UnixTimestamp::from)
+    }
+}
+
+impl fmt::Display for UnixTimestamp {
+
+    /// Formats this timestamp as its number of seconds, or, with the
+    /// alternate flag, as a date-time in UTC such as
+    /// `2001-09-09T01:46:40Z`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", Instant::from(*self))
+        }
+        else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl From<i64> for UnixTimestamp {
+    fn from(seconds: i64) -> UnixTimestamp {
+        UnixTimestamp(seconds)
+    }
+}
+
+impl From<UnixTimestamp> for i64 {
+    fn from(timestamp: UnixTimestamp) -> i64 {
+        timestamp.0
+    }
+}
+
+impl From<Instant> for UnixTimestamp {
+
+    /// Converts an instant to a timestamp, discarding any fraction of a second.
+    fn from(instant: Instant) -> UnixTimestamp {
+        UnixTimestamp(instant.seconds())
+    }
+}
+
+impl From<UnixTimestamp> for Instant {
+    fn from(timestamp: UnixTimestamp) -> Instant {
+        Instant::at(timestamp.0)
+    }
+}
+
+impl From<LocalDateTime> for UnixTimestamp {
+
+    /// Converts a date-time to a timestamp, treating it as though it were
+    /// in UTC and discarding any fraction of a second.
+    fn from(datetime: LocalDateTime) -> UnixTimestamp {
+        UnixTimestamp::from(datetime.to_instant())
+    }
+}
+
+impl From<UnixTimestamp> for LocalDateTime {
+
+    /// Converts a timestamp to a date-time in UTC.
+    fn from(timestamp: UnixTimestamp) -> LocalDateTime {
+        LocalDateTime::at(timestamp.0)
+    }
+}
+
+impl Add<Duration> for UnixTimestamp {
+    type Output = UnixTimestamp;
+
+    fn add(self, duration: Duration) -> UnixTimestamp {
+        self.checked_add(duration).expect("timestamp out of range")
+    }
+}
+
+impl Sub<Duration> for UnixTimestamp {
+    type Output = UnixTimestamp;
+
+    fn sub(self, duration: Duration) -> UnixTimestamp {
+        self.checked_sub(duration).expect("timestamp out of range")
+    }
+}
+
+impl Sub<UnixTimestamp> for UnixTimestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: UnixTimestamp) -> Duration {
+        Instant::from(self) - Instant::from(earlier)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::*;
+    pub use cal::datetime::{LocalDate, LocalTime, Month};
+
+    #[test]
+    fn formatting() {
+        assert_eq!(UnixTimestamp(0).to_string(), "0");
+        assert_eq!(UnixTimestamp(-1).to_string(), "-1");
+        assert_eq!(format!("{:#}", UnixTimestamp(-1)), "1969-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn datetimes() {
+        let datetime = LocalDateTime::new(LocalDate::ymd(2015, Month::June, 26).unwrap(), LocalTime::hms_ms(12, 0, 0, 750).unwrap());
+        let timestamp = UnixTimestamp::from(datetime);
+        assert_eq!(timestamp, UnixTimestamp(1435320000));
+        assert_eq!(LocalDateTime::from(timestamp), LocalDateTime::new(datetime.date(), LocalTime::hms(12, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn instants_round_down() {
+        assert_eq!(UnixTimestamp::from(Instant::at_ms(-1, 500)), UnixTimestamp(-1));
+        assert_eq!(Instant::from(UnixTimestamp(10)), Instant::at(10));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let timestamp = UnixTimestamp(100);
+        assert_eq!(timestamp + Duration::of(20), UnixTimestamp(120));
+        assert_eq!(timestamp - Duration::of_ms(0, 500), UnixTimestamp(99));
+        assert_eq!(UnixTimestamp(120) - timestamp, Duration::of(20));
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(UnixTimestamp(i64::max_value()).checked_add(Duration::of(1)), None);
+        assert_eq!(UnixTimestamp(i64::min_value()).checked_sub(Duration::of(1)), None);
+    }
+}
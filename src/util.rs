@@ -16,4 +16,72 @@ impl<T> RangeExt for T where T: PartialOrd<T> {
     fn is_within(&self, range: Range<Self>) -> bool {
         *self >= range.start && *self < range.end
     }
-}
\ No newline at end of file
+}
+
+/// Encodes a number of seconds since the Unix epoch and a number of
+/// nanoseconds into the twelve-byte binary encoding used by
+/// `LocalDateTime` and `Instant`.
+pub fn join_bytes(seconds: i64, nanoseconds: u32) -> [u8; 12] {
+    let mut bytes = [0; 12];
+    bytes[.. 8].copy_from_slice(&seconds.to_be_bytes());
+    bytes[8 ..].copy_from_slice(&nanoseconds.to_be_bytes());
+    bytes
+}
+
+/// Decodes the twelve-byte binary encoding back into seconds and
+/// nanoseconds, returning `None` if the nanoseconds are out of range.
+pub fn split_bytes(bytes: [u8; 12]) -> Option<(i64, u32)> {
+    let mut seconds = [0; 8];
+    let mut nanoseconds = [0; 4];
+    seconds.copy_from_slice(&bytes[.. 8]);
+    nanoseconds.copy_from_slice(&bytes[8 ..]);
+
+    let nanoseconds = u32::from_be_bytes(nanoseconds);
+    if nanoseconds < 1_000_000_000 {
+        Some((i64::from_be_bytes(seconds), nanoseconds))
+    }
+    else {
+        None
+    }
+}
+
+/// Rounds a number to the nearest whole number, with halves rounded away
+/// from zero, the same as `f64::round`, which is only available with the
+/// standard library.
+pub fn round(number: f64) -> f64 {
+    // Numbers this large are already whole, and casting them to an integer
+    // could overflow; infinities and NaN are returned as they are too.
+    if !(number.abs() < 4_503_599_627_370_496.0) {
+        return number;
+    }
+
+    let truncated = number as i64 as f64;
+    let fraction = number - truncated;
+
+    if fraction >= 0.5 {
+        truncated + 1.0
+    }
+    else if fraction <= -0.5 {
+        truncated - 1.0
+    }
+    else {
+        truncated
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    pub use super::round;
+
+    #[test]
+    fn rounding_matches_the_standard_library() {
+        let numbers = [ 0.0, 0.4, 0.5, 0.49999999999999994, 1.5, 2.5, -0.5, -1.4, -2.5, 1e15 + 0.5, 1e300, -1e300 ];
+        for &number in &numbers {
+            assert_eq!(round(number), number.round(), "rounding {}", number);
+        }
+
+        assert!(round(::std::f64::NAN).is_nan());
+        assert_eq!(round(::std::f64::INFINITY), ::std::f64::INFINITY);
+    }
+}
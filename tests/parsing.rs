@@ -1,10 +1,12 @@
 extern crate datetime;
-use datetime::{LocalDateTime, Weekday, Month, LocalDate};
+use datetime::{LocalDateTime, Weekday, Month, LocalDate, OffsetDateTime};
 extern crate regex;
+extern crate iso8601;
 
 extern crate rustc_serialize;
 use rustc_serialize::json::Json;
 
+use std::convert::TryFrom;
 use std::error;
 use std::fs::File;
 use std::io::prelude::*;
@@ -39,6 +41,15 @@ fn iso_formats(){
 }
 
 
+#[test]
+fn from_iso8601_fields(){
+    let fields = iso8601::datetime("2001-W05-6T04:05:06+07:00").unwrap();
+    assert_eq!(LocalDateTime::try_from(fields).unwrap(), LocalDateTime::from_str("2001-02-03T04:05:06").unwrap());
+    assert_eq!(OffsetDateTime::try_from(fields).unwrap(), OffsetDateTime::from_str("2001-02-03T04:05:06+07:00").unwrap());
+    assert_eq!(LocalDate::try_from(iso8601::date("2001-034").unwrap()).unwrap(), LocalDate::ymd(2001, Month::February, 3).unwrap());
+}
+
+
 #[test]
 /// comprehensive test that compares
 fn date_fromweekday_vs_new_vs_parse() {